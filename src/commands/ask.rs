@@ -13,39 +13,119 @@
 // limitations under the License.
 
 use anyhow::Result;
-use clap::Args;
+use clap::{Args, ValueEnum};
 use colored::Colorize;
 use glob::glob;
 use octomind::config::Config;
+use octomind::mcp::McpToolCall;
+use octomind::providers::ProviderFactory;
 use octomind::session::chat::markdown::{is_markdown_content, MarkdownRenderer};
-use octomind::session::{chat_completion_with_provider, Message, ProviderResponse};
+use octomind::session::image::{ImageAttachment, ImageProcessor};
+use octomind::session::{chat_completion_with_provider, Message, ProviderResponse, TokenUsage};
 use rustyline::error::ReadlineError;
 use rustyline::{CompletionType, Config as RustylineConfig, EditMode, Editor};
+use serde::Serialize;
 use std::fs;
 use std::io::IsTerminal;
 use std::io::{self, Read};
 
 #[derive(Args, Debug)]
 pub struct AskArgs {
-	/// Question or input to ask the AI
+	/// Question or input to ask the AI (appended after --input-file/--stdin content when combined)
 	#[arg(value_name = "INPUT")]
 	pub input: Option<String>,
 
+	/// Read the prompt body from a file
+	#[arg(long, value_name = "PATH", conflicts_with = "stdin")]
+	pub input_file: Option<String>,
+
+	/// Read the prompt body from stdin
+	#[arg(long)]
+	pub stdin: bool,
+
 	/// Include files as context (supports glob patterns, can be used multiple times)
 	#[arg(short = 'f', long = "file", value_name = "FILE_PATTERN")]
 	pub files: Vec<String>,
 
-	/// Use a specific model instead of the default (runtime only, not saved)
+	/// Attach an image file or URL to the question (requires a vision-capable model, can be used multiple times)
+	#[arg(long = "image", value_name = "PATH_OR_URL")]
+	pub images: Vec<String>,
+
+	/// Override the configured model, as provider:model (runtime only, not saved)
 	#[arg(long)]
 	pub model: Option<String>,
 
-	/// Temperature for the AI response (0.0 to 1.0, runtime only, not saved)
-	#[arg(long, default_value = "0.7")]
-	pub temperature: f32,
+	/// Temperature for the AI response (0.0 to 2.0, overrides the assistant role's temperature, runtime only)
+	#[arg(long)]
+	pub temperature: Option<f32>,
+
+	/// Append this text to the system prompt for this run (e.g. "Respond in JSON only").
+	/// Added last, after the built-in prompt and system_suffix; placeholders are not expanded
+	#[arg(long, value_name = "TEXT")]
+	pub append_system: Option<String>,
+
+	/// Stop generating when the model outputs this text (repeatable; replaces the configured stop_sequences)
+	#[arg(long, value_name = "SEQUENCE")]
+	pub stop: Vec<String>,
 
 	/// Output raw text without markdown rendering
 	#[arg(long)]
 	pub raw: bool,
+
+	/// Output format: human-readable text or a single JSON object per response
+	#[arg(long, value_enum, default_value = "text")]
+	pub output: OutputFormat,
+}
+
+/// Output format for the ask command
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+	Text,
+	Json,
+}
+
+/// Machine-readable response emitted with `--output json`
+#[derive(Serialize, Debug)]
+pub struct AskJsonOutput {
+	pub content: String,
+	pub model: String,
+	pub usage: Option<TokenUsage>,
+	pub cost: Option<f64>,
+	pub tool_calls: Vec<McpToolCall>,
+	pub finish_reason: Option<String>,
+}
+
+impl AskJsonOutput {
+	fn from_response(response: &ProviderResponse, model: &str) -> Self {
+		let usage = response.exchange.usage.clone();
+		let cost = usage.as_ref().and_then(|u| u.cost);
+		Self {
+			content: response.content.clone(),
+			model: model.to_string(),
+			usage,
+			cost,
+			tool_calls: response.tool_calls.clone().unwrap_or_default(),
+			finish_reason: response.finish_reason.clone(),
+		}
+	}
+}
+
+// Helper function to emit a response in the requested output format
+fn emit_response(
+	response: &ProviderResponse,
+	model: &str,
+	args: &AskArgs,
+	config: &Config,
+) -> Result<()> {
+	match args.output {
+		OutputFormat::Json => {
+			// Plain JSON only - no colors or markdown so stdout stays parseable
+			let output = AskJsonOutput::from_response(response, model);
+			println!("{}", serde_json::to_string(&output)?);
+		}
+		OutputFormat::Text => print_response(&response.content, args.raw, config),
+	}
+	Ok(())
 }
 
 // Helper function to print content with optional markdown rendering for ask command
@@ -254,6 +334,29 @@ fn get_interactive_input() -> Result<String> {
 	Ok(lines.join("\n"))
 }
 
+// Prompt from --input-file or --stdin and the positional input. The body comes first and
+// the positional input follows it, so `cat spec.md | octomind ask --stdin "Summarize"`
+// reads as the document followed by the instruction.
+fn read_prompt(args: &AskArgs) -> Result<Option<String>> {
+	let body = if let Some(path) = &args.input_file {
+		let content = fs::read_to_string(path)
+			.map_err(|e| anyhow::anyhow!("Failed to read input file '{}': {}", path, e))?;
+		Some(content)
+	} else if args.stdin {
+		let mut buffer = String::new();
+		io::stdin().read_to_string(&mut buffer)?;
+		Some(buffer)
+	} else {
+		None
+	};
+
+	Ok(match (body, &args.input) {
+		(Some(body), Some(input)) => Some(format!("{}\n\n{}", body.trim(), input)),
+		(Some(body), None) => Some(body.trim().to_string()),
+		(None, input) => input.clone(),
+	})
+}
+
 pub async fn execute(args: &AskArgs, config: &Config) -> Result<()> {
 	// Validate file patterns first, before any other processing
 	if let Err(e) = validate_file_patterns(&args.files) {
@@ -267,8 +370,18 @@ pub async fn execute(args: &AskArgs, config: &Config) -> Result<()> {
 		.clone()
 		.unwrap_or_else(|| config.get_effective_model());
 
+	// Use the assistant role's temperature unless overridden on the command line
+	let temperature = super::resolve_temperature(
+		args.temperature,
+		config.get_role_config_struct("assistant").temperature,
+	)?;
+
 	// Simple system prompt for ask command - no mode complexity needed
-	let system_prompt = "You are a helpful assistant.".to_string();
+	let system_prompt = super::finish_system_prompt(
+		"You are a helpful assistant.",
+		config,
+		args.append_system.as_deref(),
+	);
 
 	// Create a clean config with no MCP servers for ask command
 	// This ensures no tools are sent to the API
@@ -278,11 +391,19 @@ pub async fn execute(args: &AskArgs, config: &Config) -> Result<()> {
 	// Read file context once (validation already done)
 	let file_context = read_files_as_context(&args.files)?;
 
-	// Get input from argument, stdin, or interactive mode
-	if let Some(input) = &args.input {
-		// Single execution mode - input provided via argument
+	// Load images once, they are attached to every query
+	let images = load_images(&args.images, &model).await?;
+
+	// Get input from argument, input file, stdin, or interactive mode
+	if let Some(input) = read_prompt(args)? {
+		if input.is_empty() {
+			eprintln!("Error: No input provided.");
+			std::process::exit(1);
+		}
+
+		// Single execution mode - input provided via argument or input file
 		let full_input = if file_context.is_empty() {
-			input.clone()
+			input
 		} else {
 			format!("{}\n\n{}", file_context, input)
 		};
@@ -291,12 +412,13 @@ pub async fn execute(args: &AskArgs, config: &Config) -> Result<()> {
 		let response = execute_single_query(
 			&full_input,
 			&model,
-			args.temperature,
+			temperature,
 			&system_prompt,
+			&images,
 			&clean_config,
 		)
 		.await?;
-		print_response(&response.content, args.raw, config);
+		emit_response(&response, &model, args, config)?;
 		Ok(())
 	} else if !std::io::stdin().is_terminal() {
 		// Read from stdin if it's being piped
@@ -319,13 +441,14 @@ pub async fn execute(args: &AskArgs, config: &Config) -> Result<()> {
 		let response = execute_single_query(
 			&full_input,
 			&model,
-			args.temperature,
+			temperature,
 			&system_prompt,
+			&images,
 			&clean_config,
 		)
 		.await?;
-		print_response(&response.content, args.raw, config);
-		return Ok(());
+		emit_response(&response, &model, args, config)?;
+		Ok(())
 	} else {
 		// Interactive multimode - no argument provided and stdin is a terminal
 		println!(
@@ -353,15 +476,18 @@ pub async fn execute(args: &AskArgs, config: &Config) -> Result<()> {
 					match execute_single_query(
 						&full_input,
 						&model,
-						args.temperature,
+						temperature,
 						&system_prompt,
+						&images,
 						&clean_config,
 					)
 					.await
 					{
 						Ok(response) => {
-							print_response(&response.content, args.raw, config);
-							println!(); // Add spacing between responses
+							emit_response(&response, &model, args, config)?;
+							if args.output == OutputFormat::Text {
+								println!(); // Add spacing between responses
+							}
 						}
 						Err(e) => {
 							eprintln!("Error: {}", e);
@@ -380,16 +506,41 @@ pub async fn execute(args: &AskArgs, config: &Config) -> Result<()> {
 			}
 		}
 
-		return Ok(());
+		Ok(())
 	}
 }
 
+// Helper function to load --image attachments, rejecting models without vision support
+async fn load_images(paths: &[String], model: &str) -> Result<Vec<ImageAttachment>> {
+	if paths.is_empty() {
+		return Ok(Vec::new());
+	}
+
+	let (provider, model_name) = ProviderFactory::get_provider_for_model(model)?;
+	if !provider.supports_vision(&model_name) {
+		return Err(anyhow::anyhow!(
+			"Model '{}' does not support image inputs",
+			model
+		));
+	}
+
+	let mut images = Vec::with_capacity(paths.len());
+	for path in paths {
+		let image = ImageProcessor::load(path)
+			.await
+			.map_err(|e| anyhow::anyhow!("Failed to load image '{}': {}", path, e))?;
+		images.push(image);
+	}
+	Ok(images)
+}
+
 // Helper function to execute a single query
 async fn execute_single_query(
 	input: &str,
 	model: &str,
 	temperature: f32,
 	system_prompt: &str,
+	images: &[ImageAttachment],
 	config: &Config,
 ) -> Result<ProviderResponse> {
 	// Create messages
@@ -406,6 +557,8 @@ async fn execute_single_query(
 			name: None,
 			tool_calls: None,
 			images: None,
+			attachments: None,
+			pinned: false,
 		},
 		Message {
 			role: "user".to_string(),
@@ -418,7 +571,13 @@ async fn execute_single_query(
 			tool_call_id: None,
 			name: None,
 			tool_calls: None,
-			images: None,
+			images: if images.is_empty() {
+				None
+			} else {
+				Some(images.to_vec())
+			},
+			attachments: None,
+			pinned: false,
 		},
 	];
 
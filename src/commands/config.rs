@@ -271,6 +271,9 @@ pub fn execute(args: &ConfigArgs, mut config: Config) -> Result<(), anyhow::Erro
 						"type" => match value.to_lowercase().as_str() {
 							"http" => server.connection_type = McpConnectionType::Http,
 							"stdin" => server.connection_type = McpConnectionType::Stdin,
+							"websocket" | "ws" => {
+								server.connection_type = McpConnectionType::WebSocket
+							}
 							"builtin" => server.connection_type = McpConnectionType::Builtin,
 							_ => println!("Unknown server type: {}, defaulting to HTTP", value),
 						},
@@ -296,6 +299,12 @@ pub fn execute(args: &ConfigArgs, mut config: Config) -> Result<(), anyhow::Erro
 						return Ok(());
 					}
 				}
+				McpConnectionType::WebSocket => {
+					if server.url.is_none() {
+						println!("Error: url must be specified for WebSocket MCP server");
+						return Ok(());
+					}
+				}
 				McpConnectionType::Builtin => {
 					// Built-in servers are always valid
 				}
@@ -432,7 +441,9 @@ pub fn execute(args: &ConfigArgs, mut config: Config) -> Result<(), anyhow::Erro
 						"agent" => println!("  - {} (built-in agent tool) - available", name),
 						_ => println!("  - {} (built-in tools) - available", name),
 					},
-					McpConnectionType::Http | McpConnectionType::Stdin => {
+					McpConnectionType::Http
+					| McpConnectionType::Stdin
+					| McpConnectionType::WebSocket => {
 						if name == "octocode" {
 							// Check if octocode binary is available
 							use std::process::Command;
@@ -580,10 +591,92 @@ fn show_configuration(config: &Config) -> Result<(), anyhow::Error> {
 		"  MCP response warning:      {} tokens",
 		config.mcp_response_warning_threshold
 	);
+	println!(
+		"  MCP response max tokens:   {}",
+		if config.mcp_response_max_tokens > 0 {
+			format!("{} tokens", config.mcp_response_max_tokens)
+		} else {
+			"disabled".to_string()
+		}
+	);
 	println!(
 		"  Max request tokens:        {} tokens",
 		config.max_request_tokens_threshold
 	);
+	println!(
+		"  Max tool iterations:       {}",
+		if config.max_tool_iterations > 0 {
+			config.max_tool_iterations.to_string()
+		} else {
+			"unlimited".to_string()
+		}
+	);
+	println!(
+		"  API timeout:               {} seconds",
+		config.api_timeout_seconds
+	);
+	println!(
+		"  MCP restarts:              max {}, backoff {} ms",
+		config.mcp_max_restarts, config.mcp_restart_backoff_ms
+	);
+	println!(
+		"  Strict tool schemas:       {}",
+		if config.strict_tool_schemas {
+			"enabled"
+		} else {
+			"disabled"
+		}
+	);
+	println!(
+		"  Reasoning effort:          {}",
+		config
+			.reasoning_effort
+			.map_or("provider default".to_string(), |effort| effort.to_string())
+	);
+	println!(
+		"  Shell policy:              {} allowed, {} denied, default {}",
+		config.shell_policy.allow.len(),
+		config.shell_policy.deny.len(),
+		config.shell_policy.default
+	);
+	println!(
+		"  Shell dry run:             {}",
+		if config.shell_dry_run {
+			"enabled"
+		} else {
+			"disabled"
+		}
+	);
+	println!(
+		"  Test command:              {} (timeout {}s)",
+		config.test_command.as_deref().unwrap_or("auto-detect"),
+		config.test_timeout_seconds
+	);
+	println!(
+		"  Scratch directory:         {}",
+		config.scratch_dir.as_deref().unwrap_or("project directory")
+	);
+	println!(
+		"  Filesystem sandbox:        {}",
+		config.fs_sandbox_root.as_deref().unwrap_or("disabled")
+	);
+	if config.redact_secrets {
+		println!(
+			"  Secret redaction:          enabled ({} custom pattern(s))",
+			config.redact_patterns.len()
+		);
+	} else {
+		println!("  Secret redaction:          disabled");
+	}
+	println!("  Tool approval:             {}", config.tool_approval);
+	println!(
+		"  Stop sequences:            {}",
+		if config.stop_sequences.is_empty() {
+			"none".to_string()
+		} else {
+			format!("{:?}", config.stop_sequences)
+		}
+	);
 	println!(
 		"  Auto-truncation:           {}",
 		if config.enable_auto_truncation {
@@ -592,6 +685,18 @@ fn show_configuration(config: &Config) -> Result<(), anyhow::Error> {
 			"disabled"
 		}
 	);
+	println!(
+		"  Auto-compaction:           {}",
+		if config.enable_auto_compaction {
+			"enabled"
+		} else {
+			"disabled"
+		}
+	);
+	println!(
+		"  Context reduction:         {}",
+		config.context_reduction_strategy
+	);
 	println!(
 		"  Cache threshold:           {} tokens",
 		config.cache_tokens_threshold
@@ -600,6 +705,75 @@ fn show_configuration(config: &Config) -> Result<(), anyhow::Error> {
 		"  Cache timeout:             {} seconds",
 		config.cache_timeout_seconds
 	);
+	println!("  Max cache markers:         {}", config.max_cache_markers);
+	println!(
+		"  Tool definition cache:     {}",
+		if config.cache_tool_definitions {
+			"enabled"
+		} else {
+			"disabled"
+		}
+	);
+	if config.autosave_interval_seconds == 0 {
+		println!("  Autosave:                  disabled");
+	} else {
+		println!(
+			"  Autosave:                  every {} seconds",
+			config.autosave_interval_seconds
+		);
+	}
+	println!(
+		"  Dedupe user messages:      {}",
+		if config.dedupe_user_messages {
+			"enabled"
+		} else {
+			"disabled"
+		}
+	);
+	if config.session_sidecar_threshold == 0 {
+		println!("  Session sidecar files:     disabled");
+	} else {
+		println!(
+			"  Session sidecar files:     responses over {} bytes",
+			config.session_sidecar_threshold
+		);
+	}
+	if config.rate_limits.is_empty() {
+		println!("  Rate limits:               none");
+	} else {
+		for limit in &config.rate_limits {
+			println!(
+				"  Rate limit ({}):{}{} req/min{}",
+				limit.provider,
+				" ".repeat(13usize.saturating_sub(limit.provider.len())),
+				limit.requests_per_minute,
+				limit
+					.tokens_per_minute
+					.map(|tpm| format!(", {} tokens/min", tpm))
+					.unwrap_or_default()
+			);
+		}
+	}
+	if config.tool_cache.is_empty() {
+		println!("  Tool result cache:         none");
+	} else {
+		for rule in &config.tool_cache {
+			println!(
+				"  Tool result cache:         {} ({}s)",
+				rule.tool, rule.ttl_seconds
+			);
+		}
+	}
+	if config.model_metadata.is_empty() {
+		println!("  Model metadata overrides:  none");
+	} else {
+		for entry in &config.model_metadata {
+			println!(
+				"  Model metadata:            {}:{} ({} token context)",
+				entry.provider, entry.model, entry.context_window
+			);
+		}
+	}
 	println!();
 
 	// Provider API keys (from environment variables only)
@@ -744,7 +918,7 @@ fn show_mcp_servers(servers: &Vec<McpServerConfig>) {
 				"agent" => println!("      🤖 {} (built-in agent tool)", name),
 				_ => println!("      📦 {} (built-in tools)", name),
 			},
-			McpConnectionType::Http | McpConnectionType::Stdin => {
+			McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::WebSocket => {
 				if name == "octocode" {
 					// Check if octocode binary is available
 					use std::process::Command;
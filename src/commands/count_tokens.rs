@@ -0,0 +1,92 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Count-tokens command - token count and input cost of files for a model
+
+use anyhow::Result;
+use clap::Args;
+use colored::*;
+use octomind::config::Config;
+use octomind::session::estimate_tokens;
+use std::io::Read;
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct CountTokensArgs {
+	/// Model whose tokenizer and pricing are used, as provider:model (defaults to the configured model)
+	#[arg(long)]
+	pub model: Option<String>,
+
+	/// File to count (can be used multiple times)
+	#[arg(short = 'f', long = "file", value_name = "PATH")]
+	pub files: Vec<PathBuf>,
+
+	/// Also count text read from stdin
+	#[arg(long)]
+	pub stdin: bool,
+}
+
+pub fn execute(args: &CountTokensArgs, config: &Config) -> Result<()> {
+	if args.files.is_empty() && !args.stdin {
+		return Err(anyhow::anyhow!(
+			"Nothing to count: pass --file <PATH> or --stdin"
+		));
+	}
+
+	// --model has already been applied to the config
+	let model = config.get_effective_model();
+
+	let mut inputs: Vec<(String, String)> = Vec::new();
+	for path in &args.files {
+		let content = std::fs::read_to_string(path)
+			.map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+		inputs.push((path.display().to_string(), content));
+	}
+	if args.stdin {
+		let mut content = String::new();
+		std::io::stdin().read_to_string(&mut content)?;
+		inputs.push(("<stdin>".to_string(), content));
+	}
+
+	println!("{} {}", "Model:".yellow(), model.bright_white());
+
+	let mut total_tokens = 0;
+	for (label, content) in &inputs {
+		let tokens = estimate_tokens(content, &model);
+		total_tokens += tokens;
+		println!("  {}: {}", label, format_count(tokens, &model));
+	}
+
+	if inputs.len() > 1 {
+		println!(
+			"  {}: {}",
+			"TOTAL".bold(),
+			format_count(total_tokens, &model)
+		);
+	}
+
+	Ok(())
+}
+
+// "1234 tokens, $0.00370 input" or a note when the model has no pricing
+fn format_count(tokens: usize, model: &str) -> String {
+	match octomind::providers::estimate_input_cost(model, tokens as u64) {
+		Some(cost) => format!("{} tokens, ${:.5} input", tokens, cost),
+		None => format!(
+			"{} tokens {}",
+			tokens,
+			"(no pricing for this model)".dimmed()
+		),
+	}
+}
@@ -0,0 +1,127 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Doctor command - preflight check of provider credentials and MCP servers
+
+use anyhow::Result;
+use clap::Args;
+use colored::*;
+use octomind::config::{Config, McpConnectionType};
+use octomind::providers::ProviderFactory;
+use octomind::session::Message;
+
+#[derive(Args)]
+pub struct DoctorArgs {
+	/// Skip checking external MCP servers
+	#[arg(long)]
+	pub skip_mcp: bool,
+}
+
+pub async fn execute(args: &DoctorArgs, config: &Config) -> Result<()> {
+	let mut failures = 0;
+
+	println!("{}", "Providers:".bright_blue().bold());
+	for model in configured_models(config) {
+		match check_model(&model, config).await {
+			Ok(()) => println!("  ✅ {}", model.bright_green()),
+			Err(e) => {
+				failures += 1;
+				println!("  ❌ {}: {}", model.bright_red(), e);
+			}
+		}
+	}
+
+	if !args.skip_mcp {
+		println!();
+		println!("{}", "MCP servers:".bright_blue().bold());
+
+		// Builtin servers run in-process and need no check
+		let external_servers: Vec<_> = config
+			.mcp
+			.servers
+			.iter()
+			.filter(|server| server.connection_type != McpConnectionType::Builtin)
+			.collect();
+
+		if external_servers.is_empty() {
+			println!("  {}", "No external MCP servers configured".dimmed());
+		}
+
+		for server in external_servers {
+			match octomind::mcp::server::get_server_functions(server).await {
+				Ok(functions) => println!(
+					"  ✅ {} {}",
+					server.name.bright_green(),
+					format!("({} tools)", functions.len()).dimmed()
+				),
+				Err(e) => {
+					failures += 1;
+					println!("  ❌ {}: {}", server.name.bright_red(), e);
+				}
+			}
+		}
+	}
+
+	println!();
+	if failures > 0 {
+		return Err(anyhow::anyhow!("{} check(s) failed", failures));
+	}
+	println!("{}", "All checks passed".bright_green());
+	Ok(())
+}
+
+// The root model plus any layer-specific models, without duplicates
+fn configured_models(config: &Config) -> Vec<String> {
+	let mut models = vec![config.get_effective_model()];
+	if let Some(layers) = &config.layers {
+		for model in layers.iter().filter_map(|layer| layer.model.clone()) {
+			if !models.contains(&model) {
+				models.push(model);
+			}
+		}
+	}
+	models
+}
+
+// Resolve credentials and send the smallest possible authenticated request
+async fn check_model(model: &str, config: &Config) -> Result<()> {
+	let (provider, model_name) = ProviderFactory::get_provider_for_model(model)?;
+	provider.get_api_key(config)?;
+
+	let messages = vec![Message {
+		role: "user".to_string(),
+		content: "Reply with OK.".to_string(),
+		timestamp: std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_secs(),
+		cached: false,
+		tool_call_id: None,
+		name: None,
+		tool_calls: None,
+		images: None,
+		attachments: None,
+		pinned: false,
+	}];
+
+	// Probe the model alone: tool definitions would cost tokens and can fail for reasons
+	// unrelated to the model
+	let mut probe_config = config.clone();
+	probe_config.mcp.servers.clear();
+
+	provider
+		.chat_completion(&messages, &model_name, 0.0, &probe_config, None)
+		.await?;
+	Ok(())
+}
@@ -0,0 +1,99 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// MCP command - inspect the MCP servers octomind manages
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use colored::*;
+use octomind::config::Config;
+use octomind::mcp::process::ServerHealth;
+
+#[derive(Args)]
+pub struct McpArgs {
+	#[command(subcommand)]
+	pub command: McpCommand,
+}
+
+#[derive(Subcommand)]
+pub enum McpCommand {
+	/// Print the status of the MCP servers of a running session, or start the role's
+	/// servers and check them once when no session is running
+	Status {
+		/// Print a JSON array (name, health, restart_count, consecutive_failures, last_health_check)
+		#[arg(long)]
+		json: bool,
+
+		/// Role whose MCP servers are checked when no session is running
+		#[arg(long, default_value = "developer")]
+		role: String,
+	},
+}
+
+pub async fn execute(args: &McpArgs, config: &Config) -> Result<()> {
+	match &args.command {
+		McpCommand::Status { json, role } => status(*json, role, config).await,
+	}
+}
+
+async fn status(json: bool, role: &str, config: &Config) -> Result<()> {
+	// A running session publishes what its health monitor sees, restarts included
+	let snapshot = match octomind::mcp::health_monitor::read_published_status() {
+		Some(published) => published.servers,
+		None => {
+			// No session is monitoring servers, so start them here and run one check
+			// (warnings go to stderr so --json output stays parseable)
+			eprintln!(
+				"{}",
+				"No running session found; starting MCP servers to check them".dimmed()
+			);
+			let config_for_role = config.get_merged_config_for_role(role);
+			if let Err(e) = octomind::mcp::initialize_servers_for_role(&config_for_role).await {
+				eprintln!("Warning: Failed to initialize MCP servers: {}", e);
+			}
+			octomind::mcp::health_monitor::force_health_check(&config_for_role).await?;
+			octomind::mcp::server::get_server_status_snapshot()
+		}
+	};
+
+	if json {
+		println!("{}", serde_json::to_string(&snapshot)?);
+		return Ok(());
+	}
+
+	if snapshot.is_empty() {
+		println!(
+			"{}",
+			"No external MCP servers configured for this role".dimmed()
+		);
+		return Ok(());
+	}
+
+	for server in &snapshot {
+		let health_display = match server.health {
+			ServerHealth::Running => "✅ Running".green(),
+			ServerHealth::Dead => "❌ Dead".red(),
+			ServerHealth::Restarting => "🔄 Restarting".yellow(),
+			ServerHealth::Failed => "💥 Failed".bright_red(),
+		};
+		println!("{}: {}", server.name.bright_white().bold(), health_display);
+		if server.restart_count > 0 || server.consecutive_failures > 0 {
+			println!(
+				"  Restarts: {}, consecutive failures: {}",
+				server.restart_count, server.consecutive_failures
+			);
+		}
+	}
+	Ok(())
+}
@@ -14,13 +14,96 @@
 
 pub mod ask;
 pub mod config;
+pub mod count_tokens;
+pub mod doctor;
+pub mod mcp;
 pub mod session;
 pub mod shell;
+pub mod stats;
 pub mod vars;
 
 // Re-export all the command structs and enums
 pub use ask::AskArgs;
 pub use config::ConfigArgs;
+pub use count_tokens::CountTokensArgs;
+pub use doctor::DoctorArgs;
+pub use mcp::McpArgs;
 pub use session::SessionArgs;
 pub use shell::ShellArgs;
+pub use stats::StatsArgs;
 pub use vars::VarsArgs;
+
+use octomind::config::Config;
+
+// Apply a --model override to the config for this run so everything inheriting the system
+// model (including layers) picks it up
+pub fn apply_model_override(config: &mut Config, model: Option<&String>) -> anyhow::Result<()> {
+	if let Some(model) = model {
+		octomind::providers::ProviderFactory::get_provider_for_model(model)
+			.map_err(|e| anyhow::anyhow!("Invalid --model '{}': {}", model, e))?;
+		config.model = model.clone();
+		// stderr keeps machine-readable stdout (e.g. ask --output json) clean
+		eprintln!("Using model: {}", model);
+	}
+	Ok(())
+}
+
+// Resolve a --temperature override against the role default, rejecting out-of-range values
+pub fn resolve_temperature(flag: Option<f32>, default: f32) -> anyhow::Result<f32> {
+	match flag {
+		Some(t) if !(0.0..=2.0).contains(&t) => Err(anyhow::anyhow!(
+			"Invalid temperature {}. Must be between 0.0 and 2.0",
+			t
+		)),
+		Some(t) => Ok(t),
+		None => Ok(default),
+	}
+}
+
+// Wrap a command's built-in system prompt the way role prompts are wrapped, then add the
+// --append-system text last. Order: system_prefix, prompt, system_suffix, appended text.
+// None of it goes through placeholder processing.
+pub fn finish_system_prompt(system_prompt: &str, config: &Config, append: Option<&str>) -> String {
+	let append = append.map(str::trim).filter(|text| !text.is_empty());
+	[
+		config.system_prefix.as_deref(),
+		Some(system_prompt),
+		config.system_suffix.as_deref(),
+		append,
+	]
+	.into_iter()
+	.flatten()
+	.collect::<Vec<_>>()
+	.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_finish_system_prompt_order() {
+		let mut config: Config =
+			toml::from_str(include_str!("../../config-templates/default.toml")).unwrap();
+		assert_eq!(
+			finish_system_prompt("You are a helpful assistant.", &config, None),
+			"You are a helpful assistant."
+		);
+
+		config.system_suffix = Some("Never commit secrets.".to_string());
+		assert_eq!(
+			finish_system_prompt(
+				"You are a helpful assistant.",
+				&config,
+				Some("  Respond in JSON only. %{DATE}\n")
+			),
+			"You are a helpful assistant.\n\nNever commit secrets.\n\nRespond in JSON only. %{DATE}"
+		);
+
+		config.system_prefix = Some("You work for Example Corp.".to_string());
+		assert_eq!(
+			finish_system_prompt("You are a helpful assistant.", &config, Some("  ")),
+			"You work for Example Corp.\n\nYou are a helpful assistant.\n\nNever commit secrets."
+		);
+	}
+}
@@ -12,9 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use anyhow::Result;
 use clap::Args;
+use colored::Colorize;
+use octomind::config::Config;
+use octomind::session;
+use std::path::{Path, PathBuf};
 
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 pub struct SessionArgs {
 	/// Name of the session to start or resume
 	#[arg(long, short)]
@@ -24,7 +29,11 @@ pub struct SessionArgs {
 	#[arg(long, short)]
 	pub resume: Option<String>,
 
-	/// Use a specific model instead of the one configured in config (runtime only, not saved)
+	/// Resume the most recently active session
+	#[arg(long = "continue", conflicts_with_all = ["name", "resume", "replay"])]
+	pub continue_last: bool,
+
+	/// Override the configured model, as provider:model (runtime only, not saved)
 	#[arg(long)]
 	pub model: Option<String>,
 
@@ -35,8 +44,207 @@ pub struct SessionArgs {
 	/// Session role: developer (default with layers and tools) or assistant (simple chat without tools)
 	#[arg(long, default_value = "developer")]
 	pub role: String,
+
+	/// Append newline-delimited JSON events (requests, tool calls, errors) to this file for monitoring
+	#[arg(long, value_name = "PATH")]
+	pub event_log: Option<PathBuf>,
+
+	/// Block file edits and shell commands; the assistant can only read and search
+	#[arg(long)]
+	pub read_only: bool,
+
+	/// Chat without any MCP tools: no tool definitions are sent and the system prompt omits the tool list
+	#[arg(long)]
+	pub no_tools: bool,
+
+	/// Show and store tool results without masking secrets (overrides redact_secrets)
+	#[arg(long)]
+	pub no_redact: bool,
+
+	/// Sampling seed sent to providers that support it (OpenAI, OpenRouter); kept on resume and replay
+	#[arg(long)]
+	pub seed: Option<u64>,
+
+	/// Stop generating when the model outputs this text (repeatable; replaces the configured stop_sequences)
+	#[arg(long, value_name = "SEQUENCE")]
+	pub stop: Vec<String>,
+
+	/// Re-run the user prompts of this saved session in a fresh session (combine with --model to compare models)
+	#[arg(long, value_name = "SESSION", conflicts_with = "resume")]
+	pub replay: Option<String>,
+
+	/// Export the session given by --resume or --name to a Markdown file and exit
+	/// (a glob such as "251016-*" exports every match into PATH as a directory)
+	#[arg(long, value_name = "PATH")]
+	pub export: Option<PathBuf>,
+
+	/// Print a combined usage report for these sessions (names or globs like "251016-*") and exit
+	#[arg(long, value_name = "SESSION", num_args = 1..)]
+	pub report: Vec<String>,
+
+	/// List saved sessions (newest first) and exit
+	#[arg(long)]
+	pub list: bool,
+
+	/// With --list, only show sessions created within this window (e.g. 30m, 24h, 7d)
+	#[arg(long, value_name = "DURATION", requires = "list")]
+	pub since: Option<String>,
+
+	/// Search message contents of all saved sessions and exit
+	#[arg(long, value_name = "QUERY")]
+	pub search: Option<String>,
+
+	/// With --search, treat the query as a regular expression instead of literal text
+	#[arg(long, requires = "search")]
+	pub regex: bool,
+}
+
+// Export an existing session as a Markdown transcript without starting the interactive loop
+pub fn export(args: &SessionArgs, path: &Path) -> Result<()> {
+	let session_pattern = args.resume.as_ref().or(args.name.as_ref()).ok_or_else(|| {
+		anyhow::anyhow!("Specify the session to export with --resume <name> or --name <name>")
+	})?;
+
+	let session_names = session::resolve_session_names(std::slice::from_ref(session_pattern))?;
+	let is_single = session_names.len() == 1 && session_names[0] == *session_pattern;
+	if !is_single {
+		std::fs::create_dir_all(path)?;
+	}
+
+	for session_name in &session_names {
+		let session_file = session::get_sessions_dir()?.join(format!("{}.jsonl", session_name));
+		let loaded = session::load_session(&session_file)
+			.map_err(|e| anyhow::anyhow!("Failed to load session '{}': {}", session_name, e))?;
+
+		// A glob exports into the directory, one transcript per session
+		let target = if is_single {
+			path.to_path_buf()
+		} else {
+			path.join(format!("{}.md", session_name))
+		};
+		session::export::export_session_to_file(&loaded, &target)?;
+		println!(
+			"{}",
+			format!(
+				"Session '{}' exported to {}",
+				session_name,
+				target.display()
+			)
+			.bright_green()
+		);
+	}
+	Ok(())
+}
+
+// Print one usage report across several sessions without starting the interactive loop
+pub fn report(args: &SessionArgs, config: &Config) -> Result<()> {
+	let sessions_dir = session::get_sessions_dir()?;
+	let mut reports = Vec::new();
+
+	for session_name in session::resolve_session_names(&args.report)? {
+		let session_file = sessions_dir.join(format!("{}.jsonl", session_name));
+		let report =
+			session::report::SessionReport::generate_from_log(&session_file.to_string_lossy())
+				.map_err(|e| anyhow::anyhow!("Failed to read session '{}': {}", session_name, e))?;
+		reports.push((session_name, report));
+	}
+
+	session::report::SessionReport::display_combined(&reports, config);
+	Ok(())
+}
+
+// Turn --continue into --resume of the most recently active session
+pub fn continue_last(args: &SessionArgs) -> Result<SessionArgs> {
+	let name = session::most_recent_session_name()?.ok_or_else(|| {
+		anyhow::anyhow!("No saved sessions to continue - start one with: octomind session")
+	})?;
+
+	let mut resumed = args.clone();
+	resumed.resume = Some(name);
+	resumed.continue_last = false;
+	Ok(resumed)
+}
+
+// List saved sessions without starting the interactive loop
+pub fn list(args: &SessionArgs) -> Result<()> {
+	let mut sessions = session::list_available_sessions()?;
+
+	if let Some(since) = &args.since {
+		let window = session::parse_relative_duration(since)?;
+		sessions = session::filter_sessions_since(sessions, window);
+	}
+
+	if sessions.is_empty() {
+		println!("{}", "No sessions found.".bright_yellow());
+		return Ok(());
+	}
+
+	for (name, info) in &sessions {
+		let created = chrono::DateTime::<chrono::Utc>::from_timestamp(info.created_at as i64, 0)
+			.map(|dt| dt.naive_local().format("%Y-%m-%d %H:%M").to_string())
+			.unwrap_or_default();
+		println!(
+			"{}  {}  {}  ${:.5}",
+			created.dimmed(),
+			name.bright_white(),
+			info.model.cyan(),
+			info.total_cost
+		);
+	}
+
+	Ok(())
+}
+
+// Search all saved sessions for a literal or regex query
+pub fn search(args: &SessionArgs, query: &str) -> Result<()> {
+	let pattern = session::search::build_search_pattern(query, args.regex)?;
+	let matches = session::search::search_sessions(&pattern)?;
+
+	if matches.is_empty() {
+		println!(
+			"{}",
+			format!("No messages matching '{}' found.", query).bright_yellow()
+		);
+		return Ok(());
+	}
+
+	let session_count = matches
+		.iter()
+		.map(|m| &m.session_name)
+		.collect::<std::collections::HashSet<_>>()
+		.len();
+	println!(
+		"{}",
+		format!(
+			"Found {} matching messages in {} sessions",
+			matches.len(),
+			session_count
+		)
+		.bright_cyan()
+	);
+
+	for found in &matches {
+		let time = chrono::DateTime::<chrono::Utc>::from_timestamp(found.timestamp as i64, 0)
+			.map(|dt| dt.naive_local().format("%Y-%m-%d %H:%M").to_string())
+			.unwrap_or_default();
+		println!();
+		println!(
+			"{}  {}  {}",
+			found.session_name.bright_white().bold(),
+			found.role.cyan(),
+			time.dimmed()
+		);
+		println!("  {}", found.snippet);
+	}
+
+	println!();
+	println!(
+		"{}",
+		"Resume a session with: octomind session --resume <name>".dimmed()
+	);
+	Ok(())
 }
 
-// No execute function here since it's handled directly by the session::chat module
+// No execute function for interactive mode since it's handled directly by the session::chat module
 // The module is accessed in main.rs via:
 // session::chat::run_interactive_session(session_args, &store, &config).await?
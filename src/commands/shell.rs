@@ -14,12 +14,15 @@
 
 use anyhow::Result;
 use clap::Args;
-use octomind::config::Config;
+use octomind::config::{Config, ShellPolicyAction};
 use octomind::session::{chat_completion_with_provider, Message};
 use serde::{Deserialize, Serialize};
 use std::fs::OpenOptions;
 use std::io::{self, Read, Write};
 
+// Default temperature for shell command generation when --temperature is not given
+const DEFAULT_SHELL_TEMPERATURE: f32 = 0.3;
+
 // Function to add command to shell history
 fn add_to_shell_history(command: &str) -> Result<()> {
 	// Get the shell and history file path
@@ -83,7 +86,7 @@ pub struct ShellArgs {
 	#[arg(value_name = "DESCRIPTION")]
 	pub description: Option<String>,
 
-	/// Use a specific model instead of the one configured in config (runtime only, not saved)
+	/// Override the configured model, as provider:model (runtime only, not saved)
 	#[arg(long)]
 	pub model: Option<String>,
 
@@ -91,9 +94,14 @@ pub struct ShellArgs {
 	#[arg(long, short)]
 	pub yes: bool,
 
-	/// Temperature for the AI response (0.0 to 1.0, runtime only, not saved)
-	#[arg(long, default_value = "0.3")]
-	pub temperature: f32,
+	/// Temperature for the AI response (0.0 to 2.0, runtime only, not saved)
+	#[arg(long)]
+	pub temperature: Option<f32>,
+
+	/// Append this text to the system prompt for this run (e.g. "Prefer POSIX sh").
+	/// Added last, after the built-in prompt and system_suffix; placeholders are not expanded
+	#[arg(long, value_name = "TEXT")]
+	pub append_system: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -119,6 +127,9 @@ pub async fn execute(args: &ShellArgs, config: &Config) -> Result<()> {
 		std::process::exit(1);
 	}
 
+	// Low default temperature keeps generated commands predictable
+	let temperature = super::resolve_temperature(args.temperature, DEFAULT_SHELL_TEMPERATURE)?;
+
 	// Determine model to use: either from --model flag or effective config model
 	let model = args
 		.model
@@ -131,7 +142,11 @@ pub async fn execute(args: &ShellArgs, config: &Config) -> Result<()> {
 	clean_config.mcp.servers.clear();
 
 	// Create specialized system prompt for shell commands
-	let system_prompt = create_shell_system_prompt();
+	let system_prompt = super::finish_system_prompt(
+		&create_shell_system_prompt(),
+		config,
+		args.append_system.as_deref(),
+	);
 
 	// Create user prompt that asks for structured response
 	let user_prompt = format!(
@@ -158,6 +173,8 @@ pub async fn execute(args: &ShellArgs, config: &Config) -> Result<()> {
 			name: None,
 			tool_calls: None,
 			images: None,
+			attachments: None,
+			pinned: false,
 		},
 		Message {
 			role: "user".to_string(),
@@ -171,12 +188,14 @@ pub async fn execute(args: &ShellArgs, config: &Config) -> Result<()> {
 			name: None,
 			tool_calls: None,
 			images: None,
+			attachments: None,
+			pinned: false,
 		},
 	];
 
 	// Call the AI provider
 	let response =
-		chat_completion_with_provider(&messages, &model, args.temperature, &clean_config).await?;
+		chat_completion_with_provider(&messages, &model, temperature, &clean_config).await?;
 
 	// Parse the JSON response
 	let shell_response: ShellResponse = match serde_json::from_str(&response.content) {
@@ -217,8 +236,19 @@ pub async fn execute(args: &ShellArgs, config: &Config) -> Result<()> {
 		println!("⚠️  Safety notes: {}", safety_notes.yellow());
 	}
 
-	// Ask for confirmation unless --yes flag is used
-	if !args.yes {
+	// Apply the configured shell policy - denied commands never run, even with --yes
+	let action = config.shell_policy.decide(&shell_response.command);
+	if action == ShellPolicyAction::Deny {
+		use colored::Colorize;
+		eprintln!("{}", "⛔ Command rejected by shell_policy.".red());
+		std::process::exit(1);
+	}
+	if action == ShellPolicyAction::Allow && !args.yes {
+		println!("\n✅ Allowed by shell_policy, no confirmation needed.");
+	}
+
+	// Ask for confirmation unless --yes flag is used or the policy allows the command
+	if !args.yes && action == ShellPolicyAction::Confirm {
 		print!("\n❓ Execute this command? [y/N]: ");
 		io::Write::flush(&mut io::stdout())?;
 
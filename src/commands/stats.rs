@@ -0,0 +1,161 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Stats command - aggregate cost and token usage across all saved sessions
+
+use anyhow::Result;
+use clap::Args;
+use colored::*;
+use octomind::session::{self, SessionInfo};
+use std::collections::BTreeMap;
+
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+	/// Only include sessions created within this window (e.g. 24h, 7d, 30d)
+	#[arg(long, value_name = "DURATION")]
+	pub since: Option<String>,
+
+	/// Number of most expensive sessions to list
+	#[arg(long, value_name = "N", default_value_t = 10)]
+	pub top: usize,
+
+	/// Also break the totals down by model
+	#[arg(long)]
+	pub by_model: bool,
+}
+
+// Totals over a group of sessions
+#[derive(Default)]
+struct Totals {
+	sessions: usize,
+	cost: f64,
+	input_tokens: u64,
+	output_tokens: u64,
+	cached_tokens: u64,
+	tool_calls: u64,
+}
+
+impl Totals {
+	fn add(&mut self, info: &SessionInfo) {
+		self.sessions += 1;
+		self.cost += info.total_cost;
+		self.input_tokens += info.input_tokens;
+		self.output_tokens += info.output_tokens;
+		self.cached_tokens += info.cached_tokens;
+		self.tool_calls += info.tool_calls;
+	}
+}
+
+pub fn execute(args: &StatsArgs) -> Result<()> {
+	let mut sessions = session::list_available_sessions()?;
+	if let Some(since) = &args.since {
+		let window = session::parse_relative_duration(since)?;
+		sessions = session::filter_sessions_since(sessions, window);
+	}
+
+	if sessions.is_empty() {
+		println!("{}", "No sessions found.".bright_yellow());
+		return Ok(());
+	}
+
+	// The first SUMMARY line is written when a session starts; use the latest checkpoint
+	let sessions_dir = session::get_sessions_dir()?;
+	let sessions: Vec<(String, SessionInfo)> = sessions
+		.into_iter()
+		.map(|(name, info)| {
+			let session_file = sessions_dir.join(format!("{}.jsonl", name));
+			let latest = session::latest_session_info(&session_file)
+				.ok()
+				.flatten()
+				.unwrap_or(info);
+			(name, latest)
+		})
+		.collect();
+
+	let mut totals = Totals::default();
+	for (_, info) in &sessions {
+		totals.add(info);
+	}
+
+	let window = args
+		.since
+		.as_ref()
+		.map_or("all time".to_string(), |since| format!("last {}", since));
+	println!("{} ({})", "Session statistics".bright_cyan().bold(), window);
+	println!("  Sessions:       {}", totals.sessions);
+	println!(
+		"  Total cost:     {}",
+		format!("${:.5}", totals.cost).bright_green()
+	);
+	println!("  Input tokens:   {}", totals.input_tokens);
+	println!("  Output tokens:  {}", totals.output_tokens);
+	println!("  Cached tokens:  {}", totals.cached_tokens);
+	println!("  Tool calls:     {}", totals.tool_calls);
+
+	if args.by_model {
+		let mut by_model: BTreeMap<&str, Totals> = BTreeMap::new();
+		for (_, info) in &sessions {
+			by_model.entry(info.model.as_str()).or_default().add(info);
+		}
+		let mut rows: Vec<(&str, Totals)> = by_model.into_iter().collect();
+		rows.sort_by(|a, b| b.1.cost.total_cmp(&a.1.cost));
+
+		println!("\n{}", "By model".bright_cyan().bold());
+		println!(
+			"  {:>12}  {:>8}  {:>12}  {:>12}  {:>12}  {:>10}  Model",
+			"Cost", "Sessions", "Input", "Output", "Cached", "Tool calls"
+		);
+		for (model, group) in rows {
+			println!(
+				"  {:>12}  {:>8}  {:>12}  {:>12}  {:>12}  {:>10}  {}",
+				format!("${:.5}", group.cost),
+				group.sessions,
+				group.input_tokens,
+				group.output_tokens,
+				group.cached_tokens,
+				group.tool_calls,
+				model.cyan()
+			);
+		}
+	}
+
+	if args.top > 0 {
+		let mut ranked: Vec<&(String, SessionInfo)> = sessions.iter().collect();
+		ranked.sort_by(|a, b| b.1.total_cost.total_cmp(&a.1.total_cost));
+
+		println!("\n{}", "Most expensive sessions".bright_cyan().bold());
+		println!(
+			"  {:>12}  {:>12}  {:>12}  {:>10}  {:<16}  Session",
+			"Cost", "Input", "Output", "Tool calls", "Created"
+		);
+		for (name, info) in ranked.into_iter().take(args.top) {
+			let created =
+				chrono::DateTime::<chrono::Utc>::from_timestamp(info.created_at as i64, 0)
+					.map(|dt| dt.naive_local().format("%Y-%m-%d %H:%M").to_string())
+					.unwrap_or_default();
+			println!(
+				"  {:>12}  {:>12}  {:>12}  {:>10}  {:<16}  {} {}",
+				format!("${:.5}", info.total_cost),
+				info.input_tokens,
+				info.output_tokens,
+				info.tool_calls,
+				created,
+				name.bright_white(),
+				info.model.dimmed()
+			);
+		}
+	}
+
+	Ok(())
+}
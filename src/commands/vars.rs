@@ -28,11 +28,22 @@ pub struct VarsArgs {
 	/// Show full expanded values for placeholders
 	#[arg(short, long)]
 	pub expand: bool,
+
+	/// Print a JSON object mapping each placeholder to its resolved value (for editor integrations)
+	#[arg(long, conflicts_with_all = ["preview", "expand"])]
+	pub json: bool,
 }
 
-pub async fn execute(args: &VarsArgs, _config: &Config) -> Result<()> {
+pub async fn execute(args: &VarsArgs, config: &Config) -> Result<()> {
 	let current_dir = env::current_dir()?;
-	let placeholders = get_all_placeholders(&current_dir).await;
+	let placeholders = get_all_placeholders(&current_dir, &config.project_context).await;
+
+	if args.json {
+		// BTreeMap keeps keys sorted so output is stable between runs
+		let sorted: std::collections::BTreeMap<_, _> = placeholders.iter().collect();
+		println!("{}", serde_json::to_string_pretty(&sorted)?);
+		return Ok(());
+	}
 
 	println!("{}", "Available placeholders:".bright_blue().bold());
 	println!();
@@ -54,7 +65,7 @@ pub async fn execute(args: &VarsArgs, _config: &Config) -> Result<()> {
 			} else {
 				// Show preview (current behavior)
 				let lines: Vec<&str> = value.lines().collect();
-				let tokens = crate::session::estimate_tokens(value);
+				let tokens = crate::session::estimate_tokens(value, &config.get_effective_model());
 				if lines.len() <= 5 && tokens <= 200 {
 					// Short value, show inline
 					println!("  {}", value.trim());
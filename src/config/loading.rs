@@ -18,7 +18,10 @@ use std::fs;
 use super::Config;
 
 impl Config {
-	fn initialize_config(&mut self) {}
+	fn initialize_config(&mut self) {
+		// Providers consult the model table without access to the config
+		crate::providers::model_metadata::set_user_overrides(&self.model_metadata);
+	}
 
 	pub fn ensure_octomind_dir() -> Result<std::path::PathBuf> {
 		// Use the system-wide directory
@@ -75,6 +78,37 @@ impl Config {
 		Ok(config)
 	}
 
+	/// Read role system prompts given as `system_file` into the role map. The roles array
+	/// keeps the file reference, so saving the config does not inline the prompt.
+	fn load_role_system_files(&mut self) -> Result<()> {
+		let config_dir = self
+			.config_path
+			.as_deref()
+			.and_then(std::path::Path::parent)
+			.map(std::path::Path::to_path_buf)
+			.unwrap_or_default();
+
+		for role in self.role_map.values_mut() {
+			// Setting both is rejected by validation
+			if role.config.system.is_some() {
+				continue;
+			}
+			let Some(system_file) = &role.config.system_file else {
+				continue;
+			};
+
+			let path = config_dir.join(system_file);
+			let prompt = fs::read_to_string(&path).context(format!(
+				"Failed to read system_file for role '{}' from {}",
+				role.name,
+				path.display()
+			))?;
+			role.config.system = Some(prompt);
+		}
+
+		Ok(())
+	}
+
 	/// Load configuration from the system-wide config file with strict validation
 	pub fn load() -> Result<Self> {
 		// Use the new system-wide config file path
@@ -109,6 +143,7 @@ impl Config {
 
 		// Build role map from roles array
 		config.build_role_map();
+		config.load_role_system_files()?;
 
 		// REMOVED: API key population from environment variables
 		// API keys are now read directly from ENV when needed by providers
@@ -171,6 +206,7 @@ impl Config {
 
 		// Build role map from roles array
 		config.build_role_map();
+		config.load_role_system_files()?;
 
 		// Validate the configuration
 		config.validate()?;
@@ -482,4 +518,105 @@ tools = []
 		assert!(!server_names.contains(&"developer")); // Should not be included
 		assert!(!server_names.contains(&"filesystem")); // Should not be included
 	}
+
+	#[test]
+	fn test_role_system_file() {
+		let test_config = r#"
+version = 1
+log_level = "none"
+model = "openrouter:anthropic/claude-sonnet-4"
+mcp_response_warning_threshold = 20000
+max_request_tokens_threshold = 20000
+enable_auto_truncation = false
+cache_tokens_threshold = 2048
+cache_timeout_seconds = 240
+use_long_system_cache = true
+enable_markdown_rendering = true
+markdown_theme = "default"
+max_session_spending_threshold = 0.0
+
+[[roles]]
+name = "tester"
+enable_layers = false
+temperature = 0.7
+layer_refs = []
+system_file = "prompts/tester.md"
+mcp = { server_refs = [], allowed_tools = [] }
+
+[mcp]
+allowed_tools = []
+servers = []
+"#;
+
+		let temp = tempfile::tempdir().unwrap();
+		let dir = temp.path();
+		fs::create_dir_all(dir.join("prompts")).unwrap();
+		fs::write(dir.join("prompts/tester.md"), "You test things.").unwrap();
+
+		let mut config: Config = toml::from_str(test_config).expect("Failed to parse test config");
+		config.config_path = Some(dir.join("config.toml"));
+		config.build_role_map();
+		config.load_role_system_files().unwrap();
+
+		let (_, _, _, _, system_prompt) = config.get_role_config("tester");
+		assert_eq!(system_prompt.map(String::as_str), Some("You test things."));
+		// The roles array keeps the reference so saving does not inline the prompt
+		assert!(config.roles[0].config.system.is_none());
+
+		assert!(config.validate().is_ok());
+
+		// Inline system and system_file together are rejected
+		config.roles[0].config.system = Some("Inline prompt".to_string());
+		let error = config.validate().unwrap_err();
+		assert!(error.to_string().contains("system_file"));
+	}
+
+	#[test]
+	fn test_role_denied_tools_extend_global() {
+		let test_config = r#"
+version = 1
+log_level = "none"
+model = "openrouter:anthropic/claude-sonnet-4"
+mcp_response_warning_threshold = 20000
+max_request_tokens_threshold = 20000
+enable_auto_truncation = false
+cache_tokens_threshold = 2048
+cache_timeout_seconds = 240
+use_long_system_cache = true
+enable_markdown_rendering = true
+markdown_theme = "default"
+max_session_spending_threshold = 0.0
+
+[[roles]]
+name = "tester"
+enable_layers = false
+temperature = 0.7
+layer_refs = []
+mcp = { server_refs = [], allowed_tools = [], denied_tools = ["text_editor", "shell"] }
+
+[[roles]]
+name = "assistant"
+enable_layers = false
+temperature = 0.7
+layer_refs = []
+mcp = { server_refs = [], allowed_tools = [] }
+
+[mcp]
+allowed_tools = []
+denied_tools = ["shell"]
+servers = []
+"#;
+
+		let mut config: Config = toml::from_str(test_config).expect("Failed to parse test config");
+		config.build_role_map();
+
+		// The role's list adds to the global one without repeating shared entries
+		let merged_config = config.get_merged_config_for_role("tester");
+		assert_eq!(merged_config.mcp.denied_tools, vec!["shell", "text_editor"]);
+
+		// A role without its own list keeps the global denials
+		let merged_config = config.get_merged_config_for_role("assistant");
+		assert!(merged_config.mcp.is_tool_denied("shell"));
+		assert!(!merged_config.mcp.is_tool_denied("text_editor"));
+	}
 }
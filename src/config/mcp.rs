@@ -14,21 +14,18 @@
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+// Keep Default for runtime usage only (not config defaults)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
 pub enum McpConnectionType {
 	#[serde(rename = "builtin")]
 	Builtin, // Built-in server (developer, filesystem, agent)
 	#[serde(rename = "stdin")]
 	Stdin, // External server via stdin/command
 	#[serde(rename = "http")]
+	#[default]
 	Http, // External server via HTTP
-}
-
-// Keep Default for runtime usage only (not config defaults)
-impl Default for McpConnectionType {
-	fn default() -> Self {
-		Self::Http
-	}
+	#[serde(rename = "websocket", alias = "ws")]
+	WebSocket, // Already-running external server via WebSocket (ws:// or wss://)
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -158,6 +155,18 @@ pub struct McpConfig {
 
 	// Tool filtering - allows limiting tools across all enabled servers
 	pub allowed_tools: Vec<String>,
+
+	// Tools removed even when a server or allowed_tools would provide them
+	#[serde(default)]
+	pub denied_tools: Vec<String>,
+}
+
+// Result caching for a deterministic tool (e.g. "html2md"); calls with identical
+// parameters within the TTL reuse the earlier result instead of executing
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ToolCacheConfig {
+	pub tool: String,
+	pub ttl_seconds: u64,
 }
 
 // Role-specific MCP configuration with server_refs
@@ -169,6 +178,10 @@ pub struct RoleMcpConfig {
 
 	// Tool filtering - allows limiting tools across all enabled servers for this role
 	pub allowed_tools: Vec<String>,
+
+	// Tools this role may never use; takes precedence over allowed_tools
+	#[serde(default)]
+	pub denied_tools: Vec<String>,
 }
 
 // REMOVED: Default implementations - all config must be explicit
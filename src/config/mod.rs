@@ -23,7 +23,9 @@ pub mod loading;
 pub mod mcp;
 pub mod migrations;
 pub mod providers;
+pub mod reasoning;
 pub mod roles;
+pub mod shell_policy;
 pub mod validation;
 
 // Tests removed - strict configuration mode doesn't support Default implementations
@@ -33,7 +35,9 @@ pub mod validation;
 pub use layers::*;
 pub use mcp::*;
 pub use providers::*;
+pub use reasoning::*;
 pub use roles::*;
+pub use shell_policy::*;
 
 // Agent configuration
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -45,6 +49,29 @@ pub struct AgentConfig {
 	pub description: String,
 }
 
+// Which pieces of project context the %{CONTEXT}, %{README}, %{GIT_STATUS} and %{GIT_TREE}
+// placeholders include; everything is on by default
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(default)]
+pub struct ProjectContextConfig {
+	pub include_readme: bool,
+	pub include_changes: bool,
+	// Git branch and working tree status
+	pub include_git_status: bool,
+	pub include_file_tree: bool,
+}
+
+impl Default for ProjectContextConfig {
+	fn default() -> Self {
+		Self {
+			include_readme: true,
+			include_changes: true,
+			include_git_status: true,
+			include_file_tree: true,
+		}
+	}
+}
+
 // Current config version - increment when making breaking changes
 pub const CURRENT_CONFIG_VERSION: u32 = 1;
 
@@ -81,7 +108,92 @@ impl LogLevel {
 	}
 }
 
+/// How to reduce context when a request exceeds the model's input limit
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ContextReductionStrategy {
+	#[serde(rename = "truncate")]
+	Truncate,
+	#[serde(rename = "summarize")]
+	Summarize,
+	#[default]
+	#[serde(rename = "prompt")]
+	Prompt,
+}
+
+impl std::fmt::Display for ContextReductionStrategy {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ContextReductionStrategy::Truncate => write!(f, "truncate"),
+			ContextReductionStrategy::Summarize => write!(f, "summarize"),
+			ContextReductionStrategy::Prompt => write!(f, "prompt"),
+		}
+	}
+}
+
+/// When the user is asked before tool calls run in an interactive session
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ToolApproval {
+	#[default]
+	#[serde(rename = "auto")]
+	Auto,
+	#[serde(rename = "all")]
+	All,
+	#[serde(rename = "per_call")]
+	PerCall,
+}
+
+impl std::fmt::Display for ToolApproval {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ToolApproval::Auto => write!(f, "auto"),
+			ToolApproval::All => write!(f, "all"),
+			ToolApproval::PerCall => write!(f, "per_call"),
+		}
+	}
+}
+
 // REMOVED: All default functions - config must be complete and explicit
+// Exception: fields added after v1 default so existing configs keep loading
+
+fn default_api_timeout_seconds() -> u64 {
+	120
+}
+
+fn default_mcp_max_restarts() -> u32 {
+	3
+}
+
+fn default_mcp_restart_backoff_ms() -> u64 {
+	30_000
+}
+
+fn default_autosave_interval_seconds() -> u64 {
+	60
+}
+
+fn default_cache_tool_definitions() -> bool {
+	true
+}
+
+fn default_test_timeout_seconds() -> u64 {
+	600
+}
+
+fn default_redact_secrets() -> bool {
+	true
+}
+
+fn default_dedupe_user_messages() -> bool {
+	true
+}
+
+fn default_max_cache_markers() -> usize {
+	2
+}
+
+fn default_max_tool_iterations() -> usize {
+	25
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
@@ -96,23 +208,111 @@ pub struct Config {
 
 	// System-wide configuration settings (not role-specific)
 	pub mcp_response_warning_threshold: usize,
+	// Hard token budget for MCP tool responses - oversized output is truncated without prompting (0 = disabled)
+	#[serde(default)]
+	pub mcp_response_max_tokens: usize,
 	pub max_request_tokens_threshold: usize,
+	// Maximum rounds of tool calls within one turn before the model must answer (0 = unlimited)
+	#[serde(default = "default_max_tool_iterations")]
+	pub max_tool_iterations: usize,
+	// Send tool definitions in strict JSON schema mode to OpenAI and OpenRouter (per tool, when compatible)
+	#[serde(default)]
+	pub strict_tool_schemas: bool,
+	// Reasoning effort for models that support it (overridable per role and per layer/command)
+	#[serde(default)]
+	pub reasoning_effort: Option<ReasoningEffort>,
+	// Strings that end generation when the model produces them (overridable per role and per layer/command)
+	#[serde(default)]
+	pub stop_sequences: Vec<String>,
+	// Allow/deny lists for commands proposed by the shell subcommand
+	#[serde(default)]
+	pub shell_policy: ShellPolicyConfig,
+	// Return the would-be command from the shell tool instead of executing it
+	#[serde(default)]
+	pub shell_dry_run: bool,
+	// Command the run_tests tool executes (unset = detected from the project files)
+	#[serde(default)]
+	pub test_command: Option<String>,
+	// Stop run_tests after this many seconds
+	#[serde(default = "default_test_timeout_seconds")]
+	pub test_timeout_seconds: u64,
+	// Base directory for relative filesystem tool paths, shell commands and temp output (unset = project dir)
+	#[serde(default)]
+	pub scratch_dir: Option<String>,
+	// Reject text_editor and list_files paths that resolve outside this directory (unset = no sandbox)
+	#[serde(default)]
+	pub fs_sandbox_root: Option<String>,
+	// Mask credentials in tool results with the built-in patterns plus redact_patterns
+	#[serde(default = "default_redact_secrets")]
+	pub redact_secrets: bool,
+	// Extra regex patterns to redact from tool results
+	#[serde(default)]
+	pub redact_patterns: Vec<String>,
+	// Ask before running tool calls: auto (never), all (once per batch), per_call (pick which calls run)
+	#[serde(default)]
+	pub tool_approval: ToolApproval,
+	// Timeout for a single AI provider API request in seconds
+	#[serde(default = "default_api_timeout_seconds")]
+	pub api_timeout_seconds: u64,
+	// Consecutive failed restarts before the health monitor gives up on an MCP server
+	#[serde(default = "default_mcp_max_restarts")]
+	pub mcp_max_restarts: u32,
+	// Wait before the first restart attempt, doubled after each further failure
+	#[serde(default = "default_mcp_restart_backoff_ms")]
+	pub mcp_restart_backoff_ms: u64,
 	pub enable_auto_truncation: bool,
+	// Collapse repeated identical tool results before each auto-truncation check
+	#[serde(default)]
+	pub enable_auto_compaction: bool,
+	// Strategy when input exceeds the context window: truncate, summarize, or prompt (asks only on a TTY)
+	#[serde(default)]
+	pub context_reduction_strategy: ContextReductionStrategy,
 	pub cache_tokens_threshold: u64,
 	pub cache_timeout_seconds: u64,
+	// Maximum number of content cache markers kept at once (oldest evicted first)
+	#[serde(default = "default_max_cache_markers")]
+	pub max_cache_markers: usize,
 	pub enable_markdown_rendering: bool,
 	// Markdown theme for styling
 	pub markdown_theme: String,
 	// Session spending threshold in USD - if > 0, prompt user when exceeded
 	pub max_session_spending_threshold: f64,
+	// Assistant responses larger than this many bytes are stored in a sidecar file next to the session log (0 = always inline)
+	#[serde(default)]
+	pub session_sidecar_threshold: usize,
+	// Seconds between automatic session checkpoints while a session has unsaved changes (0 = disabled)
+	#[serde(default = "default_autosave_interval_seconds")]
+	pub autosave_interval_seconds: u64,
+	// Ask before resending a message identical to the previous one sent moments ago
+	#[serde(default = "default_dedupe_user_messages")]
+	pub dedupe_user_messages: bool,
 
 	// Use long-term (1h) caching for system messages (strict: must be in config)
 	pub use_long_system_cache: bool,
+	// Cache tool definitions as their own segment on providers that support caching
+	#[serde(default = "default_cache_tool_definitions")]
+	pub cache_tool_definitions: bool,
 
 	// Agent configurations - array of agent definitions
 	#[serde(default)]
 	pub agents: Vec<AgentConfig>,
 
+	// Per-provider client-side rate limits - requests queue until capacity is available
+	#[serde(default)]
+	pub rate_limits: Vec<RateLimitConfig>,
+
+	// Additions and corrections to the built-in model metadata table (checked first)
+	#[serde(default)]
+	pub model_metadata: Vec<ModelMetadata>,
+
+	// Tools whose results are reused for identical calls within a TTL (opt-in per tool)
+	#[serde(default)]
+	pub tool_cache: Vec<ToolCacheConfig>,
+
+	// Project context pieces included in prompt placeholders
+	#[serde(default)]
+	pub project_context: ProjectContextConfig,
+
 	// REMOVED: Providers configuration - API keys now only from ENV variables for security
 
 	// Role configurations - array format like layers
@@ -135,6 +335,24 @@ pub struct Config {
 	// Legacy system prompt field for backward compatibility
 	pub system: Option<String>,
 
+	// Organization-wide text wrapped around every role's system prompt
+	#[serde(default)]
+	pub system_prefix: Option<String>,
+	#[serde(default)]
+	pub system_suffix: Option<String>,
+
+	// Runtime only: set by --read-only or the role's read_only, never saved
+	#[serde(skip)]
+	pub read_only: bool,
+
+	// Runtime only: set by --no-tools, never saved
+	#[serde(skip)]
+	pub no_tools: bool,
+
+	// Runtime only: sampling seed from --seed (or the resumed/replayed session), never saved
+	#[serde(skip)]
+	pub seed: Option<u64>,
+
 	#[serde(skip)]
 	config_path: Option<PathBuf>,
 }
@@ -143,7 +361,12 @@ impl McpConfig {
 	/// Check if this config should be skipped during serialization
 	/// This helps avoid writing empty [mcp] sections when only internal servers exist
 	pub fn is_default_for_serialization(&self) -> bool {
-		self.servers.is_empty() && self.allowed_tools.is_empty()
+		self.servers.is_empty() && self.allowed_tools.is_empty() && self.denied_tools.is_empty()
+	}
+
+	/// Check if a tool has been denied for the current role
+	pub fn is_tool_denied(&self, tool_name: &str) -> bool {
+		self.denied_tools.iter().any(|denied| denied == tool_name)
 	}
 
 	/// Get all servers from the registry (for populating role configs)
@@ -178,6 +401,7 @@ impl McpConfig {
 		Self {
 			servers: servers_vec,
 			allowed_tools: allowed_tools.unwrap_or_default(),
+			denied_tools: Vec::new(),
 		}
 	}
 }
@@ -252,11 +476,16 @@ impl Config {
 			static DEFAULT_ROLE_CONFIG: RoleConfig = RoleConfig {
 				enable_layers: false,
 				system: None,
+				system_file: None,
 				temperature: 0.7, // Fallback temperature for unknown roles
+				read_only: false,
+				reasoning_effort: None,
+				stop_sequences: None,
 			};
 			static DEFAULT_MCP_CONFIG: RoleMcpConfig = RoleMcpConfig {
 				server_refs: Vec::new(),
 				allowed_tools: Vec::new(),
+				denied_tools: Vec::new(),
 			};
 			(
 				&DEFAULT_ROLE_CONFIG,
@@ -271,7 +500,7 @@ impl Config {
 	/// Get a merged config for a specific role (for backward compatibility)
 	/// This creates a new Config with role-specific settings merged into system-wide settings
 	pub fn get_merged_config_for_role(&self, mode: &str) -> Config {
-		let (_role_config, role_mcp_config, _role_layers_config, commands, system_prompt) =
+		let (role_config, role_mcp_config, _role_layers_config, commands, system_prompt) =
 			self.get_role_config(mode);
 
 		let mut merged = self.clone();
@@ -294,9 +523,18 @@ impl Config {
 			crate::log_debug!("TRACE: Adding server '{}' to merged config", server.name);
 		}
 
+		// Tools denied globally in [mcp] stay denied; the role can only add to the list
+		let mut denied_tools = self.mcp.denied_tools.clone();
+		for tool in &role_mcp_config.denied_tools {
+			if !denied_tools.contains(tool) {
+				denied_tools.push(tool.clone());
+			}
+		}
+
 		merged.mcp = McpConfig {
 			servers: enabled_servers, // Only role-enabled servers (with runtime injection)
 			allowed_tools: role_mcp_config.allowed_tools.clone(),
+			denied_tools,
 		};
 
 		// Role-specific layers (only enabled via layer_refs) - NOT USED ANYWHERE
@@ -305,10 +543,25 @@ impl Config {
 
 		merged.commands = commands.cloned();
 		merged.system = system_prompt.cloned();
+		merged.read_only = self.read_only || role_config.read_only;
+		merged.reasoning_effort = role_config.reasoning_effort.or(self.reasoning_effort);
+		if let Some(stop_sequences) = &role_config.stop_sequences {
+			merged.stop_sequences = stop_sequences.clone();
+		}
+		if self.no_tools {
+			merged.disable_tools();
+		}
 
 		merged
 	}
 
+	/// Drop all MCP servers so no tool definitions are sent (used by --no-tools)
+	pub fn disable_tools(&mut self) {
+		self.no_tools = true;
+		self.mcp.servers.clear();
+		self.mcp.allowed_tools.clear();
+	}
+
 	/// Get the role config struct for a specific role
 	pub fn get_role_config_struct(&self, role: &str) -> &RoleConfig {
 		let (role_config, _, _, _, _) = self.get_role_config(role);
@@ -30,6 +30,34 @@ pub struct ProvidersConfig {
 	pub cloudflare: ProviderConfig,
 }
 
+// Client-side rate limit for a single provider (e.g. "openrouter", "anthropic")
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RateLimitConfig {
+	pub provider: String,
+	pub requests_per_minute: u32,
+	// Optional input token budget per minute (estimated before sending)
+	#[serde(default)]
+	pub tokens_per_minute: Option<u64>,
+}
+
+// Metadata for models matching a pattern - same shape as the built-in models.json entries
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ModelMetadata {
+	pub provider: String,
+	// Substring of the model ID; when several entries match, the longest pattern wins
+	pub model: String,
+	pub context_window: usize,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_output: Option<u32>,
+	// Prices per 1M tokens in USD
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub input_price: Option<f64>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub output_price: Option<f64>,
+	#[serde(default)]
+	pub vision: bool,
+}
+
 // Legacy OpenRouterConfig for backward compatibility
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OpenRouterConfig {
@@ -0,0 +1,104 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Reasoning ("thinking") budget for models that support it
+
+use serde::{Deserialize, Serialize};
+
+/// Named reasoning effort level
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningLevel {
+	Low,
+	Medium,
+	High,
+}
+
+/// How much reasoning to ask for: a named level or an explicit token budget
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(untagged)]
+pub enum ReasoningEffort {
+	Level(ReasoningLevel),
+	Budget(u32),
+}
+
+impl ReasoningEffort {
+	/// Effort as a level, for providers that only accept low/medium/high
+	pub fn level(&self) -> &'static str {
+		match self {
+			ReasoningEffort::Level(ReasoningLevel::Low) => "low",
+			ReasoningEffort::Level(ReasoningLevel::Medium) => "medium",
+			ReasoningEffort::Level(ReasoningLevel::High) => "high",
+			ReasoningEffort::Budget(tokens) if *tokens <= 4_096 => "low",
+			ReasoningEffort::Budget(tokens) if *tokens <= 16_384 => "medium",
+			ReasoningEffort::Budget(_) => "high",
+		}
+	}
+
+	/// Effort as a token budget, for providers that take a thinking budget
+	pub fn budget_tokens(&self) -> u32 {
+		match self {
+			ReasoningEffort::Level(ReasoningLevel::Low) => 4_096,
+			ReasoningEffort::Level(ReasoningLevel::Medium) => 16_384,
+			ReasoningEffort::Level(ReasoningLevel::High) => 32_768,
+			ReasoningEffort::Budget(tokens) => *tokens,
+		}
+	}
+}
+
+impl std::fmt::Display for ReasoningEffort {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ReasoningEffort::Level(_) => write!(f, "{}", self.level()),
+			ReasoningEffort::Budget(tokens) => write!(f, "{} tokens", tokens),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Deserialize)]
+	struct Wrapper {
+		reasoning_effort: ReasoningEffort,
+	}
+
+	fn parse(value: &str) -> ReasoningEffort {
+		toml::from_str::<Wrapper>(&format!("reasoning_effort = {}", value))
+			.unwrap()
+			.reasoning_effort
+	}
+
+	#[test]
+	fn test_levels_and_budgets_parse() {
+		assert_eq!(
+			parse("\"high\""),
+			ReasoningEffort::Level(ReasoningLevel::High)
+		);
+		assert_eq!(parse("8000"), ReasoningEffort::Budget(8000));
+		assert!(toml::from_str::<Wrapper>("reasoning_effort = \"extreme\"").is_err());
+	}
+
+	#[test]
+	fn test_conversions() {
+		assert_eq!(ReasoningEffort::Budget(2_000).level(), "low");
+		assert_eq!(ReasoningEffort::Budget(10_000).level(), "medium");
+		assert_eq!(ReasoningEffort::Budget(50_000).level(), "high");
+		assert_eq!(
+			ReasoningEffort::Level(ReasoningLevel::Medium).budget_tokens(),
+			16_384
+		);
+	}
+}
@@ -15,6 +15,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::mcp::RoleMcpConfig;
+use super::reasoning::ReasoningEffort;
 
 // Role configuration - contains all behavior settings but NOT API keys or model (uses system-wide model)
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -24,8 +25,20 @@ pub struct RoleConfig {
 	pub enable_layers: bool,
 	// Custom system prompt
 	pub system: Option<String>,
+	// Read the system prompt from this file instead (relative to the config directory)
+	#[serde(default)]
+	pub system_file: Option<String>,
 	// Temperature for AI responses (0.0 to 1.0) - STRICT: must be in config
 	pub temperature: f32,
+	// Block file-mutating tools and shell for this role (reads and searches still work)
+	#[serde(default)]
+	pub read_only: bool,
+	// Reasoning effort for this role (falls back to the global reasoning_effort)
+	#[serde(default)]
+	pub reasoning_effort: Option<ReasoningEffort>,
+	// Stop sequences for this role (replace the global stop_sequences when set)
+	#[serde(default)]
+	pub stop_sequences: Option<Vec<String>>,
 }
 
 // REMOVED: Default implementations - all config must be explicit
@@ -58,6 +71,7 @@ impl RoleMcpConfig {
 		Self {
 			server_refs,
 			allowed_tools: Vec::new(),
+			denied_tools: Vec::new(),
 		}
 	}
 
@@ -69,6 +83,7 @@ impl RoleMcpConfig {
 		Self {
 			server_refs,
 			allowed_tools,
+			denied_tools: Vec::new(),
 		}
 	}
 }
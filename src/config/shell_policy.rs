@@ -0,0 +1,139 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Allow/deny policy for commands proposed by the shell subcommand
+
+use serde::{Deserialize, Serialize};
+
+/// What happens to a proposed shell command
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ShellPolicyAction {
+	#[serde(rename = "allow")]
+	Allow,
+	#[default]
+	#[serde(rename = "confirm")]
+	Confirm,
+	#[serde(rename = "deny")]
+	Deny,
+}
+
+impl std::fmt::Display for ShellPolicyAction {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ShellPolicyAction::Allow => write!(f, "allow"),
+			ShellPolicyAction::Confirm => write!(f, "confirm"),
+			ShellPolicyAction::Deny => write!(f, "deny"),
+		}
+	}
+}
+
+// Command prefixes that run without prompting or are always rejected
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct ShellPolicyConfig {
+	pub allow: Vec<String>,
+	pub deny: Vec<String>,
+	// Action for commands matching neither list
+	pub default: ShellPolicyAction,
+}
+
+impl ShellPolicyConfig {
+	/// Decide what to do with a command. Chained commands (`&&`, `|`, `;`, ...) are
+	/// checked piece by piece: any denied piece denies the whole command, and the
+	/// command is only auto-allowed when every piece is allowed and it has no
+	/// redirections or command substitutions.
+	pub fn decide(&self, command: &str) -> ShellPolicyAction {
+		let segments = split_command(command);
+
+		if segments
+			.iter()
+			.any(|segment| matches_any(segment, &self.deny))
+		{
+			return ShellPolicyAction::Deny;
+		}
+
+		let has_side_channel = command.contains('>')
+			|| command.contains('<')
+			|| command.contains('`')
+			|| command.contains("$(");
+		if !has_side_channel
+			&& !segments.is_empty()
+			&& segments
+				.iter()
+				.all(|segment| matches_any(segment, &self.allow))
+		{
+			return ShellPolicyAction::Allow;
+		}
+
+		self.default
+	}
+}
+
+// Split a command line into the individual commands joined by shell operators
+fn split_command(command: &str) -> Vec<String> {
+	command
+		.split(['\n', ';', '|', '&'])
+		.map(|segment| segment.split_whitespace().collect::<Vec<_>>().join(" "))
+		.filter(|segment| !segment.is_empty())
+		.collect()
+}
+
+// A prefix matches whole words only, so "git status" does not match "git statusx"
+fn matches_any(segment: &str, prefixes: &[String]) -> bool {
+	prefixes.iter().any(|prefix| {
+		let prefix = prefix.split_whitespace().collect::<Vec<_>>().join(" ");
+		!prefix.is_empty()
+			&& (segment == prefix
+				|| segment
+					.strip_prefix(prefix.as_str())
+					.is_some_and(|rest| rest.starts_with(' ')))
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn policy() -> ShellPolicyConfig {
+		ShellPolicyConfig {
+			allow: vec!["git status".to_string(), "ls".to_string()],
+			deny: vec!["rm -rf".to_string(), "sudo".to_string()],
+			default: ShellPolicyAction::Confirm,
+		}
+	}
+
+	#[test]
+	fn test_allow_and_deny_prefixes() {
+		let policy = policy();
+		assert_eq!(policy.decide("git status"), ShellPolicyAction::Allow);
+		assert_eq!(policy.decide("ls   -la src"), ShellPolicyAction::Allow);
+		assert_eq!(policy.decide("git statusx"), ShellPolicyAction::Confirm);
+		assert_eq!(policy.decide("sudo apt update"), ShellPolicyAction::Deny);
+		assert_eq!(policy.decide("cargo build"), ShellPolicyAction::Confirm);
+	}
+
+	#[test]
+	fn test_chained_commands_are_checked_per_segment() {
+		let policy = policy();
+		assert_eq!(policy.decide("git status && ls"), ShellPolicyAction::Allow);
+		assert_eq!(
+			policy.decide("git status && rm -rf build"),
+			ShellPolicyAction::Deny
+		);
+		assert_eq!(policy.decide("ls | xargs cat"), ShellPolicyAction::Confirm);
+		// Redirections and substitutions are never auto-allowed
+		assert_eq!(policy.decide("ls > files.txt"), ShellPolicyAction::Confirm);
+		assert_eq!(policy.decide("ls $(pwd)"), ShellPolicyAction::Confirm);
+	}
+}
@@ -30,6 +30,10 @@ impl Config {
 		if let Some(layers) = &self.layers {
 			self.validate_layers(layers)?;
 		}
+		// Commands are layers too and can name their own model
+		if let Some(commands) = &self.commands {
+			self.validate_layers(commands)?;
+		}
 
 		// STRICT: Validate required fields are not empty
 		self.validate_required_fields()?;
@@ -49,6 +53,16 @@ impl Config {
 
 		// Role configurations no longer have models - using system-wide model
 
+		// A role's prompt comes either inline or from a file, never both
+		for role in &self.roles {
+			if role.config.system.is_some() && role.config.system_file.is_some() {
+				return Err(anyhow!(
+					"Role '{}' sets both system and system_file; use only one",
+					role.name
+				));
+			}
+		}
+
 		Ok(())
 	}
 
@@ -61,6 +75,18 @@ impl Config {
 			));
 		}
 
+		// Validate content cache marker limit: the system prompt and tool definitions each
+		// take one of the provider's cache breakpoints, content markers share the rest
+		let max_content_markers = crate::session::cache::MAX_CACHE_BREAKPOINTS - 2;
+		if self.max_cache_markers == 0 || self.max_cache_markers > max_content_markers {
+			return Err(anyhow!(
+				"Invalid max_cache_markers: {}. Must be between 1 and {} (Anthropic allows {} cache breakpoints per request, including the system prompt and tool definitions)",
+				self.max_cache_markers,
+				max_content_markers,
+				crate::session::cache::MAX_CACHE_BREAKPOINTS
+			));
+		}
+
 		// Validate MCP response warning threshold (0 is valid for disabling)
 		if self.mcp_response_warning_threshold > 1_000_000 {
 			return Err(anyhow!(
@@ -69,6 +95,146 @@ impl Config {
 			));
 		}
 
+		// Validate MCP response max tokens (0 is valid for disabling)
+		if self.mcp_response_max_tokens > 1_000_000 {
+			return Err(anyhow!(
+				"MCP response max tokens too high: {}. Maximum allowed: 1,000,000",
+				self.mcp_response_max_tokens
+			));
+		}
+
+		// Validate API timeout
+		if self.api_timeout_seconds == 0 {
+			return Err(anyhow!(
+				"API timeout cannot be 0. Use a positive number of seconds."
+			));
+		}
+
+		if self.api_timeout_seconds > 3600 {
+			return Err(anyhow!(
+				"API timeout too high: {} seconds. Maximum allowed: 3600 (1 hour)",
+				self.api_timeout_seconds
+			));
+		}
+
+		// Validate model metadata overrides
+		for entry in &self.model_metadata {
+			if crate::providers::ProviderFactory::create_provider(&entry.provider).is_err() {
+				return Err(anyhow!(
+					"Invalid model_metadata provider '{}'. Supported providers: openrouter, openai, anthropic, google, amazon, cloudflare, ollama",
+					entry.provider
+				));
+			}
+
+			if entry.model.trim().is_empty() {
+				return Err(anyhow!(
+					"model_metadata entry for '{}' needs a non-empty model pattern",
+					entry.provider
+				));
+			}
+
+			if entry.context_window == 0 {
+				return Err(anyhow!(
+					"model_metadata context_window for '{}:{}' cannot be 0",
+					entry.provider,
+					entry.model
+				));
+			}
+		}
+
+		// Validate reasoning budgets (providers reject thinking budgets below 1024 tokens)
+		let role_efforts = self
+			.role_map
+			.values()
+			.filter_map(|role| role.config.reasoning_effort);
+		for effort in self.reasoning_effort.into_iter().chain(role_efforts) {
+			if let crate::config::ReasoningEffort::Budget(tokens) = effort {
+				if tokens < 1024 {
+					return Err(anyhow!(
+						"reasoning_effort budget too low: {} tokens. Minimum: 1024",
+						tokens
+					));
+				}
+			}
+		}
+
+		// Validate stop sequences - an empty one would stop generation immediately
+		let role_stop_sequences = self
+			.role_map
+			.values()
+			.filter_map(|role| role.config.stop_sequences.as_ref())
+			.flatten();
+		if self
+			.stop_sequences
+			.iter()
+			.chain(role_stop_sequences)
+			.any(|stop| stop.is_empty())
+		{
+			return Err(anyhow!("stop_sequences cannot contain empty strings"));
+		}
+
+		// Validate tool result caching - the shell and test runs can change anything, so they are never cached
+		let mut cached_tools = std::collections::HashSet::new();
+		for rule in &self.tool_cache {
+			if !cached_tools.insert(rule.tool.as_str()) {
+				return Err(anyhow!(
+					"Duplicate tool_cache entry for tool '{}'",
+					rule.tool
+				));
+			}
+			if rule.tool == "shell" || rule.tool == "run_tests" {
+				return Err(anyhow!(
+					"tool_cache cannot cache '{}': commands may change files or depend on state",
+					rule.tool
+				));
+			}
+			if rule.ttl_seconds == 0 {
+				return Err(anyhow!(
+					"tool_cache ttl_seconds for '{}' cannot be 0. Remove the entry to disable caching.",
+					rule.tool
+				));
+			}
+		}
+
+		if self.test_timeout_seconds == 0 {
+			return Err(anyhow!("test_timeout_seconds must be greater than 0"));
+		}
+
+		// Validate redaction patterns
+		crate::mcp::redact::compile_patterns(&self.redact_patterns)?;
+
+		// Validate provider rate limits
+		let mut rate_limited_providers = std::collections::HashSet::new();
+		for limit in &self.rate_limits {
+			if !rate_limited_providers.insert(limit.provider.to_lowercase()) {
+				return Err(anyhow!(
+					"Duplicate rate limit for provider '{}'",
+					limit.provider
+				));
+			}
+
+			if crate::providers::ProviderFactory::create_provider(&limit.provider).is_err() {
+				return Err(anyhow!(
+					"Invalid rate limit provider '{}'. Supported providers: openrouter, openai, anthropic, google, amazon, cloudflare, ollama",
+					limit.provider
+				));
+			}
+
+			if limit.requests_per_minute == 0 {
+				return Err(anyhow!(
+					"Rate limit requests_per_minute for '{}' cannot be 0. Remove the entry to disable limiting.",
+					limit.provider
+				));
+			}
+
+			if limit.tokens_per_minute == Some(0) {
+				return Err(anyhow!(
+					"Rate limit tokens_per_minute for '{}' cannot be 0. Omit it to disable token limiting.",
+					limit.provider
+				));
+			}
+		}
+
 		// Validate max request tokens threshold
 		// Only validate if auto-truncation is enabled
 		if self.enable_auto_truncation {
@@ -146,6 +312,27 @@ impl Config {
 					));
 				}
 			}
+
+			// WebSocket servers are connected to, never launched
+			if matches!(
+				server_config.connection_type,
+				crate::config::McpConnectionType::WebSocket
+			) {
+				let url = server_config.url.as_deref().unwrap_or_default();
+				if !url.starts_with("ws://") && !url.starts_with("wss://") {
+					return Err(anyhow!(
+						"WebSocket server '{}' must have a 'url' starting with ws:// or wss://",
+						server_name
+					));
+				}
+
+				if server_config.command.is_some() {
+					return Err(anyhow!(
+						"WebSocket server '{}' cannot have 'command' specified",
+						server_name
+					));
+				}
+			}
 		}
 
 		Ok(())
@@ -158,10 +345,30 @@ impl Config {
 				return Err(anyhow!("Layer at index {} has empty name", index));
 			}
 
-			// Validate layer name is not empty (layer_type field doesn't exist)
-			// Additional layer-specific validation can be added here if needed
+			// A layer model overrides the session model, so a typo must fail at load
+			// rather than on the first request that reaches this layer
+			if let Some(model) = &layer.model {
+				crate::providers::ProviderFactory::get_provider_for_model(model).map_err(|e| {
+					anyhow!(
+						"Layer '{}' has an invalid model '{}': {}",
+						layer.name,
+						model,
+						e
+					)
+				})?;
+			}
 
-			// Additional layer-specific validation can be added here
+			if layer
+				.stop_sequences
+				.iter()
+				.flatten()
+				.any(|stop| stop.is_empty())
+			{
+				return Err(anyhow!(
+					"Layer '{}' stop_sequences cannot contain empty strings",
+					layer.name
+				));
+			}
 		}
 
 		Ok(())
@@ -47,6 +47,18 @@ enum Commands {
 	/// Show all available placeholder variables and their values
 	Vars(commands::VarsArgs),
 
+	/// Check provider credentials and MCP server startup
+	Doctor(commands::DoctorArgs),
+
+	/// Inspect the MCP servers octomind manages
+	Mcp(commands::McpArgs),
+
+	/// Count the tokens of files or stdin for a model and estimate their input cost
+	CountTokens(commands::CountTokensArgs),
+
+	/// Summarize cost and token usage across all saved sessions
+	Stats(commands::StatsArgs),
+
 	/// Generate shell completion scripts
 	Completion {
 		/// The shell to generate completion for
@@ -73,10 +85,32 @@ async fn main() -> Result<(), anyhow::Error> {
 	result
 }
 
-async fn run_with_cleanup(args: CliArgs, config: Config) -> Result<(), anyhow::Error> {
+async fn run_with_cleanup(args: CliArgs, mut config: Config) -> Result<(), anyhow::Error> {
+	// Per-invocation model override, validated before any work starts
+	let model_override = match &args.command {
+		Commands::Ask(ask_args) => ask_args.model.as_ref(),
+		Commands::Shell(shell_args) => shell_args.model.as_ref(),
+		Commands::Session(session_args) => session_args.model.as_ref(),
+		Commands::CountTokens(count_args) => count_args.model.as_ref(),
+		_ => None,
+	};
+	commands::apply_model_override(&mut config, model_override)?;
+	// Sessions apply --stop on top of the role's settings themselves
+	if let Commands::Ask(ask_args) = &args.command {
+		if !ask_args.stop.is_empty() {
+			config.stop_sequences = ask_args.stop.clone();
+		}
+	}
+
 	// Initialize MCP servers once at startup for commands that need them
 	match &args.command {
-		Commands::Session(session_args) => {
+		Commands::Session(session_args)
+			if session_args.export.is_none()
+				&& session_args.report.is_empty()
+				&& !session_args.list
+				&& session_args.search.is_none()
+				&& !session_args.no_tools =>
+		{
 			// For session command, initialize MCP servers based on the role
 			let role = &session_args.role;
 			let config_for_role = config.get_merged_config_for_role(role);
@@ -94,11 +128,28 @@ async fn run_with_cleanup(args: CliArgs, config: Config) -> Result<(), anyhow::E
 	match &args.command {
 		Commands::Config(config_args) => commands::config::execute(config_args, config)?,
 		Commands::Session(session_args) => {
-			session::chat::run_interactive_session(session_args, &config).await?
+			if let Some(path) = &session_args.export {
+				commands::session::export(session_args, path)?
+			} else if !session_args.report.is_empty() {
+				commands::session::report(session_args, &config)?
+			} else if session_args.list {
+				commands::session::list(session_args)?
+			} else if let Some(query) = &session_args.search {
+				commands::session::search(session_args, query)?
+			} else if session_args.continue_last {
+				let resumed = commands::session::continue_last(session_args)?;
+				session::chat::run_interactive_session(&resumed, &config).await?
+			} else {
+				session::chat::run_interactive_session(session_args, &config).await?
+			}
 		}
 		Commands::Ask(ask_args) => commands::ask::execute(ask_args, &config).await?,
 		Commands::Shell(shell_args) => commands::shell::execute(shell_args, &config).await?,
 		Commands::Vars(vars_args) => commands::vars::execute(vars_args, &config).await?,
+		Commands::Doctor(doctor_args) => commands::doctor::execute(doctor_args, &config).await?,
+		Commands::Mcp(mcp_args) => commands::mcp::execute(mcp_args, &config).await?,
+		Commands::CountTokens(count_args) => commands::count_tokens::execute(count_args, &config)?,
+		Commands::Stats(stats_args) => commands::stats::execute(stats_args)?,
 		Commands::Completion { shell } => {
 			let mut app = CliArgs::command();
 			let name = app.get_name().to_string();
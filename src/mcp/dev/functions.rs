@@ -16,8 +16,9 @@
 
 use super::super::McpFunction;
 use super::shell::get_shell_function;
+use super::test_runner::get_run_tests_function;
 
 // Get all available developer functions
 pub fn get_all_functions() -> Vec<McpFunction> {
-	vec![get_shell_function()]
+	vec![get_shell_function(), get_run_tests_function()]
 }
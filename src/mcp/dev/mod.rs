@@ -13,11 +13,13 @@
 // limitations under the License.
 
 // Developer MCP provider - modular structure
-// Handles shell execution and other development tools
+// Handles shell execution, test runs and other development tools
 
 pub mod functions;
 pub mod shell;
+pub mod test_runner;
 
 // Re-export main functionality
 pub use functions::get_all_functions;
 pub use shell::execute_shell_command;
+pub use test_runner::execute_run_tests;
@@ -106,6 +106,11 @@ may show ignored or hidden files. For example *do not* use `find` or `ls -r`
 				"command": {
 					"type": "string",
 					"description": "The shell command to execute"
+				},
+				"dry_run": {
+					"type": "boolean",
+					"description": "If true, return what would be executed (command, working directory, environment) without running it",
+					"default": false
 				}
 			},
 			"required": ["command"]
@@ -113,13 +118,59 @@ may show ignored or hidden files. For example *do not* use `find` or `ls -r`
 	}
 }
 
+// Describe the command that would run without executing it
+fn dry_run_result(
+	call: &McpToolCall,
+	command: &str,
+	scratch_dir: Option<&std::path::Path>,
+) -> McpToolResult {
+	let working_directory = scratch_dir
+		.map(std::path::Path::to_path_buf)
+		.or_else(|| std::env::current_dir().ok())
+		.map(|d| d.to_string_lossy().to_string())
+		.unwrap_or_default();
+	let shell = if cfg!(target_os = "windows") {
+		"cmd /C"
+	} else {
+		"sh -c"
+	};
+
+	// Only report variables that affect how the command resolves - the rest may hold secrets
+	let environment: serde_json::Map<String, Value> = ["PATH", "HOME", "SHELL", "USER"]
+		.iter()
+		.filter_map(|name| {
+			std::env::var(name)
+				.ok()
+				.map(|value| (name.to_string(), Value::String(value)))
+		})
+		.collect();
+
+	McpToolResult {
+		tool_name: "shell".to_string(),
+		tool_id: call.tool_id.clone(),
+		result: json!({
+			"success": true,
+			"dry_run": true,
+			"output": format!("[DRY RUN] Command was NOT executed: {}", command),
+			"code": Value::Null,
+			"parameters": {
+				"command": command
+			},
+			"shell": shell,
+			"working_directory": working_directory,
+			"environment": environment,
+			"message": "Dry run: no command was executed and nothing on the system changed"
+		}),
+	}
+}
+
 // Execute a shell command
 pub async fn execute_shell_command(
 	call: &McpToolCall,
+	config: &crate::config::Config,
 	cancellation_token: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
 ) -> Result<McpToolResult> {
 	use std::sync::atomic::Ordering;
-	use tokio::process::Command as TokioCommand;
 
 	// Extract command parameter
 	let command = match call.parameters.get("command") {
@@ -134,17 +185,120 @@ pub async fn execute_shell_command(
 		}
 	}
 
+	// The config flag can't be overridden per call, so automated flows stay safe
+	let dry_run = config.shell_dry_run
+		|| call
+			.parameters
+			.get("dry_run")
+			.and_then(|v| v.as_bool())
+			.unwrap_or(false);
+	// Commands run from the scratch directory when one is configured
+	let scratch_dir = crate::mcp::scratch::scratch_dir(config)?;
+	if dry_run {
+		return Ok(dry_run_result(call, &command, scratch_dir.as_deref()));
+	}
+
 	// Add command to shell history before execution
 	let _ = add_to_shell_history(&command);
 
+	let output =
+		match run_command(&command, scratch_dir.as_deref(), cancellation_token, None).await? {
+			CommandOutcome::Completed {
+				stdout,
+				stderr,
+				code: status_code,
+				success,
+			} => {
+				// Format the output more clearly with error handling
+				let combined = if stderr.is_empty() {
+					stdout
+				} else if stdout.is_empty() {
+					stderr
+				} else {
+					format!(
+						"{}
+
+Error: {}",
+						stdout, stderr
+					)
+				};
+
+				// Add detailed execution results including status code
+				json!({
+					"success": success,
+					"output": combined,
+					"code": status_code,
+					"parameters": {
+						"command": command
+					},
+					"message": if success {
+						format!("Command executed successfully with exit code {}", status_code)
+					} else {
+						format!("Command failed with exit code {}", status_code)
+					}
+				})
+			}
+			CommandOutcome::Failed(e) => json!({
+				"success": false,
+				"output": format!("Failed to execute command: {}", e),
+				"code": -1,
+				"parameters": {
+					"command": command
+				},
+				"message": format!("Failed to execute command: {}", e)
+			}),
+			// The shell tool runs without a timeout, so only cancellation ends it early
+			CommandOutcome::Cancelled | CommandOutcome::TimedOut => json!({
+				"success": false,
+				"output": "Command execution cancelled by user (Ctrl+C)",
+				"code": -1,
+				"parameters": {
+					"command": command
+				},
+				"message": "Command execution cancelled by user"
+			}),
+		};
+
+	Ok(McpToolResult {
+		tool_name: "shell".to_string(),
+		tool_id: call.tool_id.clone(),
+		result: output,
+	})
+}
+
+/// How a command started by `run_command` ended
+pub(super) enum CommandOutcome {
+	Completed {
+		stdout: String,
+		stderr: String,
+		code: i32,
+		success: bool,
+	},
+	/// The process started but its output could not be collected
+	Failed(String),
+	Cancelled,
+	TimedOut,
+}
+
+/// Run a command through the platform shell, killing it on cancellation or timeout.
+/// With `scratch_dir` the command runs there and TMPDIR points at it.
+pub(super) async fn run_command(
+	command: &str,
+	scratch_dir: Option<&std::path::Path>,
+	cancellation_token: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+	timeout: Option<std::time::Duration>,
+) -> Result<CommandOutcome> {
+	use std::sync::atomic::Ordering;
+	use tokio::process::Command as TokioCommand;
+
 	// Use tokio::process::Command for better cancellation support
 	let mut cmd = if cfg!(target_os = "windows") {
 		let mut cmd = TokioCommand::new("cmd");
-		cmd.args(["/C", &command]);
+		cmd.args(["/C", command]);
 		cmd
 	} else {
 		let mut cmd = TokioCommand::new("sh");
-		cmd.args(["-c", &command]);
+		cmd.args(["-c", command]);
 		cmd
 	};
 
@@ -153,6 +307,9 @@ pub async fn execute_shell_command(
 		.stderr(std::process::Stdio::piped())
 		.stdin(std::process::Stdio::null())
 		.kill_on_drop(true); // CRITICAL: Kill process when dropped
+	if let Some(dir) = scratch_dir {
+		cmd.current_dir(dir).env("TMPDIR", dir);
+	}
 
 	// Spawn the process
 	let child = cmd
@@ -168,117 +325,65 @@ pub async fn execute_shell_command(
 			loop {
 				tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
 				if token.load(Ordering::SeqCst) {
-					return true; // Indicate cancellation occurred
+					return;
 				}
 			}
 		} else {
-			std::future::pending::<bool>().await
+			std::future::pending::<()>().await
 		}
 	};
-
-	// Race between command completion and cancellation
-	let output = tokio::select! {
-			result = child.wait_with_output() => {
-				match result.map_err(|e| anyhow!("Command execution failed: {}", e)) {
-					Ok(output) => {
-						let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-						let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-						// Format the output more clearly with error handling
-						let combined = if stderr.is_empty() {
-							stdout
-						} else if stdout.is_empty() {
-							stderr
-						} else {
-							format!(
-								"{}
-
-Error: {}",
-								stdout, stderr
-							)
-						};
-
-						// Add detailed execution results including status code
-						let status_code = output.status.code().unwrap_or(-1);
-						let success = output.status.success();
-
-						json!({
-							"success": success,
-							"output": combined,
-							"code": status_code,
-							"parameters": {
-								"command": command
-							},
-							"message": if success {
-							format!("Command executed successfully with exit code {}", status_code)
-						} else {
-							format!("Command failed with exit code {}", status_code)
-						}
-					})
-				}
-				Err(e) => json!({
-					"success": false,
-					"output": format!("Failed to execute command: {}", e),
-					"code": -1,
-					"parameters": {
-						"command": command
-					},
-					"message": format!("Failed to execute command: {}", e)
-				}),
-			}
+	let timeout_future = async {
+		match timeout {
+			Some(duration) => tokio::time::sleep(duration).await,
+			None => std::future::pending::<()>().await,
 		}
-		cancelled = cancellation_future => {
-			if cancelled {
-				// Try to kill the process using system commands if we have the PID
-				if let Some(pid) = child_id {
-					#[cfg(unix)]
-					{
-						// On Unix systems, try to kill the process using system commands
-						let _ = std::process::Command::new("kill")
-							.args(["-TERM", &pid.to_string()])
-							.output();
-						// Give it a moment to terminate gracefully
-						std::thread::sleep(std::time::Duration::from_millis(100));
-						let _ = std::process::Command::new("kill")
-							.args(["-KILL", &pid.to_string()])
-							.output();
-					}
-					#[cfg(windows)]
-					{
-						// On Windows, use taskkill
-						let _ = std::process::Command::new("taskkill")
-							.args(["/F", "/PID", &pid.to_string()])
-							.output();
-					}
-				}
+	};
 
-				json!({
-					"success": false,
-					"output": "Command execution cancelled by user (Ctrl+C)",
-					"code": -1,
-					"parameters": {
-						"command": command
-					},
-					"message": "Command execution cancelled by user"
-				})
-			} else {
-				// This shouldn't happen, but handle it gracefully
-				json!({
-					"success": false,
-					"output": "Unexpected cancellation state",
-					"code": -1,
-					"parameters": {
-						"command": command
-					},
-					"message": "Unexpected cancellation state"
-				})
-			}
+	// Race between command completion, cancellation and the timeout
+	let outcome = tokio::select! {
+		result = child.wait_with_output() => match result {
+			Ok(output) => CommandOutcome::Completed {
+				stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+				stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+				code: output.status.code().unwrap_or(-1),
+				success: output.status.success(),
+			},
+			Err(e) => CommandOutcome::Failed(format!("Command execution failed: {}", e)),
+		},
+		_ = cancellation_future => {
+			kill_process(child_id);
+			CommandOutcome::Cancelled
+		}
+		_ = timeout_future => {
+			kill_process(child_id);
+			CommandOutcome::TimedOut
 		}
 	};
+	Ok(outcome)
+}
 
-	Ok(McpToolResult {
-		tool_name: "shell".to_string(),
-		tool_id: call.tool_id.clone(),
-		result: output,
-	})
+// Stop a command that was cancelled or timed out
+fn kill_process(child_id: Option<u32>) {
+	// Try to kill the process using system commands if we have the PID
+	if let Some(pid) = child_id {
+		#[cfg(unix)]
+		{
+			// On Unix systems, try to kill the process using system commands
+			let _ = std::process::Command::new("kill")
+				.args(["-TERM", &pid.to_string()])
+				.output();
+			// Give it a moment to terminate gracefully
+			std::thread::sleep(std::time::Duration::from_millis(100));
+			let _ = std::process::Command::new("kill")
+				.args(["-KILL", &pid.to_string()])
+				.output();
+		}
+		#[cfg(windows)]
+		{
+			// On Windows, use taskkill
+			let _ = std::process::Command::new("taskkill")
+				.args(["/F", "/PID", &pid.to_string()])
+				.output();
+		}
+	}
 }
@@ -113,7 +113,7 @@ pub async fn undo_edit(call: &McpToolCall, path: &Path) -> Result<McpToolResult>
 }
 
 // Helper function to detect language based on file extension
-pub fn detect_language(ext: &str) -> &str {
+pub fn detect_language(ext: &str) -> &'static str {
 	match ext {
 		"rs" => "rust",
 		"py" => "python",
@@ -137,6 +137,63 @@ pub fn detect_language(ext: &str) -> &str {
 	}
 }
 
+// Detect language from the extension first, falling back to the shebang and well-known names
+pub fn detect_file_language(path: &Path, content: &str) -> &'static str {
+	let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+	match detect_language(ext) {
+		"text" => detect_language_from_content(path, content),
+		lang => lang,
+	}
+}
+
+// Fallback for extensionless or unknown files: file name, then shebang, then content markers
+fn detect_language_from_content(path: &Path, content: &str) -> &'static str {
+	match path.file_name().and_then(|n| n.to_str()).unwrap_or("") {
+		"Dockerfile" | "Containerfile" => return "dockerfile",
+		"Makefile" | "GNUmakefile" | "makefile" => return "makefile",
+		"Gemfile" | "Rakefile" => return "ruby",
+		"Jenkinsfile" => return "groovy",
+		_ => {}
+	}
+
+	let first_line = content.lines().next().unwrap_or("").trim();
+	if let Some(shebang) = first_line.strip_prefix("#!") {
+		return detect_shebang_language(shebang);
+	}
+
+	if first_line.starts_with("<?php") {
+		"php"
+	} else if first_line.starts_with("<?xml") {
+		"xml"
+	} else if first_line.to_lowercase().starts_with("<!doctype html") {
+		"html"
+	} else {
+		"text"
+	}
+}
+
+// Map a shebang line (without "#!") to a language, handling "/usr/bin/env [-S] interpreter"
+fn detect_shebang_language(shebang: &str) -> &'static str {
+	let mut words = shebang
+		.split_whitespace()
+		.map(|word| word.rsplit('/').next().unwrap_or(word));
+	let mut interpreter = words.next().unwrap_or("");
+	if interpreter == "env" {
+		interpreter = words.find(|word| !word.starts_with('-')).unwrap_or("");
+	}
+
+	// python3.12 -> python, nodejs -> node
+	match interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.') {
+		"python" | "pypy" => "python",
+		"node" | "nodejs" | "deno" | "bun" => "javascript",
+		"bash" | "sh" | "zsh" | "dash" | "ksh" => "bash",
+		"ruby" => "ruby",
+		"perl" => "perl",
+		"php" => "php",
+		_ => "text",
+	}
+}
+
 // Main execution functions
 
 // Execute a text editor command following modern text editor specifications
@@ -261,6 +318,33 @@ pub async fn execute_text_editor(
 			};
 			text_editing::str_replace_spec(call, Path::new(&path), &old_str, &new_str).await
 		},
+		"regex_replace" => {
+			// Check for cancellation before regex_replace operation
+			if let Some(ref token) = cancellation_token {
+				if token.load(Ordering::SeqCst) {
+					return Err(anyhow!("Text editor operation cancelled"));
+				}
+			}
+
+			let path = match call.parameters.get("path") {
+				Some(Value::String(p)) => p.clone(),
+				_ => return Err(anyhow!("Missing or invalid 'path' parameter for regex_replace command")),
+			};
+			let pattern = match call.parameters.get("pattern") {
+				Some(Value::String(s)) => s.clone(),
+				_ => return Err(anyhow!("Missing or invalid 'pattern' parameter for regex_replace command")),
+			};
+			let replacement = match call.parameters.get("replacement") {
+				Some(Value::String(s)) => s.clone(),
+				_ => return Err(anyhow!("Missing or invalid 'replacement' parameter for regex_replace command")),
+			};
+			let count = match call.parameters.get("count") {
+				Some(Value::Number(n)) => Some(n.as_u64().ok_or_else(|| anyhow!("Invalid 'count' parameter"))? as usize),
+				None | Some(Value::Null) => None,
+				_ => return Err(anyhow!("Invalid 'count' parameter for regex_replace command")),
+			};
+			text_editing::regex_replace_spec(call, Path::new(&path), &pattern, &replacement, count).await
+		},
 		"insert" => {
 			// Check for cancellation before insert operation
 			if let Some(ref token) = cancellation_token {
@@ -300,8 +384,8 @@ pub async fn execute_text_editor(
 					if arr.len() != 2 {
 						return Err(anyhow!("'view_range' must be an array of exactly 2 integers for line_replace command"));
 					}
-					let start = arr[0].as_u64().ok_or_else(|| anyhow!("Invalid start_line in view_range"))? as usize;
-					let end = arr[1].as_u64().ok_or_else(|| anyhow!("Invalid end_line in view_range"))? as usize;
+					let start = arr[0].as_i64().ok_or_else(|| anyhow!("Invalid start_line in view_range"))?;
+					let end = arr[1].as_i64().ok_or_else(|| anyhow!("Invalid end_line in view_range"))?;
 					(start, end)
 				},
 				_ => return Err(anyhow!("Missing or invalid 'view_range' parameter for line_replace command")),
@@ -346,7 +430,7 @@ pub async fn execute_text_editor(
 
 			text_editing::batch_edit_spec(call, operations).await
 		},
-		_ => Err(anyhow!("Invalid command: {}. Allowed commands are: view, view_many, create, str_replace, insert, line_replace, undo_edit, batch_edit", command)),
+		_ => Err(anyhow!("Invalid command: {}. Allowed commands are: view, view_many, create, str_replace, regex_replace, insert, line_replace, undo_edit, batch_edit", command)),
 	}
 }
 
@@ -383,3 +467,54 @@ pub async fn execute_html2md(
 
 	html_converter::execute_html2md(call).await
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_extension_takes_precedence_over_shebang() {
+		let lang = detect_file_language(Path::new("build.rs"), "#!/usr/bin/env python3\n");
+		assert_eq!(lang, "rust");
+	}
+
+	#[test]
+	fn test_shebang_detection_for_extensionless_scripts() {
+		let cases = [
+			("#!/usr/bin/env python3", "python"),
+			("#!/usr/bin/python3.12 -u", "python"),
+			("#!/usr/bin/env node", "javascript"),
+			("#!/usr/bin/env -S deno run", "javascript"),
+			("#!/bin/bash -e", "bash"),
+			("#!/bin/sh", "bash"),
+			("#!/usr/bin/env ruby", "ruby"),
+			("#!/usr/bin/awk -f", "text"),
+		];
+		for (shebang, expected) in cases {
+			let content = format!("{}\necho hi\n", shebang);
+			assert_eq!(
+				detect_file_language(Path::new("scripts/deploy"), &content),
+				expected,
+				"{}",
+				shebang
+			);
+		}
+	}
+
+	#[test]
+	fn test_well_known_files_and_content_markers() {
+		assert_eq!(
+			detect_file_language(Path::new("docker/Dockerfile"), "FROM rust\n"),
+			"dockerfile"
+		);
+		assert_eq!(
+			detect_file_language(Path::new("Makefile"), "all:\n"),
+			"makefile"
+		);
+		assert_eq!(
+			detect_file_language(Path::new("index"), "<!DOCTYPE html>\n<html>"),
+			"html"
+		);
+		assert_eq!(detect_file_language(Path::new("notes"), "hello\n"), "text");
+	}
+}
@@ -17,8 +17,60 @@
 use super::super::{McpToolCall, McpToolResult};
 use anyhow::{anyhow, Result};
 use serde_json::{json, Value};
+use std::collections::BTreeMap;
 use std::process::Command;
 
+// Node of the directory tree rendered by list_files in tree format
+#[derive(Default)]
+struct TreeNode {
+	dirs: BTreeMap<String, TreeNode>,
+	files: Vec<String>,
+}
+
+// Render a flat list of relative file paths as a sorted, indented directory tree
+fn render_file_tree(files: &[&str]) -> String {
+	let mut root = TreeNode::default();
+
+	for file in files {
+		let path = file.trim_start_matches("./");
+		if path.is_empty() {
+			continue;
+		}
+
+		let mut parts: Vec<&str> = path.split('/').filter(|p| !p.is_empty()).collect();
+		let file_name = match parts.pop() {
+			Some(name) => name,
+			None => continue,
+		};
+
+		let mut node = &mut root;
+		for dir in parts {
+			node = node.dirs.entry(dir.to_string()).or_default();
+		}
+		node.files.push(file_name.to_string());
+	}
+
+	let mut output = String::new();
+	write_tree_node(&root, 0, &mut output);
+	output
+}
+
+// Write directories first, then files, both sorted for deterministic output
+fn write_tree_node(node: &TreeNode, depth: usize, output: &mut String) {
+	let indent = "  ".repeat(depth);
+
+	for (name, child) in &node.dirs {
+		output.push_str(&format!("{}{}/\n", indent, name));
+		write_tree_node(child, depth + 1, output);
+	}
+
+	let mut files = node.files.clone();
+	files.sort();
+	for name in files {
+		output.push_str(&format!("{}{}\n", indent, name));
+	}
+}
+
 // Convert glob pattern to regex pattern for use with ripgrep
 fn convert_glob_to_regex(glob_pattern: &str) -> String {
 	// Handle multiple patterns separated by |
@@ -101,6 +153,21 @@ pub async fn execute_list_files(call: &McpToolCall) -> Result<McpToolResult> {
 		.and_then(|v| v.as_u64())
 		.map(|n| n as usize);
 
+	let format = call
+		.parameters
+		.get("format")
+		.and_then(|v| v.as_str())
+		.unwrap_or("flat")
+		.to_string();
+
+	if format != "flat" && format != "tree" {
+		return Err(anyhow!(
+			"Invalid 'format' parameter '{}'. Use 'flat' or 'tree'",
+			format
+		));
+	}
+	let tree_format = format == "tree";
+
 	// Build the ripgrep command based on the parameters
 	let mut cmd_args = Vec::new();
 
@@ -110,10 +177,13 @@ pub async fn execute_list_files(call: &McpToolCall) -> Result<McpToolResult> {
 
 	// Search for content in files or list files matching pattern
 	let (cmd, output_type) = if let Some(ref content_pattern) = content {
+		// Tree format needs file paths only, so list matching files instead of matching lines
+		let files_flag = if tree_format { " -l" } else { "" };
 		(
 			format!(
-				"cd '{}' && rg '{}' {}",
+				"cd '{}' && rg{} '{}' {}",
 				directory,
+				files_flag,
 				content_pattern,
 				cmd_args.join(" ")
 			),
@@ -153,13 +223,33 @@ pub async fn execute_list_files(call: &McpToolCall) -> Result<McpToolResult> {
 				let stderr = String::from_utf8_lossy(&output.stderr).to_string();
 
 				// Parse the output into a list of files
-				let files: Vec<&str> = stdout.lines().collect();
+				let mut files: Vec<&str> = stdout.lines().collect();
 				let output_str = if stdout.is_empty() && !stderr.is_empty() {
 					stderr
+				} else if tree_format {
+					files.sort();
+					render_file_tree(&files)
 				} else {
 					stdout.clone()
 				};
 
+				// Tree output already contains every path - skip the duplicate list to save tokens
+				if tree_format {
+					return json!({
+						"success": output.status.success(),
+						"output": output_str,
+						"count": files.len(),
+						"type": output_type,
+						"format": "tree",
+						"parameters": {
+							"directory": directory,
+							"pattern": pattern,
+							"content": content,
+							"max_depth": max_depth
+						}
+					});
+				}
+
 				json!({
 						"success": output.status.success(),
 						"output": output_str,
@@ -220,6 +310,23 @@ mod tests {
 		assert_eq!(convert_glob_to_regex("test?.py"), "test.\\.py$");
 	}
 
+	#[test]
+	fn test_render_file_tree() {
+		let files = vec![
+			"src/main.rs",
+			"README.md",
+			"src/mcp/mod.rs",
+			"./Cargo.toml",
+			"src/lib.rs",
+		];
+		let tree = render_file_tree(&files);
+
+		assert_eq!(
+			tree,
+			"src/\n  mcp/\n    mod.rs\n  lib.rs\n  main.rs\nCargo.toml\nREADME.md\n"
+		);
+	}
+
 	#[test]
 	fn test_single_glob_to_regex() {
 		assert_eq!(convert_single_glob_to_regex("*.rs"), ".*?\\.rs$");
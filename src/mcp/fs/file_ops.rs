@@ -15,8 +15,9 @@
 // File operations module - handling file viewing, creation, and basic manipulation
 
 use super::super::{McpToolCall, McpToolResult};
-use super::core::detect_language;
+use super::core::detect_file_language;
 use anyhow::{anyhow, Result};
+use futures::stream::{self, StreamExt};
 use serde_json::json;
 use std::path::Path;
 use tokio::fs as tokio_fs;
@@ -216,85 +217,30 @@ pub async fn create_file_spec(
 	})
 }
 
+// How many files view_many reads at once - keeps network filesystems busy without flooding them
+const VIEW_MANY_CONCURRENCY: usize = 8;
+
 // View multiple files simultaneously as part of text_editor tool
 pub async fn view_many_files_spec(call: &McpToolCall, paths: &[String]) -> Result<McpToolResult> {
 	let mut files = Vec::with_capacity(paths.len());
 	let mut failures = Vec::new();
 	let mut total_size = 0u64;
 
-	// Process each file in the list with efficient memory usage
-	for path_str in paths {
-		let path = Path::new(&path_str);
-		let path_display = path.display().to_string();
-
-		// Check if file exists and is a regular file
-		if !path.exists() {
-			failures.push(format!("File does not exist: {}", path_display));
-			continue;
-		}
-
-		if !path.is_file() {
-			failures.push(format!("Not a regular file: {}", path_display));
-			continue;
-		}
-
-		// Check file size - avoid loading very large files
-		let metadata = match tokio_fs::metadata(path).await {
-			Ok(meta) => {
-				if meta.len() > 1024 * 1024 * 5 {
-					// 5MB limit
-					failures.push(format!("File too large (>5MB): {}", path_display));
-					continue;
-				}
-				meta
-			}
-			Err(e) => {
-				failures.push(format!("Cannot read metadata for {}: {}", path_display, e));
-				continue;
-			}
-		};
-
-		// Check if file is binary
-		if let Ok(sample) = tokio_fs::read(&path).await {
-			let sample_size = sample.len().min(512);
-			let null_count = sample[..sample_size].iter().filter(|&&b| b == 0).count();
-			if null_count > sample_size / 10 {
-				failures.push(format!("Binary file skipped: {}", path_display));
-				continue;
+	// Read files concurrently; `buffered` yields results in input order
+	let results: Vec<_> = stream::iter(paths.iter().cloned())
+		.map(|path_str| async move { view_file_entry(&path_str).await })
+		.buffered(VIEW_MANY_CONCURRENCY)
+		.collect()
+		.await;
+
+	for result in results {
+		match result {
+			Ok((entry, size)) => {
+				files.push(entry);
+				total_size += size;
 			}
+			Err(failure) => failures.push(failure),
 		}
-
-		// Read file content with error handling
-		let content = match tokio_fs::read_to_string(path).await {
-			Ok(content) => content,
-			Err(e) => {
-				failures.push(format!("Cannot read content of {}: {}", path_display, e));
-				continue;
-			}
-		};
-
-		// Get language from extension for syntax highlighting
-		let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-
-		// Add line numbers to content
-		let lines: Vec<&str> = content.lines().collect();
-		let content_with_numbers = lines
-			.iter()
-			.enumerate()
-			.map(|(i, line)| format!("{}: {}", i + 1, line))
-			.collect::<Vec<_>>()
-			.join("\n");
-
-		// Add file info to collection - only store what we need
-		files.push(json!({
-			"path": path_display,
-			"content": content_with_numbers,
-			"lines": lines.len(),
-			"size": metadata.len(),
-			"lang": detect_language(ext),
-		}));
-
-		total_size += metadata.len();
 	}
 
 	// Create optimized result
@@ -311,97 +257,65 @@ pub async fn view_many_files_spec(call: &McpToolCall, paths: &[String]) -> Resul
 	})
 }
 
-// View multiple files simultaneously with optimized token usage
-pub async fn view_many_files(call: &McpToolCall, paths: &[String]) -> Result<McpToolResult> {
-	let mut files = Vec::with_capacity(paths.len());
-	let mut failures = Vec::new();
-	let mut total_size = 0u64;
+// Load one file for view_many, returning its entry and size or a failure description
+async fn view_file_entry(path_str: &str) -> std::result::Result<(serde_json::Value, u64), String> {
+	let path = Path::new(path_str);
+	let path_display = path.display().to_string();
 
-	// Process each file in the list with efficient memory usage
-	for path_str in paths {
-		let path = Path::new(&path_str);
-		let path_display = path.display().to_string();
-
-		// Check if file exists and is a regular file
-		if !path.exists() {
-			failures.push(format!("File does not exist: {}", path_display));
-			continue;
-		}
-
-		if !path.is_file() {
-			failures.push(format!("Not a regular file: {}", path_display));
-			continue;
-		}
+	// Check if file exists and is a regular file
+	if !path.exists() {
+		return Err(format!("File does not exist: {}", path_display));
+	}
 
-		// Check file size - avoid loading very large files
-		let metadata = match tokio_fs::metadata(path).await {
-			Ok(meta) => {
-				if meta.len() > 1024 * 1024 * 5 {
-					// 5MB limit
-					failures.push(format!("File too large (>5MB): {}", path_display));
-					continue;
-				}
-				meta
-			}
-			Err(e) => {
-				failures.push(format!("Cannot read metadata for {}: {}", path_display, e));
-				continue;
-			}
-		};
+	if !path.is_file() {
+		return Err(format!("Not a regular file: {}", path_display));
+	}
 
-		// Check if file is binary
-		if let Ok(sample) = tokio_fs::read(&path).await {
-			let sample_size = sample.len().min(512);
-			let null_count = sample[..sample_size].iter().filter(|&&b| b == 0).count();
-			if null_count > sample_size / 10 {
-				failures.push(format!("Binary file skipped: {}", path_display));
-				continue;
+	// Check file size - avoid loading very large files
+	let metadata = match tokio_fs::metadata(path).await {
+		Ok(meta) => {
+			if meta.len() > 1024 * 1024 * 5 {
+				// 5MB limit
+				return Err(format!("File too large (>5MB): {}", path_display));
 			}
+			meta
 		}
+		Err(e) => {
+			return Err(format!("Cannot read metadata for {}: {}", path_display, e));
+		}
+	};
 
-		// Read file content with error handling
-		let content = match tokio_fs::read_to_string(path).await {
-			Ok(content) => content,
-			Err(e) => {
-				failures.push(format!("Cannot read content of {}: {}", path_display, e));
-				continue;
-			}
-		};
-
-		// Get language from extension for syntax highlighting
-		let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-
-		// Add line numbers to content
-		let lines: Vec<&str> = content.lines().collect();
-		let content_with_numbers = lines
-			.iter()
-			.enumerate()
-			.map(|(i, line)| format!("{}: {}", i + 1, line))
-			.collect::<Vec<_>>()
-			.join("\n");
-
-		// Add file info to collection - only store what we need
-		files.push(json!({
-			"path": path_display,
-			"content": content_with_numbers,
-			"lines": lines.len(),
-			"size": metadata.len(),
-			"lang": detect_language(ext),
-		}));
-
-		total_size += metadata.len();
+	// Check if file is binary
+	if let Ok(sample) = tokio_fs::read(&path).await {
+		let sample_size = sample.len().min(512);
+		let null_count = sample[..sample_size].iter().filter(|&&b| b == 0).count();
+		if null_count > sample_size / 10 {
+			return Err(format!("Binary file skipped: {}", path_display));
+		}
 	}
 
-	// Create optimized result
-	Ok(McpToolResult {
-		tool_name: "view_many".to_string(),
-		tool_id: call.tool_id.clone(),
-		result: json!({
-			"success": !files.is_empty(),
-			"files": files,
-			"count": files.len(),
-			"total_size": total_size,
-			"failed": failures,
-		}),
-	})
+	// Read file content with error handling
+	let content = tokio_fs::read_to_string(path)
+		.await
+		.map_err(|e| format!("Cannot read content of {}: {}", path_display, e))?;
+
+	// Add line numbers to content
+	let lines: Vec<&str> = content.lines().collect();
+	let content_with_numbers = lines
+		.iter()
+		.enumerate()
+		.map(|(i, line)| format!("{}: {}", i + 1, line))
+		.collect::<Vec<_>>()
+		.join("\n");
+
+	// Only store what we need
+	let entry = json!({
+		"path": path_display,
+		"content": content_with_numbers,
+		"lines": lines.len(),
+		"size": metadata.len(),
+		"lang": detect_file_language(path, &content),
+	});
+
+	Ok((entry, metadata.len()))
 }
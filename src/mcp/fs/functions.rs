@@ -33,6 +33,7 @@ pub fn get_list_files_function() -> McpFunction {
 			- `pattern`: Optional filename pattern (uses ripgrep syntax)
 			- `content`: Optional content search within files
 			- `max_depth`: Optional depth limit for directory traversal
+			- `format`: Optional output format - `flat` (default, one path per line) or `tree` (indented directory tree)
 
 			Best Practices:
 			- Always use specific patterns - avoid listing entire large directories
@@ -45,6 +46,7 @@ pub fn get_list_files_function() -> McpFunction {
 			- Find config files: `{\"directory\": \".\", \"pattern\": \"\\*.toml|\\*.yaml|\\*.json\"}`
 			- Search for function: `{\"directory\": \"src\", \"content\": \"fn main\"}`
 			- Limited depth: `{\"directory\": \".\", \"max_depth\": 2, \"pattern\": \"\\*.rs\"}`
+			- Project layout: `{\"directory\": \".\", \"max_depth\": 3, \"format\": \"tree\"}`
 
 			Token-Efficient Usage:
 			- Use patterns to target specific file types
@@ -71,6 +73,11 @@ pub fn get_list_files_function() -> McpFunction {
 				"max_depth": {
 					"type": "integer",
 					"description": "Maximum depth of directories to descend (default: no limit)"
+				},
+				"format": {
+					"type": "string",
+					"enum": ["flat", "tree"],
+					"description": "Output format: 'flat' list of paths (default) or indented 'tree' view of directories"
 				}
 			}
 		}),
@@ -110,6 +117,13 @@ pub fn get_text_editor_function() -> McpFunction {
 			- Content-based replacement - works regardless of line numbers
 			- Use when exact text is known but line numbers uncertain
 
+			`regex_replace`: Replace regex matches in file
+			- `{\"command\": \"regex_replace\", \"path\": \"src/main.rs\", \"pattern\": \"fn\\\\s+old_(\\\\w+)\", \"replacement\": \"fn new_$1\"}`
+			- Uses Rust regex syntax; $1 or ${name} in replacement insert capture groups
+			- Replaces all matches unless `count` limits the number of replacements
+			- Returns error if the pattern matches nothing
+			- Use when whitespace varies or for structural edits where str_replace is too brittle
+
 			`insert`: Insert text at specific location in file
 			- `{\"command\": \"insert\", \"path\": \"src/main.rs\", \"insert_line\": 5, \"new_str\": \"    // New comment\\n    let x = 10;\"}`
 			- insert_line specifies the line number after which to insert (0 for beginning of file)
@@ -118,6 +132,7 @@ pub fn get_text_editor_function() -> McpFunction {
 			`line_replace`: Replace content within specific line range
 			- `{\"command\": \"line_replace\", \"path\": \"src/main.rs\", \"view_range\": [5, 8], \"new_str\": \"fn updated_function() {\\n    // New implementation\\n}\"}`
 			- Replaces lines from view_range[0] to view_range[1] (inclusive, 1-indexed)
+			- Negative numbers count from the end of the file: [-3, -1] replaces the last three lines
 			- FASTEST option - 3x faster than str_replace (no content searching)
 			- CRITICAL: Line numbers change after ANY edit operation
 			- NEVER use line_replace twice without viewing file between operations
@@ -130,7 +145,7 @@ pub fn get_text_editor_function() -> McpFunction {
 
 			`undo_edit`: Revert most recent edit to specified file
 			- `{\"command\": \"undo_edit\", \"path\": \"src/main.rs\"}`
-			- Available for str_replace, insert, and line_replace operations
+			- Available for str_replace, regex_replace, insert, and line_replace operations
 
 			`batch_edit`: Perform multiple text editing operations in single call
 			- `{\"command\": \"batch_edit\", \"operations\": [{\"operation\": \"str_replace\", \"path\": \"src/main.rs\", \"old_str\": \"old\", \"new_str\": \"new\"}, {\"operation\": \"insert\", \"path\": \"src/lib.rs\", \"insert_line\": 5, \"new_str\": \"// New comment\"}]}`
@@ -195,8 +210,8 @@ pub fn get_text_editor_function() -> McpFunction {
 			"properties": {
 				"command": {
 					"type": "string",
-					"enum": ["view", "view_many", "create", "str_replace", "insert", "line_replace", "undo_edit", "batch_edit"],
-					"description": "The operation to perform: view, view_many, create, str_replace, insert, line_replace, undo_edit, or batch_edit"
+					"enum": ["view", "view_many", "create", "str_replace", "regex_replace", "insert", "line_replace", "undo_edit", "batch_edit"],
+					"description": "The operation to perform: view, view_many, create, str_replace, regex_replace, insert, line_replace, undo_edit, or batch_edit"
 				},
 				"path": {
 					"type": "string",
@@ -227,6 +242,19 @@ pub fn get_text_editor_function() -> McpFunction {
 					"type": "string",
 					"description": "Replacement text for str_replace, text to insert for insert command, or new content for line_replace command"
 				},
+				"pattern": {
+					"type": "string",
+					"description": "Regular expression to match for regex_replace command"
+				},
+				"replacement": {
+					"type": "string",
+					"description": "Replacement for regex_replace command; $1 or ${name} insert capture groups"
+				},
+				"count": {
+					"type": "integer",
+					"minimum": 1,
+					"description": "Maximum number of replacements for regex_replace command (default: replace all)"
+				},
 				"insert_line": {
 					"type": "integer",
 					"minimum": 0,
@@ -265,7 +293,7 @@ pub fn get_text_editor_function() -> McpFunction {
 								"items": {"type": "integer"},
 								"minItems": 2,
 								"maxItems": 2,
-								"description": "Line range [start, end] for line_replace (required for line_replace)"
+								"description": "Line range [start, end] for line_replace (required for line_replace, 1-indexed; negative numbers count from the end, -1 = last line)"
 							}
 						}
 					},
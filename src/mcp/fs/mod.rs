@@ -20,6 +20,7 @@ pub mod directory;
 pub mod file_ops;
 pub mod functions;
 pub mod html_converter;
+pub mod sandbox;
 pub mod text_editing;
 
 // Re-export main functionality
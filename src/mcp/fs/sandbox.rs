@@ -0,0 +1,192 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Filesystem sandbox - keeps text_editor and list_files paths inside fs_sandbox_root
+
+use super::super::McpToolCall;
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::path::{Component, Path, PathBuf};
+
+/// Reject the call when any of its paths resolves outside the configured sandbox root.
+/// Symlinks are followed, so a link inside the root cannot reach files outside it.
+pub fn check_call(call: &McpToolCall, config: &crate::config::Config) -> Result<()> {
+	let Some(root) = config.fs_sandbox_root.as_deref().filter(|r| !r.is_empty()) else {
+		return Ok(());
+	};
+	if !matches!(call.tool_name.as_str(), "text_editor" | "list_files") {
+		return Ok(());
+	}
+
+	let root = std::env::current_dir()?
+		.join(root)
+		.canonicalize()
+		.context(format!("fs_sandbox_root {} does not exist", root))?;
+	let mut parameters = call.parameters.clone();
+	for_each_path_argument(&mut parameters, |path| check_path(path, &root))
+}
+
+/// Visit every path argument of the filesystem tools: `path`, `directory`, the
+/// `paths` of view_many and the `path` of each batch_edit operation
+pub fn for_each_path_argument(
+	parameters: &mut Value,
+	mut visit: impl FnMut(&mut String) -> Result<()>,
+) -> Result<()> {
+	for name in ["path", "directory"] {
+		if let Some(Value::String(path)) = parameters.get_mut(name) {
+			visit(path)?;
+		}
+	}
+	if let Some(Value::Array(paths)) = parameters.get_mut("paths") {
+		for value in paths {
+			if let Value::String(path) = value {
+				visit(path)?;
+			}
+		}
+	}
+	if let Some(Value::Array(operations)) = parameters.get_mut("operations") {
+		for operation in operations {
+			if let Some(Value::String(path)) = operation.get_mut("path") {
+				visit(path)?;
+			}
+		}
+	}
+	Ok(())
+}
+
+fn check_path(path: &str, root: &Path) -> Result<()> {
+	let resolved = resolve_real_path(Path::new(path))?;
+	if resolved.starts_with(root) {
+		Ok(())
+	} else {
+		Err(anyhow!(
+			"Access denied: path '{}' resolves to {}, outside the sandbox root {}",
+			path,
+			resolved.display(),
+			root.display()
+		))
+	}
+}
+
+// Canonicalize the deepest existing ancestor (resolving symlinks) and append the rest,
+// which may not exist yet when a file is about to be created
+fn resolve_real_path(path: &Path) -> Result<PathBuf> {
+	let absolute = if path.is_absolute() {
+		path.to_path_buf()
+	} else {
+		std::env::current_dir()?.join(path)
+	};
+
+	let mut existing = absolute.as_path();
+	let mut missing = Vec::new();
+	let mut resolved = loop {
+		match existing.canonicalize() {
+			Ok(real) => break real,
+			Err(_) => {
+				missing.extend(existing.components().next_back());
+				existing = existing
+					.parent()
+					.ok_or_else(|| anyhow!("Cannot resolve path '{}'", path.display()))?;
+			}
+		}
+	};
+
+	missing.reverse();
+	for (i, component) in missing.iter().enumerate() {
+		match component {
+			Component::ParentDir => {
+				// `..` may climb back into existing directories, where a symlink could
+				// lead out of the root, so resolve the remainder from there again
+				resolved.pop();
+				let rest: PathBuf = missing[i + 1..].iter().collect();
+				return resolve_real_path(&resolved.join(rest));
+			}
+			Component::CurDir => {}
+			other => resolved.push(other),
+		}
+	}
+	Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// Sandbox root with one file inside and one file next to it, outside the root
+	fn sandbox() -> (tempfile::TempDir, PathBuf, PathBuf) {
+		let temp = tempfile::tempdir().unwrap();
+		// The temp dir itself may sit behind a symlink (macOS /tmp)
+		let base = temp.path().canonicalize().unwrap();
+		let root = base.join("root");
+		std::fs::create_dir_all(root.join("src")).unwrap();
+		std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+		std::fs::write(base.join("secret.txt"), "secret\n").unwrap();
+		(temp, base, root)
+	}
+
+	#[test]
+	fn test_paths_inside_root_are_allowed() {
+		let (_temp, _, root) = sandbox();
+		let check = |path: PathBuf| check_path(&path.to_string_lossy(), &root);
+
+		assert!(check(root.join("src/main.rs")).is_ok());
+		assert!(check(root.join("src/../src/main.rs")).is_ok());
+		// Files that do not exist yet, as for create
+		assert!(check(root.join("new/dir/file.rs")).is_ok());
+	}
+
+	#[test]
+	fn test_parent_dir_traversal_is_rejected() {
+		let (_temp, _, root) = sandbox();
+		let check = |path: PathBuf| check_path(&path.to_string_lossy(), &root);
+
+		assert!(check(root.join("../secret.txt")).is_err());
+		assert!(check(root.join("src/../../secret.txt")).is_err());
+		assert!(check(root.join("missing/../../secret.txt")).is_err());
+		assert!(check(PathBuf::from("/etc/passwd")).is_err());
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_symlink_escape_is_rejected() {
+		let (_temp, base, root) = sandbox();
+		std::os::unix::fs::symlink(base.join("secret.txt"), root.join("link.txt")).unwrap();
+		std::os::unix::fs::symlink(&base, root.join("outside")).unwrap();
+		let check = |path: PathBuf| check_path(&path.to_string_lossy(), &root);
+
+		assert!(check(root.join("link.txt")).is_err());
+		assert!(check(root.join("outside/secret.txt")).is_err());
+		// Creating a new file through a linked directory escapes too
+		assert!(check(root.join("outside/new.txt")).is_err());
+		// A missing directory followed by `..` must not hide the link from resolution
+		assert!(check(root.join("new/../outside/new.txt")).is_err());
+		assert!(check(root.join("new/../src/new.rs")).is_ok());
+	}
+
+	#[test]
+	fn test_for_each_path_argument() {
+		let mut parameters = serde_json::json!({
+			"command": "batch_edit",
+			"paths": ["a.rs", "b.rs"],
+			"operations": [{"path": "c.rs"}, {"operation": "insert"}]
+		});
+		let mut seen = Vec::new();
+		for_each_path_argument(&mut parameters, |path| {
+			seen.push(path.clone());
+			Ok(())
+		})
+		.unwrap();
+		assert_eq!(seen, vec!["a.rs", "b.rs", "c.rs"]);
+	}
+}
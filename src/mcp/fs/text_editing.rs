@@ -17,6 +17,7 @@
 use super::super::{McpToolCall, McpToolResult};
 use super::core::save_file_history;
 use anyhow::{anyhow, Result};
+use regex::Regex;
 use serde_json::{json, Value};
 use std::path::Path;
 use tokio::fs as tokio_fs;
@@ -88,6 +89,93 @@ pub async fn str_replace_spec(
 	})
 }
 
+// Replace regex matches in a file; `count` limits replacements (None = replace all)
+pub async fn regex_replace_spec(
+	call: &McpToolCall,
+	path: &Path,
+	pattern: &str,
+	replacement: &str,
+	count: Option<usize>,
+) -> Result<McpToolResult> {
+	if !path.exists() {
+		return Ok(McpToolResult {
+			tool_name: "text_editor".to_string(),
+			tool_id: call.tool_id.clone(),
+			result: json!({
+				"error": "File not found",
+				"is_error": true
+			}),
+		});
+	}
+
+	let regex = match Regex::new(pattern) {
+		Ok(regex) => regex,
+		Err(e) => {
+			return Ok(McpToolResult {
+				tool_name: "text_editor".to_string(),
+				tool_id: call.tool_id.clone(),
+				result: json!({
+					"error": format!("Invalid regex pattern: {}", e),
+					"is_error": true
+				}),
+			});
+		}
+	};
+
+	// Read the file content
+	let content = tokio_fs::read_to_string(path)
+		.await
+		.map_err(|e| anyhow!("Permission denied. Cannot read file: {}", e))?;
+
+	let (new_content, replacements) = apply_regex_replace(&content, &regex, replacement, count);
+	if replacements == 0 {
+		return Ok(McpToolResult {
+			tool_name: "text_editor".to_string(),
+			tool_id: call.tool_id.clone(),
+			result: json!({
+				"error": "No match found for pattern. Please check your pattern and try again.",
+				"is_error": true
+			}),
+		});
+	}
+
+	// Save the current content for undo
+	save_file_history(path).await?;
+
+	// Write the new content
+	tokio_fs::write(path, new_content)
+		.await
+		.map_err(|e| anyhow!("Permission denied. Cannot write to file: {}", e))?;
+
+	Ok(McpToolResult {
+		tool_name: "text_editor".to_string(),
+		tool_id: call.tool_id.clone(),
+		result: json!({
+			"content": format!("Successfully made {} replacement(s).", replacements),
+			"replacements": replacements,
+			"path": path.to_string_lossy()
+		}),
+	})
+}
+
+// Apply up to `count` regex replacements (all when None), expanding $1/${name} capture groups
+fn apply_regex_replace(
+	content: &str,
+	regex: &Regex,
+	replacement: &str,
+	count: Option<usize>,
+) -> (String, usize) {
+	let matches = regex.find_iter(content).count();
+	let limit = count.unwrap_or(0);
+	let replacements = if limit == 0 {
+		matches
+	} else {
+		matches.min(limit)
+	};
+	let new_content = regex.replacen(content, limit, replacement).into_owned();
+	(new_content, replacements)
+}
+
 // Insert text at a specific location in a file following Anthropic specification
 pub async fn insert_text_spec(
 	call: &McpToolCall,
@@ -160,11 +248,21 @@ pub async fn insert_text_spec(
 	})
 }
 
+// Resolve a 1-indexed line number, where negative values count back from the end
+// of the file (-1 = last line); None when it resolves before the first line
+fn resolve_line_index(index: i64, line_count: usize) -> Option<usize> {
+	if index > 0 {
+		return Some(index as usize);
+	}
+	let resolved = line_count as i64 + 1 + index;
+	(index < 0 && resolved >= 1).then_some(resolved as usize)
+}
+
 // Replace content within a specific line range following text editor specifications
 pub async fn line_replace_spec(
 	call: &McpToolCall,
 	path: &Path,
-	view_range: (usize, usize),
+	view_range: (i64, i64),
 	new_str: &str,
 ) -> Result<McpToolResult> {
 	if !path.exists() {
@@ -189,20 +287,42 @@ pub async fn line_replace_spec(
 		});
 	}
 
-	let (start_line, end_line) = view_range;
-
 	// Validate line numbers
-	if start_line == 0 || end_line == 0 {
+	if view_range.0 == 0 || view_range.1 == 0 {
 		return Ok(McpToolResult {
 			tool_name: "text_editor".to_string(),
 			tool_id: call.tool_id.clone(),
 			result: json!({
-				"error": "Line numbers must be 1-indexed (start from 1)",
+				"error": "Line numbers must be 1-indexed (start from 1), or negative to count from the end (-1 = last line)",
 				"is_error": true
 			}),
 		});
 	}
 
+	// Read the file content
+	let file_content = tokio_fs::read_to_string(path)
+		.await
+		.map_err(|e| anyhow!("Permission denied. Cannot read file: {}", e))?;
+	let mut lines: Vec<&str> = file_content.lines().collect();
+
+	// Negative indices are resolved against the current line count
+	let (start_line, end_line) = match (
+		resolve_line_index(view_range.0, lines.len()),
+		resolve_line_index(view_range.1, lines.len()),
+	) {
+		(Some(start), Some(end)) => (start, end),
+		_ => {
+			return Ok(McpToolResult {
+				tool_name: "text_editor".to_string(),
+				tool_id: call.tool_id.clone(),
+				result: json!({
+					"error": format!("view_range [{}, {}] reaches before the start of the file ({} lines)", view_range.0, view_range.1, lines.len()),
+					"is_error": true
+				}),
+			});
+		}
+	};
+
 	if start_line > end_line {
 		return Ok(McpToolResult {
 			tool_name: "text_editor".to_string(),
@@ -214,12 +334,6 @@ pub async fn line_replace_spec(
 		});
 	}
 
-	// Read the file content
-	let file_content = tokio_fs::read_to_string(path)
-		.await
-		.map_err(|e| anyhow!("Permission denied. Cannot read file: {}", e))?;
-	let mut lines: Vec<&str> = file_content.lines().collect();
-
 	// Validate line ranges exist in file BEFORE accessing the array
 	if start_line > lines.len() {
 		return Ok(McpToolResult {
@@ -308,7 +422,7 @@ pub async fn line_replace_spec(
 	let lines_replaced_count = end_line - start_line + 1;
 	let new_lines_count = new_str.lines().count();
 
-	let content_message = if lines_replaced_count == 1 && new_lines_count == 1 {
+	let mut content_message = if lines_replaced_count == 1 && new_lines_count == 1 {
 		format!("Successfully replaced line {} with new content", start_line)
 	} else if lines_replaced_count == 1 {
 		format!(
@@ -326,6 +440,12 @@ pub async fn line_replace_spec(
 			lines_replaced_count, start_line, end_line, new_lines_count
 		)
 	};
+	if view_range.0 < 0 || view_range.1 < 0 {
+		content_message.push_str(&format!(
+			" (view_range [{}, {}] resolved to lines {}-{})",
+			view_range.0, view_range.1, start_line, end_line
+		));
+	}
 
 	Ok(McpToolResult {
 		tool_name: "text_editor".to_string(),
@@ -471,8 +591,8 @@ pub async fn batch_edit_spec(call: &McpToolCall, operations: &[Value]) -> Result
 			"line_replace" => {
 				let view_range = match operation_obj.get("view_range").and_then(|v| v.as_array()) {
 					Some(arr) if arr.len() == 2 => {
-						let start = arr[0].as_u64().unwrap_or(0) as usize;
-						let end = arr[1].as_u64().unwrap_or(0) as usize;
+						let start = arr[0].as_i64().unwrap_or(0);
+						let end = arr[1].as_i64().unwrap_or(0);
 						if start == 0 || end == 0 {
 							failed_operations += 1;
 							operation_details.push(json!({
@@ -480,7 +600,7 @@ pub async fn batch_edit_spec(call: &McpToolCall, operations: &[Value]) -> Result
 								"operation": op_type,
 								"path": path_str,
 								"status": "failed",
-								"error": "Invalid 'view_range' - line numbers must be 1-indexed"
+								"error": "Invalid 'view_range' - line numbers must be 1-indexed, or negative to count from the end"
 							}));
 							continue;
 						}
@@ -584,3 +704,36 @@ pub async fn batch_edit_spec(call: &McpToolCall, operations: &[Value]) -> Result
 		}),
 	})
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_apply_regex_replace_with_capture_groups() {
+		let regex = Regex::new(r"fn\s+(\w+)\(\)").unwrap();
+		let content = "fn  alpha() {}\nfn beta() {}\n";
+
+		let (all, replaced) = apply_regex_replace(content, &regex, "fn ${1}_v2()", None);
+		assert_eq!(replaced, 2);
+		assert_eq!(all, "fn alpha_v2() {}\nfn beta_v2() {}\n");
+
+		let (first, replaced) = apply_regex_replace(content, &regex, "fn ${1}_v2()", Some(1));
+		assert_eq!(replaced, 1);
+		assert_eq!(first, "fn alpha_v2() {}\nfn beta() {}\n");
+
+		let (unchanged, replaced) =
+			apply_regex_replace(content, &Regex::new("gamma").unwrap(), "x", None);
+		assert_eq!(replaced, 0);
+		assert_eq!(unchanged, content);
+	}
+
+	#[test]
+	fn test_resolve_line_index() {
+		assert_eq!(resolve_line_index(3, 10), Some(3));
+		assert_eq!(resolve_line_index(-1, 10), Some(10));
+		assert_eq!(resolve_line_index(-10, 10), Some(1));
+		assert_eq!(resolve_line_index(-11, 10), None);
+		assert_eq!(resolve_line_index(0, 10), None);
+	}
+}
@@ -16,6 +16,8 @@
 
 use super::process::{self, ServerHealth};
 use crate::config::{Config, McpConnectionType, McpServerConfig};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -26,6 +28,44 @@ static HEALTH_MONITOR_RUNNING: AtomicBool = AtomicBool::new(false);
 
 // Health monitoring configuration
 const HEALTH_CHECK_INTERVAL_SECONDS: u64 = 30; // Check every 30 seconds
+const MAX_RESTART_BACKOFF_MS: u64 = 60 * 60 * 1000; // Never wait more than an hour between attempts
+const STATUS_FILE_NAME: &str = "mcp_status.json"; // Server state published for `octomind mcp status`
+
+/// Server state published by a running session's health monitor. Servers only exist
+/// inside the process that started them, so other processes read it from this file.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PublishedStatus {
+	pub pid: u32,
+	pub updated_at: u64, // Unix timestamp in seconds
+	pub servers: Vec<process::ServerStatusSnapshot>,
+}
+
+/// Restart limits taken from the config
+#[derive(Debug, Clone, Copy)]
+struct RestartPolicy {
+	max_restarts: u32,
+	backoff_ms: u64,
+}
+
+impl RestartPolicy {
+	fn from_config(config: &Config) -> Self {
+		Self {
+			max_restarts: config.mcp_max_restarts,
+			backoff_ms: config.mcp_restart_backoff_ms,
+		}
+	}
+
+	/// Wait before the next restart attempt: the base backoff doubled for each
+	/// consecutive failure after the first
+	fn backoff(&self, consecutive_failures: u32) -> Duration {
+		let exponent = consecutive_failures.saturating_sub(1).min(32);
+		let backoff_ms = self
+			.backoff_ms
+			.saturating_mul(1u64 << exponent)
+			.min(MAX_RESTART_BACKOFF_MS);
+		Duration::from_millis(backoff_ms)
+	}
+}
 
 /// Start the background health monitoring task
 pub async fn start_health_monitor(config: Arc<Config>) -> Result<(), anyhow::Error> {
@@ -43,6 +83,8 @@ pub async fn start_health_monitor(config: Arc<Config>) -> Result<(), anyhow::Err
 		HEALTH_CHECK_INTERVAL_SECONDS
 	);
 
+	let policy = RestartPolicy::from_config(&config);
+
 	// Get external servers that need monitoring
 	let external_servers: Vec<McpServerConfig> = config
 		.mcp
@@ -51,7 +93,7 @@ pub async fn start_health_monitor(config: Arc<Config>) -> Result<(), anyhow::Err
 		.filter(|server| {
 			matches!(
 				server.connection_type,
-				McpConnectionType::Http | McpConnectionType::Stdin
+				McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::WebSocket
 			)
 		})
 		.cloned()
@@ -89,10 +131,14 @@ pub async fn start_health_monitor(config: Arc<Config>) -> Result<(), anyhow::Err
 
 			// Perform health check on all external servers and restart if process is dead
 			for server in &external_servers {
-				if let Err(e) = check_server_health_and_restart_if_dead(server).await {
+				if let Err(e) = check_server_health_and_restart_if_dead(server, policy).await {
 					crate::log_debug!("Health monitor error for server '{}': {}", server.name, e);
 				}
 			}
+
+			if let Err(e) = publish_status() {
+				crate::log_debug!("Failed to publish MCP server status: {}", e);
+			}
 		}
 
 		crate::log_debug!("Health monitor task completed");
@@ -108,13 +154,66 @@ pub fn stop_health_monitor() {
 		.is_ok()
 	{
 		crate::log_debug!("Stopping health monitor");
+		// Leave no state behind that claims the servers are still monitored
+		if read_published_status().is_some_and(|status| status.pid == std::process::id()) {
+			if let Ok(path) = status_file_path() {
+				let _ = std::fs::remove_file(path);
+			}
+		}
 	}
 }
 
+fn status_file_path() -> Result<PathBuf, anyhow::Error> {
+	Ok(crate::directories::get_octomind_data_dir()?.join(STATUS_FILE_NAME))
+}
+
+fn unix_now() -> u64 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.unwrap_or_default()
+		.as_secs()
+}
+
+// Write the current server state, replacing the file atomically so readers never see
+// a partial write
+fn publish_status() -> Result<(), anyhow::Error> {
+	let status = PublishedStatus {
+		pid: std::process::id(),
+		updated_at: unix_now(),
+		servers: process::get_server_status_snapshot(),
+	};
+	let path = status_file_path()?;
+	let tmp_path = path.with_extension(format!("json.{}", status.pid));
+	std::fs::write(&tmp_path, serde_json::to_string(&status)?)?;
+	std::fs::rename(&tmp_path, &path)?;
+	Ok(())
+}
+
+/// Server state published by a running health monitor, or None when no session is
+/// monitoring servers (missing file, or not updated for two check intervals)
+pub fn read_published_status() -> Option<PublishedStatus> {
+	let content = std::fs::read_to_string(status_file_path().ok()?).ok()?;
+	let status: PublishedStatus = serde_json::from_str(&content).ok()?;
+	let max_age = HEALTH_CHECK_INTERVAL_SECONDS * 2;
+	(unix_now().saturating_sub(status.updated_at) <= max_age).then_some(status)
+}
+
 /// Check a single server's health and restart ONLY if process is dead
 async fn check_server_health_and_restart_if_dead(
 	server: &McpServerConfig,
+	policy: RestartPolicy,
 ) -> Result<(), anyhow::Error> {
+	// WebSocket servers run elsewhere and cannot be restarted - only track reachability
+	if server.connection_type == McpConnectionType::WebSocket {
+		if !super::websocket::check_websocket_connectivity(server).await {
+			crate::log_debug!(
+				"Health monitor: WebSocket server '{}' is unreachable",
+				server.name
+			);
+		}
+		return Ok(());
+	}
+
 	// Get current server health status
 	let health_status = process::get_server_health(&server.name);
 	let restart_info = process::get_server_restart_info(&server.name);
@@ -141,12 +240,13 @@ async fn check_server_health_and_restart_if_dead(
 				server.name
 			);
 
-			// Check if we should attempt restart (respect max attempts)
-			if restart_info.restart_count >= 3 {
-				crate::log_debug!(
-					"Server '{}' has exceeded max restart attempts ({}), marking as failed",
+			// Give up once the server keeps dying (respect max attempts)
+			if restart_info.consecutive_failures >= policy.max_restarts {
+				crate::log_info!(
+					"Server '{}' failed {} times in a row - giving up (use /mcp reset {} to retry)",
 					server.name,
-					restart_info.restart_count
+					restart_info.consecutive_failures,
+					server.name
 				);
 
 				// Mark as failed to prevent further restart attempts
@@ -157,21 +257,29 @@ async fn check_server_health_and_restart_if_dead(
 				return Ok(());
 			}
 
-			// Check cooldown period to avoid rapid restart attempts
+			// Exponential backoff between attempts to avoid restart storms
 			if let Some(last_restart) = restart_info.last_restart_time {
 				let time_since_restart = std::time::SystemTime::now()
 					.duration_since(last_restart)
 					.unwrap_or(std::time::Duration::from_secs(0));
 
-				if time_since_restart < Duration::from_secs(30) {
+				if time_since_restart < policy.backoff(restart_info.consecutive_failures) {
 					crate::log_debug!(
-						"Server '{}' is in cooldown period, skipping restart attempt",
+						"Server '{}' is in backoff period, skipping restart attempt",
 						server.name
 					);
 					return Ok(());
 				}
 			}
 
+			// Count the attempt up front: it is cleared once the server is seen running again
+			{
+				let mut restart_info_guard = process::SERVER_RESTART_INFO.write().unwrap();
+				let info = restart_info_guard.entry(server.name.clone()).or_default();
+				info.consecutive_failures += 1;
+				info.last_restart_time = Some(std::time::SystemTime::now());
+			}
+
 			// Attempt to restart the dead server
 			match restart_dead_server(server).await {
 				Ok(()) => {
@@ -190,27 +298,11 @@ async fn check_server_health_and_restart_if_dead(
 			}
 		}
 		ServerHealth::Failed => {
-			// Server has failed - check if enough time has passed to reset failure state
-			if let Some(last_restart) = restart_info.last_restart_time {
-				let time_since_last_restart = std::time::SystemTime::now()
-					.duration_since(last_restart)
-					.unwrap_or(std::time::Duration::from_secs(0));
-
-				// Reset failure state after 5 minutes
-				if time_since_last_restart > Duration::from_secs(300) {
-					crate::log_debug!(
-						"Resetting failed state for server '{}' after cooldown period",
-						server.name
-					);
-					if let Err(e) = process::reset_server_failure_state(&server.name) {
-						crate::log_debug!(
-							"Failed to reset failure state for server '{}': {}",
-							server.name,
-							e
-						);
-					}
-				}
-			}
+			// Given up on - stays failed until reset with /mcp reset
+			crate::log_debug!(
+				"Health monitor: server '{}' has failed and will not be restarted",
+				server.name
+			);
 		}
 		ServerHealth::Running => {
 			// Server is running - verify responsiveness but don't restart on failed responses
@@ -222,6 +314,12 @@ async fn check_server_health_and_restart_if_dead(
 				);
 				// Don't mark as dead - failed responses are normal
 				// Only mark as dead if the actual process is not running
+			} else if restart_info.consecutive_failures > 0 {
+				// Still alive a full check interval after a restart - the failure streak is over
+				let mut restart_info_guard = process::SERVER_RESTART_INFO.write().unwrap();
+				if let Some(info) = restart_info_guard.get_mut(&server.name) {
+					info.consecutive_failures = 0;
+				}
 			}
 		}
 		ServerHealth::Restarting => {
@@ -280,6 +378,10 @@ async fn verify_server_responsiveness(server: &McpServerConfig) -> bool {
 			// Don't make HTTP requests - failed responses are normal
 			process::is_server_running(&server.name)
 		}
+		McpConnectionType::WebSocket => {
+			// No process to inspect - connect and complete the handshake
+			super::websocket::check_websocket_connectivity(server).await
+		}
 		McpConnectionType::Builtin => {
 			// Built-in servers are always "running"
 			true
@@ -303,14 +405,15 @@ pub async fn force_health_check(config: &Config) -> Result<(), anyhow::Error> {
 		.filter(|server| {
 			matches!(
 				server.connection_type,
-				McpConnectionType::Http | McpConnectionType::Stdin
+				McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::WebSocket
 			)
 		})
 		.cloned()
 		.collect();
 
+	let policy = RestartPolicy::from_config(config);
 	for server in &external_servers {
-		if let Err(e) = check_server_health_and_restart_if_dead(server).await {
+		if let Err(e) = check_server_health_and_restart_if_dead(server, policy).await {
 			crate::log_debug!(
 				"Force health check error for server '{}': {}",
 				server.name,
@@ -321,3 +424,24 @@ pub async fn force_health_check(config: &Config) -> Result<(), anyhow::Error> {
 
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_restart_backoff_doubles_and_caps() {
+		let policy = RestartPolicy {
+			max_restarts: 3,
+			backoff_ms: 1_000,
+		};
+		assert_eq!(policy.backoff(0), Duration::from_secs(1));
+		assert_eq!(policy.backoff(1), Duration::from_secs(1));
+		assert_eq!(policy.backoff(2), Duration::from_secs(2));
+		assert_eq!(policy.backoff(4), Duration::from_secs(8));
+		assert_eq!(
+			policy.backoff(100),
+			Duration::from_millis(MAX_RESTART_BACKOFF_MS)
+		);
+	}
+}
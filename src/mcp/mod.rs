@@ -29,12 +29,24 @@ lazy_static::lazy_static! {
 		Arc::new(RwLock::new(std::collections::HashMap::new()));
 }
 
+// Tool-to-server routing map, keyed by a hash of the server set it was built from
+pub type ToolServerMap = std::collections::HashMap<String, crate::config::McpServerConfig>;
+
+lazy_static::lazy_static! {
+	static ref TOOL_SERVER_MAP_CACHE: RwLock<Option<(u64, Arc<ToolServerMap>)>> = RwLock::new(None);
+}
+
 pub mod agent;
 pub mod dev;
 pub mod fs;
 pub mod health_monitor;
 pub mod process;
+pub mod redact;
+pub mod result_cache;
+pub mod scratch;
 pub mod server;
+pub mod strict_schema;
+pub mod websocket;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpToolCall {
@@ -250,7 +262,9 @@ pub async fn initialize_servers_for_role(config: &crate::config::Config) -> Resu
 
 	for server in &enabled_servers {
 		// Only initialize external servers that need to be started
-		if let McpConnectionType::Http | McpConnectionType::Stdin = server.connection_type {
+		if let McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::WebSocket =
+			server.connection_type
+		{
 			crate::log_debug!("Initializing external server: {}", server.name);
 
 			// Check if server is already running to avoid double initialization
@@ -356,7 +370,7 @@ pub async fn get_available_functions(config: &crate::config::Config) -> Vec<McpF
 					}
 				}
 			}
-			McpConnectionType::Http | McpConnectionType::Stdin => {
+			McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::WebSocket => {
 				// CRITICAL FIX: For external servers, use cached function discovery
 				// This avoids spawning servers during system prompt creation
 				match server::get_server_functions_cached(&server).await {
@@ -386,6 +400,9 @@ pub async fn get_available_functions(config: &crate::config::Config) -> Vec<McpF
 		}
 	}
 
+	// Denied tools win over everything else, so they never reach the model
+	functions.retain(|func| !config.mcp.is_tool_denied(&func.name));
+
 	functions
 }
 
@@ -473,26 +490,96 @@ pub async fn execute_tool_call(
 		}
 	}
 
+	// With scratch_dir set, filesystem tool paths resolve inside it
+	let scoped_call = scratch::scope_call(call, config)?;
+	let call = &scoped_call;
+	// fs_sandbox_root confines filesystem tool paths, following symlinks
+	fs::sandbox::check_call(call, config)?;
+
+	// Permissions come first: cached results were stored under whatever config ran the call
+	check_tool_permissions(call, config)?;
+
+	// Tools configured in tool_cache reuse an earlier result for identical calls
+	if let Some(cached) = result_cache::lookup(call, config) {
+		log_debug!("Tool {} answered from the result cache", call.tool_name);
+		return Ok((cached, 0));
+	}
+
 	// Track tool execution time
 	let tool_start = std::time::Instant::now();
 
 	let result = try_execute_tool_call(call, config, cancellation_token.clone()).await;
+	result_cache::invalidate_after(call);
 
 	// Calculate tool execution time
 	let tool_duration = tool_start.elapsed();
 	let tool_time_ms = tool_duration.as_millis() as u64;
 
 	match result {
-		Ok(tool_result) => Ok((tool_result, tool_time_ms)),
+		Ok(mut tool_result) => {
+			// Mask credentials before the result is displayed, logged, cached or sent
+			let redacted = redact::redact_result(&mut tool_result, config);
+			if redacted > 0 {
+				log_debug!(
+					"Redacted {} secret(s) from the {} result",
+					redacted,
+					call.tool_name
+				);
+			}
+			result_cache::store(call, config, &tool_result);
+			Ok((tool_result, tool_time_ms))
+		}
 		Err(e) => Err(e),
 	}
 }
 
 // Build a simple tool-to-server lookup map for instant routing
-pub async fn build_tool_server_map(
-	config: &crate::config::Config,
-) -> std::collections::HashMap<String, crate::config::McpServerConfig> {
+// Returns the cached map when the enabled server set is unchanged, so routing is a single lookup
+pub async fn build_tool_server_map(config: &crate::config::Config) -> Arc<ToolServerMap> {
+	let key = tool_server_map_key(config);
+
+	if let Some((cached_key, map)) = TOOL_SERVER_MAP_CACHE.read().unwrap().as_ref() {
+		if *cached_key == key {
+			return map.clone();
+		}
+	}
+
+	let (tool_map, complete) = compute_tool_server_map(config).await;
+	let tool_map = Arc::new(tool_map);
+
+	// Don't cache a map missing tools from servers that failed to respond - retry next time
+	if complete {
+		*TOOL_SERVER_MAP_CACHE.write().unwrap() = Some((key, tool_map.clone()));
+	}
+
+	tool_map
+}
+
+// Drop the cached routing map (called when servers restart or on explicit refresh)
+pub fn clear_tool_server_map_cache() {
+	if TOOL_SERVER_MAP_CACHE.write().unwrap().take().is_some() {
+		crate::log_debug!("Cleared cached tool-to-server map");
+	}
+}
+
+// Hash the parts of the config that affect routing: servers and agents (agent tools come from config)
+fn tool_server_map_key(config: &crate::config::Config) -> u64 {
+	use std::collections::hash_map::DefaultHasher;
+	use std::hash::{Hash, Hasher};
+
+	let mut hasher = DefaultHasher::new();
+	serde_json::to_string(&config.mcp.servers)
+		.unwrap_or_default()
+		.hash(&mut hasher);
+	serde_json::to_string(&config.agents)
+		.unwrap_or_default()
+		.hash(&mut hasher);
+	hasher.finish()
+}
+
+async fn compute_tool_server_map(config: &crate::config::Config) -> (ToolServerMap, bool) {
 	let mut tool_map = std::collections::HashMap::new();
+	let mut complete = true;
 	let enabled_servers: Vec<crate::config::McpServerConfig> = config.mcp.servers.to_vec();
 
 	for server in enabled_servers {
@@ -530,7 +617,7 @@ pub async fn build_tool_server_map(
 					}
 				}
 			}
-			McpConnectionType::Http | McpConnectionType::Stdin => {
+			McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::WebSocket => {
 				// For external servers, get their actual functions
 				match server::get_server_functions_cached(&server).await {
 					Ok(functions) => {
@@ -543,7 +630,11 @@ pub async fn build_tool_server_map(
 								.collect()
 						}
 					}
-					Err(_) => Vec::new(), // Server not available, skip
+					Err(_) => {
+						// Server not available, skip
+						complete = false;
+						Vec::new()
+					}
 				}
 			}
 		};
@@ -558,7 +649,28 @@ pub async fn build_tool_server_map(
 	}
 
 	crate::log_debug!("Built tool-to-server map with {} tools", tool_map.len());
-	tool_map
+	(tool_map, complete)
+}
+
+// Refuse calls the config does not allow, whether or not the tool would run
+fn check_tool_permissions(call: &McpToolCall, config: &crate::config::Config) -> Result<()> {
+	// Enforce the role's denied_tools even if the model asks for a tool it was never offered
+	if config.mcp.is_tool_denied(&call.tool_name) {
+		return Err(anyhow::anyhow!(
+			"Tool '{}' is denied for this role",
+			call.tool_name
+		));
+	}
+
+	// Read-only sessions may inspect files but never change them or run commands
+	if config.read_only && is_mutating_tool_call(call) {
+		return Err(anyhow::anyhow!(
+			"Tool '{}' is not allowed in a read-only session: only viewing, listing and searching are permitted",
+			call.tool_name
+		));
+	}
+
+	Ok(())
 }
 
 // Internal function to actually execute the tool call with cancellation support
@@ -610,8 +722,22 @@ async fn try_execute_tool_call(
 								"Executing shell command via developer server '{}'",
 								target_server.name
 							);
+							let mut result = dev::execute_shell_command(
+								call,
+								config,
+								cancellation_token.clone(),
+							)
+							.await?;
+							result.tool_id = call.tool_id.clone();
+							return handle_large_response(result, config);
+						}
+						"run_tests" => {
+							crate::log_debug!(
+								"Running tests via developer server '{}'",
+								target_server.name
+							);
 							let mut result =
-								dev::execute_shell_command(call, cancellation_token.clone())
+								dev::execute_run_tests(call, config, cancellation_token.clone())
 									.await?;
 							result.tool_id = call.tool_id.clone();
 							return handle_large_response(result, config);
@@ -692,7 +818,7 @@ async fn try_execute_tool_call(
 					}
 				}
 			}
-			McpConnectionType::Http | McpConnectionType::Stdin => {
+			McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::WebSocket => {
 				// Execute on external server
 				match server::execute_tool_call(call, target_server, cancellation_token.clone())
 					.await
@@ -717,6 +843,18 @@ async fn try_execute_tool_call(
 	))
 }
 
+// Calls that modify files or execute commands, rejected in read-only sessions
+fn is_mutating_tool_call(call: &McpToolCall) -> bool {
+	match call.tool_name.as_str() {
+		"shell" | "run_tests" => true,
+		"text_editor" => !matches!(
+			call.parameters.get("command").and_then(|c| c.as_str()),
+			Some("view") | Some("view_many")
+		),
+		_ => false,
+	}
+}
+
 // Helper function to get available tool names for error messages
 async fn get_available_tool_names(config: &crate::config::Config) -> Vec<String> {
 	let functions = get_available_functions(config).await;
@@ -729,8 +867,42 @@ fn handle_large_response(
 	config: &crate::config::Config,
 ) -> Result<McpToolResult> {
 	// Check if result is large - warn user if it exceeds threshold
-	let estimated_tokens = crate::session::estimate_tokens(&format!("{}", result.result));
+	let estimated_tokens = crate::session::estimate_tokens(
+		&format!("{}", result.result),
+		&config.get_effective_model(),
+	);
+
+	// Hard budget configured - truncate automatically without prompting
+	if config.mcp_response_max_tokens > 0 && estimated_tokens > config.mcp_response_max_tokens {
+		crate::log_info!(
+			"Tool '{}' output truncated from {} to ~{} tokens",
+			result.tool_name,
+			estimated_tokens,
+			config.mcp_response_max_tokens
+		);
+		return Ok(truncate_tool_result(
+			result,
+			estimated_tokens,
+			config.mcp_response_max_tokens,
+		));
+	}
+
 	if estimated_tokens > config.mcp_response_warning_threshold {
+		// No TTY to prompt on (ask/shell in pipelines) - truncate to the warning threshold instead
+		if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+			crate::log_info!(
+				"Tool '{}' output truncated from {} to ~{} tokens (non-interactive)",
+				result.tool_name,
+				estimated_tokens,
+				config.mcp_response_warning_threshold
+			);
+			return Ok(truncate_tool_result(
+				result,
+				estimated_tokens,
+				config.mcp_response_warning_threshold,
+			));
+		}
+
 		// Create a modified result that warns about the size
 		use colored::Colorize;
 		println!(
@@ -770,6 +942,51 @@ fn handle_large_response(
 	Ok(result)
 }
 
+// Truncate tool output to roughly max_tokens, keeping head and tail with an elision marker.
+// Always returns a single MCP text content block so the result stays protocol-compliant.
+fn truncate_tool_result(
+	result: McpToolResult,
+	estimated_tokens: usize,
+	max_tokens: usize,
+) -> McpToolResult {
+	let is_error = result
+		.result
+		.get("isError")
+		.and_then(|v| v.as_bool())
+		.unwrap_or(false);
+	let text = extract_mcp_content(&result.result);
+
+	let chars: Vec<char> = text.chars().collect();
+	let keep_chars = if estimated_tokens == 0 {
+		chars.len()
+	} else {
+		(chars.len() as u128 * max_tokens as u128 / estimated_tokens as u128) as usize
+	};
+
+	let truncated = if keep_chars >= chars.len() {
+		text
+	} else {
+		let head_len = keep_chars / 2;
+		let tail_len = keep_chars - head_len;
+		let head: String = chars[..head_len].iter().collect();
+		let tail: String = chars[chars.len() - tail_len..].iter().collect();
+		format!(
+			"{}\n\n[... output truncated: {} of ~{} tokens omitted to fit the {} token budget ...]\n\n{}",
+			head,
+			estimated_tokens.saturating_sub(max_tokens),
+			estimated_tokens,
+			max_tokens,
+			tail
+		)
+	};
+
+	if is_error {
+		McpToolResult::error(result.tool_name, result.tool_id, truncated)
+	} else {
+		McpToolResult::success(result.tool_name, result.tool_id, truncated)
+	}
+}
+
 // Execute a tool call with layer-specific restrictions
 pub async fn execute_layer_tool_call(
 	call: &McpToolCall,
@@ -795,18 +1012,172 @@ pub async fn execute_layer_tool_call(
 	execute_tool_call(call, config, None).await
 }
 
-// Execute multiple tool calls
+// Execute multiple tool calls. Every call gets a result: failures become MCP error results
+// so the model sees partial results and can recover in the same turn.
 pub async fn execute_tool_calls(
 	calls: &[McpToolCall],
 	config: &crate::config::Config,
-) -> Vec<Result<(McpToolResult, u64)>> {
-	let mut results = Vec::new();
+) -> Vec<(McpToolResult, u64)> {
+	let mut results = Vec::with_capacity(calls.len());
 
 	for call in calls {
 		// Execute the tool call
 		let result = execute_tool_call(call, config, None).await;
-		results.push(result);
+		results.push(tool_result_or_error(call, result));
 	}
 
 	results
 }
+
+// Convert a tool execution outcome into a result correlated with the originating call
+pub fn tool_result_or_error(
+	call: &McpToolCall,
+	result: Result<(McpToolResult, u64)>,
+) -> (McpToolResult, u64) {
+	match result {
+		Ok((mut tool_result, time_ms)) => {
+			if tool_result.tool_id.is_empty() {
+				tool_result.tool_id = call.tool_id.clone();
+			}
+			(tool_result, time_ms)
+		}
+		Err(e) => (
+			McpToolResult::error(
+				call.tool_name.clone(),
+				call.tool_id.clone(),
+				format!("Tool execution failed: {}", e),
+			),
+			0,
+		),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_read_only_blocks_only_mutating_calls() {
+		let call = |tool: &str, params: Value| McpToolCall {
+			tool_name: tool.to_string(),
+			parameters: params,
+			tool_id: String::new(),
+		};
+
+		assert!(is_mutating_tool_call(&call(
+			"shell",
+			json!({"command": "ls"})
+		)));
+		for command in [
+			"create",
+			"str_replace",
+			"insert",
+			"line_replace",
+			"undo_edit",
+			"batch_edit",
+		] {
+			assert!(is_mutating_tool_call(&call(
+				"text_editor",
+				json!({"command": command})
+			)));
+		}
+		assert!(!is_mutating_tool_call(&call(
+			"text_editor",
+			json!({"command": "view"})
+		)));
+		assert!(!is_mutating_tool_call(&call(
+			"text_editor",
+			json!({"command": "view_many"})
+		)));
+		assert!(!is_mutating_tool_call(&call("list_files", json!({}))));
+		assert!(!is_mutating_tool_call(&call("html2md", json!({}))));
+		assert!(!is_mutating_tool_call(&call("semantic_search", json!({}))));
+	}
+
+	#[test]
+	fn test_truncate_tool_result_keeps_head_and_tail() {
+		let content = format!("{}{}", "a".repeat(1000), "z".repeat(1000));
+		let result = McpToolResult::success("shell".to_string(), "id1".to_string(), content);
+
+		let truncated = truncate_tool_result(result, 1000, 100);
+		let text = extract_mcp_content(&truncated.result);
+
+		assert!(text.starts_with('a'));
+		assert!(text.ends_with('z'));
+		assert!(text.contains("output truncated"));
+		assert!(text.len() < 400);
+		assert_eq!(truncated.tool_id, "id1");
+		assert_eq!(truncated.result["isError"], false);
+	}
+
+	#[test]
+	fn test_mixed_batch_keeps_successes_and_converts_failures() {
+		let calls: Vec<McpToolCall> = ["call_ok", "call_fail"]
+			.iter()
+			.map(|id| McpToolCall {
+				tool_name: "shell".to_string(),
+				parameters: json!({}),
+				tool_id: id.to_string(),
+			})
+			.collect();
+		let outcomes = vec![
+			Ok((
+				McpToolResult::success("shell".to_string(), String::new(), "done".to_string()),
+				12,
+			)),
+			Err(anyhow::anyhow!("command not found")),
+		];
+
+		let results: Vec<_> = calls
+			.iter()
+			.zip(outcomes)
+			.map(|(call, outcome)| tool_result_or_error(call, outcome))
+			.collect();
+
+		assert_eq!(results.len(), 2);
+		assert_eq!(results[0].0.tool_id, "call_ok");
+		assert_eq!(results[0].0.result["isError"], false);
+		assert_eq!(results[0].1, 12);
+
+		assert_eq!(results[1].0.tool_id, "call_fail");
+		assert_eq!(results[1].0.result["isError"], true);
+		assert!(extract_mcp_content(&results[1].0.result).contains("command not found"));
+	}
+
+	#[tokio::test]
+	async fn test_cached_result_respects_denied_tools() {
+		let mut config: crate::config::Config =
+			toml::from_str(include_str!("../../config-templates/default.toml")).unwrap();
+		config.tool_cache = vec![crate::config::ToolCacheConfig {
+			tool: "permission_test_lookup".to_string(),
+			ttl_seconds: 60,
+		}];
+		let call = McpToolCall {
+			tool_name: "permission_test_lookup".to_string(),
+			parameters: json!({"query": "cached"}),
+			tool_id: "call_1".to_string(),
+		};
+		result_cache::store(
+			&call,
+			&config,
+			&McpToolResult::success(call.tool_name.clone(), String::new(), "hit".to_string()),
+		);
+
+		// The permissive config gets the cached result without running anything
+		let (result, _) = execute_tool_call(&call, &config, None).await.unwrap();
+		assert_eq!(extract_mcp_content(&result.result), "hit");
+
+		// A config that denies the tool is refused even though a result is cached
+		config.mcp.denied_tools = vec!["permission_test_lookup".to_string()];
+		let error = execute_tool_call(&call, &config, None).await.unwrap_err();
+		assert!(error.to_string().contains("denied"));
+	}
+
+	#[test]
+	fn test_truncate_tool_result_preserves_error_flag() {
+		let result = McpToolResult::error("shell".to_string(), "id2".to_string(), "x".repeat(500));
+
+		let truncated = truncate_tool_result(result, 500, 50);
+		assert_eq!(truncated.result["isError"], true);
+	}
+}
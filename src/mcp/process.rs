@@ -17,6 +17,7 @@
 use super::{McpFunction, McpToolCall, McpToolResult};
 use crate::config::{McpConnectionType, McpServerConfig};
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader, BufWriter, Write};
@@ -27,7 +28,8 @@ use std::time::{Duration, Instant, SystemTime};
 use tokio::time::sleep;
 
 // Server health status tracking
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ServerHealth {
 	Running,
 	Dead,
@@ -264,8 +266,8 @@ async fn start_server_once_if_needed(server: &McpServerConfig) -> Result<String>
 				match server.connection_type {
 					McpConnectionType::Http => return get_server_url(server),
 					McpConnectionType::Stdin => return Ok("stdin://".to_string() + server_id),
-					McpConnectionType::Builtin => {
-						unreachable!("Builtin servers should not use this function")
+					McpConnectionType::Builtin | McpConnectionType::WebSocket => {
+						unreachable!("Only locally launched servers use this function")
 					}
 				}
 			} else {
@@ -315,18 +317,16 @@ async fn start_server_once_if_needed(server: &McpServerConfig) -> Result<String>
 				info.restart_count += 1; // Track that we started it
 				info.last_restart_time = Some(SystemTime::now());
 				info.last_health_check = Some(SystemTime::now());
-				info.consecutive_failures = 0;
 			}
 			crate::log_info!("Successfully started server '{}'", server_id);
 			Ok(url)
 		}
 		Err(e) => {
-			// Server failed to start - mark as failed but don't retry
+			// Server failed to start - mark as dead, the health monitor retries with backoff
 			{
 				let mut restart_info_guard = SERVER_RESTART_INFO.write().unwrap();
 				let info = restart_info_guard.entry(server_id.clone()).or_default();
-				info.health_status = ServerHealth::Failed;
-				info.consecutive_failures += 1;
+				info.health_status = ServerHealth::Dead;
 			}
 			crate::log_error!("Failed to start server '{}': {}", server_id, e);
 			Err(anyhow::anyhow!(
@@ -337,7 +337,40 @@ async fn start_server_once_if_needed(server: &McpServerConfig) -> Result<String>
 		}
 	}
 }
-// Start a server process based on configuration
+// Expand ${VAR} references from the process environment, failing if a variable is unset
+pub fn interpolate_env_vars(value: &str) -> Result<String> {
+	let mut result = String::with_capacity(value.len());
+	let mut rest = value;
+
+	while let Some(start) = rest.find("${") {
+		result.push_str(&rest[..start]);
+		let after = &rest[start + 2..];
+		let end = after
+			.find('}')
+			.ok_or_else(|| anyhow::anyhow!("Unterminated '${{' in '{}'", value))?;
+
+		let name = &after[..end];
+		if name.is_empty() {
+			return Err(anyhow::anyhow!(
+				"Empty variable reference '${{}}' in '{}'",
+				value
+			));
+		}
+
+		let var = std::env::var(name).map_err(|_| {
+			anyhow::anyhow!(
+				"Environment variable '{}' referenced in '{}' is not set",
+				name,
+				value
+			)
+		})?;
+		result.push_str(&var);
+		rest = &after[end + 1..];
+	}
+
+	result.push_str(rest);
+	Ok(result)
+}
 
 // Start a server process based on configuration
 async fn start_server_process(server: &McpServerConfig) -> Result<String> {
@@ -349,12 +382,22 @@ async fn start_server_process(server: &McpServerConfig) -> Result<String> {
 		)
 	})?;
 
+	// Expand ${ENV_VAR} references so configs can stay machine-independent
+	let command = interpolate_env_vars(command)
+		.map_err(|e| anyhow::anyhow!("MCP server '{}' command: {}", server.name, e))?;
+	let args = server
+		.args
+		.iter()
+		.map(|arg| interpolate_env_vars(arg))
+		.collect::<Result<Vec<_>>>()
+		.map_err(|e| anyhow::anyhow!("MCP server '{}' args: {}", server.name, e))?;
+
 	// Build and start the command
-	let mut cmd = Command::new(command);
+	let mut cmd = Command::new(&command);
 
 	// Add arguments if present
-	if !server.args.is_empty() {
-		cmd.args(&server.args);
+	if !args.is_empty() {
+		cmd.args(&args);
 	}
 
 	// Configure standard I/O based on connection type
@@ -498,6 +541,10 @@ async fn start_server_process(server: &McpServerConfig) -> Result<String> {
 		McpConnectionType::Builtin => Err(anyhow::anyhow!(
 			"Builtin servers should not use process management"
 		)),
+		McpConnectionType::WebSocket => Err(anyhow::anyhow!(
+			"WebSocket server '{}' is not launched locally - it must already be running",
+			server.name
+		)),
 	}
 }
 
@@ -1117,6 +1164,7 @@ pub fn reset_server_failure_state(server_name: &str) -> Result<()> {
 	if let Some(info) = restart_info_guard.get_mut(server_name) {
 		info.restart_count = 0;
 		info.consecutive_failures = 0;
+		info.last_restart_time = None; // No backoff for the next attempt
 		info.health_status = ServerHealth::Dead; // Will be updated on next check
 		crate::log_debug!("Reset failure state for server '{}'", server_name);
 		Ok(())
@@ -1165,6 +1213,43 @@ pub fn get_server_status_report() -> HashMap<String, (ServerHealth, ServerRestar
 	report
 }
 
+/// Serializable view of one monitored server, for external supervisors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerStatusSnapshot {
+	pub name: String,
+	pub health: ServerHealth,
+	pub restart_count: u32,
+	pub consecutive_failures: u32,
+	pub last_health_check: Option<u64>, // Unix timestamp in seconds
+}
+
+impl ServerStatusSnapshot {
+	fn new(name: &str, health: ServerHealth, info: &ServerRestartInfo) -> Self {
+		Self {
+			name: name.to_string(),
+			health,
+			restart_count: info.restart_count,
+			consecutive_failures: info.consecutive_failures,
+			last_health_check: info.last_health_check.and_then(|checked| {
+				checked
+					.duration_since(std::time::UNIX_EPOCH)
+					.ok()
+					.map(|d| d.as_secs())
+			}),
+		}
+	}
+}
+
+// Snapshot of all monitored servers, sorted by name
+pub fn get_server_status_snapshot() -> Vec<ServerStatusSnapshot> {
+	let mut snapshot: Vec<ServerStatusSnapshot> = get_server_status_report()
+		.iter()
+		.map(|(name, (health, info))| ServerStatusSnapshot::new(name, *health, info))
+		.collect();
+	snapshot.sort_by(|a, b| a.name.cmp(&b.name));
+	snapshot
+}
+
 // Try to communicate with a stdin-based server, ignoring errors
 async fn try_communicate_with_stdin_server(
 	server_name: &str,
@@ -1176,3 +1261,46 @@ async fn try_communicate_with_stdin_server(
 	}
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_interpolate_env_vars() {
+		std::env::set_var("OCTOMIND_TEST_MCP_TOKEN", "secret");
+
+		assert_eq!(
+			interpolate_env_vars("--token=${OCTOMIND_TEST_MCP_TOKEN}").unwrap(),
+			"--token=secret"
+		);
+		assert_eq!(interpolate_env_vars("plain-arg").unwrap(), "plain-arg");
+		assert_eq!(interpolate_env_vars("$HOME").unwrap(), "$HOME");
+
+		assert!(interpolate_env_vars("${OCTOMIND_TEST_MCP_UNSET_VAR}").is_err());
+		assert!(interpolate_env_vars("${OCTOMIND_TEST_MCP_TOKEN").is_err());
+		assert!(interpolate_env_vars("${}").is_err());
+	}
+
+	#[test]
+	fn test_server_status_snapshot_serialization() {
+		let info = ServerRestartInfo {
+			restart_count: 2,
+			consecutive_failures: 1,
+			last_health_check: Some(std::time::UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+			..Default::default()
+		};
+		let snapshot = ServerStatusSnapshot::new("octocode", ServerHealth::Restarting, &info);
+
+		assert_eq!(
+			serde_json::to_value(&snapshot).unwrap(),
+			json!({
+				"name": "octocode",
+				"health": "restarting",
+				"restart_count": 2,
+				"consecutive_failures": 1,
+				"last_health_check": 1_700_000_000u64
+			})
+		);
+	}
+}
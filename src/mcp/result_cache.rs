@@ -0,0 +1,260 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Opt-in cache of tool results for deterministic tools, keyed by tool name and parameters
+
+use super::{McpToolCall, McpToolResult};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime};
+
+lazy_static::lazy_static! {
+	static ref TOOL_RESULT_CACHE: Mutex<HashMap<u64, CachedToolResult>> = Mutex::new(HashMap::new());
+}
+
+// Parameters that name local files; their modification times guard cached results
+const PATH_PARAMETERS: [&str; 4] = ["path", "paths", "directory", "sources"];
+
+struct CachedToolResult {
+	result: McpToolResult,
+	stored_at: Instant,
+	ttl_seconds: u64,
+	// Files named by the call and their modification times when the result was stored
+	// (None for paths that did not resolve); empty only when the call names no files
+	file_mtimes: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+/// Cached result for this call, re-labelled with the call's tool_id
+pub fn lookup(call: &McpToolCall, config: &crate::config::Config) -> Option<McpToolResult> {
+	ttl_for_call(call, config)?;
+	let key = cache_key(call);
+	let mut cache = TOOL_RESULT_CACHE.lock().ok()?;
+
+	let fresh = cache.get(&key).map(|cached| {
+		cached.stored_at.elapsed().as_secs() < cached.ttl_seconds
+			&& cached
+				.file_mtimes
+				.iter()
+				.all(|(path, mtime)| modified_time(path) == *mtime)
+	})?;
+	if !fresh {
+		cache.remove(&key);
+		return None;
+	}
+
+	let mut result = cache.get(&key)?.result.clone();
+	result.tool_id = call.tool_id.clone();
+	Some(result)
+}
+
+/// Remember a successful result when the tool is configured for caching
+pub fn store(call: &McpToolCall, config: &crate::config::Config, result: &McpToolResult) {
+	let Some(ttl_seconds) = ttl_for_call(call, config) else {
+		return;
+	};
+	if is_error_result(result) {
+		return;
+	}
+
+	let file_mtimes = referenced_files(call)
+		.into_iter()
+		.map(|path| {
+			let mtime = modified_time(&path);
+			(path, mtime)
+		})
+		.collect();
+	if let Ok(mut cache) = TOOL_RESULT_CACHE.lock() {
+		cache.insert(
+			cache_key(call),
+			CachedToolResult {
+				result: result.clone(),
+				stored_at: Instant::now(),
+				ttl_seconds,
+				file_mtimes,
+			},
+		);
+	}
+}
+
+/// Drop results that depend on files once a call may have changed them; modification
+/// times alone can miss edits made within the filesystem's timestamp resolution, and
+/// paths that did not resolve have no modification time to compare at all
+pub fn invalidate_after(call: &McpToolCall) {
+	if !is_mutating(call) {
+		return;
+	}
+	if let Ok(mut cache) = TOOL_RESULT_CACHE.lock() {
+		cache.retain(|_, cached| cached.file_mtimes.is_empty());
+	}
+}
+
+// TTL configured for this tool, or None when the call must not be cached
+fn ttl_for_call(call: &McpToolCall, config: &crate::config::Config) -> Option<u64> {
+	if is_mutating(call) {
+		return None;
+	}
+	config
+		.tool_cache
+		.iter()
+		.find(|rule| rule.tool == call.tool_name)
+		.map(|rule| rule.ttl_seconds)
+}
+
+// Shell commands, test runs and text_editor writes depend on or change files, so they are never cached
+fn is_mutating(call: &McpToolCall) -> bool {
+	match call.tool_name.as_str() {
+		"shell" | "run_tests" => true,
+		"text_editor" => !matches!(
+			call.parameters.get("command").and_then(|c| c.as_str()),
+			Some("view") | Some("view_many")
+		),
+		_ => false,
+	}
+}
+
+fn is_error_result(result: &McpToolResult) -> bool {
+	result.result.get("isError").and_then(|e| e.as_bool()) == Some(true)
+		|| result.result.get("is_error").and_then(|e| e.as_bool()) == Some(true)
+		|| result.result.get("error").is_some()
+}
+
+fn cache_key(call: &McpToolCall) -> u64 {
+	use std::collections::hash_map::DefaultHasher;
+	use std::hash::{Hash, Hasher};
+
+	let mut hasher = DefaultHasher::new();
+	call.tool_name.hash(&mut hasher);
+	canonical_json(&call.parameters).hash(&mut hasher);
+	hasher.finish()
+}
+
+// JSON text with object keys sorted at every depth, so the key does not depend on the
+// order the model happened to write the parameters in
+fn canonical_json(value: &serde_json::Value) -> String {
+	match value {
+		serde_json::Value::Object(map) => {
+			let mut entries: Vec<_> = map.iter().collect();
+			entries.sort_by_key(|(key, _)| *key);
+			let fields: Vec<String> = entries
+				.into_iter()
+				.map(|(key, value)| {
+					format!(
+						"{}:{}",
+						serde_json::Value::String(key.clone()),
+						canonical_json(value)
+					)
+				})
+				.collect();
+			format!("{{{}}}", fields.join(","))
+		}
+		serde_json::Value::Array(items) => {
+			let items: Vec<String> = items.iter().map(canonical_json).collect();
+			format!("[{}]", items.join(","))
+		}
+		other => other.to_string(),
+	}
+}
+
+// Local files and directories named in the call parameters. Paths are kept even when
+// they do not resolve from here, so the entry still counts as file-dependent.
+fn referenced_files(call: &McpToolCall) -> Vec<PathBuf> {
+	PATH_PARAMETERS
+		.iter()
+		.filter_map(|name| call.parameters.get(*name))
+		.flat_map(|value| match value {
+			serde_json::Value::String(s) => vec![s.as_str()],
+			serde_json::Value::Array(items) => items.iter().filter_map(|v| v.as_str()).collect(),
+			_ => Vec::new(),
+		})
+		// `sources` may also hold URLs, which do not depend on local files
+		.filter(|source| !source.contains("://"))
+		.map(PathBuf::from)
+		.collect()
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+	std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	fn call(tool_name: &str, parameters: serde_json::Value) -> McpToolCall {
+		McpToolCall {
+			tool_name: tool_name.to_string(),
+			parameters,
+			tool_id: "call_1".to_string(),
+		}
+	}
+
+	#[test]
+	fn test_mutating_calls_are_not_cacheable() {
+		assert!(is_mutating(&call("shell", json!({"command": "ls"}))));
+		assert!(is_mutating(&call(
+			"text_editor",
+			json!({"command": "str_replace", "path": "a.rs"})
+		)));
+		assert!(!is_mutating(&call(
+			"text_editor",
+			json!({"command": "view", "path": "a.rs"})
+		)));
+		assert!(!is_mutating(&call(
+			"html2md",
+			json!({"sources": "https://example.com"})
+		)));
+	}
+
+	#[test]
+	fn test_cache_key_depends_on_parameters() {
+		let first = call("html2md", json!({"sources": "https://example.com/a"}));
+		let second = call("html2md", json!({"sources": "https://example.com/b"}));
+		assert_eq!(cache_key(&first), cache_key(&first.clone()));
+		assert_ne!(cache_key(&first), cache_key(&second));
+
+		let ordered = call("view", json!({"path": "a.rs", "lines": [1, 2]}));
+		let reordered = call("view", json!({"lines": [1, 2], "path": "a.rs"}));
+		assert_eq!(cache_key(&ordered), cache_key(&reordered));
+	}
+
+	#[test]
+	fn test_unresolved_paths_are_invalidated_by_writes() {
+		let mut config: crate::config::Config =
+			toml::from_str(include_str!("../../config-templates/default.toml")).unwrap();
+		config.tool_cache = vec![crate::config::ToolCacheConfig {
+			tool: "result_cache_test_view".to_string(),
+			ttl_seconds: 60,
+		}];
+		let view = call(
+			"result_cache_test_view",
+			json!({"path": "does/not/exist/from/here.rs"}),
+		);
+		let result = McpToolResult::success(
+			view.tool_name.clone(),
+			view.tool_id.clone(),
+			"fn main() {}".to_string(),
+		);
+
+		store(&view, &config, &result);
+		assert!(lookup(&view, &config).is_some());
+
+		invalidate_after(&call(
+			"text_editor",
+			json!({"command": "str_replace", "path": "here.rs"}),
+		));
+		assert!(lookup(&view, &config).is_none());
+	}
+}
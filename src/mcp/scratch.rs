@@ -0,0 +1,125 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Scratch directory - base for relative tool paths and temp output when scratch_dir is set
+
+use super::McpToolCall;
+use anyhow::{anyhow, Context, Result};
+use std::path::{Component, Path, PathBuf};
+
+/// Configured scratch directory, created on first use. None means tools work in the
+/// project directory as usual.
+pub fn scratch_dir(config: &crate::config::Config) -> Result<Option<PathBuf>> {
+	let Some(dir) = config.scratch_dir.as_deref().filter(|d| !d.is_empty()) else {
+		return Ok(None);
+	};
+
+	let project_dir = std::env::current_dir()?;
+	let dir = normalize(&project_dir.join(dir));
+	std::fs::create_dir_all(&dir)
+		.context(format!("Failed to create scratch_dir {}", dir.display()))?;
+	Ok(Some(dir))
+}
+
+/// Copy of the call with filesystem tool paths resolved inside the scratch directory.
+/// Absolute paths into the project are kept so existing files can still be read and edited.
+pub fn scope_call(call: &McpToolCall, config: &crate::config::Config) -> Result<McpToolCall> {
+	let mut scoped = call.clone();
+	if !matches!(call.tool_name.as_str(), "text_editor" | "list_files") {
+		return Ok(scoped);
+	}
+	let Some(scratch) = scratch_dir(config)? else {
+		return Ok(scoped);
+	};
+	let project_dir = std::env::current_dir()?;
+
+	super::fs::sandbox::for_each_path_argument(&mut scoped.parameters, |path| {
+		*path = resolve_path(path, &scratch, &project_dir)?;
+		Ok(())
+	})?;
+
+	Ok(scoped)
+}
+
+// Relative paths are joined to the scratch directory and may not climb out of it;
+// absolute paths must point into the scratch or project directory
+fn resolve_path(path: &str, scratch: &Path, project_dir: &Path) -> Result<String> {
+	let requested = Path::new(path);
+	if requested.is_absolute() {
+		let resolved = normalize(requested);
+		if resolved.starts_with(scratch) || resolved.starts_with(project_dir) {
+			return Ok(resolved.to_string_lossy().to_string());
+		}
+		return Err(anyhow!(
+			"Path '{}' is outside the scratch directory {} and the project directory {}",
+			path,
+			scratch.display(),
+			project_dir.display()
+		));
+	}
+
+	let resolved = normalize(&scratch.join(requested));
+	if !resolved.starts_with(scratch) {
+		return Err(anyhow!(
+			"Path '{}' leaves the scratch directory {}; use an absolute path to reach project files",
+			path,
+			scratch.display()
+		));
+	}
+	Ok(resolved.to_string_lossy().to_string())
+}
+
+// Resolve `.` and `..` without touching the filesystem, since the path may not exist yet
+fn normalize(path: &Path) -> PathBuf {
+	let mut normalized = PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::CurDir => {}
+			Component::ParentDir => {
+				normalized.pop();
+			}
+			other => normalized.push(other),
+		}
+	}
+	normalized
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_resolve_path_in_scratch_dir() {
+		let project = Path::new("/work/project");
+		let scratch = Path::new("/work/project/.scratch");
+
+		assert_eq!(
+			resolve_path("notes/out.txt", scratch, project).unwrap(),
+			"/work/project/.scratch/notes/out.txt"
+		);
+		assert_eq!(
+			resolve_path("./a/../b.txt", scratch, project).unwrap(),
+			"/work/project/.scratch/b.txt"
+		);
+		// Project files stay reachable through absolute paths
+		assert_eq!(
+			resolve_path("/work/project/src/main.rs", scratch, project).unwrap(),
+			"/work/project/src/main.rs"
+		);
+
+		assert!(resolve_path("../src/main.rs", scratch, project).is_err());
+		assert!(resolve_path("/etc/passwd", scratch, project).is_err());
+		assert!(resolve_path("/work/project/../other/file", scratch, project).is_err());
+	}
+}
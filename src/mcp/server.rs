@@ -15,6 +15,7 @@
 // External MCP server provider
 
 use super::process;
+use super::websocket;
 use super::{McpFunction, McpToolCall, McpToolResult};
 use crate::config::{Config, McpConnectionType, McpServerConfig};
 use anyhow::Result;
@@ -138,6 +139,10 @@ pub async fn get_server_functions(server: &McpServerConfig) -> Result<Vec<McpFun
 			process::ensure_server_running(server).await?;
 			process::get_stdin_server_functions(server).await
 		}
+		McpConnectionType::WebSocket => {
+			// WebSocket servers are already running elsewhere - just connect
+			websocket::get_websocket_server_functions(server).await
+		}
 		McpConnectionType::Builtin => {
 			// Built-in servers don't need external processes
 			Err(anyhow::anyhow!(
@@ -159,8 +164,13 @@ pub async fn get_server_functions_cached(server: &McpServerConfig) -> Result<Vec
 		}
 	}
 
-	// Check if server is currently running
-	let is_running = is_server_running_for_cache_check(server_id);
+	// Check if server is currently running (WebSocket servers have no local process,
+	// so use the result of the last connection attempt)
+	let is_running = if server.connection_type == McpConnectionType::WebSocket {
+		process::get_server_health(server_id) == process::ServerHealth::Running
+	} else {
+		is_server_running_for_cache_check(server_id)
+	};
 
 	if is_running {
 		// Server is running - get fresh functions and cache them
@@ -266,6 +276,9 @@ pub fn clear_function_cache_for_server(server_name: &str) {
 			server_name
 		);
 	}
+
+	// Routing may point at the dead process - rebuild on next tool call
+	super::clear_tool_server_map_cache();
 }
 
 // Clear all cached functions (useful for cleanup)
@@ -276,6 +289,8 @@ pub fn clear_all_function_cache() {
 	if count > 0 {
 		crate::log_debug!("Cleared function cache for {} servers", count);
 	}
+
+	super::clear_tool_server_map_cache();
 }
 
 // Check if a server is already running with enhanced health checking
@@ -292,6 +307,10 @@ pub fn is_server_already_running_with_config(server: &crate::config::McpServerCo
 			}
 			true
 		}
+		McpConnectionType::WebSocket => {
+			// No local process - rely on the last connectivity check
+			process::get_server_health(&server.name) == process::ServerHealth::Running
+		}
 		McpConnectionType::Http | McpConnectionType::Stdin => {
 			// External servers - check the process registry
 			let is_process_running = {
@@ -420,6 +439,12 @@ pub async fn execute_tool_call(
 		}
 	}
 
+	// WebSocket servers are not processes we manage - every call connects afresh,
+	// so connection failures are reported by the call itself
+	if server.connection_type == McpConnectionType::WebSocket {
+		return execute_tool_call_internal(call, server, cancellation_token).await;
+	}
+
 	// Check server health before attempting execution (but don't restart)
 	let server_health = process::get_server_health(&server.name);
 	match server_health {
@@ -570,6 +595,9 @@ async fn execute_tool_call_internal(
 			// For stdin-based servers, use the stdin communication channel with cancellation support
 			process::execute_stdin_tool_call(call, server, cancellation_token).await
 		}
+		McpConnectionType::WebSocket => {
+			websocket::execute_websocket_tool_call(call, server, cancellation_token).await
+		}
 		McpConnectionType::Builtin => {
 			// Built-in servers should not use this function
 			Err(anyhow::anyhow!(
@@ -608,6 +636,12 @@ async fn get_server_base_url(server: &McpServerConfig) -> Result<String> {
 			// Built-in servers don't have URLs
 			Err(anyhow::anyhow!("Built-in servers don't have URLs"))
 		}
+		McpConnectionType::WebSocket => {
+			// WebSocket servers are reached through their own transport
+			Err(anyhow::anyhow!(
+				"WebSocket servers don't use HTTP endpoints"
+			))
+		}
 	}
 }
 
@@ -672,3 +706,8 @@ pub fn get_server_status_report(
 ) -> std::collections::HashMap<String, (process::ServerHealth, process::ServerRestartInfo)> {
 	process::get_server_status_report()
 }
+
+// Get a serializable snapshot of all monitored servers
+pub fn get_server_status_snapshot() -> Vec<process::ServerStatusSnapshot> {
+	process::get_server_status_snapshot()
+}
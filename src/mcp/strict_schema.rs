@@ -0,0 +1,346 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Conversion of tool parameter schemas to OpenAI strict mode
+
+use super::McpFunction;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+lazy_static::lazy_static! {
+	// Original parameter schemas of the tools last sent in strict mode, by tool name,
+	// so null arguments can be traced back to the optional properties made nullable
+	static ref STRICT_TOOL_SCHEMAS: RwLock<HashMap<String, Value>> = RwLock::new(HashMap::new());
+}
+
+// Keywords strict mode rejects - a schema using them is sent non-strict instead
+const UNSUPPORTED_KEYWORDS: &[&str] = &[
+	"oneOf",
+	"allOf",
+	"not",
+	"if",
+	"then",
+	"else",
+	"patternProperties",
+	"unevaluatedProperties",
+	"propertyNames",
+	"minProperties",
+	"maxProperties",
+	"unevaluatedItems",
+	"contains",
+	"minContains",
+	"maxContains",
+	"minItems",
+	"maxItems",
+	"uniqueItems",
+	"minLength",
+	"maxLength",
+	"pattern",
+	"minimum",
+	"maximum",
+	"exclusiveMinimum",
+	"exclusiveMaximum",
+	"multipleOf",
+];
+
+/// Convert a tool parameter schema to strict mode: every object gets
+/// `additionalProperties: false`, every property becomes required, and
+/// properties that were optional become nullable.
+/// Returns the reason when the schema cannot be expressed in strict mode.
+pub fn to_strict_schema(schema: &Value) -> Result<Value, String> {
+	if schema.get("type").and_then(|t| t.as_str()) != Some("object") {
+		return Err("root schema is not an object".to_string());
+	}
+	convert(schema, "parameters")
+}
+
+fn convert(schema: &Value, path: &str) -> Result<Value, String> {
+	let Some(object) = schema.as_object() else {
+		return Err(format!("{} is not a schema object", path));
+	};
+
+	if let Some(keyword) = UNSUPPORTED_KEYWORDS
+		.iter()
+		.find(|keyword| object.contains_key(**keyword))
+	{
+		return Err(format!("{} uses unsupported keyword '{}'", path, keyword));
+	}
+
+	let mut result = object.clone();
+
+	let is_object = object.get("type").and_then(|t| t.as_str()) == Some("object")
+		|| object.contains_key("properties");
+	if is_object {
+		match object.get("additionalProperties") {
+			None | Some(Value::Bool(false)) => {}
+			Some(_) => return Err(format!("{} allows additional properties", path)),
+		}
+		// Without declared properties the object is a free-form map; strict mode would
+		// only accept an empty object there
+		if !object.contains_key("properties") {
+			return Err(format!("{} is a free-form object", path));
+		}
+
+		let required: Vec<&str> = object
+			.get("required")
+			.and_then(|r| r.as_array())
+			.map(|r| r.iter().filter_map(|name| name.as_str()).collect())
+			.unwrap_or_default();
+
+		let mut properties = Map::new();
+		if let Some(props) = object.get("properties").and_then(|p| p.as_object()) {
+			for (name, prop) in props {
+				let prop_path = format!("{}.{}", path, name);
+				let mut converted = convert(prop, &prop_path)?;
+				if !required.contains(&name.as_str()) {
+					make_nullable(&mut converted, &prop_path)?;
+				}
+				properties.insert(name.clone(), converted);
+			}
+		}
+
+		result.insert(
+			"required".to_string(),
+			Value::Array(properties.keys().cloned().map(Value::String).collect()),
+		);
+		result.insert("properties".to_string(), Value::Object(properties));
+		result.insert("additionalProperties".to_string(), Value::Bool(false));
+	}
+
+	if let Some(items) = object.get("items") {
+		result.insert("items".to_string(), convert(items, &format!("{}[]", path))?);
+	}
+
+	if let Some(any_of) = object.get("anyOf").and_then(|a| a.as_array()) {
+		let variants = any_of
+			.iter()
+			.map(|variant| convert(variant, path))
+			.collect::<Result<Vec<_>, _>>()?;
+		result.insert("anyOf".to_string(), Value::Array(variants));
+	}
+
+	Ok(Value::Object(result))
+}
+
+// Optional properties must still be present in strict mode, so they accept null instead
+fn make_nullable(schema: &mut Value, path: &str) -> Result<(), String> {
+	// An enum restricts the values on top of the type, so null has to be listed as well
+	if let Some(Value::Array(values)) = schema.get_mut("enum") {
+		if !values.contains(&Value::Null) {
+			values.push(Value::Null);
+		}
+	}
+
+	let null = Value::String("null".to_string());
+	match schema.get_mut("type") {
+		Some(Value::String(kind)) => {
+			let kind = Value::String(kind.clone());
+			schema["type"] = Value::Array(vec![kind, null]);
+			Ok(())
+		}
+		Some(Value::Array(kinds)) => {
+			if !kinds.contains(&null) {
+				kinds.push(null);
+			}
+			Ok(())
+		}
+		_ => {
+			if let Some(Value::Array(variants)) = schema.get_mut("anyOf") {
+				variants.push(serde_json::json!({ "type": "null" }));
+				Ok(())
+			} else {
+				Err(format!("{} is optional but has no type", path))
+			}
+		}
+	}
+}
+
+/// Remove the null values strict mode sends for omitted optional parameters of a tool
+/// sent in strict mode. Only properties that were made nullable are stripped; nulls the
+/// original schema requires are passed through.
+pub fn strip_null_arguments(tool_name: &str, arguments: &mut Value) {
+	let Ok(schemas) = STRICT_TOOL_SCHEMAS.read() else {
+		return;
+	};
+	if let Some(schema) = schemas.get(tool_name) {
+		strip_nullable(schema, arguments);
+	}
+}
+
+fn strip_nullable(schema: &Value, arguments: &mut Value) {
+	match arguments {
+		Value::Object(map) => {
+			let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) else {
+				return;
+			};
+			let required = schema.get("required").and_then(|r| r.as_array());
+			map.retain(|name, value| {
+				let optional = required.is_none_or(|r| !r.contains(&Value::String(name.clone())));
+				!(value.is_null() && optional && properties.contains_key(name))
+			});
+			for (name, value) in map.iter_mut() {
+				if let Some(prop) = properties.get(name) {
+					strip_nullable(prop, value);
+				}
+			}
+		}
+		Value::Array(items) => {
+			if let Some(item_schema) = schema.get("items") {
+				items
+					.iter_mut()
+					.for_each(|item| strip_nullable(item_schema, item));
+			}
+		}
+		_ => {}
+	}
+}
+
+/// Build an OpenAI-style function tool definition, in strict mode when requested
+/// and the schema allows it (other tools are sent unchanged)
+pub fn function_tool_definition(function: &McpFunction, strict: bool) -> Value {
+	if strict {
+		match to_strict_schema(&function.parameters) {
+			Ok(parameters) => {
+				if let Ok(mut schemas) = STRICT_TOOL_SCHEMAS.write() {
+					schemas.insert(function.name.clone(), function.parameters.clone());
+				}
+				return serde_json::json!({
+					"type": "function",
+					"function": {
+						"name": function.name,
+						"description": function.description,
+						"parameters": parameters,
+						"strict": true
+					}
+				});
+			}
+			Err(reason) => {
+				crate::log_debug!(
+					"Sending tool '{}' without strict mode: {}",
+					function.name,
+					reason
+				);
+			}
+		}
+	}
+
+	serde_json::json!({
+		"type": "function",
+		"function": {
+			"name": function.name,
+			"description": function.description,
+			"parameters": function.parameters
+		}
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn test_optional_properties_become_nullable() {
+		let schema = json!({
+			"type": "object",
+			"properties": {
+				"path": {"type": "string"},
+				"lines": {"type": "array", "items": {"type": "integer"}}
+			},
+			"required": ["path"]
+		});
+
+		let strict = to_strict_schema(&schema).unwrap();
+		assert_eq!(strict["additionalProperties"], json!(false));
+		let required = strict["required"].as_array().unwrap();
+		assert_eq!(required.len(), 2);
+		assert!(required.contains(&json!("lines")) && required.contains(&json!("path")));
+		assert_eq!(strict["properties"]["path"]["type"], json!("string"));
+		assert_eq!(
+			strict["properties"]["lines"]["type"],
+			json!(["array", "null"])
+		);
+	}
+
+	#[test]
+	fn test_incompatible_schemas_are_rejected() {
+		let open_object = json!({
+			"type": "object",
+			"properties": {"env": {"type": "object", "additionalProperties": {"type": "string"}}}
+		});
+		assert!(to_strict_schema(&open_object).is_err());
+
+		let constrained = json!({
+			"type": "object",
+			"properties": {"count": {"type": "integer", "minimum": 1}},
+			"required": ["count"]
+		});
+		assert!(to_strict_schema(&constrained).is_err());
+
+		assert!(to_strict_schema(&json!({"oneOf": []})).is_err());
+
+		// A free-form map would be narrowed to an empty object
+		let free_form = json!({
+			"type": "object",
+			"properties": {"headers": {"type": "object"}}
+		});
+		assert!(to_strict_schema(&free_form).is_err());
+	}
+
+	#[test]
+	fn test_optional_enum_accepts_null() {
+		let schema = json!({
+			"type": "object",
+			"properties": {"mode": {"type": "string", "enum": ["fast", "full"]}}
+		});
+		let strict = to_strict_schema(&schema).unwrap();
+		assert_eq!(
+			strict["properties"]["mode"]["enum"],
+			json!(["fast", "full", null])
+		);
+	}
+
+	#[test]
+	fn test_strip_null_arguments() {
+		let function = McpFunction {
+			name: "strict_strip_test".to_string(),
+			description: String::new(),
+			parameters: json!({
+				"type": "object",
+				"properties": {
+					"path": {"type": "string"},
+					"lines": {"type": "array", "items": {"type": "integer"}},
+					"nested": {
+						"type": "object",
+						"properties": {"a": {"type": "string"}, "b": {"type": "integer"}},
+						"required": ["a"]
+					}
+				},
+				"required": ["path", "nested"]
+			}),
+		};
+		function_tool_definition(&function, true);
+
+		let mut arguments = json!({"path": "src", "lines": null, "nested": {"a": null, "b": null}});
+		strip_null_arguments(&function.name, &mut arguments);
+		// Only the optional properties made nullable are stripped
+		assert_eq!(arguments, json!({"path": "src", "nested": {"a": null}}));
+
+		// Tools not sent in strict mode are left untouched
+		let mut other = json!({"value": null});
+		strip_null_arguments("not_strict", &mut other);
+		assert_eq!(other, json!({"value": null}));
+	}
+}
@@ -0,0 +1,529 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// WebSocket transport for already-running MCP servers (JSON-RPC over ws:// or wss://)
+
+use super::process::{ServerHealth, SERVER_RESTART_INFO};
+use super::{McpFunction, McpToolCall, McpToolResult};
+use crate::config::McpServerConfig;
+use anyhow::{anyhow, Result};
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{header::AUTHORIZATION, HeaderValue};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+type Connection = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+// Connecting and the initialize handshake must finish within this time
+const CONNECT_TIMEOUT_SECONDS: u64 = 10;
+
+// How often to check for cancellation while waiting for a response
+const CANCEL_POLL_INTERVAL_MS: u64 = 100;
+
+// The initialize handshake uses id 1, the actual request id 2
+const INITIALIZE_ID: u64 = 1;
+const REQUEST_ID: u64 = 2;
+
+// Get tool definitions from a WebSocket server
+pub async fn get_websocket_server_functions(server: &McpServerConfig) -> Result<Vec<McpFunction>> {
+	let message = json!({
+		"jsonrpc": "2.0",
+		"id": REQUEST_ID,
+		"method": "tools/list",
+		"params": {}
+	});
+
+	let response = request_once(server, &message, None).await?;
+
+	if let Some(error) = response.get("error") {
+		return Err(anyhow!(
+			"Server '{}' returned error during tools/list: {}",
+			server.name,
+			error
+		));
+	}
+
+	let mut functions = Vec::new();
+	if let Some(tools) = response
+		.get("result")
+		.and_then(|result| result.get("tools"))
+		.and_then(|tools| tools.as_array())
+	{
+		for tool in tools {
+			if let (Some(name), Some(description)) = (
+				tool.get("name").and_then(|n| n.as_str()),
+				tool.get("description").and_then(|d| d.as_str()),
+			) {
+				// Check if this tool is enabled
+				if server.tools.is_empty() || server.tools.contains(&name.to_string()) {
+					functions.push(McpFunction {
+						name: name.to_string(),
+						description: description.to_string(),
+						parameters: tool.get("inputSchema").cloned().unwrap_or(json!({})),
+					});
+				}
+			}
+		}
+	}
+
+	Ok(functions)
+}
+
+// Execute a tool on a WebSocket server
+pub async fn execute_websocket_tool_call(
+	call: &McpToolCall,
+	server: &McpServerConfig,
+	cancellation_token: Option<Arc<AtomicBool>>,
+) -> Result<McpToolResult> {
+	let message = json!({
+		"jsonrpc": "2.0",
+		"id": REQUEST_ID,
+		"method": "tools/call",
+		"params": {
+			"name": call.tool_name,
+			"arguments": call.parameters
+		}
+	});
+
+	let response = match request_once(server, &message, cancellation_token).await {
+		Ok(response) => response,
+		Err(e) => {
+			// Return a formatted error as the tool result rather than failing
+			return Ok(McpToolResult::error(
+				call.tool_name.clone(),
+				call.tool_id.clone(),
+				format!("Error executing tool: {}", e),
+			));
+		}
+	};
+
+	if let Some(error) = response.get("error") {
+		let error_message = error
+			.get("message")
+			.and_then(|m| m.as_str())
+			.unwrap_or("Unknown error");
+		let error_code = error.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
+
+		return Ok(McpToolResult::error(
+			call.tool_name.clone(),
+			call.tool_id.clone(),
+			format!("{} (code: {})", error_message, error_code),
+		));
+	}
+
+	let output = response
+		.get("result")
+		.cloned()
+		.unwrap_or(json!("No result"));
+
+	Ok(McpToolResult::success(
+		call.tool_name.clone(),
+		call.tool_id.clone(),
+		serde_json::to_string_pretty(&output).unwrap_or_else(|_| output.to_string()),
+	))
+}
+
+// Health check: the server counts as running if it accepts a connection and completes
+// the initialize handshake. The result is recorded like process health for other transports.
+pub async fn check_websocket_connectivity(server: &McpServerConfig) -> bool {
+	let reachable = match connect(server).await {
+		Ok(mut connection) => {
+			let _ = connection.close(None).await;
+			true
+		}
+		Err(e) => {
+			crate::log_debug!("WebSocket server '{}' is unreachable: {}", server.name, e);
+			false
+		}
+	};
+
+	record_health(server, reachable);
+	reachable
+}
+
+// Open a connection, send one request, wait for its response and close again
+async fn request_once(
+	server: &McpServerConfig,
+	message: &Value,
+	cancellation_token: Option<Arc<AtomicBool>>,
+) -> Result<Value> {
+	let mut connection = match connect(server).await {
+		Ok(connection) => connection,
+		Err(e) => {
+			record_health(server, false);
+			return Err(e);
+		}
+	};
+	record_health(server, true);
+
+	let response = send_and_wait(
+		&mut connection,
+		message,
+		REQUEST_ID,
+		Duration::from_secs(server.timeout_seconds),
+		cancellation_token.as_ref(),
+	)
+	.await;
+
+	let _ = connection.close(None).await;
+	response
+}
+
+// Connect and perform the MCP initialize handshake
+async fn connect(server: &McpServerConfig) -> Result<Connection> {
+	let url = server
+		.url
+		.as_deref()
+		.ok_or_else(|| anyhow!("WebSocket server '{}' has no url configured", server.name))?;
+
+	let mut request = url.into_client_request()?;
+	if let Some(token) = &server.auth_token {
+		request.headers_mut().insert(
+			AUTHORIZATION,
+			HeaderValue::from_str(&format!("Bearer {}", token))?,
+		);
+	}
+
+	let timeout = Duration::from_secs(CONNECT_TIMEOUT_SECONDS);
+	let (mut connection, _) = tokio::time::timeout(timeout, connect_async(request))
+		.await
+		.map_err(|_| anyhow!("Timed out connecting to WebSocket server '{}'", server.name))??;
+
+	let init_message = json!({
+		"jsonrpc": "2.0",
+		"id": INITIALIZE_ID,
+		"method": "initialize",
+		"params": {
+			"clientInfo": {
+				"name": "octomind",
+				"version": env!("CARGO_PKG_VERSION")
+			},
+			"protocolVersion": "2025-03-26",
+			"capabilities": {}
+		}
+	});
+
+	let response =
+		send_and_wait(&mut connection, &init_message, INITIALIZE_ID, timeout, None).await?;
+	if let Some(error) = response.get("error") {
+		return Err(anyhow!(
+			"Server returned error during initialization: {}",
+			error
+		));
+	}
+
+	let initialized_message = json!({
+		"jsonrpc": "2.0",
+		"method": "notifications/initialized",
+		"params": {}
+	});
+	connection
+		.send(Message::Text(initialized_message.to_string().into()))
+		.await?;
+
+	Ok(connection)
+}
+
+// Send a JSON-RPC message and wait for the response carrying the same id,
+// skipping notifications and anything that isn't JSON
+async fn send_and_wait(
+	connection: &mut Connection,
+	message: &Value,
+	id: u64,
+	timeout: Duration,
+	cancellation_token: Option<&Arc<AtomicBool>>,
+) -> Result<Value> {
+	connection
+		.send(Message::Text(message.to_string().into()))
+		.await?;
+
+	let deadline = Instant::now() + timeout;
+	loop {
+		if cancellation_token.is_some_and(|token| token.load(Ordering::SeqCst)) {
+			return Err(anyhow!("Operation cancelled"));
+		}
+		if Instant::now() >= deadline {
+			return Err(anyhow!(
+				"Timed out after {}s waiting for server response",
+				timeout.as_secs()
+			));
+		}
+
+		let poll = Duration::from_millis(CANCEL_POLL_INTERVAL_MS);
+		let frame = match tokio::time::timeout(poll, connection.next()).await {
+			Ok(Some(frame)) => frame?,
+			Ok(None) => return Err(anyhow!("Connection closed before a response arrived")),
+			Err(_) => continue,
+		};
+
+		let text = match frame {
+			Message::Text(text) => text.as_str().to_string(),
+			Message::Binary(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+			Message::Close(_) => {
+				return Err(anyhow!("Connection closed before a response arrived"))
+			}
+			// Ping/pong frames are answered by the library
+			_ => continue,
+		};
+
+		if let Ok(response) = serde_json::from_str::<Value>(&text) {
+			if response.get("id").and_then(|v| v.as_u64()) == Some(id) {
+				return Ok(response);
+			}
+		}
+	}
+}
+
+fn record_health(server: &McpServerConfig, reachable: bool) {
+	let mut restart_info_guard = SERVER_RESTART_INFO.write().unwrap();
+	let info = restart_info_guard.entry(server.name.clone()).or_default();
+	info.health_status = if reachable {
+		ServerHealth::Running
+	} else {
+		ServerHealth::Dead
+	};
+	info.last_health_check = Some(std::time::SystemTime::now());
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::McpConnectionType;
+	use tokio::net::TcpListener;
+
+	// What the test server sends back for a request other than initialize
+	type Reply = fn(&Value) -> Vec<Message>;
+
+	fn text(value: Value) -> Message {
+		Message::Text(value.to_string().into())
+	}
+
+	fn server_config(url: &str) -> McpServerConfig {
+		McpServerConfig {
+			name: "ws_test".to_string(),
+			connection_type: McpConnectionType::WebSocket,
+			url: Some(url.to_string()),
+			auth_token: None,
+			command: None,
+			args: Vec::new(),
+			timeout_seconds: 5,
+			tools: Vec::new(),
+		}
+	}
+
+	// Loopback MCP server for one connection: answers initialize, ignores notifications
+	// and hands every other request to `reply`
+	async fn spawn_server(reply: Reply) -> McpServerConfig {
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let url = format!("ws://{}", listener.local_addr().unwrap());
+
+		tokio::spawn(async move {
+			let (stream, _) = listener.accept().await.unwrap();
+			let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+			while let Some(Ok(Message::Text(frame))) = socket.next().await {
+				let request: Value = serde_json::from_str(frame.as_str()).unwrap();
+				let frames = match request.get("method").and_then(|m| m.as_str()) {
+					Some("initialize") => vec![text(json!({
+						"jsonrpc": "2.0",
+						"id": request["id"],
+						"result": {"capabilities": {}}
+					}))],
+					_ if request.get("id").is_none() => Vec::new(),
+					_ => reply(&request),
+				};
+				for frame in frames {
+					let closing = matches!(frame, Message::Close(_));
+					if socket.send(frame).await.is_err() || closing {
+						return;
+					}
+				}
+			}
+		});
+
+		server_config(&url)
+	}
+
+	fn call(tool_name: &str) -> McpToolCall {
+		McpToolCall {
+			tool_name: tool_name.to_string(),
+			parameters: json!({"text": "hi"}),
+			tool_id: "call_1".to_string(),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_response_matched_by_id_out_of_order() {
+		let server = spawn_server(|request| {
+			vec![
+				text(json!({"jsonrpc": "2.0", "method": "notifications/progress", "params": {}})),
+				text(json!({"jsonrpc": "2.0", "id": 99, "result": {"content": "someone else's"}})),
+				Message::Text("not json".into()),
+				text(json!({
+					"jsonrpc": "2.0",
+					"id": request["id"],
+					"result": {"content": [{"type": "text", "text": "echo: hi"}]}
+				})),
+			]
+		})
+		.await;
+
+		let result = execute_websocket_tool_call(&call("echo"), &server, None)
+			.await
+			.unwrap();
+		assert_eq!(result.tool_id, "call_1");
+		assert_eq!(result.result["isError"], false);
+		let output = super::super::extract_mcp_content(&result.result);
+		assert!(output.contains("echo: hi"));
+		assert!(!output.contains("someone else's"));
+	}
+
+	#[tokio::test]
+	async fn test_unmatched_ids_then_close_is_an_error() {
+		let server = spawn_server(|_| {
+			vec![
+				text(json!({"jsonrpc": "2.0", "id": 7, "result": {}})),
+				Message::Close(None),
+			]
+		})
+		.await;
+
+		let error = get_websocket_server_functions(&server).await.unwrap_err();
+		assert!(error.to_string().contains("Connection closed"));
+	}
+
+	#[tokio::test]
+	async fn test_close_frame_becomes_tool_error() {
+		let server = spawn_server(|_| vec![Message::Close(None)]).await;
+
+		let result = execute_websocket_tool_call(&call("echo"), &server, None)
+			.await
+			.unwrap();
+		assert_eq!(result.result["isError"], true);
+		assert!(super::super::extract_mcp_content(&result.result).contains("Connection closed"));
+	}
+
+	#[tokio::test]
+	async fn test_json_rpc_error_responses() {
+		let server = spawn_server(|request| {
+			vec![text(json!({
+				"jsonrpc": "2.0",
+				"id": request["id"],
+				"error": {"code": -32601, "message": "Unknown tool"}
+			}))]
+		})
+		.await;
+		let result = execute_websocket_tool_call(&call("missing"), &server, None)
+			.await
+			.unwrap();
+		assert_eq!(result.result["isError"], true);
+		assert!(super::super::extract_mcp_content(&result.result)
+			.contains("Unknown tool (code: -32601)"));
+
+		let server = spawn_server(|request| {
+			vec![text(json!({
+				"jsonrpc": "2.0",
+				"id": request["id"],
+				"error": {"code": -32603, "message": "boom"}
+			}))]
+		})
+		.await;
+		let error = get_websocket_server_functions(&server).await.unwrap_err();
+		assert!(error.to_string().contains("error during tools/list"));
+	}
+
+	#[tokio::test]
+	async fn test_tools_list_respects_tool_filter() {
+		let mut server = spawn_server(|request| {
+			vec![text(json!({
+				"jsonrpc": "2.0",
+				"id": request["id"],
+				"result": {"tools": [
+					{"name": "echo", "description": "Echo text", "inputSchema": {"type": "object"}},
+					{"name": "shout", "description": "Echo loudly"}
+				]}
+			}))]
+		})
+		.await;
+		server.tools = vec!["echo".to_string()];
+
+		let functions = get_websocket_server_functions(&server).await.unwrap();
+		assert_eq!(functions.len(), 1);
+		assert_eq!(functions[0].name, "echo");
+		assert_eq!(functions[0].parameters, json!({"type": "object"}));
+	}
+
+	#[tokio::test]
+	async fn test_unreachable_and_missing_urls() {
+		// Bind and drop a listener to get a port nothing is listening on
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let url = format!("ws://{}", listener.local_addr().unwrap());
+		drop(listener);
+		assert!(!check_websocket_connectivity(&server_config(&url)).await);
+
+		let mut server = server_config(&url);
+		server.url = None;
+		let error = get_websocket_server_functions(&server).await.unwrap_err();
+		assert!(error.to_string().contains("has no url configured"));
+
+		let error = get_websocket_server_functions(&server_config("not a url"))
+			.await
+			.unwrap_err();
+		assert!(!error.to_string().is_empty());
+	}
+
+	#[test]
+	fn test_websocket_server_config() {
+		let mut config: crate::config::Config =
+			toml::from_str(include_str!("../../config-templates/default.toml")).unwrap();
+		let servers: crate::config::McpConfig = toml::from_str(
+			r#"
+allowed_tools = []
+
+[[servers]]
+name = "remote"
+type = "ws"
+url = "wss://mcp.example.com/socket"
+auth_token = "secret"
+args = []
+timeout_seconds = 30
+tools = []
+"#,
+		)
+		.unwrap();
+		let server = servers.servers[0].clone();
+		assert_eq!(server.connection_type, McpConnectionType::WebSocket);
+		assert_eq!(server.auth_token.as_deref(), Some("secret"));
+
+		config.mcp.servers.push(server.clone());
+		assert!(config.validate().is_ok());
+
+		// Only ws:// and wss:// URLs are accepted, and nothing is launched
+		let last = config.mcp.servers.len() - 1;
+		config.mcp.servers[last].url = Some("http://mcp.example.com".to_string());
+		assert!(config.validate().unwrap_err().to_string().contains("ws://"));
+		config.mcp.servers[last].url = server.url.clone();
+		config.mcp.servers[last].command = Some("mcp-server".to_string());
+		assert!(config
+			.validate()
+			.unwrap_err()
+			.to_string()
+			.contains("cannot have 'command'"));
+	}
+}
@@ -14,7 +14,7 @@
 
 // Amazon Bedrock provider implementation
 
-use super::{AiProvider, ProviderExchange, ProviderResponse, TokenUsage};
+use super::{model_metadata, AiProvider, ProviderExchange, ProviderResponse, TokenUsage};
 use crate::config::Config;
 use crate::log_debug;
 use crate::session::Message;
@@ -23,43 +23,12 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::env;
 
-/// Amazon Bedrock pricing constants (per 1M tokens in USD)
-/// Source: https://aws.amazon.com/bedrock/pricing/ (as of January 2025)
-const PRICING: &[(&str, f64, f64)] = &[
-	// Model, Input price per 1M tokens, Output price per 1M tokens
-	// Anthropic Claude models on Bedrock
-	("claude-3-5-sonnet", 3.00, 15.00),
-	("claude-3-5-haiku", 0.80, 4.00),
-	("claude-3-opus", 15.00, 75.00),
-	("claude-3-sonnet", 3.00, 15.00),
-	("claude-3-haiku", 0.25, 1.25),
-	// Meta Llama models on Bedrock
-	("llama3-2-90b", 2.00, 2.00),
-	("llama3-2-11b", 0.35, 0.35),
-	("llama3-2-3b", 0.06, 0.06),
-	("llama3-2-1b", 0.035, 0.035),
-	("llama3-1-405b", 5.32, 16.00),
-	("llama3-1-70b", 0.99, 0.99),
-	("llama3-1-8b", 0.22, 0.22),
-	// Cohere Command models on Bedrock
-	("command-r-plus", 3.00, 15.00),
-	("command-r", 0.50, 1.50),
-	("command-light", 0.30, 0.60),
-	// AI21 Jamba models on Bedrock
-	("jamba-1-5-large", 2.00, 8.00),
-	("jamba-1-5-mini", 0.20, 0.40),
-];
-
 /// Calculate cost for Amazon Bedrock models
 fn calculate_cost(model: &str, prompt_tokens: u64, completion_tokens: u64) -> Option<f64> {
-	for (pricing_model, input_price, output_price) in PRICING {
-		if model.contains(pricing_model) {
-			let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
-			let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
-			return Some(input_cost + output_cost);
-		}
-	}
-	None
+	let (input_price, output_price) = model_metadata::pricing("amazon", model)?;
+	let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
+	let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
+	Some(input_cost + output_cost)
 }
 
 /// Amazon Bedrock provider implementation
@@ -188,35 +157,15 @@ impl AiProvider for AmazonBedrockProvider {
 	}
 
 	fn supports_vision(&self, model: &str) -> bool {
-		// Amazon Bedrock vision-capable models
-		// Claude 3+ models on Bedrock support vision
-		// Source: https://docs.aws.amazon.com/bedrock/latest/userguide/model-parameters-anthropic-claude-messages.html
-		model.contains("claude-3") || model.contains("claude-4")
+		model_metadata::supports_vision(self.name(), model)
+	}
+
+	fn estimate_cost(&self, model: &str, input_tokens: u64, output_tokens: u64) -> Option<f64> {
+		calculate_cost(&self.get_full_model_id(model), input_tokens, output_tokens)
 	}
 
 	fn get_max_input_tokens(&self, model: &str) -> usize {
-		// Amazon Bedrock model input limits (total context minus reserved output tokens)
-		// Claude models on Bedrock: 200K total context
-		if model.contains("claude") {
-			return 200_000 - 32_768; // Reserve 32K for output = ~167K input max
-		}
-		// Llama models on Bedrock: varies by version
-		if model.contains("llama-3.1") || model.contains("llama-3.2") {
-			return 128_000 - 4_096; // Reserve 4K for output = ~124K input max
-		}
-		if model.contains("llama") {
-			return 32_768 - 2_048; // Older Llama models
-		}
-		// Cohere models on Bedrock: typically 128K
-		if model.contains("cohere") {
-			return 128_000 - 4_096;
-		}
-		// Titan models on Bedrock: 32K
-		if model.contains("titan") {
-			return 32_768 - 2_048;
-		}
-		// Default conservative limit
-		32_768 - 2_048
+		model_metadata::context_window(self.name(), &self.get_full_model_id(model))
 	}
 
 	async fn chat_completion(
@@ -270,6 +219,16 @@ impl AiProvider for AmazonBedrockProvider {
 			})
 		};
 
+		// Only the Claude request format on Bedrock takes stop sequences
+		let stop_limit = if full_model_id.contains("anthropic.claude") {
+			None
+		} else {
+			Some(0)
+		};
+		if let Some(stop) = super::stop_sequences_for_provider(config, self.name(), stop_limit) {
+			request_body["stop_sequences"] = serde_json::json!(stop);
+		}
+
 		// Add tool definitions if MCP has any servers configured
 		// Different models on Bedrock have different tool formats
 		if !config.mcp.servers.is_empty() {
@@ -355,7 +314,13 @@ impl AiProvider for AmazonBedrockProvider {
 		// Track API request time
 		let api_start = std::time::Instant::now();
 
-		let response = request_builder.send().await?;
+		let response = super::send_request(
+			self.name(),
+			request_builder,
+			config,
+			cancellation_token.clone(),
+		)
+		.await?;
 
 		// Calculate API request time
 		let api_duration = api_start.elapsed();
@@ -523,6 +488,7 @@ impl AiProvider for AmazonBedrockProvider {
 				total_tokens,
 				cached_tokens: 0, // Amazon Bedrock doesn't support caching yet
 				cost,
+				reasoning_tokens: 0,
 				request_time_ms: Some(api_time_ms), // Track API timing for Amazon
 			})
 		} else {
@@ -14,7 +14,7 @@
 
 // Anthropic provider implementation
 
-use super::{AiProvider, ProviderExchange, ProviderResponse, TokenUsage};
+use super::{model_metadata, AiProvider, ProviderExchange, ProviderResponse, TokenUsage};
 use crate::config::Config;
 use crate::log_debug;
 use crate::session::Message;
@@ -23,27 +23,16 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::env;
 
-/// Anthropic pricing constants (per 1M tokens in USD)
-/// Source: https://docs.anthropic.com/en/docs/about-claude/models/overview (as of January 2025)
-const PRICING: &[(&str, f64, f64)] = &[
-	// Model, Input price per 1M tokens, Output price per 1M tokens
-	// Claude 4 models
-	("claude-opus-4-0", 15.00, 75.00),
-	("claude-sonnet-4-0", 3.00, 15.00),
-	// Claude 3.7 models
-	("claude-3-7-sonnet", 3.00, 15.00),
-	// Claude 3.5 models
-	("claude-3-5-sonnet", 3.00, 15.00),
-	("claude-3-5-haiku", 0.80, 4.00),
-	// Claude 3 models
-	("claude-3-opus", 15.00, 75.00),
-	("claude-3-sonnet", 3.00, 15.00),
-	("claude-3-haiku", 0.25, 1.25),
-	// Legacy models
-	("claude-2.1", 8.00, 24.00),
-	("claude-2.0", 8.00, 24.00),
-	("claude-instant-1.2", 0.80, 2.40),
-];
+/// Cache reads are billed at this fraction of the normal input price
+const CACHE_READ_PRICE_MULTIPLIER: f64 = 0.1;
+
+/// Check if a model supports extended thinking (Claude 3.7 and the Claude 4 family)
+fn supports_thinking(model: &str) -> bool {
+	model.contains("claude-3-7")
+		|| model.contains("sonnet-4")
+		|| model.contains("opus-4")
+		|| model.contains("haiku-4")
+}
 
 /// Token usage breakdown for cache-aware pricing
 struct CacheTokenUsage {
@@ -61,49 +50,45 @@ struct CacheTokenUsage {
 /// - regular_input_tokens: charged at normal price
 /// - output_tokens: charged at normal price
 fn calculate_cost_with_cache(model: &str, usage: CacheTokenUsage) -> Option<f64> {
-	for (pricing_model, input_price, output_price) in PRICING {
-		if model.contains(pricing_model) {
-			// Regular input tokens at normal price
-			let regular_input_cost =
-				(usage.regular_input_tokens as f64 / 1_000_000.0) * input_price;
-
-			// Cache creation tokens at 1.25x price (25% more expensive) for 5m cache
-			let cache_creation_cost =
-				(usage.cache_creation_tokens as f64 / 1_000_000.0) * input_price * 1.25;
-
-			// Cache creation tokens at 2x price (100% more expensive) for 1h cache
-			let cache_creation_cost_1h =
-				(usage.cache_creation_tokens_1h as f64 / 1_000_000.0) * input_price * 2.0;
-
-			// Cache read tokens at 0.1x price (90% cheaper)
-			let cache_read_cost =
-				(usage.cache_read_tokens as f64 / 1_000_000.0) * input_price * 0.1;
-
-			// Output tokens at normal price (never cached)
-			let output_cost = (usage.output_tokens as f64 / 1_000_000.0) * output_price;
-
-			let total_cost = regular_input_cost
-				+ cache_creation_cost
-				+ cache_creation_cost_1h
-				+ cache_read_cost
-				+ output_cost;
-
-			// Debug: Log detailed cost calculation breakdown
-			crate::log_debug!(
-				"Anthropic detailed cost calculation for {}: Regular input: ${:.8} ({} tokens @ ${:.2}/1M), Cache creation 5m: ${:.8} ({} tokens @ ${:.2}/1M), Cache creation 1h: ${:.8} ({} tokens @ ${:.2}/1M), Cache read: ${:.8} ({} tokens @ ${:.2}/1M), Output: ${:.8} ({} tokens @ ${:.2}/1M), Total: ${:.8}",
-				model,
-				regular_input_cost, usage.regular_input_tokens, input_price,
-				cache_creation_cost, usage.cache_creation_tokens, input_price * 1.25,
-				cache_creation_cost_1h, usage.cache_creation_tokens_1h, input_price * 2.0,
-				cache_read_cost, usage.cache_read_tokens, input_price * 0.1,
-				output_cost, usage.output_tokens, output_price,
-				total_cost
-			);
-
-			return Some(total_cost);
-		}
-	}
-	None
+	let (input_price, output_price) = model_metadata::pricing("anthropic", model)?;
+
+	// Regular input tokens at normal price
+	let regular_input_cost = (usage.regular_input_tokens as f64 / 1_000_000.0) * input_price;
+
+	// Cache creation tokens at 1.25x price (25% more expensive) for 5m cache
+	let cache_creation_cost =
+		(usage.cache_creation_tokens as f64 / 1_000_000.0) * input_price * 1.25;
+
+	// Cache creation tokens at 2x price (100% more expensive) for 1h cache
+	let cache_creation_cost_1h =
+		(usage.cache_creation_tokens_1h as f64 / 1_000_000.0) * input_price * 2.0;
+
+	// Cache read tokens at 0.1x price (90% cheaper)
+	let cache_read_cost =
+		(usage.cache_read_tokens as f64 / 1_000_000.0) * input_price * CACHE_READ_PRICE_MULTIPLIER;
+
+	// Output tokens at normal price (never cached)
+	let output_cost = (usage.output_tokens as f64 / 1_000_000.0) * output_price;
+
+	let total_cost = regular_input_cost
+		+ cache_creation_cost
+		+ cache_creation_cost_1h
+		+ cache_read_cost
+		+ output_cost;
+
+	// Debug: Log detailed cost calculation breakdown
+	crate::log_debug!(
+		"Anthropic detailed cost calculation for {}: Regular input: ${:.8} ({} tokens @ ${:.2}/1M), Cache creation 5m: ${:.8} ({} tokens @ ${:.2}/1M), Cache creation 1h: ${:.8} ({} tokens @ ${:.2}/1M), Cache read: ${:.8} ({} tokens @ ${:.2}/1M), Output: ${:.8} ({} tokens @ ${:.2}/1M), Total: ${:.8}",
+		model,
+		regular_input_cost, usage.regular_input_tokens, input_price,
+		cache_creation_cost, usage.cache_creation_tokens, input_price * 1.25,
+		cache_creation_cost_1h, usage.cache_creation_tokens_1h, input_price * 2.0,
+		cache_read_cost, usage.cache_read_tokens, input_price * CACHE_READ_PRICE_MULTIPLIER,
+		output_cost, usage.output_tokens, output_price,
+		total_cost
+	);
+
+	Some(total_cost)
 }
 
 /// Anthropic provider implementation
@@ -124,6 +109,7 @@ impl AnthropicProvider {
 // Constants
 const ANTHROPIC_API_KEY_ENV: &str = "ANTHROPIC_API_KEY";
 const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
+const DEFAULT_MAX_TOKENS: u32 = 32_768;
 
 /// Message format for the Anthropic API
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -159,43 +145,36 @@ impl AiProvider for AnthropicProvider {
 	}
 
 	fn supports_vision(&self, model: &str) -> bool {
-		// Claude 3+ models support vision
-		model.contains("claude-3")
-			|| model.contains("claude-4")
-			|| model.contains("claude-3.5")
-			|| model.contains("claude-3.7")
+		model_metadata::supports_vision(self.name(), model)
 	}
 
-	fn get_max_input_tokens(&self, model: &str) -> usize {
-		// Anthropic model context window limits (what we can send as input)
-		// These are the actual context windows - no output reservation needed
-		// The API will handle output limits internally
+	fn supports_documents(&self, model: &str) -> bool {
+		// PDF input is available on the same models that accept images
+		self.supports_vision(model)
+	}
 
-		// Claude 4 models: 200K context window
-		if model.contains("claude-opus-4") || model.contains("claude-sonnet-4") {
-			return 200_000;
-		}
-		// Claude 3.7 models: 200K context window
-		if model.contains("claude-3-7-sonnet") {
-			return 200_000;
-		}
-		// Claude 3.5 models: 200K context window
-		if model.contains("claude-3-5-sonnet") || model.contains("claude-3-5-haiku") {
-			return 200_000;
-		}
-		// Claude 3 models: 200K context window
-		if model.contains("claude-3-opus")
-			|| model.contains("claude-3-sonnet")
-			|| model.contains("claude-3-haiku")
-		{
-			return 200_000;
-		}
-		// Legacy models: 100K context window
-		if model.contains("claude-2") || model.contains("claude-instant") {
-			return 100_000;
-		}
-		// Default conservative limit
-		100_000
+	fn estimate_cost(&self, model: &str, input_tokens: u64, output_tokens: u64) -> Option<f64> {
+		// Assume no cache hits so the estimate errs on the expensive side
+		calculate_cost_with_cache(
+			model,
+			CacheTokenUsage {
+				regular_input_tokens: input_tokens,
+				cache_creation_tokens: 0,
+				cache_creation_tokens_1h: 0,
+				cache_read_tokens: 0,
+				output_tokens,
+			},
+		)
+	}
+
+	fn estimate_cache_savings(&self, model: &str, cached_tokens: u64) -> Option<f64> {
+		model_metadata::pricing(self.name(), model).map(|(input_price, _)| {
+			(cached_tokens as f64 / 1_000_000.0) * input_price * (1.0 - CACHE_READ_PRICE_MULTIPLIER)
+		})
+	}
+
+	fn get_max_input_tokens(&self, model: &str) -> usize {
+		model_metadata::context_window(self.name(), model)
 	}
 
 	async fn chat_completion(
@@ -215,9 +194,6 @@ impl AiProvider for AnthropicProvider {
 		// Get API key
 		let api_key = self.get_api_key(config)?;
 
-		// Convert messages to Anthropic format with automatic cache markers
-		let anthropic_messages = convert_messages(messages);
-
 		// Extract system message if present and handle caching
 		let system_message = messages
 			.iter()
@@ -227,14 +203,43 @@ impl AiProvider for AnthropicProvider {
 
 		let system_cached = messages.iter().any(|m| m.role == "system" && m.cached);
 
+		// Older models reject a max_tokens above their output limit
+		let max_tokens = model_metadata::max_output_tokens(self.name(), model)
+			.map_or(DEFAULT_MAX_TOKENS, |max| max.min(DEFAULT_MAX_TOKENS));
+
 		// Create the request body
 		let mut request_body = serde_json::json!({
 			"model": model,
-			"max_tokens": 32768,
-			"messages": anthropic_messages,
+			"max_tokens": max_tokens,
 			"temperature": temperature,
 		});
 
+		if let Some(stop) = super::stop_sequences_for_provider(config, self.name(), None) {
+			request_body["stop_sequences"] = serde_json::json!(stop);
+		}
+
+		// Extended thinking is only enabled on turns that start from a user message: a tool
+		// result continuation would otherwise need the previous thinking blocks sent back
+		let starts_turn = messages
+			.iter()
+			.rev()
+			.find(|m| m.role != "system")
+			.is_some_and(|m| m.role == "user");
+		if let Some(effort) = config.reasoning_effort {
+			// The thinking budget has to leave room for the visible answer
+			let budget_tokens = effort.budget_tokens().min(max_tokens / 2);
+			if supports_thinking(model) && starts_turn && budget_tokens >= 1024 {
+				request_body["thinking"] = serde_json::json!({
+					"type": "enabled",
+					"budget_tokens": budget_tokens,
+				});
+				// Thinking requires the default temperature
+				if let Some(body) = request_body.as_object_mut() {
+					body.remove("temperature");
+				}
+			}
+		}
+
 		// Add system message with cache control if needed
 		if system_cached {
 			let ttl = if config.use_long_system_cache {
@@ -255,6 +260,7 @@ impl AiProvider for AnthropicProvider {
 		}
 
 		// Add tool definitions if MCP has any servers configured
+		let mut tool_cache_segment = None;
 		if !config.mcp.servers.is_empty() {
 			let functions = crate::mcp::get_available_functions(config).await;
 			if !functions.is_empty() {
@@ -274,29 +280,24 @@ impl AiProvider for AnthropicProvider {
 					})
 					.collect::<Vec<_>>();
 
-				// CRITICAL FIX: Cache control should be handled consistently
-				// Add cache control to the LAST tool definition ONLY if the model supports caching
-				// and we actually want to cache tool definitions (check session state)
-				if self.supports_caching(model) && !tools.is_empty() {
-					// Check if any system message is cached - if so, we should cache tool definitions too
-					let system_cached = messages
-						.iter()
-						.any(|msg| msg.role == "system" && msg.cached);
-
-					if system_cached {
-						if let Some(last_tool) = tools.last_mut() {
-							last_tool["cache_control"] = serde_json::json!({
-								"type": "ephemeral",
-								"ttl": "1h"
-							});
-						}
-					}
+				// Tool definitions get their own cache breakpoint, independent of the system
+				// prompt, so they stay cached while the system prompt or history changes
+				if self.supports_caching(model) {
+					tool_cache_segment = crate::session::cache::mark_tool_definitions_for_caching(
+						&mut tools, config, model,
+					);
 				}
 
 				request_body["tools"] = serde_json::json!(tools);
 			}
 		}
 
+		// Convert messages to Anthropic format, keeping the total number of cache
+		// breakpoints (system, tools and content markers) within the provider limit
+		let messages =
+			crate::session::cache::limit_cache_breakpoints(messages, tool_cache_segment.is_some());
+		request_body["messages"] = serde_json::json!(convert_messages(&messages));
+
 		// Check for cancellation before making HTTP request
 		if let Some(ref token) = cancellation_token {
 			if token.load(std::sync::atomic::Ordering::SeqCst) {
@@ -311,38 +312,18 @@ impl AiProvider for AnthropicProvider {
 		let api_start = std::time::Instant::now();
 
 		// Create the HTTP request
-		let request_future = client
+		let request = client
 			.post(ANTHROPIC_API_URL)
 			.header("x-api-key", api_key)
 			.header("Content-Type", "application/json")
 			.header("anthropic-version", "2023-06-01")
 			.header("anthropic-beta", "extended-cache-ttl-2025-04-11")
 			.header("anthropic-beta", "token-efficient-tools-2025-02-19")
-			.json(&request_body)
-			.send();
-
-		// Race the HTTP request against cancellation
-		let response = if let Some(ref token) = cancellation_token {
-			let cancellation_future = async {
-				loop {
-					if token.load(std::sync::atomic::Ordering::SeqCst) {
-						break;
-					}
-					tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
-				}
-			};
+			.json(&request_body);
 
-			tokio::select! {
-				result = request_future => {
-					result?
-				}
-				_ = cancellation_future => {
-					return Err(anyhow::anyhow!("Request cancelled during HTTP call"));
-				}
-			}
-		} else {
-			request_future.await?
-		};
+		// Send with timeout, racing the HTTP request against cancellation
+		let response =
+			super::send_request(self.name(), request, config, cancellation_token.clone()).await?;
 
 		// Calculate API request time
 		let api_duration = api_start.elapsed();
@@ -481,6 +462,12 @@ impl AiProvider for AnthropicProvider {
 			// - This way we show the total prompt tokens processed, with breakdown of what was cached
 
 			let cached_tokens = cache_read_input_tokens; // Only cache reads are "cached"
+			if let Some(segment_tokens) = tool_cache_segment {
+				crate::session::cache::record_tool_definition_cache_usage(
+					segment_tokens,
+					cache_read_input_tokens,
+				);
+			}
 
 			// For cost calculation, we need to separate the different token types
 			let regular_input_tokens = input_tokens; // These are already regular tokens from API
@@ -518,6 +505,7 @@ impl AiProvider for AnthropicProvider {
 					+ output_tokens,
 				cached_tokens, // Only cache_read_input_tokens are truly "cached"
 				cost,          // Pre-calculated with proper cache pricing
+				reasoning_tokens: 0,
 				request_time_ms: Some(api_time_ms), // Track API timing for Anthropic
 			})
 		} else {
@@ -666,6 +654,24 @@ fn convert_messages(messages: &[Message]) -> Vec<AnthropicMessage> {
 					}
 				}
 
+				// Add PDF attachments as document blocks (text attachments are already inlined)
+				if let Some(ref attachments) = msg.attachments {
+					for attachment in attachments {
+						if let crate::session::attachment::AttachmentContent::Document(ref data) =
+							attachment.content
+						{
+							content_blocks.push(serde_json::json!({
+								"type": "document",
+								"source": {
+									"type": "base64",
+									"media_type": attachment.media_type,
+									"data": data
+								}
+							}));
+						}
+					}
+				}
+
 				// Only create message if we have content
 				if !content_blocks.is_empty() {
 					result.push(AnthropicMessage {
@@ -14,7 +14,7 @@
 
 // Cloudflare Workers AI provider implementation
 
-use super::{AiProvider, ProviderExchange, ProviderResponse, TokenUsage};
+use super::{model_metadata, AiProvider, ProviderExchange, ProviderResponse, TokenUsage};
 use crate::config::Config;
 use crate::log_debug;
 use crate::session::Message;
@@ -23,51 +23,13 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::env;
 
-/// Cloudflare Workers AI pricing constants (per 1M tokens in USD)
-/// Source: https://developers.cloudflare.com/workers-ai/platform/pricing/ (as of January 2025)
-const PRICING: &[(&str, f64, f64)] = &[
-	// Model, Input price per 1M tokens, Output price per 1M tokens
-	// Meta Llama models
-	("llama-3.1-8b-instruct", 0.125, 0.125),
-	("llama-3.1-70b-instruct", 0.59, 0.80),
-	("llama-3.2-1b-instruct", 0.04, 0.04),
-	("llama-3.2-3b-instruct", 0.06, 0.06),
-	("llama-2-7b-chat", 0.125, 0.125),
-	("llama-2-13b-chat", 0.25, 0.25),
-	// Mistral models
-	("mistral-7b-instruct", 0.125, 0.125),
-	// Microsoft models
-	("phi-2", 0.125, 0.125),
-	// Qwen models
-	("qwen1.5-0.5b-chat", 0.04, 0.04),
-	("qwen1.5-1.8b-chat", 0.04, 0.04),
-	("qwen1.5-7b-chat", 0.125, 0.125),
-	("qwen1.5-14b-chat", 0.25, 0.25),
-	// TinyLlama models
-	("tinyllama-1.1b-chat", 0.04, 0.04),
-	// Neural Chat models
-	("neural-chat-7b", 0.125, 0.125),
-	// Gemma models
-	("gemma-2b-it", 0.04, 0.04),
-	("gemma-7b-it", 0.125, 0.125),
-	// Code Llama models
-	("codellama-7b-instruct", 0.125, 0.125),
-	// Hermes models
-	("hermes-2-pro-mistral-7b", 0.125, 0.125),
-];
-
 /// Calculate cost for Cloudflare Workers AI models
 fn calculate_cost(model: &str, prompt_tokens: u64, completion_tokens: u64) -> Option<f64> {
-	for (pricing_model, input_price, output_price) in PRICING {
-		if model.contains(pricing_model) {
-			let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
-			let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
-			return Some(input_cost + output_cost);
-		}
-	}
 	// Default pricing for unknown models (roughly similar to small models)
-	let input_cost = (prompt_tokens as f64 / 1_000_000.0) * 0.125;
-	let output_cost = (completion_tokens as f64 / 1_000_000.0) * 0.125;
+	let (input_price, output_price) =
+		model_metadata::pricing("cloudflare", model).unwrap_or((0.125, 0.125));
+	let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
+	let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
 	Some(input_cost + output_cost)
 }
 
@@ -206,31 +168,15 @@ impl AiProvider for CloudflareWorkersAiProvider {
 	}
 
 	fn supports_vision(&self, model: &str) -> bool {
-		// Cloudflare Workers AI vision-capable models
-		// Llama 3.2 vision models support multimodal input
-		// Source: https://developers.cloudflare.com/workers-ai/models/
-		model.contains("llama-3.2") && model.contains("vision")
+		model_metadata::supports_vision(self.name(), model)
+	}
+
+	fn estimate_cost(&self, model: &str, input_tokens: u64, output_tokens: u64) -> Option<f64> {
+		calculate_cost(&self.get_full_model_id(model), input_tokens, output_tokens)
 	}
 
 	fn get_max_input_tokens(&self, model: &str) -> usize {
-		// Cloudflare Workers AI model input limits (total context minus reserved output tokens)
-		// Llama models: varies by version
-		if model.contains("llama-3.1") || model.contains("llama-3.2") {
-			return 128_000 - 4_096; // Reserve 4K for output = ~124K input max
-		}
-		if model.contains("llama") {
-			return 32_768 - 2_048; // Older Llama models
-		}
-		// Qwen models: typically 32K
-		if model.contains("qwen") {
-			return 32_768 - 2_048;
-		}
-		// Mistral models: varies
-		if model.contains("mistral") {
-			return 32_768 - 2_048;
-		}
-		// Default conservative limit for Workers AI
-		16_384 - 1_024
+		model_metadata::context_window(self.name(), &self.get_full_model_id(model))
 	}
 
 	async fn chat_completion(
@@ -265,6 +211,9 @@ impl AiProvider for CloudflareWorkersAiProvider {
 			"max_tokens": 16384,
 		});
 
+		// Workers AI has no stop parameter; this only warns when stop sequences are set
+		super::stop_sequences_for_provider(config, self.name(), Some(0));
+
 		// Add tool definitions if MCP has any servers configured
 		// Cloudflare Workers AI uses OpenAI-compatible tools format
 		if !config.mcp.servers.is_empty() {
@@ -307,13 +256,13 @@ impl AiProvider for CloudflareWorkersAiProvider {
 		let api_start = std::time::Instant::now();
 
 		// Make the API request
-		let response = client
+		let request = client
 			.post(&api_url)
 			.header("Authorization", format!("Bearer {}", api_token))
 			.header("Content-Type", "application/json")
-			.json(&request_body)
-			.send()
-			.await?;
+			.json(&request_body);
+		let response =
+			super::send_request(self.name(), request, config, cancellation_token.clone()).await?;
 
 		// Calculate API request time
 		let api_duration = api_start.elapsed();
@@ -475,6 +424,7 @@ impl AiProvider for CloudflareWorkersAiProvider {
 			total_tokens,
 			cached_tokens: 0, // Cloudflare Workers AI doesn't support caching yet
 			cost,
+			reasoning_tokens: 0,
 			request_time_ms: Some(api_time_ms), // Track API timing for Cloudflare
 		});
 
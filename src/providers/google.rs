@@ -14,7 +14,7 @@
 
 // Google Vertex AI provider implementation
 
-use super::{AiProvider, ProviderExchange, ProviderResponse, TokenUsage};
+use super::{model_metadata, AiProvider, ProviderExchange, ProviderResponse, TokenUsage};
 use crate::config::Config;
 use crate::log_debug;
 use crate::session::Message;
@@ -23,39 +23,12 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::env;
 
-/// Google Vertex AI pricing constants (per 1M tokens in USD)
-/// Source: https://cloud.google.com/vertex-ai/generative-ai/pricing (as of January 2025)
-const PRICING: &[(&str, f64, f64)] = &[
-	// Model, Input price per 1M tokens, Output price per 1M tokens
-	// Gemini 2.5 models (latest)
-	("gemini-2.5-pro", 1.25, 10.00), // <= 200K tokens, higher rates for >200K
-	("gemini-2.5-flash", 0.15, 0.60), // Consistent pricing
-	// Gemini 2.0 models
-	("gemini-2.0-flash", 0.15, 0.60), // Token-based pricing
-	("gemini-2.0-flash-lite", 0.075, 0.30),
-	// Gemini 1.5 models
-	("gemini-1.5-pro", 0.3125, 1.25), // <= 128K tokens, converted from character pricing
-	("gemini-1.5-flash", 0.075, 0.30), // <= 128K tokens, converted from character pricing
-	// Gemini 1.0 models
-	("gemini-1.0-pro", 0.50, 1.50), // Converted from character pricing
-	("gemini-pro", 0.50, 1.50),     // Alias for gemini-1.0-pro
-	// Legacy models
-	("text-bison", 1.00, 2.00),
-	("chat-bison", 1.00, 2.00),
-	("code-bison", 1.00, 2.00),
-	("codechat-bison", 1.00, 2.00),
-];
-
 /// Calculate cost for Google Vertex AI models
 fn calculate_cost(model: &str, prompt_tokens: u64, completion_tokens: u64) -> Option<f64> {
-	for (pricing_model, input_price, output_price) in PRICING {
-		if model.contains(pricing_model) {
-			let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
-			let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
-			return Some(input_cost + output_cost);
-		}
-	}
-	None
+	let (input_price, output_price) = model_metadata::pricing("google", model)?;
+	let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
+	let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
+	Some(input_cost + output_cost)
 }
 
 /// Google Vertex AI provider implementation
@@ -132,32 +105,15 @@ impl AiProvider for GoogleVertexProvider {
 	}
 
 	fn supports_vision(&self, model: &str) -> bool {
-		// Google Vertex AI vision-capable models
-		// Gemini 1.5+ models support multimodal input including images
-		// Source: https://cloud.google.com/vertex-ai/generative-ai/docs/multimodal/overview
-		model.contains("gemini-2.5") || model.contains("gemini-2.0") || model.contains("gemini-1.5")
+		model_metadata::supports_vision(self.name(), model)
+	}
+
+	fn estimate_cost(&self, model: &str, input_tokens: u64, output_tokens: u64) -> Option<f64> {
+		calculate_cost(model, input_tokens, output_tokens)
 	}
 
 	fn get_max_input_tokens(&self, model: &str) -> usize {
-		// Google Vertex AI model context window limits
-		// Gemini 2.5 models: 2M context window
-		if model.contains("gemini-2.5") {
-			return 2_000_000;
-		}
-		// Gemini 2.0 models: 1M context window
-		if model.contains("gemini-2.0") {
-			return 1_000_000;
-		}
-		// Gemini 1.5 models: 1M context window
-		if model.contains("gemini-1.5") {
-			return 1_000_000;
-		}
-		// Gemini 1.0 models: 32K context window
-		if model.contains("gemini-1.0") || model.contains("gemini-pro") {
-			return 32_768;
-		}
-		// Default conservative limit
-		32_768
+		model_metadata::context_window(self.name(), model)
 	}
 
 	async fn chat_completion(
@@ -202,6 +158,10 @@ impl AiProvider for GoogleVertexProvider {
 			}
 		});
 
+		if let Some(stop) = super::stop_sequences_for_provider(config, self.name(), Some(5)) {
+			request_body["generationConfig"]["stopSequences"] = serde_json::json!(stop);
+		}
+
 		// Add tool definitions if MCP has any servers configured (simplified for Vertex AI)
 		if !config.mcp.servers.is_empty() {
 			let functions = crate::mcp::get_available_functions(config).await;
@@ -235,13 +195,13 @@ impl AiProvider for GoogleVertexProvider {
 		let api_start = std::time::Instant::now();
 
 		// Make the actual API request
-		let response = client
+		let request = client
 			.post(&api_url)
 			.header("Authorization", format!("Bearer {}", access_token))
 			.header("Content-Type", "application/json")
-			.json(&request_body)
-			.send()
-			.await?;
+			.json(&request_body);
+		let response =
+			super::send_request(self.name(), request, config, cancellation_token.clone()).await?;
 
 		// Calculate API request time
 		let api_duration = api_start.elapsed();
@@ -378,6 +338,7 @@ impl AiProvider for GoogleVertexProvider {
 				total_tokens,
 				cached_tokens: 0, // Google Vertex AI doesn't support caching yet
 				cost,
+				reasoning_tokens: 0,
 				request_time_ms: Some(api_time_ms), // Track API timing for Google
 			})
 		} else {
@@ -18,22 +18,40 @@ use crate::config::Config;
 use crate::session::Message;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub mod amazon;
 pub mod anthropic;
 pub mod cloudflare;
 pub mod google;
+pub mod model_metadata;
+pub mod ollama;
 pub mod openai;
 pub mod openrouter;
+pub mod rate_limit;
+// Building block for streamed responses; every provider still requests complete
+// responses, so nothing feeds it yet
+#[allow(dead_code)]
+mod tool_call_stream;
 
 // Re-export provider implementations
 pub use amazon::AmazonBedrockProvider;
 pub use anthropic::AnthropicProvider;
 pub use cloudflare::CloudflareWorkersAiProvider;
 pub use google::GoogleVertexProvider;
+pub use ollama::OllamaProvider;
 pub use openai::OpenAiProvider;
 pub use openrouter::OpenRouterProvider;
+pub use rate_limit::acquire_rate_limit;
+
+/// A model offered by a provider, as shown by `/model list`
+#[derive(Debug, Clone)]
+pub struct ModelInfo {
+	pub id: String,
+	pub context_window: Option<usize>,
+}
 
 /// Common token usage structure across all providers
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -43,6 +61,8 @@ pub struct TokenUsage {
 	pub total_tokens: u64,  // prompt_tokens + output_tokens
 	pub cached_tokens: u64, // Subset of prompt_tokens that came from cache (discounted)
 	#[serde(default)]
+	pub reasoning_tokens: u64, // Subset of output_tokens spent on hidden reasoning (when reported)
+	#[serde(default)]
 	pub cost: Option<f64>, // Pre-calculated total cost (provider handles cache pricing)
 	// Time tracking
 	#[serde(default)]
@@ -88,6 +108,72 @@ pub struct ProviderResponse {
 	pub finish_reason: Option<String>,
 }
 
+/// Error returned when a provider request exceeds the configured `api_timeout_seconds`
+#[derive(Debug)]
+pub struct ProviderTimeoutError {
+	pub provider: String,
+	pub timeout_seconds: u64,
+}
+
+impl std::fmt::Display for ProviderTimeoutError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{} API request timed out after {} seconds",
+			self.provider, self.timeout_seconds
+		)
+	}
+}
+
+impl std::error::Error for ProviderTimeoutError {}
+
+/// Check whether an error is a provider timeout (transient, safe to retry)
+pub fn is_timeout_error(error: &anyhow::Error) -> bool {
+	error.downcast_ref::<ProviderTimeoutError>().is_some()
+}
+
+/// Send a provider HTTP request with the configured timeout, aborting if the cancellation token fires
+pub async fn send_request(
+	provider: &str,
+	request: reqwest::RequestBuilder,
+	config: &Config,
+	cancellation_token: Option<Arc<AtomicBool>>,
+) -> Result<reqwest::Response> {
+	let timeout_seconds = config.api_timeout_seconds;
+	let request_future = request.timeout(Duration::from_secs(timeout_seconds)).send();
+
+	// Race the HTTP request against cancellation
+	let result = if let Some(ref token) = cancellation_token {
+		let cancellation_future = async {
+			loop {
+				if token.load(Ordering::SeqCst) {
+					break;
+				}
+				tokio::time::sleep(Duration::from_millis(10)).await;
+			}
+		};
+
+		tokio::select! {
+			result = request_future => result,
+			_ = cancellation_future => {
+				return Err(anyhow::anyhow!("Request cancelled during HTTP call"));
+			}
+		}
+	} else {
+		request_future.await
+	};
+
+	match result {
+		Ok(response) => Ok(response),
+		Err(e) if e.is_timeout() => Err(ProviderTimeoutError {
+			provider: provider.to_string(),
+			timeout_seconds,
+		}
+		.into()),
+		Err(e) => Err(e.into()),
+	}
+}
+
 /// Trait that all AI providers must implement
 #[async_trait::async_trait]
 pub trait AiProvider: Send + Sync {
@@ -131,6 +217,110 @@ pub trait AiProvider: Send + Sync {
 		// Default implementation - providers can override
 		false
 	}
+
+	/// Check if the provider/model accepts PDF documents through a document API
+	/// (text attachments are inlined for every provider)
+	fn supports_documents(&self, _model: &str) -> bool {
+		false
+	}
+
+	/// Estimate the cost in USD of a request before sending it
+	/// Returns None when the provider does not know the model's pricing up front
+	fn estimate_cost(&self, _model: &str, _input_tokens: u64, _output_tokens: u64) -> Option<f64> {
+		// Default implementation - providers with pricing tables override
+		None
+	}
+
+	/// Estimate how much USD cache reads saved compared to paying the full input price
+	/// Returns None when the provider has no pricing or cache discount for the model
+	fn estimate_cache_savings(&self, _model: &str, _cached_tokens: u64) -> Option<f64> {
+		// Default implementation - providers with cache-aware pricing override
+		None
+	}
+
+	/// Curated model IDs shown when the provider has no model list endpoint
+	fn curated_models(&self) -> Vec<&'static str> {
+		model_metadata::known_models(self.name())
+	}
+
+	/// List the models available from this provider
+	/// Providers with a models endpoint override this; others return the curated list
+	async fn list_models(&self, _config: &Config) -> Result<Vec<ModelInfo>> {
+		Ok(self
+			.curated_models()
+			.into_iter()
+			.map(|id| ModelInfo {
+				id: id.to_string(),
+				context_window: Some(self.get_max_input_tokens(id)),
+			})
+			.collect())
+	}
+}
+
+/// Estimate cache savings for a "provider:model" string (None if unknown)
+pub fn estimate_cache_savings(model: &str, cached_tokens: u64) -> Option<f64> {
+	let (provider, model_name) = ProviderFactory::get_provider_for_model(model).ok()?;
+	provider.estimate_cache_savings(&model_name, cached_tokens)
+}
+
+/// Estimate the input cost of a number of tokens for a "provider:model" string (None if unknown)
+pub fn estimate_input_cost(model: &str, tokens: u64) -> Option<f64> {
+	let (provider, model_name) = ProviderFactory::parse_model(model).ok()?;
+	let (input_price, _) = model_metadata::pricing(&provider, &model_name)?;
+	Some(tokens as f64 / 1_000_000.0 * input_price)
+}
+
+lazy_static::lazy_static! {
+	// Providers already warned about dropped stop sequences, so each warns only once
+	static ref WARNED_STOP_SEQUENCES: std::sync::Mutex<std::collections::HashSet<String>> =
+		std::sync::Mutex::new(std::collections::HashSet::new());
+}
+
+/// Configured stop sequences that this provider accepts, or None when there are none to send.
+/// `limit` is the most the provider takes (None = no limit, Some(0) = not supported); the
+/// first ones are kept and a warning is printed once when any are dropped.
+pub fn stop_sequences_for_provider(
+	config: &Config,
+	provider: &str,
+	limit: Option<usize>,
+) -> Option<Vec<String>> {
+	let (kept, dropped) = clamp_stop_sequences(&config.stop_sequences, limit);
+	if dropped > 0 {
+		let first_time = WARNED_STOP_SEQUENCES
+			.lock()
+			.map(|mut warned| warned.insert(provider.to_string()))
+			.unwrap_or(false);
+		if first_time {
+			use colored::Colorize;
+			let message = match limit {
+				Some(0) => format!(
+					"Warning: {} does not support stop sequences; ignoring stop_sequences",
+					provider
+				),
+				_ => format!(
+					"Warning: {} accepts at most {} stop sequences; ignoring the last {}",
+					provider,
+					kept.len(),
+					dropped
+				),
+			};
+			eprintln!("{}", message.yellow());
+		}
+	}
+
+	if kept.is_empty() {
+		None
+	} else {
+		Some(kept)
+	}
+}
+
+// Keep the first `limit` stop sequences, returning them and how many were dropped
+fn clamp_stop_sequences(stop_sequences: &[String], limit: Option<usize>) -> (Vec<String>, usize) {
+	let keep = limit.map_or(stop_sequences.len(), |limit| {
+		limit.min(stop_sequences.len())
+	});
+	(stop_sequences[..keep].to_vec(), stop_sequences.len() - keep)
 }
 
 /// Provider factory to create the appropriate provider based on model string
@@ -165,7 +355,8 @@ impl ProviderFactory {
 			"google" => Ok(Box::new(GoogleVertexProvider::new())),
 			"amazon" => Ok(Box::new(AmazonBedrockProvider::new())),
 			"cloudflare" => Ok(Box::new(CloudflareWorkersAiProvider::new())),
-			_ => Err(anyhow::anyhow!("Unsupported provider: {}. Supported providers: openrouter, openai, anthropic, google, amazon, cloudflare", provider_name)),
+			"ollama" => Ok(Box::new(OllamaProvider::new())),
+			_ => Err(anyhow::anyhow!("Unsupported provider: {}. Supported providers: openrouter, openai, anthropic, google, amazon, cloudflare, ollama", provider_name)),
 		}
 	}
 
@@ -191,6 +382,22 @@ impl ProviderFactory {
 mod tests {
 	use super::*;
 
+	#[test]
+	fn test_clamp_stop_sequences() {
+		let stop: Vec<String> = ["END", "###", "</answer>"]
+			.iter()
+			.map(|s| s.to_string())
+			.collect();
+
+		assert_eq!(clamp_stop_sequences(&stop, None), (stop.clone(), 0));
+		assert_eq!(clamp_stop_sequences(&stop, Some(4)), (stop.clone(), 0));
+		assert_eq!(
+			clamp_stop_sequences(&stop, Some(2)),
+			(stop[..2].to_vec(), 1)
+		);
+		assert_eq!(clamp_stop_sequences(&stop, Some(0)), (Vec::new(), 3));
+	}
+
 	#[test]
 	fn test_parse_model() {
 		// Test with provider prefix
@@ -218,6 +425,13 @@ mod tests {
 		// Test empty model
 		let result = ProviderFactory::parse_model("openai:");
 		assert!(result.is_err());
+
+		// Test local model with tag - only the first colon separates the provider
+		let result = ProviderFactory::parse_model("ollama:llama3.1:8b");
+		assert!(result.is_ok());
+		let (provider, model) = result.unwrap();
+		assert_eq!(provider, "ollama");
+		assert_eq!(model, "llama3.1:8b");
 	}
 
 	#[test]
@@ -241,8 +455,50 @@ mod tests {
 		let provider = ProviderFactory::create_provider("cloudflare");
 		assert!(provider.is_ok());
 
+		let provider = ProviderFactory::create_provider("ollama");
+		assert!(provider.is_ok());
+
 		// Test invalid provider
 		let provider = ProviderFactory::create_provider("invalid");
 		assert!(provider.is_err());
 	}
+
+	#[test]
+	fn test_is_timeout_error() {
+		let timeout: anyhow::Error = ProviderTimeoutError {
+			provider: "openai".to_string(),
+			timeout_seconds: 120,
+		}
+		.into();
+		assert!(is_timeout_error(&timeout));
+		assert_eq!(
+			timeout.to_string(),
+			"openai API request timed out after 120 seconds"
+		);
+
+		let other = anyhow::anyhow!("connection refused");
+		assert!(!is_timeout_error(&other));
+	}
+
+	#[test]
+	fn test_curated_models() {
+		// Providers without a models endpoint fall back to their pricing tables
+		let anthropic = ProviderFactory::create_provider("anthropic").unwrap();
+		assert!(!anthropic.curated_models().is_empty());
+
+		// Ollama models are local, so there is nothing to curate
+		let ollama = ProviderFactory::create_provider("ollama").unwrap();
+		assert!(ollama.curated_models().is_empty());
+	}
+
+	#[test]
+	fn test_estimate_cache_savings() {
+		// Anthropic cache reads cost 10% of input, so 1M cached Sonnet tokens save 90% of $3
+		let saved =
+			estimate_cache_savings("anthropic:claude-3-5-sonnet-20241022", 1_000_000).unwrap();
+		assert!((saved - 2.7).abs() < 1e-9);
+
+		// OpenRouter reports cost from the API and has no local pricing
+		assert!(estimate_cache_savings("openrouter:anthropic/claude-sonnet-4", 1_000).is_none());
+	}
 }
@@ -0,0 +1,162 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Model metadata table - context windows, output limits, pricing and vision support
+
+use crate::config::ModelMetadata;
+use colored::Colorize;
+use std::collections::HashSet;
+use std::sync::{Mutex, RwLock};
+
+/// Context window assumed for models missing from the table
+pub const DEFAULT_CONTEXT_WINDOW: usize = 32_768;
+
+/// Built-in model table embedded in the binary
+const BUILTIN_MODELS_JSON: &str = include_str!("models.json");
+
+lazy_static::lazy_static! {
+	static ref BUILTIN_MODELS: Vec<ModelMetadata> = serde_json::from_str(BUILTIN_MODELS_JSON)
+		.expect("Embedded models.json must be valid");
+	// Entries from the user's [[model_metadata]] config, consulted before the built-in table
+	static ref USER_OVERRIDES: RwLock<Vec<ModelMetadata>> = RwLock::new(Vec::new());
+	// Unknown models already warned about, so the warning is shown once per model
+	static ref WARNED_MODELS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+/// Replace the user overrides (called when the configuration is loaded)
+pub fn set_user_overrides(entries: &[ModelMetadata]) {
+	if let Ok(mut overrides) = USER_OVERRIDES.write() {
+		*overrides = entries.to_vec();
+	}
+}
+
+/// The entry whose model pattern is the longest substring of the model ID
+fn best_match<'a>(
+	entries: &'a [ModelMetadata],
+	provider: &str,
+	model: &str,
+) -> Option<&'a ModelMetadata> {
+	entries
+		.iter()
+		.filter(|entry| entry.provider == provider && model.contains(entry.model.as_str()))
+		.max_by_key(|entry| entry.model.len())
+}
+
+/// Look up metadata for a model, preferring user overrides over the built-in table
+pub fn lookup(provider: &str, model: &str) -> Option<ModelMetadata> {
+	if let Ok(overrides) = USER_OVERRIDES.read() {
+		if let Some(entry) = best_match(&overrides, provider, model) {
+			return Some(entry.clone());
+		}
+	}
+	best_match(&BUILTIN_MODELS, provider, model).cloned()
+}
+
+/// Context window for a model, falling back to a conservative default for unknown models
+pub fn context_window(provider: &str, model: &str) -> usize {
+	if let Some(entry) = lookup(provider, model) {
+		return entry.context_window;
+	}
+
+	let key = format!("{}:{}", provider, model);
+	let first_time = WARNED_MODELS
+		.lock()
+		.map(|mut warned| warned.insert(key.clone()))
+		.unwrap_or(false);
+	if first_time {
+		eprintln!(
+			"{}",
+			format!(
+				"Warning: no metadata for model '{}', assuming a {} token context window. Add a [[model_metadata]] entry to your config to set it.",
+				key, DEFAULT_CONTEXT_WINDOW
+			)
+			.yellow()
+		);
+	}
+	DEFAULT_CONTEXT_WINDOW
+}
+
+/// Maximum output tokens for a model, if known
+pub fn max_output_tokens(provider: &str, model: &str) -> Option<u32> {
+	lookup(provider, model)?.max_output
+}
+
+/// Input and output prices per 1M tokens in USD, if known
+pub fn pricing(provider: &str, model: &str) -> Option<(f64, f64)> {
+	let entry = lookup(provider, model)?;
+	Some((entry.input_price?, entry.output_price?))
+}
+
+/// Whether a model accepts image input
+pub fn supports_vision(provider: &str, model: &str) -> bool {
+	lookup(provider, model).is_some_and(|entry| entry.vision)
+}
+
+/// Model patterns of the built-in table for a provider
+pub fn known_models(provider: &str) -> Vec<&'static str> {
+	BUILTIN_MODELS
+		.iter()
+		.filter(|entry| entry.provider == provider)
+		.map(|entry| entry.model.as_str())
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_builtin_table_parses() {
+		assert!(!BUILTIN_MODELS.is_empty());
+		assert!(BUILTIN_MODELS.iter().all(|entry| entry.context_window > 0));
+	}
+
+	#[test]
+	fn test_longest_pattern_wins() {
+		// "gpt-4o-mini" also contains "gpt-4o" and "gpt-4"
+		assert_eq!(pricing("openai", "gpt-4o-mini"), Some((0.15, 0.60)));
+		assert_eq!(context_window("openai", "gpt-4"), 8_192);
+		assert_eq!(context_window("openai", "gpt-4-turbo"), 128_000);
+		assert!(supports_vision("openai", "gpt-4-vision-preview"));
+		assert!(!supports_vision("openai", "gpt-4"));
+	}
+
+	#[test]
+	fn test_unknown_model_falls_back() {
+		assert!(lookup("anthropic", "mystery-model").is_none());
+		assert_eq!(
+			context_window("anthropic", "mystery-model"),
+			DEFAULT_CONTEXT_WINDOW
+		);
+		assert_eq!(pricing("anthropic", "mystery-model"), None);
+	}
+
+	#[test]
+	fn test_best_match_on_custom_entries() {
+		let entry = |model: &str, context_window: usize| ModelMetadata {
+			provider: "custom".to_string(),
+			model: model.to_string(),
+			context_window,
+			max_output: None,
+			input_price: None,
+			output_price: None,
+			vision: false,
+		};
+		let entries = vec![entry("qwen", 32_768), entry("qwen3-coder", 262_144)];
+
+		let matched = best_match(&entries, "custom", "qwen/qwen3-coder-plus").unwrap();
+		assert_eq!(matched.context_window, 262_144);
+		assert!(best_match(&entries, "openrouter", "qwen/qwen3-coder").is_none());
+	}
+}
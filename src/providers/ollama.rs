@@ -0,0 +1,457 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Ollama provider implementation (local models)
+
+use super::{
+	model_metadata, AiProvider, ModelInfo, ProviderExchange, ProviderResponse, TokenUsage,
+};
+use crate::config::Config;
+use crate::log_debug;
+use crate::session::Message;
+use anyhow::Result;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+// Constants
+const OLLAMA_HOST_ENV: &str = "OLLAMA_HOST";
+const OLLAMA_CONTEXT_WINDOW_ENV: &str = "OLLAMA_CONTEXT_WINDOW";
+const OLLAMA_DEFAULT_HOST: &str = "http://localhost:11434";
+// Ollama does not reliably advertise model context windows, so use a conservative default
+const OLLAMA_DEFAULT_CONTEXT_WINDOW: usize = 8_192;
+
+/// Ollama provider implementation
+pub struct OllamaProvider;
+
+impl Default for OllamaProvider {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl OllamaProvider {
+	pub fn new() -> Self {
+		Self
+	}
+
+	/// Get the Ollama chat endpoint, honoring OLLAMA_HOST if set
+	fn get_api_url(&self) -> String {
+		format!("{}/api/chat", self.get_host())
+	}
+
+	/// Get the Ollama server base URL from OLLAMA_HOST or the default
+	fn get_host(&self) -> String {
+		let host = env::var(OLLAMA_HOST_ENV)
+			.ok()
+			.filter(|h| !h.trim().is_empty())
+			.unwrap_or_else(|| OLLAMA_DEFAULT_HOST.to_string());
+
+		// OLLAMA_HOST is commonly set without a scheme (e.g. "0.0.0.0:11434")
+		let host = if host.starts_with("http://") || host.starts_with("https://") {
+			host
+		} else {
+			format!("http://{}", host)
+		};
+
+		host.trim_end_matches('/').to_string()
+	}
+
+	/// Get the configured context window from OLLAMA_CONTEXT_WINDOW or fall back to the default
+	fn get_context_window(&self) -> usize {
+		env::var(OLLAMA_CONTEXT_WINDOW_ENV)
+			.ok()
+			.and_then(|v| v.trim().parse::<usize>().ok())
+			.filter(|v| *v > 0)
+			.unwrap_or(OLLAMA_DEFAULT_CONTEXT_WINDOW)
+	}
+}
+
+/// Message format for the Ollama chat API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaMessage {
+	pub role: String,
+	pub content: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub images: Option<Vec<String>>, // Base64 encoded images without data URL prefix
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub tool_calls: Option<serde_json::Value>, // For assistant messages: array of tool calls
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub tool_name: Option<String>, // For tool messages: the name of the tool
+}
+
+#[async_trait::async_trait]
+impl AiProvider for OllamaProvider {
+	fn name(&self) -> &str {
+		"ollama"
+	}
+
+	fn supports_model(&self, model: &str) -> bool {
+		// Any locally pulled model can be used - Ollama will report unknown models itself
+		!model.trim().is_empty()
+	}
+
+	fn get_api_key(&self, _config: &Config) -> Result<String> {
+		// Local Ollama server does not require authentication
+		Ok(String::new())
+	}
+
+	fn supports_caching(&self, _model: &str) -> bool {
+		false
+	}
+
+	fn supports_vision(&self, model: &str) -> bool {
+		// Common multimodal models available in the Ollama library
+		model.contains("llava")
+			|| model.contains("bakllava")
+			|| model.contains("vision")
+			|| model.contains("moondream")
+			|| model.contains("minicpm-v")
+			|| model.contains("gemma3")
+	}
+
+	fn estimate_cost(&self, _model: &str, _input_tokens: u64, _output_tokens: u64) -> Option<f64> {
+		// Local inference is free
+		Some(0.0)
+	}
+
+	async fn list_models(&self, config: &Config) -> Result<Vec<ModelInfo>> {
+		// Locally pulled models; Ollama does not report context windows here. Bounded by
+		// api_timeout_seconds so an unreachable host cannot hang /model list
+		let url = format!("{}/api/tags", self.get_host());
+		let response =
+			super::send_request(self.name(), Client::new().get(&url), config, None).await?;
+		if !response.status().is_success() {
+			return Err(anyhow::anyhow!(
+				"Ollama models request failed: {}",
+				response.status()
+			));
+		}
+
+		let body: serde_json::Value = response.json().await?;
+		let mut models: Vec<ModelInfo> = body["models"]
+			.as_array()
+			.map(|data| {
+				data.iter()
+					.filter_map(|model| model["name"].as_str())
+					.map(|name| ModelInfo {
+						id: name.to_string(),
+						context_window: None,
+					})
+					.collect()
+			})
+			.unwrap_or_default();
+		models.sort_by(|a, b| a.id.cmp(&b.id));
+		Ok(models)
+	}
+
+	fn get_max_input_tokens(&self, model: &str) -> usize {
+		// Local models are not in the built-in table, but users can describe them in config
+		model_metadata::lookup(self.name(), model)
+			.map(|metadata| metadata.context_window)
+			.unwrap_or_else(|| self.get_context_window())
+	}
+
+	async fn chat_completion(
+		&self,
+		messages: &[Message],
+		model: &str,
+		temperature: f32,
+		config: &Config,
+		cancellation_token: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+	) -> Result<ProviderResponse> {
+		// Check for cancellation before starting
+		if let Some(ref token) = cancellation_token {
+			if token.load(std::sync::atomic::Ordering::SeqCst) {
+				return Err(anyhow::anyhow!("Request cancelled before starting"));
+			}
+		}
+
+		// Convert messages to Ollama format
+		let ollama_messages = convert_messages(messages);
+
+		// Create the request body - non-streaming so we get a single JSON response
+		let mut request_body = serde_json::json!({
+			"model": model,
+			"messages": ollama_messages,
+			"stream": false,
+			"options": {
+				"temperature": temperature,
+				"num_ctx": self.get_context_window(),
+			},
+		});
+
+		if let Some(stop) = super::stop_sequences_for_provider(config, self.name(), None) {
+			request_body["options"]["stop"] = serde_json::json!(stop);
+		}
+
+		// Add tool definitions if MCP has any servers configured
+		if !config.mcp.servers.is_empty() {
+			let functions = crate::mcp::get_available_functions(config).await;
+			if !functions.is_empty() {
+				// Sort functions by name to guarantee consistent ordering across API calls
+				let mut sorted_functions = functions;
+				sorted_functions.sort_by(|a, b| a.name.cmp(&b.name));
+
+				let tools = sorted_functions
+					.iter()
+					.map(|f| {
+						serde_json::json!({
+							"type": "function",
+							"function": {
+								"name": f.name,
+								"description": f.description,
+								"parameters": f.parameters
+							}
+						})
+					})
+					.collect::<Vec<_>>();
+
+				request_body["tools"] = serde_json::json!(tools);
+			}
+		}
+
+		// Create HTTP client
+		let client = Client::new();
+
+		// Track API request time
+		let api_start = std::time::Instant::now();
+
+		// Make the actual API request
+		let request = client
+			.post(self.get_api_url())
+			.header("Content-Type", "application/json")
+			.json(&request_body);
+		let response =
+			super::send_request(self.name(), request, config, cancellation_token.clone())
+				.await
+				.map_err(|e| {
+					if super::is_timeout_error(&e) {
+						e
+					} else {
+						anyhow::anyhow!(
+							"Failed to reach Ollama at {}: {}. Is 'ollama serve' running?",
+							self.get_api_url(),
+							e
+						)
+					}
+				})?;
+
+		// Calculate API request time
+		let api_time_ms = api_start.elapsed().as_millis() as u64;
+
+		// Get response status
+		let status = response.status();
+
+		// Get response body as text first for debugging
+		let response_text = response.text().await?;
+
+		// Parse the text to JSON
+		let mut response_json: serde_json::Value = match serde_json::from_str(&response_text) {
+			Ok(json) => json,
+			Err(e) => {
+				return Err(anyhow::anyhow!(
+					"Failed to parse response JSON: {}. Response: {}",
+					e,
+					response_text
+				));
+			}
+		};
+
+		// Handle error responses - Ollama returns {"error": "..."} on failure
+		if !status.is_success() || response_json.get("error").is_some() {
+			let message = response_json
+				.get("error")
+				.and_then(|e| e.as_str())
+				.unwrap_or(&response_text);
+			return Err(anyhow::anyhow!(
+				"Ollama API error: HTTP {} | Message: {}",
+				status,
+				message
+			));
+		}
+
+		let message = response_json.get("message").cloned().ok_or_else(|| {
+			anyhow::anyhow!("Invalid response format from Ollama: {}", response_text)
+		})?;
+
+		// Extract content
+		let content = message
+			.get("content")
+			.and_then(|c| c.as_str())
+			.unwrap_or("")
+			.to_string();
+
+		// Extract finish reason
+		let finish_reason = response_json
+			.get("done_reason")
+			.and_then(|r| r.as_str())
+			.map(|s| s.to_string());
+
+		if let Some(ref reason) = finish_reason {
+			log_debug!("Finish reason: {}", reason);
+		}
+
+		// Extract tool calls - models without function calling simply won't return any,
+		// in which case we fall back to a plain text response
+		let tool_calls = if let Some(tool_calls_val) = message.get("tool_calls") {
+			let calls = tool_calls_val.as_array().cloned().unwrap_or_default();
+			let mut extracted_tool_calls = Vec::new();
+
+			for tool_call in &calls {
+				if let Some(function) = tool_call.get("function") {
+					if let Some(name) = function.get("name").and_then(|n| n.as_str()) {
+						// Ollama returns arguments as an object, but some models emit a JSON string
+						let params = match function.get("arguments") {
+							Some(serde_json::Value::String(args)) => {
+								if args.trim().is_empty() {
+									serde_json::json!({})
+								} else {
+									serde_json::from_str::<serde_json::Value>(args)
+										.unwrap_or_else(|_| serde_json::Value::String(args.clone()))
+								}
+							}
+							Some(args) => args.clone(),
+							None => serde_json::json!({}),
+						};
+
+						let tool_id = tool_call.get("id").and_then(|i| i.as_str()).unwrap_or("");
+						extracted_tool_calls.push(crate::mcp::McpToolCall {
+							tool_name: name.to_string(),
+							parameters: params,
+							tool_id: tool_id.to_string(),
+						});
+					}
+				}
+			}
+
+			if extracted_tool_calls.is_empty() {
+				None
+			} else {
+				crate::mcp::ensure_tool_call_ids(&mut extracted_tool_calls);
+
+				// Store tool calls so the assistant message can be reconstructed on the next turn
+				response_json["tool_calls_content"] = serde_json::json!(calls);
+				Some(extracted_tool_calls)
+			}
+		} else {
+			None
+		};
+
+		// Extract token usage - local inference is free
+		let prompt_tokens = response_json
+			.get("prompt_eval_count")
+			.and_then(|v| v.as_u64())
+			.unwrap_or(0);
+		let output_tokens = response_json
+			.get("eval_count")
+			.and_then(|v| v.as_u64())
+			.unwrap_or(0);
+
+		let usage = Some(TokenUsage {
+			prompt_tokens,
+			output_tokens,
+			total_tokens: prompt_tokens + output_tokens,
+			cached_tokens: 0,
+			cost: Some(0.0),
+			reasoning_tokens: 0,
+			request_time_ms: Some(api_time_ms),
+		});
+
+		// Create exchange record
+		let exchange = ProviderExchange::new(request_body, response_json, usage, self.name());
+
+		Ok(ProviderResponse {
+			content,
+			exchange,
+			tool_calls,
+			finish_reason,
+		})
+	}
+}
+
+// Convert our session messages to Ollama format
+fn convert_messages(messages: &[Message]) -> Vec<OllamaMessage> {
+	let mut result = Vec::new();
+
+	for msg in messages {
+		match msg.role.as_str() {
+			"tool" => {
+				result.push(OllamaMessage {
+					role: "tool".to_string(),
+					content: msg.content.clone(),
+					images: None,
+					tool_calls: None,
+					tool_name: msg.name.clone(),
+				});
+			}
+			"assistant" => {
+				result.push(OllamaMessage {
+					role: "assistant".to_string(),
+					content: msg.content.clone(),
+					images: None,
+					tool_calls: msg.tool_calls.clone(),
+					tool_name: None,
+				});
+			}
+			_ => {
+				// Ollama expects raw base64 image data attached to the message
+				let images = msg.images.as_ref().map(|images| {
+					images
+						.iter()
+						.filter_map(|img| match img.data {
+							crate::session::image::ImageData::Base64(ref data) => {
+								Some(data.clone())
+							}
+							_ => None,
+						})
+						.collect::<Vec<_>>()
+				});
+
+				result.push(OllamaMessage {
+					role: msg.role.clone(),
+					content: msg.content.clone(),
+					images: images.filter(|i| !i.is_empty()),
+					tool_calls: None,
+					tool_name: None,
+				});
+			}
+		}
+	}
+
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_supports_model() {
+		let provider = OllamaProvider::new();
+
+		assert!(provider.supports_model("llama3.1"));
+		assert!(provider.supports_model("qwen2.5-coder:7b"));
+		assert!(!provider.supports_model(""));
+	}
+
+	#[test]
+	fn test_supports_vision() {
+		let provider = OllamaProvider::new();
+
+		assert!(provider.supports_vision("llava"));
+		assert!(provider.supports_vision("llama3.2-vision"));
+		assert!(!provider.supports_vision("llama3.1"));
+	}
+}
@@ -14,7 +14,9 @@
 
 // OpenAI provider implementation
 
-use super::{AiProvider, ProviderExchange, ProviderResponse, TokenUsage};
+use super::{
+	model_metadata, AiProvider, ModelInfo, ProviderExchange, ProviderResponse, TokenUsage,
+};
 use crate::config::Config;
 use crate::log_debug;
 use crate::session::Message;
@@ -23,59 +25,17 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::env;
 
-/// OpenAI pricing constants (per 1M tokens in USD)
-/// Source: https://platform.openai.com/docs/pricing (as of January 2025)
-const PRICING: &[(&str, f64, f64)] = &[
-	// Model, Input price per 1M tokens, Output price per 1M tokens
-	// GPT-4o models
-	("gpt-4o", 2.50, 10.00),
-	("gpt-4o-mini", 0.15, 0.60),
-	("gpt-4o-2024-11-20", 2.50, 10.00),
-	("gpt-4o-2024-08-06", 2.50, 10.00),
-	("gpt-4o-2024-05-13", 5.00, 15.00),
-	("chatgpt-4o-latest", 2.50, 10.00),
-	// O-series reasoning models
-	("o4", 25.00, 100.00),    // Latest O4 model
-	("o3", 20.00, 80.00),     // O3 model
-	("o3-mini", 5.00, 20.00), // O3 mini variant
-	("o1", 15.00, 60.00),
-	("o1-preview", 15.00, 60.00),
-	("o1-mini", 3.00, 12.00),
-	// GPT-4.5 models (newest series)
-	("gpt-4.5-turbo", 6.00, 20.00),
-	("gpt-4.5", 20.00, 40.00),
-	("gpt-4.5-preview", 6.00, 20.00),
-	// GPT-4.1 models (newer series)
-	("gpt-4.1-turbo", 8.00, 25.00),
-	("gpt-4.1", 25.00, 50.00),
-	("gpt-4.1-preview", 8.00, 25.00),
-	// GPT-4 Turbo models
-	("gpt-4-turbo", 10.00, 30.00),
-	("gpt-4-turbo-2024-04-09", 10.00, 30.00),
-	("gpt-4-0125-preview", 10.00, 30.00),
-	("gpt-4-1106-preview", 10.00, 30.00),
-	// GPT-4 models
-	("gpt-4", 30.00, 60.00),
-	("gpt-4-0613", 30.00, 60.00),
-	("gpt-4-0314", 30.00, 60.00),
-	// GPT-3.5 Turbo models
-	("gpt-3.5-turbo", 0.50, 1.50),
-	("gpt-3.5-turbo-0125", 0.50, 1.50),
-	("gpt-3.5-turbo-1106", 1.00, 2.00),
-];
-
 /// Calculate cost for OpenAI models with basic pricing
 fn calculate_cost(model: &str, prompt_tokens: u64, completion_tokens: u64) -> Option<f64> {
-	for (pricing_model, input_price, output_price) in PRICING {
-		if model.contains(pricing_model) {
-			let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
-			let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
-			return Some(input_cost + output_cost);
-		}
-	}
-	None
+	let (input_price, output_price) = model_metadata::pricing("openai", model)?;
+	let input_cost = (prompt_tokens as f64 / 1_000_000.0) * input_price;
+	let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
+	Some(input_cost + output_cost)
 }
 
+/// Cache reads are billed at this fraction of the normal input price
+const CACHE_READ_PRICE_MULTIPLIER: f64 = 0.25;
+
 /// Calculate cost for OpenAI models with cache-aware pricing
 /// - cache_read_tokens: charged at 0.25x normal price (75% cheaper)
 /// - regular_input_tokens: charged at normal price (includes cache write tokens)
@@ -86,21 +46,19 @@ fn calculate_cost_with_cache(
 	cache_read_tokens: u64,
 	completion_tokens: u64,
 ) -> Option<f64> {
-	for (pricing_model, input_price, output_price) in PRICING {
-		if model.contains(pricing_model) {
-			// Regular input tokens at normal price (includes cache write - no additional cost)
-			let regular_input_cost = (regular_input_tokens as f64 / 1_000_000.0) * input_price;
+	let (input_price, output_price) = model_metadata::pricing("openai", model)?;
 
-			// Cache read tokens at 0.25x price (75% cheaper)
-			let cache_read_cost = (cache_read_tokens as f64 / 1_000_000.0) * input_price * 0.25;
+	// Regular input tokens at normal price (includes cache write - no additional cost)
+	let regular_input_cost = (regular_input_tokens as f64 / 1_000_000.0) * input_price;
 
-			// Output tokens at normal price (never cached)
-			let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
+	// Cache read tokens at 0.25x price (75% cheaper)
+	let cache_read_cost =
+		(cache_read_tokens as f64 / 1_000_000.0) * input_price * CACHE_READ_PRICE_MULTIPLIER;
 
-			return Some(regular_input_cost + cache_read_cost + output_cost);
-		}
-	}
-	None
+	// Output tokens at normal price (never cached)
+	let output_cost = (completion_tokens as f64 / 1_000_000.0) * output_price;
+
+	Some(regular_input_cost + cache_read_cost + output_cost)
 }
 
 /// Check if a model supports the temperature parameter
@@ -130,6 +88,7 @@ impl OpenAiProvider {
 // Constants
 const OPENAI_API_KEY_ENV: &str = "OPENAI_API_KEY";
 const OPENAI_API_URL: &str = "https://api.openai.com/v1/chat/completions";
+const OPENAI_MODELS_URL: &str = "https://api.openai.com/v1/models";
 
 /// Message format for the OpenAI API
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -181,39 +140,60 @@ impl AiProvider for OpenAiProvider {
 	}
 
 	fn supports_vision(&self, model: &str) -> bool {
-		// OpenAI vision-capable models
-		model.contains("gpt-4o")
-			|| model.contains("gpt-4.1")
-			|| model.contains("gpt-4-turbo")
-			|| model.contains("gpt-4-vision-preview")
-			|| model.starts_with("gpt-4o-")
+		model_metadata::supports_vision(self.name(), model)
 	}
 
-	fn get_max_input_tokens(&self, model: &str) -> usize {
-		// OpenAI model context window limits (what we can send as input)
-		// These are the actual context windows - API handles output limits
+	fn supports_documents(&self, model: &str) -> bool {
+		// PDF input is available on the same models that accept images
+		self.supports_vision(model)
+	}
 
-		// GPT-4o models: 128K context window
-		if model.contains("gpt-4o") {
-			return 128_000;
-		}
-		// GPT-4 models: varies by version
-		if model.contains("gpt-4-turbo") || model.contains("gpt-4.5") || model.contains("gpt-4.1") {
-			return 128_000;
-		}
-		if model.contains("gpt-4") && !model.contains("gpt-4o") {
-			return 8_192; // Old GPT-4: 8K context window
-		}
-		// O-series models: 128K context window
-		if model.starts_with("o1") || model.starts_with("o2") || model.starts_with("o3") {
-			return 128_000;
-		}
-		// GPT-3.5: 16K context window
-		if model.contains("gpt-3.5") {
-			return 16_384;
+	fn estimate_cost(&self, model: &str, input_tokens: u64, output_tokens: u64) -> Option<f64> {
+		calculate_cost(model, input_tokens, output_tokens)
+	}
+
+	fn estimate_cache_savings(&self, model: &str, cached_tokens: u64) -> Option<f64> {
+		model_metadata::pricing(self.name(), model).map(|(input_price, _)| {
+			(cached_tokens as f64 / 1_000_000.0) * input_price * (1.0 - CACHE_READ_PRICE_MULTIPLIER)
+		})
+	}
+
+	async fn list_models(&self, config: &Config) -> Result<Vec<ModelInfo>> {
+		let api_key = self.get_api_key(config)?;
+		// Bounded by api_timeout_seconds so /model list cannot hang
+		let request = Client::new()
+			.get(OPENAI_MODELS_URL)
+			.header("Authorization", format!("Bearer {}", api_key));
+		let response = super::send_request(self.name(), request, config, None).await?;
+		if !response.status().is_success() {
+			return Err(anyhow::anyhow!(
+				"OpenAI models request failed: {}",
+				response.status()
+			));
 		}
-		// Default conservative limit
-		8_192
+
+		// The endpoint also lists embedding, audio and image models - keep chat models only
+		let body: serde_json::Value = response.json().await?;
+		let mut models: Vec<ModelInfo> = body["data"]
+			.as_array()
+			.map(|data| {
+				data.iter()
+					.filter_map(|model| model["id"].as_str())
+					.filter(|id| self.supports_model(id))
+					.map(|id| ModelInfo {
+						id: id.to_string(),
+						context_window: model_metadata::lookup(self.name(), id)
+							.map(|metadata| metadata.context_window),
+					})
+					.collect()
+			})
+			.unwrap_or_default();
+		models.sort_by(|a, b| a.id.cmp(&b.id));
+		Ok(models)
+	}
+
+	fn get_max_input_tokens(&self, model: &str) -> usize {
+		model_metadata::context_window(self.name(), model)
 	}
 
 	async fn chat_completion(
@@ -246,6 +226,22 @@ impl AiProvider for OpenAiProvider {
 		// O1/O2 series models don't support temperature parameter
 		if supports_temperature(model) {
 			request_body["temperature"] = serde_json::json!(temperature);
+		} else if let Some(effort) = config.reasoning_effort {
+			// Reasoning models take an effort level instead of a temperature
+			request_body["reasoning_effort"] = serde_json::json!(effort.level());
+		}
+
+		// Fixed seed for best-effort reproducible sampling
+		if let Some(seed) = config.seed {
+			request_body["seed"] = serde_json::json!(seed);
+		}
+
+		// Reasoning models reject the stop parameter, like temperature
+		let stop_limit = if supports_temperature(model) { 4 } else { 0 };
+		if let Some(stop) =
+			super::stop_sequences_for_provider(config, self.name(), Some(stop_limit))
+		{
+			request_body["stop"] = serde_json::json!(stop);
 		}
 
 		// Add tool definitions if MCP has any servers configured
@@ -260,14 +256,10 @@ impl AiProvider for OpenAiProvider {
 				let tools = sorted_functions
 					.iter()
 					.map(|f| {
-						serde_json::json!({
-								"type": "function",
-								"function": {
-								"name": f.name,
-								"description": f.description,
-								"parameters": f.parameters
-							}
-						})
+						crate::mcp::strict_schema::function_tool_definition(
+							f,
+							config.strict_tool_schemas,
+						)
 					})
 					.collect::<Vec<_>>();
 
@@ -292,13 +284,13 @@ impl AiProvider for OpenAiProvider {
 		let api_start = std::time::Instant::now();
 
 		// Make the actual API request
-		let response = client
+		let request = client
 			.post(OPENAI_API_URL)
 			.header("Authorization", format!("Bearer {}", api_key))
 			.header("Content-Type", "application/json")
-			.json(&request_body)
-			.send()
-			.await?;
+			.json(&request_body);
+		let response =
+			super::send_request(self.name(), request, config, cancellation_token.clone()).await?;
 
 		// Calculate API request time
 		let api_duration = api_start.elapsed();
@@ -398,7 +390,7 @@ impl AiProvider for OpenAiProvider {
 							function.get("name").and_then(|n| n.as_str()),
 							function.get("arguments").and_then(|a| a.as_str()),
 						) {
-							let params = if args.trim().is_empty() {
+							let mut params = if args.trim().is_empty() {
 								serde_json::json!({})
 							} else {
 								match serde_json::from_str::<serde_json::Value>(args) {
@@ -406,6 +398,9 @@ impl AiProvider for OpenAiProvider {
 									Err(_) => serde_json::Value::String(args.to_string()),
 								}
 							};
+							if config.strict_tool_schemas {
+								crate::mcp::strict_schema::strip_null_arguments(name, &mut params);
+							}
 
 							let tool_id =
 								tool_call.get("id").and_then(|i| i.as_str()).unwrap_or("");
@@ -473,11 +468,19 @@ impl AiProvider for OpenAiProvider {
 			// Simple interface: only expose cached tokens (OpenAI only has cache reads, no extra cost for writes)
 			let cached_tokens = cache_read_tokens;
 
+			// Reasoning models report their hidden reasoning as part of completion_tokens
+			let reasoning_tokens = usage_obj
+				.get("completion_tokens_details")
+				.and_then(|details| details.get("reasoning_tokens"))
+				.and_then(|v| v.as_u64())
+				.unwrap_or(0);
+
 			Some(TokenUsage {
 				prompt_tokens,
 				output_tokens: completion_tokens,
 				total_tokens,
-				cached_tokens,                      // Simple: total tokens that came from cache
+				cached_tokens, // Simple: total tokens that came from cache
+				reasoning_tokens,
 				cost,                               // Pre-calculated with proper cache pricing
 				request_time_ms: Some(api_time_ms), // Track API timing for OpenAI
 			})
@@ -584,8 +587,8 @@ fn convert_messages(messages: &[Message]) -> Vec<OpenAiMessage> {
 		}
 
 		// Regular messages - handle both text and images
-		if msg.role == "user" && msg.images.is_some() {
-			// User message with images - use multimodal format
+		if msg.role == "user" && (msg.images.is_some() || msg.attachments.is_some()) {
+			// User message with images or documents - use multimodal format
 			let mut content_parts = Vec::new();
 
 			// Add text content if not empty
@@ -610,6 +613,9 @@ fn convert_messages(messages: &[Message]) -> Vec<OpenAiMessage> {
 				}
 			}
 
+			// Add PDF attachments as file inputs
+			content_parts.extend(file_content_parts(msg));
+
 			result.push(OpenAiMessage {
 				role: msg.role.clone(),
 				content: serde_json::json!(content_parts),
@@ -632,6 +638,26 @@ fn convert_messages(messages: &[Message]) -> Vec<OpenAiMessage> {
 	result
 }
 
+// PDF attachments as "file" content parts (shared with OpenRouter, which uses the same format)
+pub(crate) fn file_content_parts(msg: &Message) -> Vec<serde_json::Value> {
+	msg.attachments
+		.iter()
+		.flatten()
+		.filter_map(|attachment| match &attachment.content {
+			crate::session::attachment::AttachmentContent::Document(data) => {
+				Some(serde_json::json!({
+					"type": "file",
+					"file": {
+						"filename": attachment.file_name,
+						"file_data": format!("data:{};base64,{}", attachment.media_type, data)
+					}
+				}))
+			}
+			crate::session::attachment::AttachmentContent::Text(_) => None,
+		})
+		.collect()
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -14,8 +14,10 @@
 
 // OpenRouter provider implementation
 
-use super::{AiProvider, ProviderExchange, ProviderResponse, TokenUsage};
-use crate::config::Config;
+use super::{
+	model_metadata, AiProvider, ModelInfo, ProviderExchange, ProviderResponse, TokenUsage,
+};
+use crate::config::{Config, ReasoningEffort};
 use crate::log_debug;
 use crate::session::Message;
 use anyhow::Result;
@@ -34,6 +36,8 @@ struct ResponseProcessingContext<'a> {
 	request_body: &'a serde_json::Value,
 	response_text: &'a str,
 	config: &'a Config,
+	// Estimated size of the tool definitions marked for caching, if they were
+	tool_cache_segment: Option<u64>,
 }
 
 // Global HTTP client with optimized settings - PERFORMANCE BEAST! 🔥
@@ -44,7 +48,6 @@ fn get_optimized_client() -> &'static Client {
 		Client::builder()
 			.pool_max_idle_per_host(10) // Keep connections alive
 			.pool_idle_timeout(std::time::Duration::from_secs(90)) // Connection reuse
-			.timeout(std::time::Duration::from_secs(300)) // 5 min timeout
 			.build()
 			.expect("Failed to create optimized HTTP client")
 	})
@@ -68,6 +71,7 @@ impl OpenRouterProvider {
 // Constants
 const OPENROUTER_API_KEY_ENV: &str = "OPENROUTER_API_KEY";
 const OPENROUTER_API_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
+const OPENROUTER_MODELS_URL: &str = "https://openrouter.ai/api/v1/models";
 
 /// Message format for the OpenRouter API
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -117,51 +121,46 @@ impl AiProvider for OpenRouterProvider {
 	}
 
 	fn supports_vision(&self, model: &str) -> bool {
-		// OpenRouter supports vision through various models
-		model.contains("gpt-4o")
-			|| model.contains("gpt-4.1")
-			|| model.contains("gpt-4-vision")
-			|| model.contains("gpt-4-turbo")
-			|| model.contains("claude-3")
-			|| model.contains("claude-sonnet-4")
-			|| model.contains("claude-opus-4")
-			|| model.contains("gemini")
-			|| model.contains("llama-3.2-vision")
-			|| model.contains("pixtral")
+		model_metadata::supports_vision(self.name(), model)
 	}
 
-	fn get_max_input_tokens(&self, model: &str) -> usize {
-		// OpenRouter model input limits depend on underlying provider
-		// Claude models through OpenRouter: 200K total context
-		if model.contains("claude") {
-			return 200_000 - 32_768; // Reserve 32K for output = ~167K input max
-		}
-		// GPT models through OpenRouter: varies by model
-		if model.contains("gpt-4o") {
-			return 128_000 - 4_096;
-		}
-		if model.contains("gpt-4") {
-			return 128_000 - 4_096;
-		}
-		if model.contains("gpt-3.5") {
-			return 16_384 - 2_048;
-		}
-		// Gemini models through OpenRouter
-		if model.contains("gemini-2.5") {
-			return 2_000_000 - 8_192;
-		}
-		if model.contains("gemini-2.0") || model.contains("gemini-1.5") {
-			return 1_000_000 - 8_192;
-		}
-		if model.contains("gemini") {
-			return 32_768 - 2_048;
-		}
-		// Llama models through OpenRouter: typically 128K
-		if model.contains("llama") {
-			return 128_000 - 4_096;
+	fn supports_documents(&self, model: &str) -> bool {
+		// PDF input is available on the same models that accept images
+		self.supports_vision(model)
+	}
+
+	async fn list_models(&self, config: &Config) -> Result<Vec<ModelInfo>> {
+		// The models endpoint is public, no API key required; bounded by api_timeout_seconds
+		// so /model list cannot hang
+		let request = get_optimized_client().get(OPENROUTER_MODELS_URL);
+		let response = super::send_request(self.name(), request, config, None).await?;
+		if !response.status().is_success() {
+			return Err(anyhow::anyhow!(
+				"OpenRouter models request failed: {}",
+				response.status()
+			));
 		}
-		// Default conservative limit for unknown models
-		32_768 - 2_048
+
+		let body: serde_json::Value = response.json().await?;
+		let mut models: Vec<ModelInfo> = body["data"]
+			.as_array()
+			.map(|data| {
+				data.iter()
+					.filter_map(|model| {
+						Some(ModelInfo {
+							id: model["id"].as_str()?.to_string(),
+							context_window: model["context_length"].as_u64().map(|n| n as usize),
+						})
+					})
+					.collect()
+			})
+			.unwrap_or_default();
+		models.sort_by(|a, b| a.id.cmp(&b.id));
+		Ok(models)
+	}
+
+	fn get_max_input_tokens(&self, model: &str) -> usize {
+		model_metadata::context_window(self.name(), model)
 	}
 
 	async fn chat_completion(
@@ -182,13 +181,9 @@ impl AiProvider for OpenRouterProvider {
 		// Get API key
 		let api_key = self.get_api_key(config)?;
 
-		// Convert messages to OpenRouter format
-		let openrouter_messages = convert_messages(messages, config);
-
 		// Create the request body
 		let mut request_body = serde_json::json!({
 			"model": model,
-			"messages": openrouter_messages,
 			"temperature": temperature,
 			"top_p": 0.3,
 			"repetition_penalty": 1.1,
@@ -209,7 +204,26 @@ impl AiProvider for OpenRouterProvider {
 			},
 		});
 
+		// Fixed seed, forwarded to the underlying providers that support it
+		if let Some(seed) = config.seed {
+			request_body["seed"] = serde_json::json!(seed);
+		}
+
+		// Clamped to OpenAI's limit, the lowest among the providers OpenRouter routes to
+		if let Some(stop) = super::stop_sequences_for_provider(config, self.name(), Some(4)) {
+			request_body["stop"] = serde_json::json!(stop);
+		}
+
+		// OpenRouter normalizes reasoning settings across models and ignores them for the rest
+		if let Some(effort) = config.reasoning_effort {
+			request_body["reasoning"] = match effort {
+				ReasoningEffort::Level(_) => serde_json::json!({ "effort": effort.level() }),
+				ReasoningEffort::Budget(tokens) => serde_json::json!({ "max_tokens": tokens }),
+			};
+		}
+
 		// Add tool definitions if MCP has any servers configured
+		let mut tool_cache_segment = None;
 		if !config.mcp.servers.is_empty() {
 			let functions = crate::mcp::get_available_functions(config).await;
 			if !functions.is_empty() {
@@ -221,14 +235,10 @@ impl AiProvider for OpenRouterProvider {
 				let mut tools = sorted_functions
 					.iter()
 					.map(|f| {
-						serde_json::json!({
-								"type": "function",
-								"function": {
-								"name": f.name,
-								"description": f.description,
-								"parameters": f.parameters
-							}
-						})
+						crate::mcp::strict_schema::function_tool_definition(
+							f,
+							config.strict_tool_schemas,
+						)
 					})
 					.collect::<Vec<_>>();
 
@@ -237,23 +247,12 @@ impl AiProvider for OpenRouterProvider {
 				// in our MCP setup and cause different tool arrays between Anthropic and OpenRouter,
 				// breaking cache effectiveness. Only use tools from MCP configuration.
 
-				// CRITICAL FIX: Cache control should be handled consistently
-				// Add cache control to the LAST tool definition ONLY if the model supports caching
-				// and we actually want to cache tool definitions (check session state)
-				if self.supports_caching(model) && !tools.is_empty() {
-					// Check if any system message is cached - if so, we should cache tool definitions too
-					let system_cached = messages
-						.iter()
-						.any(|msg| msg.role == "system" && msg.cached);
-
-					if system_cached {
-						if let Some(last_tool) = tools.last_mut() {
-							last_tool["cache_control"] = serde_json::json!({
-								"type": "ephemeral",
-								"ttl": "1h"
-							});
-						}
-					}
+				// Tool definitions get their own cache breakpoint, independent of the system
+				// prompt, so they stay cached while the system prompt or history changes
+				if self.supports_caching(model) {
+					tool_cache_segment = crate::session::cache::mark_tool_definitions_for_caching(
+						&mut tools, config, model,
+					);
 				}
 
 				request_body["tools"] = serde_json::json!(tools);
@@ -261,6 +260,12 @@ impl AiProvider for OpenRouterProvider {
 			}
 		}
 
+		// Convert messages to OpenRouter format, keeping the total number of cache
+		// breakpoints (system, tools and content markers) within the provider limit
+		let messages =
+			crate::session::cache::limit_cache_breakpoints(messages, tool_cache_segment.is_some());
+		request_body["messages"] = serde_json::json!(convert_messages(&messages, config));
+
 		// Check for cancellation before making HTTP request
 		if let Some(ref token) = cancellation_token {
 			if token.load(std::sync::atomic::Ordering::SeqCst) {
@@ -275,37 +280,17 @@ impl AiProvider for OpenRouterProvider {
 		let api_start = std::time::Instant::now();
 
 		// Create the HTTP request
-		let request_future = client
+		let request = client
 			.post(OPENROUTER_API_URL)
 			.header("Authorization", format!("Bearer {}", api_key))
 			.header("Content-Type", "application/json")
 			.header("HTTP-Referer", "https://github.com/muvon/octomind")
 			.header("X-Title", "Octomind")
-			.json(&request_body)
-			.send();
-
-		// Race the HTTP request against cancellation
-		let response = if let Some(ref token) = cancellation_token {
-			let cancellation_future = async {
-				loop {
-					if token.load(std::sync::atomic::Ordering::SeqCst) {
-						break;
-					}
-					tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
-				}
-			};
+			.json(&request_body);
 
-			tokio::select! {
-				result = request_future => {
-					result?
-				}
-				_ = cancellation_future => {
-					return Err(anyhow::anyhow!("Request cancelled during HTTP call"));
-				}
-			}
-		} else {
-			request_future.await?
-		};
+		// Send with timeout, racing the HTTP request against cancellation
+		let response =
+			super::send_request(self.name(), request, config, cancellation_token.clone()).await?;
 
 		// Calculate API request time
 		let api_duration = api_start.elapsed();
@@ -349,6 +334,7 @@ impl AiProvider for OpenRouterProvider {
 			request_body: &request_body,
 			response_text: &response_text,
 			config,
+			tool_cache_segment,
 		})
 		.await
 	}
@@ -512,7 +498,7 @@ impl OpenRouterProvider {
 							function.get("name").and_then(|n| n.as_str()),
 							function.get("arguments").and_then(|a| a.as_str()),
 						) {
-							let params = if args.trim().is_empty() {
+							let mut params = if args.trim().is_empty() {
 								serde_json::json!({})
 							} else {
 								match serde_json::from_str::<serde_json::Value>(args) {
@@ -520,6 +506,9 @@ impl OpenRouterProvider {
 									Err(_) => serde_json::Value::String(args.to_string()),
 								}
 							};
+							if ctx.config.strict_tool_schemas {
+								crate::mcp::strict_schema::strip_null_arguments(name, &mut params);
+							}
 
 							let tool_id =
 								tool_call.get("id").and_then(|i| i.as_str()).unwrap_or("");
@@ -589,12 +578,25 @@ impl OpenRouterProvider {
 				.and_then(|details| details.get("cached_tokens"))
 				.and_then(|v| v.as_u64())
 				.unwrap_or(0);
+			if let Some(segment_tokens) = ctx.tool_cache_segment {
+				crate::session::cache::record_tool_definition_cache_usage(
+					segment_tokens,
+					cached_tokens,
+				);
+			}
+
+			let reasoning_tokens = usage_obj
+				.get("completion_tokens_details")
+				.and_then(|details| details.get("reasoning_tokens"))
+				.and_then(|v| v.as_u64())
+				.unwrap_or(0);
 
 			Some(TokenUsage {
 				prompt_tokens,
 				output_tokens: completion_tokens,
 				total_tokens,
 				cached_tokens, // OpenRouter provides cached token information
+				reasoning_tokens,
 				cost,
 				request_time_ms: Some(ctx.api_time_ms),
 			})
@@ -773,8 +775,8 @@ fn convert_messages(messages: &[Message], config: &Config) -> Vec<OpenRouterMess
 					}
 				}
 
-				// Handle user messages with images - use OpenAI/OpenRouter multimodal format
-				if msg.images.is_some() {
+				// Handle user messages with images or documents - use OpenAI/OpenRouter multimodal format
+				if msg.images.is_some() || msg.attachments.is_some() {
 					let mut content_parts = Vec::new();
 
 					// Add text content if not empty
@@ -809,6 +811,9 @@ fn convert_messages(messages: &[Message], config: &Config) -> Vec<OpenRouterMess
 						}
 					}
 
+					// Add PDF attachments as file inputs
+					content_parts.extend(super::openai::file_content_parts(msg));
+
 					result.push(OpenRouterMessage {
 						role: msg.role.clone(),
 						content: serde_json::json!(content_parts),
@@ -0,0 +1,227 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Client-side per-provider rate limiting using token buckets
+
+use crate::config::{Config, RateLimitConfig};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+lazy_static::lazy_static! {
+	// Buckets are shared across the whole process so layers, agents and the main
+	// session all draw from the same provider budget
+	static ref PROVIDER_BUCKETS: Mutex<HashMap<String, ProviderBuckets>> = Mutex::new(HashMap::new());
+}
+
+/// A single token bucket that refills continuously over one minute
+#[derive(Debug, Clone)]
+struct TokenBucket {
+	capacity: f64,
+	available: f64,
+	last_refill: Instant,
+}
+
+impl TokenBucket {
+	fn new(per_minute: f64, now: Instant) -> Self {
+		Self {
+			capacity: per_minute,
+			available: per_minute,
+			last_refill: now,
+		}
+	}
+
+	fn refill(&mut self, now: Instant) {
+		let elapsed = now
+			.saturating_duration_since(self.last_refill)
+			.as_secs_f64();
+		self.available = (self.available + elapsed * self.capacity / 60.0).min(self.capacity);
+		self.last_refill = now;
+	}
+
+	/// Time until `amount` becomes available (zero if it already is)
+	fn wait_time(&self, amount: f64) -> Duration {
+		if self.available >= amount {
+			Duration::ZERO
+		} else {
+			Duration::from_secs_f64((amount - self.available) * 60.0 / self.capacity)
+		}
+	}
+}
+
+/// Request and optional token buckets for one provider
+#[derive(Debug, Clone)]
+struct ProviderBuckets {
+	limit: (u32, Option<u64>),
+	requests: TokenBucket,
+	tokens: Option<TokenBucket>,
+}
+
+impl ProviderBuckets {
+	fn new(limit: &RateLimitConfig, now: Instant) -> Self {
+		Self {
+			limit: (limit.requests_per_minute, limit.tokens_per_minute),
+			requests: TokenBucket::new(limit.requests_per_minute as f64, now),
+			tokens: limit
+				.tokens_per_minute
+				.map(|tpm| TokenBucket::new(tpm as f64, now)),
+		}
+	}
+
+	/// Try to take one request and `estimated_tokens` from the buckets.
+	/// Returns how long to wait before retrying if there is not enough capacity.
+	fn try_acquire(&mut self, estimated_tokens: u64, now: Instant) -> Option<Duration> {
+		self.requests.refill(now);
+		let mut wait = self.requests.wait_time(1.0);
+
+		// A single request larger than the whole budget is allowed once the bucket is full,
+		// otherwise it would wait forever
+		let token_cost = self
+			.tokens
+			.as_mut()
+			.map(|bucket| {
+				bucket.refill(now);
+				let cost = (estimated_tokens as f64).min(bucket.capacity);
+				wait = wait.max(bucket.wait_time(cost));
+				cost
+			})
+			.unwrap_or(0.0);
+
+		if !wait.is_zero() {
+			return Some(wait);
+		}
+
+		self.requests.available -= 1.0;
+		if let Some(bucket) = self.tokens.as_mut() {
+			bucket.available -= token_cost;
+		}
+		None
+	}
+}
+
+/// Find the configured rate limit for a provider, if any
+fn find_rate_limit<'a>(config: &'a Config, provider: &str) -> Option<&'a RateLimitConfig> {
+	config
+		.rate_limits
+		.iter()
+		.find(|limit| limit.provider.eq_ignore_ascii_case(provider))
+}
+
+/// Wait until the provider's rate limit allows another request.
+/// Requests are queued rather than rejected; returns an error only when cancelled.
+pub async fn acquire_rate_limit(
+	provider: &str,
+	config: &Config,
+	estimated_tokens: usize,
+	cancellation_token: Option<Arc<AtomicBool>>,
+) -> Result<()> {
+	let limit = match find_rate_limit(config, provider) {
+		Some(limit) => limit,
+		None => return Ok(()),
+	};
+
+	let key = provider.to_lowercase();
+	let mut notified = false;
+
+	loop {
+		let wait = {
+			let mut buckets = PROVIDER_BUCKETS.lock().unwrap();
+			let now = Instant::now();
+			let entry = buckets
+				.entry(key.clone())
+				.or_insert_with(|| ProviderBuckets::new(limit, now));
+
+			// Config may have been reloaded with different limits
+			if entry.limit != (limit.requests_per_minute, limit.tokens_per_minute) {
+				*entry = ProviderBuckets::new(limit, now);
+			}
+
+			match entry.try_acquire(estimated_tokens as u64, now) {
+				Some(wait) => wait,
+				None => return Ok(()),
+			}
+		};
+
+		if !notified {
+			crate::log_info!(
+				"Rate limit reached for {}, waiting {:.1}s before sending request",
+				provider,
+				wait.as_secs_f64()
+			);
+			notified = true;
+		}
+
+		// Sleep in short slices so cancellation is honored promptly
+		let deadline = Instant::now() + wait;
+		while Instant::now() < deadline {
+			if let Some(ref token) = cancellation_token {
+				if token.load(Ordering::SeqCst) {
+					return Err(anyhow::anyhow!(
+						"Request cancelled while waiting for rate limit"
+					));
+				}
+			}
+			let remaining = deadline.saturating_duration_since(Instant::now());
+			tokio::time::sleep(remaining.min(Duration::from_millis(100))).await;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn limit(requests_per_minute: u32, tokens_per_minute: Option<u64>) -> RateLimitConfig {
+		RateLimitConfig {
+			provider: "openai".to_string(),
+			requests_per_minute,
+			tokens_per_minute,
+		}
+	}
+
+	#[test]
+	fn test_request_bucket_refills_over_time() {
+		let start = Instant::now();
+		let mut buckets = ProviderBuckets::new(&limit(2, None), start);
+
+		assert!(buckets.try_acquire(0, start).is_none());
+		assert!(buckets.try_acquire(0, start).is_none());
+
+		// Bucket is empty: one request refills every 30 seconds at 2 rpm
+		let wait = buckets.try_acquire(0, start).unwrap();
+		assert_eq!(wait.as_secs(), 30);
+
+		assert!(buckets
+			.try_acquire(0, start + Duration::from_secs(30))
+			.is_none());
+	}
+
+	#[test]
+	fn test_token_bucket_limits_large_requests() {
+		let start = Instant::now();
+		let mut buckets = ProviderBuckets::new(&limit(100, Some(6000)), start);
+
+		assert!(buckets.try_acquire(5000, start).is_none());
+
+		// 3000 more tokens need 2000 refilled at 100 tokens/second
+		let wait = buckets.try_acquire(3000, start).unwrap();
+		assert_eq!(wait.as_secs(), 20);
+
+		// Requests larger than the whole budget are capped so they can eventually proceed
+		let later = start + Duration::from_secs(60);
+		assert!(buckets.try_acquire(50_000, later).is_none());
+	}
+}
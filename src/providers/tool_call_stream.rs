@@ -0,0 +1,214 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Assembles tool calls whose arguments arrive in fragments while a response streams
+
+use crate::mcp::McpToolCall;
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+
+// One tool call being streamed; the arguments stay raw text until the call completes
+#[derive(Debug, Default)]
+struct PartialToolCall {
+	id: String,
+	name: String,
+	arguments: String,
+}
+
+/// Buffers streamed tool calls per index and turns them into `McpToolCall`s once the
+/// stream reaches a tool-call boundary. Arguments are only parsed when complete, since
+/// fragments are rarely valid JSON on their own.
+#[derive(Debug, Default)]
+pub(crate) struct ToolCallAssembler {
+	calls: BTreeMap<usize, PartialToolCall>,
+}
+
+impl ToolCallAssembler {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Record the start of a tool call (Anthropic `content_block_start` with `tool_use`)
+	pub fn start(&mut self, index: usize, id: &str, name: &str) {
+		let call = self.calls.entry(index).or_default();
+		call.id = id.to_string();
+		call.name = name.to_string();
+	}
+
+	/// Append an argument fragment (Anthropic `input_json_delta.partial_json`)
+	pub fn append_arguments(&mut self, index: usize, fragment: &str) {
+		self.calls
+			.entry(index)
+			.or_default()
+			.arguments
+			.push_str(fragment);
+	}
+
+	/// Apply one OpenAI-compatible `delta.tool_calls[]` entry. The id and name come with
+	/// the first fragment of a call; later fragments only carry the index and arguments.
+	pub fn push_delta(&mut self, delta: &serde_json::Value) -> Result<()> {
+		let index = delta
+			.get("index")
+			.and_then(|i| i.as_u64())
+			.ok_or_else(|| anyhow!("Streamed tool call delta has no index: {}", delta))?
+			as usize;
+		let call = self.calls.entry(index).or_default();
+
+		if let Some(id) = delta.get("id").and_then(|i| i.as_str()) {
+			call.id = id.to_string();
+		}
+		if let Some(function) = delta.get("function") {
+			if let Some(name) = function.get("name").and_then(|n| n.as_str()) {
+				call.name.push_str(name);
+			}
+			if let Some(arguments) = function.get("arguments").and_then(|a| a.as_str()) {
+				call.arguments.push_str(arguments);
+			}
+		}
+		Ok(())
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.calls.is_empty()
+	}
+
+	/// Complete the call at this index (Anthropic `content_block_stop`)
+	pub fn finish_call(
+		&mut self,
+		index: usize,
+		config: &crate::config::Config,
+	) -> Result<McpToolCall> {
+		let call = self
+			.calls
+			.remove(&index)
+			.ok_or_else(|| anyhow!("No streamed tool call at index {}", index))?;
+		let mut tool_call = assemble(index, call, config.strict_tool_schemas)?;
+		crate::mcp::ensure_tool_call_ids(std::slice::from_mut(&mut tool_call));
+		Ok(tool_call)
+	}
+
+	/// Complete every buffered call in stream order (OpenAI `finish_reason: "tool_calls"`)
+	pub fn finish(self, config: &crate::config::Config) -> Result<Vec<McpToolCall>> {
+		let mut tool_calls = self
+			.calls
+			.into_iter()
+			.map(|(index, call)| assemble(index, call, config.strict_tool_schemas))
+			.collect::<Result<Vec<_>>>()?;
+		crate::mcp::ensure_tool_call_ids(&mut tool_calls);
+		Ok(tool_calls)
+	}
+}
+
+// Validate the assembled arguments; unlike complete responses, a stream that ends
+// mid-call leaves truncated JSON, so it is an error rather than a string parameter
+fn assemble(index: usize, call: PartialToolCall, strict_tool_schemas: bool) -> Result<McpToolCall> {
+	if call.name.is_empty() {
+		return Err(anyhow!(
+			"Streamed tool call at index {} ended without a tool name",
+			index
+		));
+	}
+
+	let mut parameters = parse_arguments(&call.arguments).map_err(|e| {
+		anyhow!(
+			"Streamed arguments for tool '{}' are not valid JSON ({}): {}",
+			call.name,
+			e,
+			call.arguments
+		)
+	})?;
+	if strict_tool_schemas {
+		crate::mcp::strict_schema::strip_null_arguments(&call.name, &mut parameters);
+	}
+
+	Ok(McpToolCall {
+		tool_name: call.name,
+		parameters,
+		tool_id: call.id,
+	})
+}
+
+// Tools without parameters stream no argument text at all
+fn parse_arguments(arguments: &str) -> serde_json::Result<serde_json::Value> {
+	if arguments.trim().is_empty() {
+		Ok(serde_json::json!({}))
+	} else {
+		serde_json::from_str(arguments)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn test_assemble_fragments() {
+		let mut assembler = ToolCallAssembler::new();
+		assembler.start(0, "call_1", "shell");
+		for fragment in ["{\"com", "mand\": \"l", "s -la\"}"] {
+			assembler.append_arguments(0, fragment);
+		}
+		let call = assembler.calls.remove(&0).unwrap();
+		let tool_call = assemble(0, call, false).unwrap();
+		assert_eq!(tool_call.tool_name, "shell");
+		assert_eq!(tool_call.tool_id, "call_1");
+		assert_eq!(tool_call.parameters, json!({"command": "ls -la"}));
+
+		// Tools without parameters stream no argument text
+		assembler.start(1, "call_2", "list_files");
+		let call = assembler.calls.remove(&1).unwrap();
+		assert_eq!(assemble(1, call, false).unwrap().parameters, json!({}));
+	}
+
+	#[test]
+	fn test_assemble_rejects_truncated_arguments() {
+		let mut assembler = ToolCallAssembler::new();
+		assembler.start(0, "call_1", "shell");
+		assembler.append_arguments(0, "{\"command\": \"ls");
+		let call = assembler.calls.remove(&0).unwrap();
+		let error = assemble(0, call, false).unwrap_err();
+		assert!(error.to_string().contains("not valid JSON"));
+
+		// Arguments without a name cannot be dispatched
+		assembler.append_arguments(1, "{}");
+		let call = assembler.calls.remove(&1).unwrap();
+		assert!(assemble(1, call, false).is_err());
+	}
+
+	#[test]
+	fn test_push_delta_tracks_calls_per_index() {
+		let mut assembler = ToolCallAssembler::new();
+		let deltas = [
+			json!({"index": 0, "id": "call_a", "function": {"name": "shell", "arguments": ""}}),
+			json!({"index": 1, "id": "call_b", "function": {"name": "text_editor", "arguments": "{\"command\":"}}),
+			json!({"index": 0, "function": {"arguments": "{\"command\": \"pwd\"}"}}),
+			json!({"index": 1, "function": {"arguments": " \"view\"}"}}),
+		];
+		for delta in &deltas {
+			assembler.push_delta(delta).unwrap();
+		}
+
+		let first = &assembler.calls[&0];
+		assert_eq!(
+			(first.id.as_str(), first.name.as_str()),
+			("call_a", "shell")
+		);
+		assert_eq!(first.arguments, "{\"command\": \"pwd\"}");
+		let second = &assembler.calls[&1];
+		assert_eq!(second.arguments, "{\"command\": \"view\"}");
+
+		assert!(assembler.push_delta(&json!({"function": {}})).is_err());
+	}
+}
@@ -0,0 +1,247 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// File attachments (documents and text files) for messages
+
+use crate::providers::AiProvider;
+use crate::session::Message;
+use anyhow::Result;
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::path::Path;
+
+/// Largest PDF accepted (providers cap whole requests at roughly 32MB)
+const MAX_DOCUMENT_SIZE: u64 = 10 * 1024 * 1024;
+/// Largest text file accepted - it is inlined into the prompt
+const MAX_TEXT_SIZE: u64 = 1024 * 1024;
+
+/// File attached to a message
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FileAttachment {
+	pub file_name: String,
+	pub media_type: String,
+	pub content: AttachmentContent,
+	pub size_bytes: u64,
+}
+
+/// Attachment payload: documents are sent through the provider's document API,
+/// text files are inlined into the message
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum AttachmentContent {
+	Document(String), // Base64 encoded
+	Text(String),
+}
+
+impl FileAttachment {
+	/// Load and validate a file for attaching
+	pub fn load_from_path(path: &Path) -> Result<Self> {
+		let file_name = path
+			.file_name()
+			.and_then(|n| n.to_str())
+			.ok_or_else(|| anyhow::anyhow!("Invalid file path: {}", path.display()))?
+			.to_string();
+		let extension = path
+			.extension()
+			.and_then(|e| e.to_str())
+			.unwrap_or_default()
+			.to_lowercase();
+		let media_type = media_type_for_extension(&extension).ok_or_else(|| {
+			anyhow::anyhow!(
+				"Unsupported attachment type '{}'. Supported: pdf, {}",
+				file_name,
+				TEXT_EXTENSIONS.join(", ")
+			)
+		})?;
+
+		let size_bytes = std::fs::metadata(path)?.len();
+		let is_document = media_type == "application/pdf";
+		let max_size = if is_document {
+			MAX_DOCUMENT_SIZE
+		} else {
+			MAX_TEXT_SIZE
+		};
+		if size_bytes > max_size {
+			return Err(anyhow::anyhow!(
+				"Attachment too large: {} is {} KB (max {} KB)",
+				file_name,
+				size_bytes / 1024,
+				max_size / 1024
+			));
+		}
+
+		let bytes = std::fs::read(path)?;
+		let content = if is_document {
+			if !bytes.starts_with(b"%PDF") {
+				return Err(anyhow::anyhow!("{} is not a valid PDF file", file_name));
+			}
+			AttachmentContent::Document(general_purpose::STANDARD.encode(&bytes))
+		} else {
+			AttachmentContent::Text(
+				String::from_utf8(bytes)
+					.map_err(|_| anyhow::anyhow!("{} is not a UTF-8 text file", file_name))?,
+			)
+		};
+
+		Ok(Self {
+			file_name,
+			media_type: media_type.to_string(),
+			content,
+			size_bytes,
+		})
+	}
+
+	/// Text block used when the attachment is inlined into the message
+	pub fn inline_text(&self) -> Option<String> {
+		match &self.content {
+			AttachmentContent::Text(text) => Some(format!(
+				"<attached_file name=\"{}\">\n{}\n</attached_file>",
+				self.file_name, text
+			)),
+			AttachmentContent::Document(_) => None,
+		}
+	}
+}
+
+// Text formats that are inlined as-is
+const TEXT_EXTENSIONS: [&str; 11] = [
+	"txt", "md", "csv", "tsv", "json", "yaml", "yml", "xml", "html", "log", "toml",
+];
+
+fn media_type_for_extension(extension: &str) -> Option<&'static str> {
+	match extension {
+		"pdf" => Some("application/pdf"),
+		"csv" => Some("text/csv"),
+		"tsv" => Some("text/tab-separated-values"),
+		"md" => Some("text/markdown"),
+		"json" => Some("application/json"),
+		"html" => Some("text/html"),
+		"xml" => Some("application/xml"),
+		ext if TEXT_EXTENSIONS.contains(&ext) => Some("text/plain"),
+		_ => None,
+	}
+}
+
+/// Prepare messages with attachments for a provider: text attachments are inlined
+/// into the message content, documents stay attached when the model has a document
+/// API and are rejected otherwise
+pub fn prepare_attachments_for_provider<'a>(
+	messages: &'a [Message],
+	provider: &dyn AiProvider,
+	model: &str,
+) -> Result<Cow<'a, [Message]>> {
+	if messages.iter().all(|msg| msg.attachments.is_none()) {
+		return Ok(Cow::Borrowed(messages));
+	}
+
+	let supports_documents = provider.supports_documents(model);
+	let mut prepared = messages.to_vec();
+	for msg in &mut prepared {
+		let Some(attachments) = msg.attachments.take() else {
+			continue;
+		};
+
+		let mut documents = Vec::new();
+		for attachment in attachments {
+			match attachment.inline_text() {
+				Some(text) => {
+					if !msg.content.is_empty() {
+						msg.content.push_str("\n\n");
+					}
+					msg.content.push_str(&text);
+				}
+				None if supports_documents => documents.push(attachment),
+				None => {
+					return Err(anyhow::anyhow!(
+						"Model {}:{} does not support document attachments ({}). Switch to a model with document support (for example Claude or GPT-4o) with /model, or attach the document's text instead",
+						provider.name(),
+						model,
+						attachment.file_name
+					));
+				}
+			}
+		}
+		if !documents.is_empty() {
+			msg.attachments = Some(documents);
+		}
+	}
+
+	Ok(Cow::Owned(prepared))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn message_with(attachments: Vec<FileAttachment>) -> Message {
+		Message {
+			role: "user".to_string(),
+			content: "Summarize this".to_string(),
+			timestamp: 0,
+			cached: false,
+			tool_call_id: None,
+			name: None,
+			tool_calls: None,
+			images: None,
+			attachments: Some(attachments),
+			pinned: false,
+		}
+	}
+
+	fn attachment(file_name: &str, content: AttachmentContent) -> FileAttachment {
+		FileAttachment {
+			file_name: file_name.to_string(),
+			media_type: "text/csv".to_string(),
+			content,
+			size_bytes: 1,
+		}
+	}
+
+	#[test]
+	fn test_text_attachments_are_inlined() {
+		let provider = crate::providers::ProviderFactory::create_provider("ollama").unwrap();
+		let messages = vec![message_with(vec![attachment(
+			"data.csv",
+			AttachmentContent::Text("a,b\n1,2".to_string()),
+		)])];
+
+		let prepared =
+			prepare_attachments_for_provider(&messages, provider.as_ref(), "llama3").unwrap();
+		assert!(prepared[0].attachments.is_none());
+		assert!(prepared[0]
+			.content
+			.ends_with("<attached_file name=\"data.csv\">\na,b\n1,2\n</attached_file>"));
+	}
+
+	#[test]
+	fn test_documents_need_document_support() {
+		let provider = crate::providers::ProviderFactory::create_provider("ollama").unwrap();
+		let messages = vec![message_with(vec![attachment(
+			"spec.pdf",
+			AttachmentContent::Document("JVBERi0=".to_string()),
+		)])];
+
+		let error = prepare_attachments_for_provider(&messages, provider.as_ref(), "llama3")
+			.unwrap_err()
+			.to_string();
+		assert!(error.contains("does not support document attachments"));
+	}
+
+	#[test]
+	fn test_media_types() {
+		assert_eq!(media_type_for_extension("pdf"), Some("application/pdf"));
+		assert_eq!(media_type_for_extension("yml"), Some("text/plain"));
+		assert_eq!(media_type_for_extension("docx"), None);
+	}
+}
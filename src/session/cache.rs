@@ -19,6 +19,111 @@ use crate::session::chat::format_number;
 use crate::session::{Message, Session};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Smallest tool definition block given its own cache breakpoint; Anthropic does not
+/// cache prefixes shorter than 1024 tokens
+pub const MIN_CACHEABLE_TOOL_TOKENS: u64 = 1024;
+
+/// Anthropic rejects requests with more than 4 `cache_control` blocks
+pub const MAX_CACHE_BREAKPOINTS: usize = 4;
+
+lazy_static::lazy_static! {
+	// Tool definition cache usage for this run, reported by /cache stats
+	static ref TOOL_DEFINITION_CACHE: Mutex<ToolDefinitionCacheStats> =
+		Mutex::new(ToolDefinitionCacheStats::default());
+}
+
+/// Usage of the tool definition cache segment. Providers only report cache totals per
+/// request, but tools come first in the cached prefix, so any cache read includes them.
+#[derive(Debug, Clone, Default)]
+pub struct ToolDefinitionCacheStats {
+	/// Estimated size of the cached tool definitions in the latest request
+	pub segment_tokens: u64,
+	/// Requests sent with the tool definitions marked for caching
+	pub requests: u64,
+	/// Requests that read the tool definitions from the cache
+	pub hits: u64,
+	/// Tool definition tokens read from the cache
+	pub tokens_read: u64,
+}
+
+/// Put a cache breakpoint on the last tool definition so the tools are cached as their
+/// own segment, independent of the system prompt. Returns the segment size when marked.
+pub fn mark_tool_definitions_for_caching(
+	tools: &mut [serde_json::Value],
+	config: &Config,
+	model: &str,
+) -> Option<u64> {
+	if !config.cache_tool_definitions {
+		return None;
+	}
+	mark_last_tool(tools, model)
+}
+
+fn mark_last_tool(tools: &mut [serde_json::Value], model: &str) -> Option<u64> {
+	let segment_tokens =
+		crate::session::estimate_tokens(&serde_json::to_string(tools).ok()?, model) as u64;
+	if segment_tokens < MIN_CACHEABLE_TOOL_TOKENS {
+		return None;
+	}
+
+	// Tools precede the system prompt in the prefix, so the 1h TTL is always allowed here
+	tools.last_mut()?["cache_control"] = serde_json::json!({
+		"type": "ephemeral",
+		"ttl": "1h"
+	});
+	Some(segment_tokens)
+}
+
+/// Keep the request within `MAX_CACHE_BREAKPOINTS`: cached system messages and the tool
+/// breakpoint always stay, and the oldest content markers are dropped to make room.
+pub fn limit_cache_breakpoints(
+	messages: &[Message],
+	tools_marked: bool,
+) -> std::borrow::Cow<'_, [Message]> {
+	let reserved = usize::from(tools_marked)
+		+ messages
+			.iter()
+			.filter(|m| m.cached && m.role == "system")
+			.count();
+	let budget = MAX_CACHE_BREAKPOINTS.saturating_sub(reserved);
+
+	let content_markers: Vec<usize> = messages
+		.iter()
+		.enumerate()
+		.filter(|(_, m)| m.cached && m.role != "system")
+		.map(|(i, _)| i)
+		.collect();
+	if content_markers.len() <= budget {
+		return std::borrow::Cow::Borrowed(messages);
+	}
+
+	let mut limited = messages.to_vec();
+	for &i in &content_markers[..content_markers.len() - budget] {
+		limited[i].cached = false;
+	}
+	std::borrow::Cow::Owned(limited)
+}
+
+/// Record a response to a request whose tool definitions were marked for caching
+pub fn record_tool_definition_cache_usage(segment_tokens: u64, cache_read_tokens: u64) {
+	if let Ok(mut stats) = TOOL_DEFINITION_CACHE.lock() {
+		stats.segment_tokens = segment_tokens;
+		stats.requests += 1;
+		if cache_read_tokens > 0 {
+			stats.hits += 1;
+			stats.tokens_read += cache_read_tokens.min(segment_tokens);
+		}
+	}
+}
+
+pub fn tool_definition_cache_stats() -> ToolDefinitionCacheStats {
+	TOOL_DEFINITION_CACHE
+		.lock()
+		.map(|stats| stats.clone())
+		.unwrap_or_default()
+}
 
 /// Cache marker types to track different caching strategies
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -46,7 +151,7 @@ pub struct CacheMarker {
 
 /// Comprehensive cache management system
 pub struct CacheManager {
-	/// Maximum number of content cache markers allowed (implements 2-marker system)
+	/// Maximum number of content cache markers allowed (oldest is evicted when exceeded)
 	max_content_markers: usize,
 }
 
@@ -63,6 +168,28 @@ impl CacheManager {
 		Self::default()
 	}
 
+	/// Create a cache manager honoring the configured `max_cache_markers`
+	pub fn from_config(config: &Config) -> Self {
+		Self::with_max_markers(config.max_cache_markers)
+	}
+
+	/// Create a cache manager with an explicit content marker limit (minimum 1)
+	pub fn with_max_markers(max_content_markers: usize) -> Self {
+		Self {
+			max_content_markers: max_content_markers.max(1),
+		}
+	}
+
+	/// Create a cache manager from the current thread config, falling back to defaults
+	pub fn from_thread_config() -> Self {
+		crate::config::with_thread_config(Self::from_config).unwrap_or_default()
+	}
+
+	/// Maximum number of content cache markers this manager keeps
+	pub fn max_content_markers(&self) -> usize {
+		self.max_content_markers
+	}
+
 	/// Add automatic cache markers for system messages and tool definitions
 	/// This should be called when preparing messages for API requests
 	/// CRITICAL FIX: This method should only be called during session initialization,
@@ -112,7 +239,7 @@ impl CacheManager {
 		}
 	}
 
-	/// Manage user content cache markers using 2-marker system
+	/// Manage user content cache markers, keeping at most `max_content_markers`
 	/// Returns true if a marker was added/moved, false otherwise
 	pub fn manage_content_cache_markers(
 		&self,
@@ -168,48 +295,20 @@ impl CacheManager {
 			return Ok(false); // Already cached
 		}
 
-		// Implement 2-marker system logic
-		match existing_markers.len().cmp(&self.max_content_markers) {
-			std::cmp::Ordering::Less => {
-				// We have space for another marker
-				if let Some(target_msg) = session.messages.get_mut(target_index) {
-					target_msg.cached = true;
-					return Ok(true);
-				}
-			}
-			std::cmp::Ordering::Equal => {
-				// We're at capacity, move the first marker to the new position
-				if let Some(first_marker_index) = existing_markers.first() {
-					// Remove cache from first marker
-					if let Some(first_msg) = session.messages.get_mut(*first_marker_index) {
-						first_msg.cached = false;
-					}
-					// Add cache to new position
-					if let Some(target_msg) = session.messages.get_mut(target_index) {
-						target_msg.cached = true;
-						return Ok(true);
-					}
-				}
-			}
-			std::cmp::Ordering::Greater => {
-				// This shouldn't happen in normal usage but handle gracefully
-				// Remove excess markers starting from the first
-				while existing_markers.len() > self.max_content_markers {
-					if let Some(first_marker_index) = existing_markers.first() {
-						if let Some(first_msg) = session.messages.get_mut(*first_marker_index) {
-							first_msg.cached = false;
-						}
-						existing_markers.remove(0);
-					}
-				}
-				// Now add the new marker
-				if let Some(target_msg) = session.messages.get_mut(target_index) {
-					target_msg.cached = true;
-					return Ok(true);
-				}
+		// Evict the oldest markers until there is room for the new one
+		while existing_markers.len() >= self.max_content_markers {
+			let oldest_marker_index = existing_markers.remove(0);
+			if let Some(oldest_msg) = session.messages.get_mut(oldest_marker_index) {
+				oldest_msg.cached = false;
 			}
 		}
 
+		// Add the new marker
+		if let Some(target_msg) = session.messages.get_mut(target_index) {
+			target_msg.cached = true;
+			return Ok(true);
+		}
+
 		Ok(false)
 	}
 
@@ -449,23 +548,44 @@ impl CacheManager {
 		let mut content_markers = 0;
 		let mut system_markers = 0;
 		let mut tool_markers = 0;
+		let mut marker_details = Vec::new();
+		// Tokens accumulated since the previous marker - each marker caches this span
+		let mut span_tokens = 0u64;
+
+		for (index, msg) in session.messages.iter().enumerate() {
+			span_tokens +=
+				crate::session::estimate_tokens(&msg.content, &session.info.model) as u64;
 
-		for msg in &session.messages {
 			if msg.cached {
-				match msg.role.as_str() {
-					"system" => system_markers += 1,
-					"user" => content_markers += 1,
+				let is_content_marker = match msg.role.as_str() {
+					"system" => {
+						system_markers += 1;
+						false
+					}
+					"user" => true,
 					"tool" => {
 						// Only count tool RESULTS as content markers, not tool definitions
 						if msg.tool_call_id.is_some() {
-							content_markers += 1;
+							true
 						} else {
 							tool_markers += 1; // Tool definitions go to tool markers
+							false
 						}
 					}
-					"assistant" => content_markers += 1, // Always count assistant messages as content markers
-					_ => {}
+					"assistant" => true, // Always count assistant messages as content markers
+					_ => false,
+				};
+
+				if is_content_marker {
+					content_markers += 1;
 				}
+
+				marker_details.push(CacheMarkerDetail {
+					message_index: index,
+					role: msg.role.clone(),
+					span_tokens,
+				});
+				span_tokens = 0;
 			}
 		}
 
@@ -496,11 +616,17 @@ impl CacheManager {
 				tool_markers = 1; // Tool definitions cached (virtual marker)
 			}
 		}
+		// Tool definitions are also cached on their own once large enough
+		if tool_markers == 0 && tool_definition_cache_stats().requests > 0 {
+			tool_markers = 1;
+		}
 
 		CacheStatistics {
 			content_markers,
 			system_markers,
 			tool_markers,
+			max_content_markers: self.max_content_markers,
+			marker_details,
 			total_cached_tokens: session.info.cached_tokens,
 			total_input_tokens: session.info.input_tokens + session.info.cached_tokens,
 			total_output_tokens: session.info.output_tokens,
@@ -515,6 +641,7 @@ impl CacheManager {
 			} else {
 				0.0
 			},
+			tool_definitions: tool_definition_cache_stats(),
 		}
 	}
 
@@ -560,9 +687,8 @@ impl CacheManager {
 			}
 		}
 
-		// Count existing content cache markers and find first marker to potentially remove
+		// Count existing content cache markers
 		let mut existing_markers: Vec<usize> = Vec::new();
-		let mut first_marker_to_remove: Option<usize> = None;
 
 		for (i, msg) in session.messages.iter().enumerate() {
 			if msg.cached && (msg.role == "user" || msg.role == "tool" || msg.role == "assistant") {
@@ -577,16 +703,12 @@ impl CacheManager {
 			return Ok(false); // Already cached
 		}
 
-		// Determine if we need to remove a marker due to 2-marker limit
-		if existing_markers.len() >= self.max_content_markers {
-			first_marker_to_remove = existing_markers.first().copied();
-		}
-
 		// Apply changes to the session
-		// First remove the old marker if needed
-		if let Some(first_marker_index) = first_marker_to_remove {
-			if let Some(first_msg) = session.messages.get_mut(first_marker_index) {
-				first_msg.cached = false;
+		// First evict the oldest markers if we're at the configured limit
+		while existing_markers.len() >= self.max_content_markers {
+			let oldest_marker_index = existing_markers.remove(0);
+			if let Some(oldest_msg) = session.messages.get_mut(oldest_marker_index) {
+				oldest_msg.cached = false;
 			}
 		}
 
@@ -680,18 +802,29 @@ impl CacheManager {
 	}
 }
 
+/// Details of a single active cache marker
+#[derive(Debug, Clone)]
+pub struct CacheMarkerDetail {
+	pub message_index: usize,
+	pub role: String,
+	pub span_tokens: u64, // Estimated tokens covered since the previous marker
+}
+
 /// Cache statistics for display and monitoring
 #[derive(Debug, Clone)]
 pub struct CacheStatistics {
 	pub content_markers: usize,
 	pub system_markers: usize,
 	pub tool_markers: usize,
+	pub max_content_markers: usize,
+	pub marker_details: Vec<CacheMarkerDetail>,
 	pub total_cached_tokens: u64,
 	pub total_input_tokens: u64,  // Total input tokens (cacheable)
 	pub total_output_tokens: u64, // Total output tokens (not cacheable)
 	pub current_non_cached_tokens: u64,
 	pub current_total_tokens: u64,
 	pub cache_efficiency: f64, // Percentage of INPUT tokens that were cached
+	pub tool_definitions: ToolDefinitionCacheStats,
 }
 
 impl CacheStatistics {
@@ -705,11 +838,20 @@ impl CacheStatistics {
 
 		if self.content_markers > 0 || self.system_markers > 0 || self.tool_markers > 0 {
 			output.push_str(&format!(
-				"Active markers: {} content, {} system, {} tool\n",
+				"Active markers: {} content (max {}), {} system, {} tool\n",
 				self.content_markers.to_string().bright_blue(),
+				self.max_content_markers,
 				self.system_markers.to_string().bright_green(),
 				self.tool_markers.to_string().bright_yellow()
 			));
+			for marker in &self.marker_details {
+				output.push_str(&format!(
+					"  #{} {} - ~{} tokens since previous marker\n",
+					marker.message_index.to_string().bright_white(),
+					marker.role.bright_black(),
+					format_number(marker.span_tokens).bright_cyan()
+				));
+			}
 		} else {
 			output.push_str(&format!("{}\n", "No active cache markers".bright_black()));
 		}
@@ -736,6 +878,17 @@ impl CacheStatistics {
 			));
 		}
 
+		let tools = &self.tool_definitions;
+		if tools.requests > 0 {
+			output.push_str(&format!(
+				"Tool definitions: ~{} tokens cached separately, {}/{} requests hit ({} tokens read)\n",
+				format_number(tools.segment_tokens).bright_yellow(),
+				tools.hits.to_string().bright_green(),
+				tools.requests,
+				format_number(tools.tokens_read).bright_magenta()
+			));
+		}
+
 		// Show session-wide cache efficiency in a clearer way
 		if self.total_input_tokens > 0 {
 			let session_cached_pct =
@@ -776,12 +929,17 @@ mod tests {
 				total_api_time_ms: 0,
 				total_layer_time_ms: 0,
 				total_tool_time_ms: 0,
+				markdown_theme: None,
+				tool_iteration_limit_hits: 0,
+				reasoning_tokens: 0,
+				seed: None,
 			},
 			messages: Vec::new(),
 			session_file: None,
 			current_non_cached_tokens: 0,
 			current_total_tokens: 0,
 			last_cache_checkpoint_time: 0,
+			saved_model: None,
 		}
 	}
 
@@ -824,6 +982,51 @@ mod tests {
 		assert!(session.messages[4].cached); // Third marker added
 	}
 
+	#[test]
+	fn test_configurable_marker_limit() {
+		// A single marker moves to each new position
+		let manager = CacheManager::with_max_markers(1);
+		let mut session = create_test_session();
+		for i in 0..3 {
+			session.add_message("user", &format!("Message {}", i));
+		}
+		for i in 0..3 {
+			let result = manager.manage_content_cache_markers(&mut session, Some(i), false);
+			assert!(result.unwrap());
+		}
+		assert!(!session.messages[0].cached);
+		assert!(!session.messages[1].cached);
+		assert!(session.messages[2].cached);
+		let stats = manager.get_cache_statistics(&session);
+		assert_eq!(stats.content_markers, 1);
+		assert_eq!(stats.max_content_markers, 1);
+
+		// At the upper limit of 2, the oldest marker is evicted
+		let manager = CacheManager::with_max_markers(2);
+		let mut session = create_test_session();
+		for i in 0..3 {
+			session.add_message("user", &format!("Message {}", i));
+		}
+		for i in 0..3 {
+			let result = manager.manage_content_cache_markers(&mut session, Some(i), false);
+			assert!(result.unwrap());
+		}
+		assert!(!session.messages[0].cached);
+		assert!(session.messages[1].cached);
+		assert!(session.messages[2].cached);
+
+		// Marker details report every active marker
+		let stats = manager.get_cache_statistics(&session);
+		assert_eq!(stats.content_markers, 2);
+		assert_eq!(stats.max_content_markers, 2);
+		let indices: Vec<usize> = stats
+			.marker_details
+			.iter()
+			.map(|m| m.message_index)
+			.collect();
+		assert_eq!(indices, vec![1, 2]);
+	}
+
 	#[test]
 	fn test_cache_support_validation() {
 		let manager = CacheManager::new();
@@ -865,6 +1068,8 @@ mod tests {
 				name: None,
 				tool_calls: None,
 				images: None,
+				attachments: None,
+				pinned: false,
 			},
 			Message {
 				role: "user".to_string(),
@@ -875,6 +1080,8 @@ mod tests {
 				name: None,
 				tool_calls: None,
 				images: None,
+				attachments: None,
+				pinned: false,
 			},
 		];
 
@@ -885,4 +1092,55 @@ mod tests {
 		// User message should not be automatically cached
 		assert!(!messages[1].cached);
 	}
+
+	#[test]
+	fn test_tool_definitions_cached_as_own_segment() {
+		let tool = |name: &str, description: String| serde_json::json!({"name": name, "description": description, "input_schema": {}});
+
+		// Too small to be cached by the provider
+		let mut small = vec![tool("shell", "Run a command".to_string())];
+		assert!(mark_last_tool(&mut small, "claude-sonnet-4").is_none());
+		assert!(small[0].get("cache_control").is_none());
+
+		let mut large = vec![
+			tool("shell", "run the command ".repeat(400)),
+			tool("text_editor", "edit the file ".repeat(400)),
+		];
+		let segment_tokens = mark_last_tool(&mut large, "claude-sonnet-4").unwrap();
+		assert!(segment_tokens >= MIN_CACHEABLE_TOOL_TOKENS);
+		assert!(large[0].get("cache_control").is_none());
+		assert_eq!(large[1]["cache_control"]["type"], "ephemeral");
+	}
+	#[test]
+	fn test_cache_breakpoints_never_exceed_limit() {
+		let message = |role: &str, cached: bool| Message {
+			role: role.to_string(),
+			content: "content".to_string(),
+			timestamp: 0,
+			cached,
+			tool_call_id: None,
+			name: None,
+			tool_calls: None,
+			images: None,
+			attachments: None,
+			pinned: false,
+		};
+		let messages = vec![
+			message("system", true),
+			message("user", true),
+			message("assistant", true),
+			message("user", true),
+		];
+
+		// System + 3 content markers fit without the tool breakpoint
+		assert!(matches!(
+			limit_cache_breakpoints(&messages, false),
+			std::borrow::Cow::Borrowed(_)
+		));
+
+		// With tools marked, the oldest content marker is dropped
+		let limited = limit_cache_breakpoints(&messages, true);
+		let cached: Vec<bool> = limited.iter().map(|m| m.cached).collect();
+		assert_eq!(cached, vec![true, false, true, true]);
+	}
 }
@@ -151,6 +151,7 @@ pub async fn execute_command_layer(
 			&effective_model,
 			usage.prompt_tokens,
 			usage.output_tokens,
+			usage.cached_tokens,
 			cost,
 		);
 
@@ -37,8 +37,18 @@ pub const MCP_COMMAND: &str = "/mcp";
 pub const REPORT_COMMAND: &str = "/report";
 pub const IMAGE_COMMAND: &str = "/image";
 pub const CONTEXT_COMMAND: &str = "/context";
+pub const BRANCH_COMMAND: &str = "/branch";
+pub const FORK_FROM_COMMAND: &str = "/fork-from";
+pub const EXPORT_COMMAND: &str = "/export";
+pub const DIFF_COMMAND: &str = "/diff";
+pub const THEME_COMMAND: &str = "/theme";
+pub const COMPACT_COMMAND: &str = "/compact";
+pub const RETRY_COMMAND: &str = "/retry";
+pub const ATTACH_COMMAND: &str = "/attach";
+pub const PIN_COMMAND: &str = "/pin";
+pub const TOKENS_COMMAND: &str = "/tokens";
 // List of all available commands for autocomplete
-pub const COMMANDS: [&str; 22] = [
+pub const COMMANDS: [&str; 32] = [
 	HELP_COMMAND,
 	HELP_COMMAND_ALT,
 	EXIT_COMMAND,
@@ -61,4 +71,14 @@ pub const COMMANDS: [&str; 22] = [
 	REPORT_COMMAND,
 	IMAGE_COMMAND,
 	CONTEXT_COMMAND,
+	BRANCH_COMMAND,
+	FORK_FROM_COMMAND,
+	EXPORT_COMMAND,
+	DIFF_COMMAND,
+	THEME_COMMAND,
+	COMPACT_COMMAND,
+	RETRY_COMMAND,
+	ATTACH_COMMAND,
+	PIN_COMMAND,
+	TOKENS_COMMAND,
 ];
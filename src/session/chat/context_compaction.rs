@@ -0,0 +1,149 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tool result compaction - collapse repeated identical tool outputs to save context
+
+use crate::session::chat::session::ChatSession;
+use crate::session::Message;
+use anyhow::Result;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// Result of a compaction pass
+pub struct CompactionStats {
+	pub collapsed: usize,
+	pub tokens_saved: usize,
+}
+
+/// Replace all but the most recent copy of each identical tool result with a short placeholder.
+/// Messages are never removed and tool_call_id values are untouched, so every tool call keeps its result.
+pub fn collapse_duplicate_tool_results(messages: &mut [Message]) -> usize {
+	// (tool name, content hash) -> tool_call_id of the newest copy
+	let mut newest: HashMap<(String, u64), String> = HashMap::new();
+	let mut collapsed = 0;
+
+	for msg in messages.iter_mut().rev() {
+		if msg.role != "tool" {
+			continue;
+		}
+
+		let name = msg.name.clone().unwrap_or_default();
+		let key = (name.clone(), content_hash(&msg.content));
+		let kept_id = match newest.get(&key) {
+			Some(kept_id) => kept_id.clone(),
+			None => {
+				newest.insert(key, msg.tool_call_id.clone().unwrap_or_default());
+				continue;
+			}
+		};
+
+		let placeholder = format!(
+			"[Identical to the later {} result with tool_call_id {}; content removed to save context]",
+			name, kept_id
+		);
+		if placeholder.len() < msg.content.len() {
+			msg.content = placeholder;
+			collapsed += 1;
+		}
+	}
+
+	collapsed
+}
+
+/// Run the compaction pass on the session and save it when anything changed
+pub fn compact_tool_results(chat_session: &mut ChatSession) -> Result<CompactionStats> {
+	let tokens_before = crate::session::estimate_message_tokens(
+		&chat_session.session.messages,
+		&chat_session.model,
+	);
+	let collapsed = collapse_duplicate_tool_results(&mut chat_session.session.messages);
+	let tokens_after = crate::session::estimate_message_tokens(
+		&chat_session.session.messages,
+		&chat_session.model,
+	);
+
+	if collapsed > 0 {
+		chat_session.save()?;
+	}
+
+	Ok(CompactionStats {
+		collapsed,
+		tokens_saved: tokens_before.saturating_sub(tokens_after),
+	})
+}
+
+fn content_hash(content: &str) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	content.hash(&mut hasher);
+	hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn message(
+		role: &str,
+		content: &str,
+		name: Option<&str>,
+		tool_call_id: Option<&str>,
+	) -> Message {
+		Message {
+			role: role.to_string(),
+			content: content.to_string(),
+			timestamp: 0,
+			cached: false,
+			tool_call_id: tool_call_id.map(str::to_string),
+			name: name.map(str::to_string),
+			tool_calls: None,
+			images: None,
+			attachments: None,
+			pinned: false,
+		}
+	}
+
+	#[test]
+	fn test_collapses_all_but_newest_identical_result() {
+		let file = "fn main() {}\n".repeat(50);
+		let mut messages = vec![
+			message("user", "look at main.rs", None, None),
+			message("tool", &file, Some("text_editor"), Some("call_1")),
+			message("tool", "other output", Some("shell"), Some("call_2")),
+			message("tool", &file, Some("text_editor"), Some("call_3")),
+			message("tool", &file, Some("text_editor"), Some("call_4")),
+		];
+
+		assert_eq!(collapse_duplicate_tool_results(&mut messages), 2);
+
+		assert!(messages[1].content.contains("call_4"));
+		assert!(messages[3].content.contains("call_4"));
+		assert_eq!(messages[4].content, file);
+		assert_eq!(messages[2].content, "other output");
+		// Ids stay paired with their tool calls
+		assert_eq!(messages[1].tool_call_id.as_deref(), Some("call_1"));
+		assert_eq!(messages[3].tool_call_id.as_deref(), Some("call_3"));
+	}
+
+	#[test]
+	fn test_same_content_from_different_tools_is_kept() {
+		let output = "x".repeat(500);
+		let mut messages = vec![
+			message("tool", &output, Some("shell"), Some("call_1")),
+			message("tool", &output, Some("text_editor"), Some("call_2")),
+		];
+
+		assert_eq!(collapse_duplicate_tool_results(&mut messages), 0);
+	}
+}
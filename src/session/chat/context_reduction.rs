@@ -135,6 +135,15 @@ pub async fn perform_context_reduction(
 				.find(|m| m.role == "system")
 				.cloned();
 
+			// Pinned messages survive the reset verbatim
+			let pinned_messages: Vec<_> = chat_session
+				.session
+				.messages
+				.iter()
+				.filter(|m| m.role != "system" && m.pinned)
+				.cloned()
+				.collect();
+
 			// Clear all messages
 			chat_session.session.messages.clear();
 
@@ -142,6 +151,7 @@ pub async fn perform_context_reduction(
 			if let Some(system) = system_message {
 				chat_session.session.messages.push(system);
 			}
+			chat_session.session.messages.extend(pinned_messages);
 
 			// Add the summary as an assistant message (this is our new context)
 			chat_session
@@ -21,6 +21,7 @@ use crate::session::SmartSummarizer;
 use anyhow::Result;
 use colored::Colorize;
 use regex::Regex;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
@@ -345,6 +346,112 @@ impl ContentCompressor {
 	}
 }
 
+// Tool call ids declared by an assistant message
+fn tool_call_ids(msg: &crate::session::Message) -> Vec<String> {
+	msg.tool_calls
+		.as_ref()
+		.and_then(|value| value.as_array())
+		.map(|calls| {
+			calls
+				.iter()
+				.filter_map(|call| call.get("id").and_then(|v| v.as_str()))
+				.map(|id| id.to_string())
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+/// Positions of messages belonging to an incomplete tool-call pair: tool results whose
+/// assistant tool_call is missing, and assistant tool_call messages missing any of their
+/// results (together with the results they do have). Pairs are kept or dropped as a unit.
+fn incomplete_tool_pair_positions(messages: &[crate::session::Message]) -> HashSet<usize> {
+	let mut call_owner: HashMap<String, usize> = HashMap::new();
+	for (i, msg) in messages.iter().enumerate() {
+		if msg.role == "assistant" {
+			for id in tool_call_ids(msg) {
+				call_owner.insert(id, i);
+			}
+		}
+	}
+
+	let answered: HashSet<&str> = messages
+		.iter()
+		.filter(|msg| msg.role == "tool")
+		.filter_map(|msg| msg.tool_call_id.as_deref())
+		.collect();
+
+	let mut incomplete = HashSet::new();
+	for (i, msg) in messages.iter().enumerate() {
+		if msg.role == "assistant"
+			&& tool_call_ids(msg)
+				.iter()
+				.any(|id| !answered.contains(id.as_str()))
+		{
+			incomplete.insert(i);
+		}
+	}
+
+	for (i, msg) in messages.iter().enumerate() {
+		if msg.role == "tool" {
+			let orphaned = match msg.tool_call_id.as_ref().and_then(|id| call_owner.get(id)) {
+				Some(owner) => incomplete.contains(owner),
+				None => true,
+			};
+			if orphaned {
+				incomplete.insert(i);
+			}
+		}
+	}
+
+	incomplete
+}
+
+/// Tool call ids that have no counterpart: results without a matching assistant tool_call,
+/// or assistant tool_calls without a matching result. Empty for a valid message list.
+pub fn find_dangling_tool_call_ids(messages: &[crate::session::Message]) -> Vec<String> {
+	let declared: HashSet<String> = messages
+		.iter()
+		.filter(|msg| msg.role == "assistant")
+		.flat_map(tool_call_ids)
+		.collect();
+	let answered: HashSet<&str> = messages
+		.iter()
+		.filter(|msg| msg.role == "tool")
+		.filter_map(|msg| msg.tool_call_id.as_deref())
+		.collect();
+
+	let mut dangling = Vec::new();
+	for msg in messages {
+		if msg.role == "tool" {
+			match msg.tool_call_id.as_deref() {
+				Some(id) if declared.contains(id) => {}
+				Some(id) => dangling.push(id.to_string()),
+				None => dangling.push("<missing tool_call_id>".to_string()),
+			}
+		} else if msg.role == "assistant" {
+			dangling.extend(
+				tool_call_ids(msg)
+					.into_iter()
+					.filter(|id| !answered.contains(id.as_str())),
+			);
+		}
+	}
+	dangling
+}
+
+// Refuse to save a message list the provider would reject
+fn ensure_no_dangling_tool_calls(messages: &[crate::session::Message]) -> Result<()> {
+	let dangling = find_dangling_tool_call_ids(messages);
+	if dangling.is_empty() {
+		Ok(())
+	} else {
+		Err(anyhow::anyhow!(
+			"Context reduction left dangling tool call ids: {}",
+			dangling.join(", ")
+		))
+	}
+}
+
 // Perform smart context truncation when token limit is approaching
 pub async fn check_and_truncate_context(
 	chat_session: &mut ChatSession,
@@ -352,13 +459,28 @@ pub async fn check_and_truncate_context(
 	_role: &str,
 	_operation_cancelled: Arc<AtomicBool>,
 ) -> Result<()> {
+	// Cheap lossless pass first: repeated tool results may be all the bloat there is
+	if config.enable_auto_compaction {
+		let stats = super::compact_tool_results(chat_session)?;
+		if stats.collapsed > 0 {
+			log_conditional!(
+				debug: format!("Auto-compaction collapsed {} repeated tool results, reclaimed {} tokens",
+					stats.collapsed, stats.tokens_saved).bright_green(),
+				default: format!("Compacted repeated tool results, reclaimed {} tokens", stats.tokens_saved).bright_green()
+			);
+		}
+	}
+
 	// Check if auto truncation is enabled in config
 	if !config.enable_auto_truncation {
 		return Ok(());
 	}
 
 	// Estimate current token usage
-	let current_tokens = crate::session::estimate_message_tokens(&chat_session.session.messages);
+	let current_tokens = crate::session::estimate_message_tokens(
+		&chat_session.session.messages,
+		&chat_session.model,
+	);
 
 	// If we're under the threshold, nothing to do
 	if current_tokens < config.max_request_tokens_threshold {
@@ -380,6 +502,9 @@ pub async fn perform_smart_truncation(
 		return Ok(()); // Nothing to truncate
 	}
 
+	// Token estimates below use the session model's tokenizer
+	let model = chat_session.model.clone();
+
 	// We need to truncate - inform the user with minimal info
 	log_conditional!(
 		debug: format!("\nℹ️  Message history exceeds configured token limit ({} > {})\nApplying enhanced smart truncation to reduce context size.",
@@ -397,6 +522,7 @@ pub async fn perform_smart_truncation(
 
 	let mut system_message = None;
 	let mut preserved_messages = Vec::new();
+	let mut kept_indices = HashSet::new();
 
 	// Extract system message
 	for msg in &chat_session.session.messages {
@@ -430,10 +556,16 @@ pub async fn perform_smart_truncation(
 
 		for (i, (_, importance)) in message_scores.iter().enumerate() {
 			let original_msg = non_system_messages[i];
-			let compressed_msg = ContentCompressor::compress_message(original_msg, importance);
+			// Pinned messages are kept verbatim
+			let compressed_msg = if original_msg.pinned {
+				original_msg.clone()
+			} else {
+				ContentCompressor::compress_message(original_msg, importance)
+			};
 
-			let original_tokens = crate::session::estimate_tokens(&original_msg.content);
-			let compressed_tokens = crate::session::estimate_tokens(&compressed_msg.content);
+			let original_tokens = crate::session::estimate_tokens(&original_msg.content, &model);
+			let compressed_tokens =
+				crate::session::estimate_tokens(&compressed_msg.content, &model);
 			compression_savings += original_tokens.saturating_sub(compressed_tokens);
 
 			compressed_messages.push(compressed_msg);
@@ -450,7 +582,7 @@ pub async fn perform_smart_truncation(
 		// Calculate how many messages we can keep based on token budget
 		let system_tokens = system_message
 			.as_ref()
-			.map(|msg| crate::session::estimate_tokens(&msg.content))
+			.map(|msg| crate::session::estimate_tokens(&msg.content, &model))
 			.unwrap_or(0);
 
 		let available_tokens = config
@@ -470,12 +602,26 @@ pub async fn perform_smart_truncation(
 		let mut current_token_count = 0usize;
 		let mut selected_indices = std::collections::HashSet::new();
 
+		// Pinned messages are always kept, whatever the budget
+		for (index, msg) in compressed_messages.iter().enumerate() {
+			if msg.pinned {
+				selected_messages.push((index, msg.clone()));
+				selected_indices.insert(index);
+				current_token_count += crate::session::estimate_tokens(&msg.content, &model);
+			}
+		}
+
 		// First pass: Select high-importance messages
+		// Tool-call pairs are left to the second pass, which selects them as units
 		for (original_index, importance) in &message_scores {
+			let msg = &compressed_messages[*original_index];
+			if msg.role == "tool" || msg.tool_calls.is_some() {
+				continue;
+			}
+
 			if importance.total_score > 0.7 {
 				// High importance threshold
-				let msg = &compressed_messages[*original_index];
-				let msg_tokens = crate::session::estimate_tokens(&msg.content);
+				let msg_tokens = crate::session::estimate_tokens(&msg.content, &model);
 
 				if current_token_count + msg_tokens <= target_tokens {
 					selected_messages.push((*original_index, msg.clone()));
@@ -538,7 +684,9 @@ pub async fn perform_smart_truncation(
 				// Calculate total tokens for this sequence
 				let sequence_tokens: usize = sequence_indices
 					.iter()
-					.map(|&idx| crate::session::estimate_tokens(&compressed_messages[idx].content))
+					.map(|&idx| {
+						crate::session::estimate_tokens(&compressed_messages[idx].content, &model)
+					})
 					.sum();
 
 				tool_sequences.push((sequence_indices, sequence_tokens));
@@ -556,7 +704,7 @@ pub async fn perform_smart_truncation(
 			}
 
 			let msg = &compressed_messages[i];
-			let msg_tokens = crate::session::estimate_tokens(&msg.content);
+			let msg_tokens = crate::session::estimate_tokens(&msg.content, &model);
 
 			// Check if this message is part of a tool sequence
 			let mut is_part_of_sequence = false;
@@ -628,94 +776,23 @@ pub async fn perform_smart_truncation(
 
 		// Sort selected messages by original index to maintain chronological order
 		selected_messages.sort_by_key(|(index, _)| *index);
-		preserved_messages = selected_messages.into_iter().map(|(_, msg)| msg).collect();
 
-		// Remove any orphaned tool messages (tool messages without corresponding assistant message with tool_calls)
-		// Build a map of assistant messages and their tool_call_ids in preserved messages
-		let mut preserved_tool_call_map: std::collections::HashMap<String, bool> =
-			std::collections::HashMap::new();
-
-		for msg in &preserved_messages {
-			if msg.role == "assistant" && msg.tool_calls.is_some() {
-				if let Some(tool_calls_value) = &msg.tool_calls {
-					if let Some(tool_calls_array) = tool_calls_value.as_array() {
-						for tool_call in tool_calls_array {
-							if let Some(id) = tool_call.get("id").and_then(|v| v.as_str()) {
-								preserved_tool_call_map.insert(id.to_string(), true);
-							}
-						}
-					}
-				}
+		// Drop any tool-call pair that did not make it in whole, so no tool result
+		// or tool_call is left without its counterpart
+		let (indices, messages): (Vec<usize>, Vec<crate::session::Message>) =
+			selected_messages.into_iter().unzip();
+		let incomplete = incomplete_tool_pair_positions(&messages);
+		for (position, (index, msg)) in indices.into_iter().zip(messages).enumerate() {
+			if !incomplete.contains(&position) {
+				kept_indices.insert(index);
+				preserved_messages.push(msg);
 			}
 		}
 
-		// Remove tool messages that don't have corresponding assistant messages
-		let mut i = 0;
-		while i < preserved_messages.len() {
-			let msg = &preserved_messages[i];
-
-			if msg.role == "tool" {
-				let mut is_orphaned = true;
-
-				// Check if this tool message has a corresponding assistant message
-				if let Some(tool_call_id) = &msg.tool_call_id {
-					if preserved_tool_call_map.contains_key(tool_call_id) {
-						is_orphaned = false;
-					}
-				}
-
-				if is_orphaned {
-					// Remove orphaned tool message
-					preserved_messages.remove(i);
-					continue; // Don't increment i since we removed an element
-				}
-			}
-			i += 1;
-		}
-
-		// Also remove assistant messages with tool_calls if none of their tool results are preserved
-		let mut i = 0;
-		while i < preserved_messages.len() {
-			let msg = &preserved_messages[i];
-
-			if msg.role == "assistant" && msg.tool_calls.is_some() {
-				let mut has_tool_results = false;
-
-				// Check if any tool results for this assistant message are preserved
-				if let Some(tool_calls_value) = &msg.tool_calls {
-					if let Some(tool_calls_array) = tool_calls_value.as_array() {
-						for tool_call in tool_calls_array {
-							if let Some(id) = tool_call.get("id").and_then(|v| v.as_str()) {
-								// Look for tool messages with this tool_call_id
-								for tool_msg in &preserved_messages {
-									if tool_msg.role == "tool"
-										&& tool_msg.tool_call_id.as_ref() == Some(&id.to_string())
-									{
-										has_tool_results = true;
-										break;
-									}
-								}
-								if has_tool_results {
-									break;
-								}
-							}
-						}
-					}
-				}
-
-				// If this assistant message has tool_calls but no tool results are preserved, remove it
-				if !has_tool_results {
-					preserved_messages.remove(i);
-					continue; // Don't increment i since we removed an element
-				}
-			}
-			i += 1;
-		}
-
 		// Recalculate token count after any removals
 		current_token_count = preserved_messages
 			.iter()
-			.map(|msg| crate::session::estimate_tokens(&msg.content))
+			.map(|msg| crate::session::estimate_tokens(&msg.content, &model))
 			.sum();
 
 		log_conditional!(
@@ -744,12 +821,13 @@ pub async fn perform_smart_truncation(
 	if preserved_messages.len() < non_system_messages.len() {
 		let removed_count = non_system_messages.len() - preserved_messages.len();
 
-		// Get the messages that were removed for summarization
+		// Get the messages that were removed for summarization; dropped tool-call
+		// pairs are summarized together with their results
 		let removed_messages: Vec<_> = non_system_messages
 			.iter()
-			.take(removed_count)
-			.cloned()
-			.cloned()
+			.enumerate()
+			.filter(|(i, _)| !kept_indices.contains(i))
+			.map(|(_, msg)| (*msg).clone())
 			.collect();
 
 		// Create smart summary of removed messages
@@ -782,18 +860,24 @@ pub async fn perform_smart_truncation(
 			name: None,
 			tool_calls: None,
 			images: None,
+			attachments: None,
+			pinned: false,
 		};
 		truncated_messages.push(summary_msg);
 	}
 
 	// Add preserved messages
 	truncated_messages.extend(preserved_messages);
+	ensure_no_dangling_tool_calls(&truncated_messages)?;
 
 	// Replace session messages with truncated version
 	chat_session.session.messages = truncated_messages;
 
 	// Calculate and report savings
-	let new_token_count = crate::session::estimate_message_tokens(&chat_session.session.messages);
+	let new_token_count = crate::session::estimate_message_tokens(
+		&chat_session.session.messages,
+		&chat_session.model,
+	);
 	let tokens_saved = current_tokens.saturating_sub(new_token_count);
 
 	log_conditional!(
@@ -827,12 +911,19 @@ pub async fn perform_smart_full_summarization(
 		.find(|m| m.role == "system")
 		.cloned();
 
-	// Get all non-system messages for summarization
+	// Get all non-system messages for summarization; pinned ones are kept as they are
 	let conversation_messages: Vec<_> = chat_session
 		.session
 		.messages
 		.iter()
-		.filter(|m| m.role != "system")
+		.filter(|m| m.role != "system" && !m.pinned)
+		.cloned()
+		.collect();
+	let pinned_messages: Vec<_> = chat_session
+		.session
+		.messages
+		.iter()
+		.filter(|m| m.role != "system" && m.pinned)
 		.cloned()
 		.collect();
 
@@ -864,6 +955,7 @@ pub async fn perform_smart_full_summarization(
 	if let Some(sys_msg) = system_message {
 		new_messages.push(sys_msg);
 	}
+	new_messages.extend(pinned_messages);
 
 	// Add comprehensive summary as assistant message
 	let summary_note = format!(
@@ -883,8 +975,11 @@ pub async fn perform_smart_full_summarization(
 		name: None,
 		tool_calls: None,
 		images: None,
+		attachments: None,
+		pinned: false,
 	};
 	new_messages.push(summary_msg);
+	ensure_no_dangling_tool_calls(&new_messages)?;
 
 	// Replace session messages with summarized version
 	let original_count = chat_session.session.messages.len();
@@ -913,6 +1008,7 @@ pub async fn perform_smart_full_summarization(
 
 #[cfg(test)]
 mod tests {
+	use super::{find_dangling_tool_call_ids, incomplete_tool_pair_positions};
 	use crate::session::Message;
 	use serde_json::json;
 
@@ -932,12 +1028,14 @@ mod tests {
 			name,
 			tool_calls,
 			images: None,
+			attachments: None,
+			pinned: false,
 		}
 	}
 
 	#[test]
 	fn test_tool_sequence_identification() {
-		let messages = vec![
+		let messages = [
 			create_test_message("user", "Hello", None, None, None),
 			create_test_message(
 				"assistant",
@@ -1103,4 +1201,70 @@ mod tests {
 		assert_eq!(messages[1].role, "tool");
 		assert_eq!(messages[1].tool_call_id, Some("call_123".to_string()));
 	}
+
+	#[test]
+	fn test_truncation_never_orphans_tool_results() {
+		// A selection where the assistant call for call_1 was dropped and only one of
+		// the two results for the parallel call_2/call_3 made it in
+		let selected = vec![
+			create_test_message(
+				"tool",
+				"Result 1",
+				None,
+				Some("call_1".to_string()),
+				Some("view".to_string()),
+			),
+			create_test_message("user", "Now check both files", None, None, None),
+			create_test_message(
+				"assistant",
+				"Checking",
+				Some(json!([
+					{"id": "call_2", "type": "function", "function": {"name": "view"}},
+					{"id": "call_3", "type": "function", "function": {"name": "view"}}
+				])),
+				None,
+				None,
+			),
+			create_test_message(
+				"tool",
+				"Result 2",
+				None,
+				Some("call_2".to_string()),
+				Some("view".to_string()),
+			),
+			create_test_message(
+				"assistant",
+				"Running",
+				Some(json!([{"id": "call_4", "type": "function", "function": {"name": "shell"}}])),
+				None,
+				None,
+			),
+			create_test_message(
+				"tool",
+				"Result 4",
+				None,
+				Some("call_4".to_string()),
+				Some("shell".to_string()),
+			),
+		];
+
+		let mut dangling = find_dangling_tool_call_ids(&selected);
+		dangling.sort();
+		assert_eq!(dangling, vec!["call_1", "call_3"]);
+
+		let incomplete = incomplete_tool_pair_positions(&selected);
+		let kept: Vec<Message> = selected
+			.into_iter()
+			.enumerate()
+			.filter(|(i, _)| !incomplete.contains(i))
+			.map(|(_, msg)| msg)
+			.collect();
+
+		// The orphaned result and the partial call_2/call_3 pair go as a unit
+		assert_eq!(kept.len(), 3);
+		assert_eq!(kept[0].role, "user");
+		assert_eq!(kept[1].content, "Running");
+		assert_eq!(kept[2].tool_call_id, Some("call_4".to_string()));
+		assert!(find_dangling_tool_call_ids(&kept).is_empty());
+	}
 }
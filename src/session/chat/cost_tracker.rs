@@ -38,6 +38,7 @@ impl CostTracker {
 			if let Some(api_time_ms) = usage.request_time_ms {
 				chat_session.session.info.total_api_time_ms += api_time_ms;
 			}
+			chat_session.session.info.reasoning_tokens += usage.reasoning_tokens;
 
 			// Update session token counts using cache manager
 			let cache_manager = crate::session::cache::CacheManager::new();
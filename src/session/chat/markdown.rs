@@ -595,8 +595,7 @@ impl MarkdownRenderer {
 	}
 
 	fn preprocess_code_blocks(&self, markdown: &str) -> Result<String> {
-		// Regex to match fenced code blocks with optional language specification
-		let code_block_regex = Regex::new(r"```(\w+)?\n([\s\S]*?)\n```")?;
+		let code_block_regex = code_block_regex()?;
 
 		let mut result = String::new();
 		let mut last_end = 0;
@@ -660,7 +659,7 @@ impl MarkdownRenderer {
 
 	fn render_with_syntax_highlighting(&self, markdown: &str) -> Result<()> {
 		// Split markdown by code blocks and process each part separately
-		let code_block_regex = Regex::new(r"```(\w+)?\n([\s\S]*?)\n```")?;
+		let code_block_regex = code_block_regex()?;
 
 		let mut last_end = 0;
 
@@ -718,6 +717,11 @@ impl Default for MarkdownRenderer {
 	}
 }
 
+// Fenced code blocks with optional language tag (diff/patch blocks are colored per line)
+fn code_block_regex() -> Result<Regex> {
+	Ok(Regex::new(r"```(\w+)?\n([\s\S]*?)\n```")?)
+}
+
 // Helper function to check if content looks like markdown
 pub fn is_markdown_content(content: &str) -> bool {
 	// Simple heuristics to detect markdown content
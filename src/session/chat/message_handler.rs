@@ -64,6 +64,8 @@ impl MessageHandler {
 			name: None,
 			tool_calls: original_tool_calls, // Store the original tool_calls for proper reconstruction
 			images: None,
+			attachments: None,
+			pinned: false,
 		};
 
 		// Add the assistant message to the session
@@ -17,6 +17,7 @@ mod animation;
 pub mod assistant_output;
 mod command_executor;
 mod commands;
+mod context_compaction;
 mod context_reduction;
 mod context_truncation;
 mod cost_tracker;
@@ -28,6 +29,7 @@ mod message_handler;
 pub mod response;
 pub mod session;
 mod syntax;
+mod tool_approval;
 mod tool_error_tracker;
 mod tool_processor;
 
@@ -41,6 +43,7 @@ pub use commands::{
 	CACHE_COMMAND, CLEAR_COMMAND, COMMANDS, COPY_COMMAND, DONE_COMMAND, EXIT_COMMAND, HELP_COMMAND,
 	QUIT_COMMAND, RUN_COMMAND, SAVE_COMMAND,
 };
+pub use context_compaction::compact_tool_results;
 pub use context_reduction::perform_context_reduction;
 pub use context_truncation::{
 	check_and_truncate_context, perform_smart_full_summarization, perform_smart_truncation,
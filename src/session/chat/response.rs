@@ -64,7 +64,12 @@ fn handle_final_response(
 		Some(current_exchange)
 	} else {
 		// This is a modified content after tool calls, so costs were already tracked
-		// in the tool response handling code, so pass None to avoid double counting
+		// in the tool response handling code, so pass None to avoid double counting.
+		// The follow-up exchange still belongs in the session log.
+		let _ = crate::session::logger::log_exchange(
+			&chat_session.session.info.name,
+			&current_exchange,
+		);
 		None
 	};
 
@@ -274,6 +279,27 @@ fn resolve_tool_calls(
 	}
 }
 
+// Error results returned instead of executing tools once max_tool_iterations is exceeded
+fn iteration_limit_results(
+	tool_calls: Vec<crate::mcp::McpToolCall>,
+	limit: usize,
+) -> Vec<crate::mcp::McpToolResult> {
+	tool_calls
+		.into_iter()
+		.map(|call| {
+			crate::mcp::McpToolResult::error(
+				call.tool_name,
+				call.tool_id,
+				format!(
+					"Tool iteration limit ({}) reached for this turn; this tool was not executed. \
+					 Do not call more tools - summarize your progress and give your final answer.",
+					limit
+				),
+			)
+		})
+		.collect()
+}
+
 // Helper function to check for cancellation
 fn check_cancellation(operation_cancelled: &Arc<AtomicBool>) -> Result<()> {
 	if operation_cancelled.load(Ordering::SeqCst) {
@@ -311,6 +337,8 @@ fn add_assistant_message_with_tool_calls(
 		name: None,
 		tool_calls: original_tool_calls, // Store the original tool_calls for proper reconstruction
 		images: None,
+		attachments: None,
+		pinned: false,
 	};
 
 	// Add the assistant message to the session
@@ -324,7 +352,7 @@ fn add_assistant_message_with_tool_calls(
 		&chat_session.session.info.name,
 		current_content,
 	);
-	let _ = crate::session::logger::log_raw_exchange(current_exchange);
+	let _ = crate::session::logger::log_exchange(&chat_session.session.info.name, current_exchange);
 
 	Ok(())
 }
@@ -365,6 +393,7 @@ pub async fn process_response(
 	let mut current_content = content.clone();
 	let mut current_exchange = exchange;
 	let mut current_tool_calls_param = tool_calls.clone(); // Track the tool_calls parameter
+	let mut tool_iterations = 0;
 
 	loop {
 		// Check for cancellation at the start of each loop iteration
@@ -400,15 +429,37 @@ pub async fn process_response(
 					return Ok(());
 				}
 
-				// Execute all tool calls in parallel using the new module
-				let (tool_results, total_tool_time_ms) = tool_execution::execute_tools_parallel(
-					current_tool_calls,
-					chat_session,
-					config,
-					&mut tool_processor,
-					operation_cancelled.clone(),
-				)
-				.await?;
+				// Stop executing tools once the per-turn iteration limit is reached; the model
+				// still gets a result for every call so it can wrap up with a final answer
+				let limit_reached =
+					config.max_tool_iterations > 0 && tool_iterations >= config.max_tool_iterations;
+				tool_iterations += 1;
+
+				let (tool_results, total_tool_time_ms) = if limit_reached {
+					println!(
+						"{}",
+						format!(
+							"Tool iteration limit ({}) reached - asking the model to finish this turn",
+							config.max_tool_iterations
+						)
+						.bright_yellow()
+					);
+					chat_session.session.info.tool_iteration_limit_hits += 1;
+					(
+						iteration_limit_results(current_tool_calls, config.max_tool_iterations),
+						0,
+					)
+				} else {
+					// Execute all tool calls in parallel using the new module
+					tool_execution::execute_tools_parallel(
+						current_tool_calls,
+						chat_session,
+						config,
+						&mut tool_processor,
+						operation_cancelled.clone(),
+					)
+					.await?
+				};
 
 				// Final cancellation check after all tools processed
 				if operation_cancelled.load(Ordering::SeqCst) {
@@ -451,6 +502,11 @@ pub async fn process_response(
 						current_exchange = new_exchange;
 						current_tool_calls_param = new_tool_calls;
 
+						// The model was already told to stop - never run another round
+						if limit_reached {
+							break;
+						}
+
 						// Check if there are more tools to process
 						if current_tool_calls_param.is_some()
 							&& !current_tool_calls_param.as_ref().unwrap().is_empty()
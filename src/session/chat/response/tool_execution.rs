@@ -83,6 +83,17 @@ impl ToolExecutionContext<'_> {
 		}
 	}
 
+	/// Remember files modified by text_editor so /diff can scope to them
+	pub fn record_edited_file(&mut self, tool_call: &crate::mcp::McpToolCall) {
+		if let ToolExecutionContext::MainSession { chat_session, .. } = self {
+			if let Some(path) = edited_file_path(tool_call) {
+				if !chat_session.edited_files.contains(&path) {
+					chat_session.edited_files.push(path);
+				}
+			}
+		}
+	}
+
 	/// Handle declined output by removing tool call from conversation
 	pub fn handle_declined_output(&mut self, tool_id: &str) {
 		if let ToolExecutionContext::MainSession { chat_session, .. } = self {
@@ -92,6 +103,24 @@ impl ToolExecutionContext<'_> {
 	}
 }
 
+/// Get the file path a tool call modifies, if it is a text_editor write command
+fn edited_file_path(tool_call: &crate::mcp::McpToolCall) -> Option<String> {
+	if tool_call.tool_name != "text_editor" {
+		return None;
+	}
+
+	let command = tool_call.parameters.get("command")?.as_str()?;
+	match command {
+		"create" | "str_replace" | "regex_replace" | "insert" | "line_replace" | "undo_edit"
+		| "batch_edit" => tool_call
+			.parameters
+			.get("path")
+			.and_then(|p| p.as_str())
+			.map(|p| p.to_string()),
+		_ => None,
+	}
+}
+
 /// Execute all tool calls in parallel and collect results - unified interface
 pub async fn execute_tools_parallel_unified(
 	current_tool_calls: Vec<crate::mcp::McpToolCall>,
@@ -135,18 +164,28 @@ pub async fn execute_tools_parallel(
 	tool_processor: &mut ToolProcessor,
 	operation_cancelled: Arc<AtomicBool>,
 ) -> Result<(Vec<crate::mcp::McpToolResult>, u64)> {
+	// Declined calls are answered with error results instead of running
+	let (approved_tool_calls, declined_results) = super::super::tool_approval::review_tool_calls(
+		current_tool_calls.clone(),
+		config.tool_approval,
+	)?;
+
 	let mut context = ToolExecutionContext::MainSession {
 		chat_session,
 		tool_processor,
 	};
 
 	let result = execute_tools_parallel_unified(
-		current_tool_calls.clone(),
+		approved_tool_calls,
 		&mut context,
 		config,
 		Some(operation_cancelled),
 	)
-	.await;
+	.await
+	.map(|(mut results, total_tool_time_ms)| {
+		results.extend(declined_results);
+		(results, total_tool_time_ms)
+	});
 
 	// CRITICAL FIX: Ensure conversation state integrity after tool execution
 	// Fix the assistant message's tool_calls field to match actual tool results
@@ -172,6 +211,7 @@ async fn execute_tools_parallel_internal(
 	for tool_call in current_tool_calls.clone() {
 		// Increment tool call counter
 		context.increment_tool_calls();
+		context.record_edited_file(&tool_call);
 
 		// CRITICAL FIX: Use the EXACT tool_id from the original API response
 		// Don't generate a new UUID - use the one from the original tool_calls
@@ -191,6 +231,14 @@ async fn execute_tools_parallel_internal(
 			&original_tool_id,
 			&params_clone,
 		);
+		crate::session::event_log::emit_event(
+			"tool_called",
+			serde_json::json!({
+				"tool_name": tool_name,
+				"tool_id": original_tool_id,
+				"args_hash": crate::session::event_log::hash_arguments(&params_clone),
+			}),
+		);
 
 		let tool_id_for_task = original_tool_id.clone();
 		let tool_call_clone = tool_call.clone(); // Clone for async move
@@ -227,7 +275,7 @@ async fn execute_tools_parallel_internal(
 			}
 		};
 
-		tool_tasks.push((tool_name, task, original_tool_id));
+		tool_tasks.push((tool_name, task, original_tool_id, tool_call));
 	}
 
 	// Collect all results and display them cleanly with real-time cancellation feedback
@@ -235,7 +283,7 @@ async fn execute_tools_parallel_internal(
 	let mut _has_error = false;
 	let mut total_tool_time_ms = 0; // Track cumulative tool execution time
 
-	for (tool_name, task, tool_id) in tool_tasks {
+	for (tool_name, task, tool_id, tool_call) in tool_tasks {
 		// IMMEDIATE cancellation check - no delays, no grace periods
 		if operation_cancelled.load(Ordering::SeqCst) {
 			use colored::*;
@@ -305,52 +353,39 @@ async fn execute_tools_parallel_internal(
 						false
 					};
 
-					if loop_detected {
-						// Always show loop detection warning since it's critical
-						if let Some(error_tracker) = context.error_tracker() {
+					// Failed calls become MCP error results correlated with the call, so the
+					// model gets them alongside the successful results of this turn
+					let mut metadata = serde_json::json!({ "tool_name": tool_name });
+					let error = match context.error_tracker() {
+						Some(error_tracker) if loop_detected => {
+							// Always show loop detection warning since it's critical
 							println!("{}", format!("⚠ Warning: {} failed {} times in a row - AI should try a different approach",
 								tool_name, error_tracker.max_consecutive_errors()).bright_yellow());
 
-							// Add a detailed error result for loop detection
-							let loop_error_result = crate::mcp::McpToolResult {
-								tool_name: tool_name.clone(),
-								tool_id: tool_id.clone(),
-								result: serde_json::json!({
-									"error": format!("LOOP DETECTED: Tool '{}' failed {} consecutive times. Last error: {}. Please try a completely different approach or ask the user for guidance.", tool_name, error_tracker.max_consecutive_errors(), e),
-									"tool_name": tool_name,
-									"consecutive_failures": error_tracker.max_consecutive_errors(),
-									"loop_detected": true,
-									"suggestion": "Try a different tool or approach, or ask user for clarification"
-								}),
-							};
-							tool_results.push(loop_error_result);
+							metadata["consecutive_failures"] =
+								serde_json::json!(error_tracker.max_consecutive_errors());
+							metadata["loop_detected"] = serde_json::json!(true);
+							metadata["suggestion"] = serde_json::json!(
+								"Try a different tool or approach, or ask user for clarification"
+							);
+							anyhow::anyhow!("LOOP DETECTED: Tool '{}' failed {} consecutive times. Last error: {}. Please try a completely different approach or ask the user for guidance.", tool_name, error_tracker.max_consecutive_errors(), e)
 						}
-					} else {
-						// Regular error - add normal error result
-						let error_result = if let Some(error_tracker) = context.error_tracker() {
-							crate::mcp::McpToolResult {
-								tool_name: tool_name.clone(),
-								tool_id: tool_id.clone(),
-								result: serde_json::json!({
-									"error": format!("Tool execution failed: {}", e),
-									"tool_name": tool_name,
-									"attempt": error_tracker.get_error_count(&tool_name),
-									"max_attempts": error_tracker.max_consecutive_errors()
-								}),
-							}
-						} else {
-							// For layers without error tracking
-							crate::mcp::McpToolResult {
-								tool_name: tool_name.clone(),
-								tool_id: tool_id.clone(),
-								result: serde_json::json!({
-									"error": format!("Tool execution failed: {}", e),
-									"tool_name": tool_name,
-								}),
-							}
-						};
-						tool_results.push(error_result);
+						Some(error_tracker) => {
+							metadata["attempt"] =
+								serde_json::json!(error_tracker.get_error_count(&tool_name));
+							metadata["max_attempts"] =
+								serde_json::json!(error_tracker.max_consecutive_errors());
+							e
+						}
+						// For layers without error tracking
+						None => e,
+					};
+					let (mut error_result, _) =
+						crate::mcp::tool_result_or_error(&tool_call, Err(error));
+					error_result.result["metadata"] = metadata;
+					tool_results.push(error_result);
 
+					if !loop_detected {
 						if let Some(error_tracker) = context.error_tracker() {
 							log_info!(
 								"Tool '{}' failed {} of {} times. Adding error to context.",
@@ -378,15 +413,14 @@ async fn execute_tools_parallel_internal(
 				println!("✗ Task error for '{}': {}", tool_name, e);
 
 				// ALWAYS add error result for task failures too (unless it was a user decline)
-				let error_result = crate::mcp::McpToolResult {
-					tool_name: tool_name.clone(),
-					tool_id: tool_id.clone(),
-					result: serde_json::json!({
-						"error": format!("Internal task error: {}", e),
-						"tool_name": tool_name,
-						"error_type": "task_failure"
-					}),
-				};
+				let (mut error_result, _) = crate::mcp::tool_result_or_error(
+					&tool_call,
+					Err(anyhow::anyhow!("Internal task error: {}", e)),
+				);
+				error_result.result["metadata"] = serde_json::json!({
+					"tool_name": tool_name,
+					"error_type": "task_failure"
+				});
 				tool_results.push(error_result);
 			}
 		}
@@ -429,6 +463,15 @@ fn display_tool_success(
 	// Log the tool response with session name and timing
 	let _ =
 		crate::session::logger::log_tool_result(session_name, tool_id, &res.result, tool_time_ms);
+	crate::session::event_log::emit_event(
+		"tool_result",
+		serde_json::json!({
+			"tool_name": tool_name,
+			"tool_id": tool_id,
+			"result_bytes": res.result.to_string().len(),
+			"duration_ms": tool_time_ms,
+		}),
+	);
 }
 
 // Display tool output in smart format (for info mode)
@@ -474,6 +517,14 @@ fn display_tool_error(
 
 	// Show error status
 	println!("✗ Tool '{}' failed: {}", tool_name, error);
+	crate::session::event_log::emit_event(
+		"error",
+		serde_json::json!({
+			"stage": "tool",
+			"tool_name": tool_name,
+			"message": error.to_string(),
+		}),
+	);
 }
 
 // Handle user-declined large output (internal implementation)
@@ -78,7 +78,7 @@ pub async fn process_tool_results(
 	// IMPROVED APPROACH: Add tool results as proper "tool" role messages
 	// This follows the standard OpenAI/Anthropic format and avoids double-serialization
 	// CRITICAL FIX: Check cache threshold after EACH tool result, not after all
-	let cache_manager = crate::session::cache::CacheManager::new();
+	let cache_manager = crate::session::cache::CacheManager::from_config(config);
 	let supports_caching = crate::session::model_supports_caching(&chat_session.model);
 
 	let mut cache_check_time = 0u128;
@@ -405,6 +405,7 @@ fn handle_follow_up_cost_tracking(
 		if let Some(api_time_ms) = usage.request_time_ms {
 			chat_session.session.info.total_api_time_ms += api_time_ms;
 		}
+		chat_session.session.info.reasoning_tokens += usage.reasoning_tokens;
 
 		// Update cost
 		if let Some(cost) = usage.cost {
@@ -0,0 +1,45 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Attach command handler - attach PDF and text files to the next message
+
+use super::super::core::ChatSession;
+use anyhow::Result;
+use colored::Colorize;
+
+pub fn handle_attach(session: &mut ChatSession, params: &[&str]) -> Result<bool> {
+	if params.is_empty() {
+		println!("{}", "Usage: /attach <path>".bright_yellow());
+		println!(
+			"{}",
+			"PDFs are sent as documents (Claude, GPT-4o and other vision-capable models); text files (txt, md, csv, tsv, json, yaml, xml, html, log, toml) are inlined for any model".bright_blue()
+		);
+		if !session.pending_attachments.is_empty() {
+			let names: Vec<&str> = session
+				.pending_attachments
+				.iter()
+				.map(|attachment| attachment.file_name.as_str())
+				.collect();
+			println!("Pending: {}", names.join(", "));
+		}
+		return Ok(false);
+	}
+
+	// Paths may contain spaces
+	let path = params.join(" ");
+	if let Err(e) = session.attach_file_from_path(&path) {
+		println!("{}: {}", "Failed to attach file".bright_red(), e);
+	}
+	Ok(false)
+}
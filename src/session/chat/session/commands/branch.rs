@@ -0,0 +1,234 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Branch command handlers - fork the current session, or its first messages, into a new one
+
+use super::super::core::{sanitize_session_name, ChatSession};
+use crate::session::{
+	append_to_session_file, get_sessions_dir, message_to_session_line, Message, SessionInfo,
+};
+use anyhow::Result;
+use colored::Colorize;
+use std::fs::File;
+
+pub fn handle_branch(session: &mut ChatSession, params: &[&str]) -> Result<bool> {
+	// Use the provided name or derive one from the current session
+	let branch_name = if params.is_empty() {
+		format!("{}_branch_{}", session.session.info.name, unix_now())
+	} else {
+		sanitize_session_name(&params.join(" "))
+	};
+
+	let messages = session.session.messages.clone();
+	if !write_branch(session, &branch_name, &messages)? {
+		return Ok(false);
+	}
+
+	println!(
+		"{}",
+		format!(
+			"Branched {} messages into new session: {}",
+			messages.len(),
+			branch_name
+		)
+		.bright_green()
+	);
+
+	// Signal the main loop to switch to the branch, same as /session <name>
+	session.session.info.name = branch_name;
+	Ok(true)
+}
+
+// Fork a new session from the first n messages (numbered as in /context) and switch to it;
+// the current session is left as it is
+pub fn handle_fork_from(session: &mut ChatSession, params: &[&str]) -> Result<bool> {
+	let message_count = session.session.messages.len();
+	let Some(number) = params
+		.first()
+		.and_then(|number| number.parse::<usize>().ok())
+		.filter(|number| (1..=message_count).contains(number))
+	else {
+		if message_count == 0 {
+			println!("{}", "No messages to fork from yet".bright_yellow());
+		} else {
+			println!(
+				"{}",
+				format!(
+					"Usage: /fork-from <number> [name] - number is 1-{} (see /context)",
+					message_count
+				)
+				.bright_yellow()
+			);
+		}
+		return Ok(false);
+	};
+
+	let messages = &session.session.messages[..number];
+	if let Some(problem) = split_tool_exchange(messages, &session.session.messages[number..]) {
+		println!(
+			"{}",
+			format!("Cannot fork from message {}: {}", number, problem).bright_red()
+		);
+		return Ok(false);
+	}
+	let messages = messages.to_vec();
+
+	let branch_name = if params.len() > 1 {
+		sanitize_session_name(&params[1..].join(" "))
+	} else {
+		format!("{}_fork_{}", session.session.info.name, unix_now())
+	};
+	if !write_branch(session, &branch_name, &messages)? {
+		return Ok(false);
+	}
+
+	println!(
+		"{}",
+		format!(
+			"Forked messages 1-{} of {} into new session: {} (~{} context tokens)",
+			number,
+			message_count,
+			branch_name,
+			crate::session::estimate_message_tokens(&messages, &session.model)
+		)
+		.bright_green()
+	);
+
+	// Signal the main loop to switch to the fork, same as /session <name>
+	session.session.info.name = branch_name;
+	Ok(true)
+}
+
+// Why cutting after `kept` would break a tool call/result exchange, if it would
+fn split_tool_exchange(kept: &[Message], dropped: &[Message]) -> Option<&'static str> {
+	let last = kept.last()?;
+	if last.role == "assistant" && last.tool_calls.is_some() {
+		return Some("it requests tool calls whose results would be cut off");
+	}
+	if dropped.first().is_some_and(|next| next.role == "tool") {
+		return Some("later results of the same tool calls would be cut off");
+	}
+	None
+}
+
+fn unix_now() -> u64 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.unwrap_or_default()
+		.as_secs()
+}
+
+// Write a new session file with the given history. Usage stats start at zero since the
+// branch keeps its own accounting. Returns false when the name is taken.
+fn write_branch(session: &ChatSession, branch_name: &str, messages: &[Message]) -> Result<bool> {
+	let branch_file = get_sessions_dir()?.join(format!("{}.jsonl", branch_name));
+	if branch_file.exists() {
+		println!(
+			"{}",
+			format!(
+				"Session '{}' already exists. Choose another name for the branch.",
+				branch_name
+			)
+			.bright_red()
+		);
+		return Ok(false);
+	}
+
+	let now = unix_now();
+	let branch_info = SessionInfo {
+		name: branch_name.to_string(),
+		created_at: now,
+		model: session.session.info.model.clone(),
+		provider: session.session.info.provider.clone(),
+		input_tokens: 0,
+		output_tokens: 0,
+		cached_tokens: 0,
+		total_cost: 0.0,
+		duration_seconds: 0,
+		layer_stats: Vec::new(),
+		tool_calls: 0,
+		total_api_time_ms: 0,
+		total_tool_time_ms: 0,
+		total_layer_time_ms: 0,
+		markdown_theme: session.session.info.markdown_theme.clone(),
+		tool_iteration_limit_hits: 0,
+		reasoning_tokens: 0,
+		seed: session.session.info.seed,
+	};
+
+	// Write the branch file: summary first, then the shared message history
+	drop(File::create(&branch_file)?);
+	let summary_entry = serde_json::json!({
+		"type": "SUMMARY",
+		"timestamp": now,
+		"session_info": &branch_info
+	});
+	append_to_session_file(&branch_file, &serde_json::to_string(&summary_entry)?)?;
+	for message in messages {
+		append_to_session_file(
+			&branch_file,
+			&message_to_session_line(&branch_file, message)?,
+		)?;
+	}
+	Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn message(role: &str, tool_calls: bool) -> Message {
+		Message {
+			role: role.to_string(),
+			content: String::new(),
+			timestamp: 0,
+			cached: false,
+			tool_call_id: None,
+			name: None,
+			tool_calls: tool_calls.then(|| serde_json::json!([])),
+			images: None,
+			attachments: None,
+			pinned: false,
+		}
+	}
+
+	#[test]
+	fn test_fork_point_keeps_tool_exchanges_whole() {
+		let messages = [
+			message("system", false),
+			message("user", false),
+			message("assistant", true),
+			message("tool", false),
+			message("tool", false),
+			message("assistant", false),
+		];
+		let cut = |n: usize| split_tool_exchange(&messages[..n], &messages[n..]);
+
+		assert!(cut(2).is_none());
+		assert!(cut(3).is_some());
+		assert!(cut(4).is_some());
+		assert!(cut(5).is_none());
+		assert!(cut(6).is_none());
+	}
+
+	#[test]
+	fn test_branch_names_are_sanitized() {
+		assert_eq!(sanitize_session_name("try-2_fix"), "try-2_fix");
+		assert_eq!(
+			sanitize_session_name("../other/session"),
+			"___other_session"
+		);
+		assert_eq!(sanitize_session_name("new idea.v2"), "new_idea_v2");
+	}
+}
@@ -49,7 +49,7 @@ pub async fn handle_cache(
 			);
 
 			// Show cache statistics
-			let cache_manager = crate::session::cache::CacheManager::new();
+			let cache_manager = crate::session::cache::CacheManager::from_config(config);
 			let stats =
 				cache_manager.get_cache_statistics_with_config(&session.session, Some(config));
 			println!("{}", stats.format_for_display());
@@ -63,14 +63,14 @@ pub async fn handle_cache(
 		match params[0] {
 			"stats" => {
 				// Show detailed cache statistics
-				let cache_manager = crate::session::cache::CacheManager::new();
+				let cache_manager = crate::session::cache::CacheManager::from_config(config);
 				let stats =
 					cache_manager.get_cache_statistics_with_config(&session.session, Some(config));
 				println!("{}", stats.format_for_display());
 			}
 			"clear" => {
 				// Clear content cache markers (but keep system markers)
-				let cache_manager = crate::session::cache::CacheManager::new();
+				let cache_manager = crate::session::cache::CacheManager::from_config(config);
 				let cleared = cache_manager.clear_content_cache_markers(&mut session.session);
 
 				if cleared > 0 {
@@ -0,0 +1,50 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Compact command handler
+
+use super::super::core::ChatSession;
+use super::utils::format_number;
+use anyhow::Result;
+use colored::Colorize;
+
+pub fn handle_compact(session: &mut ChatSession) -> Result<bool> {
+	match crate::session::chat::compact_tool_results(session) {
+		Ok(stats) if stats.collapsed > 0 => {
+			let new_tokens =
+				crate::session::estimate_message_tokens(&session.session.messages, &session.model);
+			println!(
+				"{}",
+				format!(
+					"Compaction completed: {} repeated tool result(s) collapsed, {} tokens reclaimed, new context size: {} tokens",
+					stats.collapsed,
+					format_number(stats.tokens_saved as u64),
+					format_number(new_tokens as u64)
+				)
+				.bright_green()
+			);
+		}
+		Ok(_) => {
+			println!(
+				"{}",
+				"No repeated tool results found - nothing to compact".bright_yellow()
+			);
+		}
+		Err(e) => {
+			println!("{}: {}", "Compaction failed".bright_red(), e);
+		}
+	}
+
+	Ok(false)
+}
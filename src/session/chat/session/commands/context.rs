@@ -19,6 +19,13 @@ use crate::config::Config;
 use anyhow::Result;
 
 pub fn handle_context(session: &ChatSession, config: &Config, params: &[&str]) -> Result<bool> {
+	// Per-message token breakdown, optionally largest first
+	if params.first() == Some(&"tokens") {
+		let sort_largest_first = params.get(1) == Some(&"sort");
+		session.display_token_distribution(sort_largest_first);
+		return Ok(false);
+	}
+
 	// Parse filter parameter if provided
 	let filter = if params.is_empty() {
 		"all".to_string()
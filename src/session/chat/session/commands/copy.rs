@@ -18,26 +18,132 @@ use anyhow::Result;
 use arboard::Clipboard;
 use colored::Colorize;
 
-pub fn handle_copy(last_response: &str) -> Result<bool> {
+pub fn handle_copy(last_response: &str, params: &[&str]) -> Result<bool> {
 	if last_response.is_empty() {
 		println!(
 			"{}",
 			"No response to copy. Send a message first.".bright_yellow()
 		);
-	} else {
-		match Clipboard::new() {
-			Ok(mut clipboard) => match clipboard.set_text(last_response) {
-				Ok(_) => {
-					println!("{}", "Last response copied to clipboard.".bright_green());
+		return Ok(false);
+	}
+
+	if params.first() != Some(&"code") {
+		copy_to_clipboard(last_response, "Last response copied to clipboard.");
+		return Ok(false);
+	}
+
+	let blocks = extract_code_blocks(last_response);
+	if blocks.is_empty() {
+		println!(
+			"{}",
+			"No code blocks in the last response, copying the whole response instead."
+				.bright_yellow()
+		);
+		copy_to_clipboard(last_response, "Last response copied to clipboard.");
+		return Ok(false);
+	}
+
+	match params.get(1) {
+		Some(index) => match index.parse::<usize>() {
+			Ok(n) if n >= 1 && n <= blocks.len() => {
+				copy_to_clipboard(
+					&blocks[n - 1],
+					&format!("Code block {} of {} copied to clipboard.", n, blocks.len()),
+				);
+			}
+			_ => {
+				println!(
+					"{}",
+					format!(
+						"Invalid code block number '{}'. The last response has {} code block(s).",
+						index,
+						blocks.len()
+					)
+					.bright_red()
+				);
+			}
+		},
+		None => {
+			copy_to_clipboard(
+				&blocks.join("\n"),
+				&format!("{} code block(s) copied to clipboard.", blocks.len()),
+			);
+		}
+	}
+	Ok(false)
+}
+
+fn copy_to_clipboard(text: &str, success_message: &str) {
+	match Clipboard::new() {
+		Ok(mut clipboard) => match clipboard.set_text(text) {
+			Ok(_) => {
+				println!("{}", success_message.bright_green());
+			}
+			Err(e) => {
+				println!("{}: {}", "Failed to copy to clipboard".bright_red(), e);
+			}
+		},
+		Err(e) => {
+			println!("{}: {}", "Failed to access clipboard".bright_red(), e);
+		}
+	}
+}
+
+// Contents of fenced (``` or ~~~) code blocks, without the fence lines
+fn extract_code_blocks(markdown: &str) -> Vec<String> {
+	let mut blocks = Vec::new();
+	let mut current: Option<(String, Vec<&str>)> = None;
+
+	for line in markdown.lines() {
+		let trimmed = line.trim_start();
+		match current.take() {
+			None => {
+				if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+					let fence_char = if trimmed.starts_with('`') { '`' } else { '~' };
+					let fence: String = trimmed.chars().take_while(|c| *c == fence_char).collect();
+					current = Some((fence, Vec::new()));
 				}
-				Err(e) => {
-					println!("{}: {}", "Failed to copy to clipboard".bright_red(), e);
+			}
+			Some((fence, mut lines)) => {
+				// A closing fence uses the same character and is at least as long as the opening one
+				if trimmed.starts_with(&fence)
+					&& trimmed.trim_end().chars().all(|c| fence.starts_with(c))
+				{
+					let mut block = lines.join("\n");
+					block.push('\n');
+					blocks.push(block);
+				} else {
+					lines.push(line);
+					current = Some((fence, lines));
 				}
-			},
-			Err(e) => {
-				println!("{}: {}", "Failed to access clipboard".bright_red(), e);
 			}
 		}
 	}
-	Ok(false)
+
+	blocks
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_extract_code_blocks() {
+		let response = "Here is the fix:\n\n```rust\nfn main() {\n    println!(\"hi\");\n}\n```\n\nThen run:\n\n~~~bash\ncargo run\n~~~\n";
+		let blocks = extract_code_blocks(response);
+
+		assert_eq!(blocks.len(), 2);
+		assert_eq!(blocks[0], "fn main() {\n    println!(\"hi\");\n}\n");
+		assert_eq!(blocks[1], "cargo run\n");
+
+		assert!(extract_code_blocks("No code here.").is_empty());
+	}
+
+	#[test]
+	fn test_extract_code_blocks_nested_fences() {
+		let response = "````markdown\n```rust\nlet x = 1;\n```\n````";
+		let blocks = extract_code_blocks(response);
+
+		assert_eq!(blocks, vec!["```rust\nlet x = 1;\n```\n".to_string()]);
+	}
 }
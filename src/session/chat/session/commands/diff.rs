@@ -0,0 +1,171 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Diff command handler
+
+use super::super::core::ChatSession;
+use anyhow::Result;
+use colored::Colorize;
+use tokio::process::Command;
+
+pub async fn handle_diff(session: &ChatSession, params: &[&str]) -> Result<bool> {
+	let show_all = params.first().is_some_and(|p| *p == "all");
+	let edited_files = &session.edited_files;
+
+	if !show_all && edited_files.is_empty() {
+		println!(
+			"{}",
+			"No files edited in this session yet. Use '/diff all' to see all uncommitted changes."
+				.bright_yellow()
+		);
+		return Ok(false);
+	}
+
+	let diff = if is_git_repository().await {
+		let paths: &[String] = if show_all { &[] } else { edited_files };
+		git_diff(paths).await
+	} else if show_all {
+		println!(
+			"{}",
+			"Not a git repository: '/diff all' is only available inside git repositories."
+				.bright_yellow()
+		);
+		return Ok(false);
+	} else {
+		history_diff(edited_files).await
+	};
+
+	match diff {
+		Ok(diff) if diff.trim().is_empty() => {
+			println!("{}", "No changes.".bright_green());
+		}
+		Ok(diff) => print!("{}", crate::session::chat::syntax::highlight_diff(&diff)),
+		Err(e) => {
+			println!("{}: {}", "Failed to generate diff".bright_red(), e);
+		}
+	}
+
+	Ok(false)
+}
+
+async fn is_git_repository() -> bool {
+	Command::new("git")
+		.args(["rev-parse", "--is-inside-work-tree"])
+		.output()
+		.await
+		.map(|output| output.status.success())
+		.unwrap_or(false)
+}
+
+async fn has_head() -> bool {
+	Command::new("git")
+		.args(["rev-parse", "--verify", "--quiet", "HEAD"])
+		.output()
+		.await
+		.map(|output| output.status.success())
+		.unwrap_or(false)
+}
+
+// Diff against HEAD, limited to `paths` when given. Untracked files are shown as new files.
+// A repository without commits has no HEAD, so the working tree is diffed against the index.
+async fn git_diff(paths: &[String]) -> Result<String> {
+	let base: &[&str] = if has_head().await { &["HEAD"] } else { &[] };
+	let output = Command::new("git")
+		.args(["diff", "--no-color"])
+		.args(base)
+		.arg("--")
+		.args(paths)
+		.output()
+		.await?;
+
+	if !output.status.success() {
+		return Err(anyhow::anyhow!(
+			"{}",
+			String::from_utf8_lossy(&output.stderr).trim()
+		));
+	}
+
+	let mut diff = String::from_utf8_lossy(&output.stdout).to_string();
+
+	// Files created during the session are not known to git yet
+	let untracked = Command::new("git")
+		.args(["ls-files", "--others", "--exclude-standard", "--"])
+		.args(paths)
+		.output()
+		.await?;
+
+	for path in String::from_utf8_lossy(&untracked.stdout).lines() {
+		// --no-index exits with 1 when files differ, so the status is not an error here
+		let output = Command::new("git")
+			.args(["diff", "--no-color", "--no-index", "--", "/dev/null", path])
+			.output()
+			.await?;
+		diff.push_str(&String::from_utf8_lossy(&output.stdout));
+	}
+
+	Ok(diff)
+}
+
+// Diff each edited file against its oldest snapshot kept by the filesystem tools
+async fn history_diff(paths: &[String]) -> Result<String> {
+	let snapshots: Vec<(String, Option<String>)> = {
+		let history = crate::mcp::fs::core::get_file_history()
+			.lock()
+			.map_err(|_| anyhow::anyhow!("Failed to acquire lock on file history"))?;
+		paths
+			.iter()
+			.map(|path| {
+				let original = history.get(path).and_then(|h| h.first().cloned());
+				(path.clone(), original)
+			})
+			.collect()
+	};
+
+	let mut diff = String::new();
+	for (index, (path, original)) in snapshots.iter().enumerate() {
+		// Files without a snapshot were created during the session
+		let original_path = match original {
+			Some(content) => {
+				let snapshot_path = std::env::temp_dir().join(format!(
+					"octomind-diff-{}-{}",
+					std::process::id(),
+					index
+				));
+				tokio::fs::write(&snapshot_path, content).await?;
+				snapshot_path.to_string_lossy().to_string()
+			}
+			None => "/dev/null".to_string(),
+		};
+
+		let output = Command::new("diff")
+			.args(["-u", "-N", "-L"])
+			.arg(format!("a/{}", path))
+			.arg("-L")
+			.arg(format!("b/{}", path))
+			.arg(&original_path)
+			.arg(path)
+			.output()
+			.await;
+
+		if original.is_some() {
+			let _ = tokio::fs::remove_file(&original_path).await;
+		}
+
+		// diff exits with 1 when files differ
+		let output = output?;
+		diff.push_str(&String::from_utf8_lossy(&output.stdout));
+	}
+
+	Ok(diff)
+}
@@ -0,0 +1,42 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Export command handler
+
+use super::super::core::ChatSession;
+use anyhow::Result;
+use colored::Colorize;
+use std::path::PathBuf;
+
+pub fn handle_export(session: &ChatSession, params: &[&str]) -> Result<bool> {
+	// Default to <session_name>.md in the current directory
+	let path = if params.is_empty() {
+		PathBuf::from(format!("{}.md", session.session.info.name))
+	} else {
+		PathBuf::from(params.join(" "))
+	};
+
+	match crate::session::export::export_session_to_file(&session.session, &path) {
+		Ok(()) => {
+			println!(
+				"{}",
+				format!("Session exported to {}", path.display()).bright_green()
+			);
+		}
+		Err(e) => {
+			println!("{}: {}", "Failed to export session".bright_red(), e);
+		}
+	}
+	Ok(false)
+}
@@ -23,18 +23,37 @@ use colored::Colorize;
 pub async fn handle_help(config: &Config, role: &str) -> Result<bool> {
 	println!("{}", "\nAvailable commands:\n".bright_cyan());
 	println!("{} - Show this help message", HELP_COMMAND.cyan());
-	println!("{} - Copy last response to clipboard", COPY_COMMAND.cyan());
+	println!(
+		"{} [code [N]] - Copy last response to clipboard ('code' copies only fenced code blocks, N picks one)",
+		COPY_COMMAND.cyan()
+	);
 	println!("{} - Clear the screen", CLEAR_COMMAND.cyan());
 	println!("{} - Save the session", SAVE_COMMAND.cyan());
+	println!(
+		"{} [path] - Export the session as a Markdown transcript (default: <session_name>.md)",
+		EXPORT_COMMAND.cyan()
+	);
+	println!(
+		"{} [all] - Show uncommitted changes to files edited in this session ('all' for the whole repository)",
+		DIFF_COMMAND.cyan()
+	);
 	println!(
 		"{} - Manage cache checkpoints: /cache [stats|clear|threshold]",
 		CACHE_COMMAND.cyan()
 	);
 	println!(
-		"{} [page] - List all available sessions with pagination (default: page 1)",
+		"{} [--since <30m|24h|7d>] [page] - List available sessions with pagination (default: page 1), optionally only recent ones",
 		LIST_COMMAND.cyan()
 	);
 	println!("{} [name] - Switch to another session or create a new one (without name creates fresh session)", SESSION_COMMAND.cyan());
+	println!(
+		"{} [name] - Fork the current conversation into a new session and switch to it (original stays intact)",
+		BRANCH_COMMAND.cyan()
+	);
+	println!(
+		"{} <number> [name] - Fork a new session from messages 1 to <number> (see /context) and switch to it",
+		FORK_FROM_COMMAND.cyan()
+	);
 	println!(
 		"{} - Display detailed token and cost breakdown for this session",
 		INFO_COMMAND.cyan()
@@ -44,6 +63,10 @@ pub async fn handle_help(config: &Config, role: &str) -> Result<bool> {
 		LAYERS_COMMAND.cyan()
 	);
 	println!("{} - Finalize task with memorization, summarization, and auto-commit (resets layered processing for next task)", DONE_COMMAND.cyan());
+	println!(
+		"{} [--model <model>] [--temperature <value>] - Discard the last response and resend your last message (overrides apply to this attempt only)",
+		RETRY_COMMAND.cyan()
+	);
 	println!(
 		"{} [level] - Set logging level: none, info, or debug",
 		LOGLEVEL_COMMAND.cyan()
@@ -52,6 +75,10 @@ pub async fn handle_help(config: &Config, role: &str) -> Result<bool> {
 		"{} - Perform smart context truncation to reduce token usage",
 		TRUNCATE_COMMAND.cyan()
 	);
+	println!(
+		"{} - Collapse repeated identical tool results into placeholders to reclaim context",
+		COMPACT_COMMAND.cyan()
+	);
 	println!(
 		"{} - Create intelligent summary of entire conversation using local processing",
 		SUMMARIZE_COMMAND.cyan()
@@ -64,22 +91,50 @@ pub async fn handle_help(config: &Config, role: &str) -> Result<bool> {
 		"{} [model] - Show current model or change to a different model (runtime only)",
 		MODEL_COMMAND.cyan()
 	);
+	println!(
+		"{} list [filter] - List models offered by the current provider with context windows",
+		MODEL_COMMAND.cyan()
+	);
+	println!(
+		"{} info - Show the current model's provider, context window, capabilities and pricing",
+		MODEL_COMMAND.cyan()
+	);
+	println!(
+		"{} [name|list] - Show, set or list markdown themes (remembered for this session)",
+		THEME_COMMAND.cyan()
+	);
 	println!(
 		"{} [list|info|full] - Show MCP server status and tools (info is default)",
 		MCP_COMMAND.cyan()
 	);
 	println!(
-		"{} - Generate detailed usage report with cost breakdown per request",
+		"{} [csv [path]] - Generate detailed usage report with cost breakdown per request",
 		REPORT_COMMAND.cyan()
 	);
 	println!(
-		"{} [filter] - Display session context with optional filtering: all, assistant, user, tool, large",
+		"{} [filter] - Display session context with optional filtering: all, assistant, user, tool, pinned, large",
 		CONTEXT_COMMAND.cyan()
 	);
+	println!(
+		"{} tokens [sort] - Show token distribution per message (sort: largest first)",
+		CONTEXT_COMMAND.cyan()
+	);
+	println!(
+		"{} - Show used and remaining context tokens for the current model",
+		TOKENS_COMMAND.cyan()
+	);
 	println!(
 		"{} <path_or_url> - Attach image to your next message (supports PNG, JPEG, GIF, WebP, BMP)",
 		IMAGE_COMMAND.cyan()
 	);
+	println!(
+		"{} <path> - Attach a PDF or text file (csv, md, json, ...) to your next message",
+		ATTACH_COMMAND.cyan()
+	);
+	println!(
+		"{} [number|list|remove <number>] - Pin a message (latest by default, numbers as in /context) so truncation and summarization keep it verbatim",
+		PIN_COMMAND.cyan()
+	);
 	println!(
 		"{} or {} - Exit the session\n",
 		EXIT_COMMAND.cyan(),
@@ -106,7 +161,8 @@ pub async fn handle_help(config: &Config, role: &str) -> Result<bool> {
 	println!("Use '/cache stats' to view detailed cache statistics and efficiency.");
 	println!("Use '/cache clear' to remove content cache markers (keeps system/tool caches).");
 	println!("Use '/cache threshold' to view auto-cache settings.");
-	println!("Supports 2-marker system: when you add a 3rd marker, the first one moves to the new position.");
+	println!("Keeps up to max_cache_markers content markers (default 2): when the limit is exceeded, the oldest one moves to the new position.");
+	println!("max_cache_markers is capped at 2: providers allow 4 cache breakpoints per request, and the system prompt and tool definitions use one each.");
 	println!("Automatic caching triggers based on token threshold (configurable).");
 	println!("Cached tokens reduce costs on subsequent requests with the same content.\n");
 
@@ -15,9 +15,101 @@
 // Info command handler
 
 use super::super::core::ChatSession;
+use super::utils::format_number;
+use crate::config::Config;
+use crate::session::chat::formatting::format_duration;
+use crate::session::chat::markdown::MarkdownRenderer;
+use crate::session::LayerStats;
 use anyhow::Result;
 
-pub fn handle_info(session: &ChatSession) -> Result<bool> {
+pub fn handle_info(session: &ChatSession, config: &Config) -> Result<bool> {
 	session.display_session_info();
+
+	if !session.session.info.layer_stats.is_empty() {
+		let markdown_content = cost_breakdown_markdown(&session.session.info.layer_stats);
+		if config.enable_markdown_rendering {
+			let theme = config.markdown_theme.parse().unwrap_or_default();
+			let renderer = MarkdownRenderer::with_theme(theme);
+			if renderer.render_and_print(&markdown_content).is_err() {
+				println!("{}", markdown_content);
+			}
+		} else {
+			println!("{}", markdown_content);
+		}
+	}
+
 	Ok(false)
 }
+
+// Subtotals for one (layer, model) pair
+#[derive(Default)]
+struct CostGroup {
+	runs: usize,
+	input_tokens: u64,
+	output_tokens: u64,
+	cached_tokens: u64,
+	cost: f64,
+	api_time_ms: u64,
+	tool_time_ms: u64,
+}
+
+impl CostGroup {
+	fn add(&mut self, stat: &LayerStats) {
+		self.runs += 1;
+		self.input_tokens += stat.input_tokens;
+		self.output_tokens += stat.output_tokens;
+		self.cached_tokens += stat.cached_tokens;
+		self.cost += stat.cost;
+		self.api_time_ms += stat.api_time_ms;
+		self.tool_time_ms += stat.tool_time_ms;
+	}
+
+	fn table_row(&self, layer: &str, model: &str) -> String {
+		format!(
+			"| {} | {} | {} | {} | {} | {} | ${:.5} | {} | {} |\n",
+			layer,
+			model,
+			self.runs,
+			format_number(self.input_tokens),
+			format_number(self.output_tokens),
+			format_number(self.cached_tokens),
+			self.cost,
+			format_duration(self.api_time_ms),
+			format_duration(self.tool_time_ms)
+		)
+	}
+}
+
+// Build a per-layer, per-model cost table, most expensive first
+fn cost_breakdown_markdown(layer_stats: &[LayerStats]) -> String {
+	let mut groups: std::collections::HashMap<(&str, &str), CostGroup> =
+		std::collections::HashMap::new();
+	let mut total = CostGroup::default();
+
+	for stat in layer_stats {
+		groups
+			.entry((stat.layer_type.as_str(), stat.model.as_str()))
+			.or_default()
+			.add(stat);
+		total.add(stat);
+	}
+
+	let mut groups: Vec<_> = groups.into_iter().collect();
+	groups.sort_by(|a, b| b.1.cost.total_cmp(&a.1.cost));
+
+	let mut markdown_content = String::new();
+	markdown_content.push_str("## Cost Breakdown by Layer and Model\n\n");
+	markdown_content.push_str(
+		"| Layer | Model | Runs | Input | Output | Cached | Cost | API time | Tool time |\n",
+	);
+	markdown_content.push_str(
+		"|-------|-------|------|-------|--------|--------|------|----------|-----------|\n",
+	);
+
+	for ((layer, model), group) in &groups {
+		markdown_content.push_str(&group.table_row(layer, model));
+	}
+	markdown_content.push_str(&total.table_row("**Total**", ""));
+
+	markdown_content
+}
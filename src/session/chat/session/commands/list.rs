@@ -24,9 +24,29 @@ use chrono::{DateTime, Utc};
 use colored::Colorize;
 
 pub fn handle_list(session: &ChatSession, config: &Config, params: &[&str]) -> Result<bool> {
-	// Parse optional page parameter
-	let page = if !params.is_empty() {
-		match params[0].parse::<usize>() {
+	// Parse optional --since <duration> and page parameters in any order
+	let mut since: Option<&str> = None;
+	let mut page_param: Option<&str> = None;
+	let mut iter = params.iter();
+	while let Some(param) = iter.next() {
+		if *param == "--since" {
+			match iter.next() {
+				Some(value) => since = Some(*value),
+				None => {
+					println!(
+						"{}: --since requires a duration like 30m, 24h or 7d",
+						"Error".bright_red()
+					);
+					return Ok(false);
+				}
+			}
+		} else {
+			page_param = Some(*param);
+		}
+	}
+
+	let page = if let Some(page_param) = page_param {
+		match page_param.parse::<usize>() {
 			Ok(p) if p > 0 => p,
 			_ => {
 				println!(
@@ -40,10 +60,32 @@ pub fn handle_list(session: &ChatSession, config: &Config, params: &[&str]) -> R
 		1 // Default to page 1
 	};
 
+	let since_seconds = match since.map(crate::session::parse_relative_duration) {
+		Some(Ok(seconds)) => Some(seconds),
+		Some(Err(e)) => {
+			println!("{}: {}", "Error".bright_red(), e);
+			return Ok(false);
+		}
+		None => None,
+	};
+
 	match list_available_sessions() {
 		Ok(sessions) => {
+			let sessions = match since_seconds {
+				Some(seconds) => crate::session::filter_sessions_since(sessions, seconds),
+				None => sessions,
+			};
+			// Keep the filter in navigation hints
+			let since_arg = since.map(|s| format!("--since {} ", s)).unwrap_or_default();
+
 			if sessions.is_empty() {
-				println!("{}", "No sessions found.".bright_yellow());
+				match since {
+					Some(since) => println!(
+						"{}",
+						format!("No sessions created in the last {}.", since).bright_yellow()
+					),
+					None => println!("{}", "No sessions found.".bright_yellow()),
+				}
 			} else {
 				// Pagination settings
 				const SESSIONS_PER_PAGE: usize = 15;
@@ -129,14 +171,22 @@ pub fn handle_list(session: &ChatSession, config: &Config, params: &[&str]) -> R
 				markdown_content.push_str("\n## Navigation\n\n");
 				if total_pages > 1 {
 					if page > 1 {
-						markdown_content.push_str(&format!("- Previous: `/list {}`\n", page - 1));
+						markdown_content.push_str(&format!(
+							"- Previous: `/list {}{}`\n",
+							since_arg,
+							page - 1
+						));
 					}
 					if page < total_pages {
-						markdown_content.push_str(&format!("- Next: `/list {}`\n", page + 1));
+						markdown_content.push_str(&format!(
+							"- Next: `/list {}{}`\n",
+							since_arg,
+							page + 1
+						));
 					}
 					markdown_content.push_str(&format!(
-						"- Go to page: `/list <page>` (1-{})\n\n",
-						total_pages
+						"- Go to page: `/list {}<page>` (1-{})\n\n",
+						since_arg, total_pages
 					));
 				}
 
@@ -29,6 +29,7 @@ pub async fn handle_mcp(config: &Config, role: &str, params: &[&str]) -> Result<
 		"info" => handle_mcp_info(config, role).await,
 		"full" => handle_mcp_full(config, role).await,
 		"health" => handle_mcp_health(config, role).await,
+		"reset" => handle_mcp_reset(config, role, &params[1..]).await,
 		"dump" => handle_mcp_dump(config, role).await,
 		"validate" => handle_mcp_validate(config, role).await,
 		_ => handle_mcp_invalid(),
@@ -99,7 +100,7 @@ async fn handle_mcp_info(config: &Config, role: &str) -> Result<bool> {
 					Default::default(),
 				)
 			}
-			McpConnectionType::Http | McpConnectionType::Stdin => {
+			McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::WebSocket => {
 				// External servers - get from status report
 				server_report
 					.get(&server.name)
@@ -211,10 +212,12 @@ async fn handle_mcp_full(config: &Config, role: &str) -> Result<bool> {
 				crate::mcp::process::ServerHealth::Running,
 				Default::default(),
 			),
-			McpConnectionType::Http | McpConnectionType::Stdin => server_report
-				.get(&server.name)
-				.map(|(h, r)| (*h, r.clone()))
-				.unwrap_or((crate::mcp::process::ServerHealth::Dead, Default::default())),
+			McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::WebSocket => {
+				server_report
+					.get(&server.name)
+					.map(|(h, r)| (*h, r.clone()))
+					.unwrap_or((crate::mcp::process::ServerHealth::Dead, Default::default()))
+			}
 		};
 
 		let health_display = match health {
@@ -409,11 +412,16 @@ async fn handle_mcp_health(config: &Config, role: &str) -> Result<bool> {
 		return Ok(false);
 	}
 
+	// Force-refresh tool routing so calls don't go to a restarted server's old process
+	crate::mcp::clear_tool_server_map_cache();
+
 	// Show updated server status
 	let server_report = crate::mcp::server::get_server_status_report();
 
 	for server in &config_for_role.mcp.servers {
-		if let McpConnectionType::Http | McpConnectionType::Stdin = server.connection_type {
+		if let McpConnectionType::Http | McpConnectionType::Stdin | McpConnectionType::WebSocket =
+			server.connection_type
+		{
 			let (health, restart_info) = server_report
 				.get(&server.name)
 				.map(|(h, r)| (*h, r.clone()))
@@ -428,7 +436,16 @@ async fn handle_mcp_health(config: &Config, role: &str) -> Result<bool> {
 
 			println!("{}: {}", server.name.bright_white().bold(), health_display);
 
-			if restart_info.restart_count > 0 {
+			if health == crate::mcp::process::ServerHealth::Failed {
+				println!(
+					"  {}",
+					format!(
+						"Given up after {} consecutive failures - run /mcp reset {} to try again",
+						restart_info.consecutive_failures, server.name
+					)
+					.yellow()
+				);
+			} else if restart_info.restart_count > 0 {
 				println!("  Restart count: {}", restart_info.restart_count);
 				if restart_info.consecutive_failures > 0 {
 					println!(
@@ -452,6 +469,44 @@ async fn handle_mcp_health(config: &Config, role: &str) -> Result<bool> {
 	Ok(false)
 }
 
+async fn handle_mcp_reset(config: &Config, role: &str, names: &[&str]) -> Result<bool> {
+	// Clear the failure state of servers the health monitor gave up on, then retry them
+	let config_for_role = config.get_merged_config_for_role(role);
+	let targets: Vec<String> = if names.is_empty() {
+		config_for_role
+			.mcp
+			.servers
+			.iter()
+			.filter(|server| {
+				crate::mcp::server::get_server_health_status(&server.name)
+					== crate::mcp::process::ServerHealth::Failed
+			})
+			.map(|server| server.name.clone())
+			.collect()
+	} else {
+		names.iter().map(|name| name.to_string()).collect()
+	};
+
+	if targets.is_empty() {
+		println!("{}", "No failed MCP servers to reset.".yellow());
+		return Ok(false);
+	}
+
+	for name in &targets {
+		match crate::mcp::server::reset_server_failure_state(name) {
+			Ok(()) => println!("{} {}", "Reset failure state for".bright_green(), name),
+			Err(e) => println!("{}: {}", "Reset failed".bright_red(), e),
+		}
+	}
+
+	// Retry right away instead of waiting for the next monitor cycle
+	if let Err(e) = crate::mcp::health_monitor::force_health_check(&config_for_role).await {
+		println!("{}: {}", "Health check failed".bright_red(), e);
+	}
+	crate::mcp::clear_tool_server_map_cache();
+	Ok(false)
+}
+
 async fn handle_mcp_dump(config: &Config, role: &str) -> Result<bool> {
 	// Dump raw tool definitions in JSON format for debugging
 	println!();
@@ -540,6 +595,14 @@ async fn handle_mcp_validate(config: &Config, role: &str) -> Result<bool> {
 
 			if issues.is_empty() {
 				println!("  {}", "✅ Valid schema".bright_green());
+				match crate::mcp::strict_schema::to_strict_schema(&func.parameters) {
+					Ok(_) => println!("  {}", "✅ Strict mode compatible".bright_green()),
+					Err(reason) => println!(
+						"  {} {}",
+						"⚠️  Not strict mode compatible (sent non-strict):".yellow(),
+						reason.dimmed()
+					),
+				}
 			} else {
 				all_valid = false;
 				println!("  {}", "❌ Schema issues found:".bright_red());
@@ -585,6 +648,10 @@ fn handle_mcp_invalid() -> Result<bool> {
 		"  {} - Check server health and attempt restart if needed",
 		"/mcp health".cyan()
 	);
+	println!(
+		"  {} - Retry servers the health monitor gave up on (all failed servers by default)",
+		"/mcp reset [server]".cyan()
+	);
 	println!(
 		"  {} - Dump raw tool definitions in JSON format",
 		"/mcp dump".cyan()
@@ -597,7 +664,7 @@ fn handle_mcp_invalid() -> Result<bool> {
 	println!();
 	println!(
 		"{}",
-		"Usage: /mcp [list|info|full|health|dump|validate]".bright_blue()
+		"Usage: /mcp [list|info|full|health|reset|dump|validate]".bright_blue()
 	);
 	Ok(false)
 }
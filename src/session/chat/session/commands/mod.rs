@@ -14,11 +14,16 @@
 
 // Session command processing - refactored into separate modules
 
+mod attach;
+mod branch;
 mod cache;
 mod clear;
+mod compact;
 mod context;
 mod copy;
+mod diff;
 mod exit;
+mod export;
 mod help;
 mod image;
 mod info;
@@ -27,11 +32,15 @@ mod list;
 mod loglevel;
 mod mcp;
 mod model;
+mod pin;
 mod report;
+mod retry;
 mod run;
 mod save;
 mod session;
 mod summarize;
+mod theme;
+mod tokens;
 mod truncate;
 mod utils;
 
@@ -40,6 +49,8 @@ use super::core::ChatSession;
 use crate::config::Config;
 use anyhow::Result;
 
+pub use retry::{prepare_retry, RetryRequest};
+
 // Process user commands
 pub async fn process_command(
 	session: &mut ChatSession,
@@ -58,28 +69,77 @@ pub async fn process_command(
 
 	match command {
 		EXIT_COMMAND | QUIT_COMMAND => exit::handle_exit(),
-		HELP_COMMAND => help::handle_help(config, role).await,
-		COPY_COMMAND => copy::handle_copy(&session.last_response),
+		HELP_COMMAND | HELP_COMMAND_ALT => help::handle_help(config, role).await,
+		COPY_COMMAND => copy::handle_copy(&session.last_response, params),
 		CLEAR_COMMAND => clear::handle_clear(),
 		SAVE_COMMAND => save::handle_save(session),
-		INFO_COMMAND => info::handle_info(session),
-		REPORT_COMMAND => report::handle_report(session, config),
+		INFO_COMMAND => info::handle_info(session, config),
+		REPORT_COMMAND => report::handle_report(session, config, params),
 		CONTEXT_COMMAND => context::handle_context(session, config, params),
+		TOKENS_COMMAND => tokens::handle_tokens(session, config),
 		LAYERS_COMMAND => layers::handle_layers(session, config, role).await,
 		LOGLEVEL_COMMAND => loglevel::handle_loglevel(config, params),
 		TRUNCATE_COMMAND => truncate::handle_truncate(session, config).await,
+		COMPACT_COMMAND => compact::handle_compact(session),
 		SUMMARIZE_COMMAND => summarize::handle_summarize(session, config).await,
 		CACHE_COMMAND => cache::handle_cache(session, config, params).await,
 		LIST_COMMAND => list::handle_list(session, config, params),
-		MODEL_COMMAND => model::handle_model(session, config, params),
+		MODEL_COMMAND => model::handle_model(session, config, params).await,
+		THEME_COMMAND => theme::handle_theme(session, config, params),
 		SESSION_COMMAND => session::handle_session(session, params),
+		BRANCH_COMMAND => branch::handle_branch(session, params),
+		FORK_FROM_COMMAND => branch::handle_fork_from(session, params),
+		EXPORT_COMMAND => export::handle_export(session, params),
+		DIFF_COMMAND => diff::handle_diff(session, params).await,
 		MCP_COMMAND => mcp::handle_mcp(config, role, params).await,
 		RUN_COMMAND => run::handle_run(session, config, role, params).await,
 		IMAGE_COMMAND => image::handle_image(session, params).await,
-		_ => handle_unknown_command(command, config, role).await,
+		ATTACH_COMMAND => attach::handle_attach(session, params),
+		PIN_COMMAND => pin::handle_pin(session, params),
+		_ => {
+			// Offer to run the closest known command when this looks like a typo. Only
+			// commands dispatched here qualify: /retry is handled by the runner before
+			// reaching this point and /done has no handler
+			let dispatched: Vec<&str> = COMMANDS
+				.iter()
+				.copied()
+				.filter(|known| ![DONE_COMMAND, RETRY_COMMAND].contains(known))
+				.collect();
+			if let Some(suggestion) = utils::closest_command(command, &dispatched) {
+				if confirm_suggestion(command, suggestion)? {
+					let corrected = input.replacen(command, suggestion, 1);
+					return Box::pin(process_command(session, &corrected, config, role)).await;
+				}
+				return Ok(false);
+			}
+			handle_unknown_command(command, config, role).await
+		}
 	}
 }
 
+// Ask whether to run the suggested command instead of the mistyped one
+fn confirm_suggestion(command: &str, suggestion: &str) -> Result<bool> {
+	use colored::Colorize;
+	use std::io::{self, Write};
+
+	println!(
+		"{} {}. Did you mean {}?",
+		"Unknown command".bright_red(),
+		command.bright_yellow(),
+		suggestion.bright_green()
+	);
+	print!(
+		"{}",
+		format!("Run {}? (y/N): ", suggestion).bright_white().bold()
+	);
+	io::stdout().flush()?;
+
+	let mut input = String::new();
+	io::stdin().read_line(&mut input)?;
+	let response = input.trim().to_lowercase();
+	Ok(response == "y" || response == "yes")
+}
+
 // Handle unknown commands by showing error and available commands
 async fn handle_unknown_command(command: &str, config: &Config, role: &str) -> Result<bool> {
 	use colored::Colorize;
@@ -97,25 +157,59 @@ async fn handle_unknown_command(command: &str, config: &Config, role: &str) -> R
 	// Basic session commands
 	println!("{} - Show help and available commands", HELP_COMMAND.cyan());
 	println!("{} - Display token usage and costs", INFO_COMMAND.cyan());
-	println!("{} - Generate detailed usage report", REPORT_COMMAND.cyan());
-	println!("{} - Copy last response to clipboard", COPY_COMMAND.cyan());
+	println!(
+		"{} [csv [path]] - Generate detailed usage report",
+		REPORT_COMMAND.cyan()
+	);
+	println!(
+		"{} [code [N]] - Copy last response (or its code blocks) to clipboard",
+		COPY_COMMAND.cyan()
+	);
 	println!("{} - Clear the screen", CLEAR_COMMAND.cyan());
 	println!("{} - Save the session", SAVE_COMMAND.cyan());
+	println!("{} - Export session as Markdown", EXPORT_COMMAND.cyan());
+	println!(
+		"{} - Show changes to files edited in this session",
+		DIFF_COMMAND.cyan()
+	);
 	println!("{} - List all sessions", LIST_COMMAND.cyan());
 	println!("{} - Switch to another session", SESSION_COMMAND.cyan());
-	println!("{} - Show/change current model", MODEL_COMMAND.cyan());
+	println!("{} - Fork session into a new one", BRANCH_COMMAND.cyan());
+	println!(
+		"{} <number> - Fork from an earlier message into a new session",
+		FORK_FROM_COMMAND.cyan()
+	);
+	println!(
+		"{} [model|list|info] - Show/change current model, list models or show model info",
+		MODEL_COMMAND.cyan()
+	);
+	println!(
+		"{} [name|list] - Show/change markdown theme for this session",
+		THEME_COMMAND.cyan()
+	);
 	println!("{} - Set logging level", LOGLEVEL_COMMAND.cyan());
 
 	// Advanced commands
 	println!("{} - Toggle layered processing", LAYERS_COMMAND.cyan());
 	println!("{} - Optimize session context", DONE_COMMAND.cyan());
+	println!("{} - Resend the last message", RETRY_COMMAND.cyan());
 	println!("{} - Smart context truncation", TRUNCATE_COMMAND.cyan());
+	println!(
+		"{} - Collapse repeated tool results",
+		COMPACT_COMMAND.cyan()
+	);
 	println!("{} - Summarize conversation", SUMMARIZE_COMMAND.cyan());
 	println!("{} - Manage cache checkpoints", CACHE_COMMAND.cyan());
 	println!("{} - Display session context", CONTEXT_COMMAND.cyan());
+	println!("{} - Show remaining context budget", TOKENS_COMMAND.cyan());
 	println!("{} - Show MCP server status", MCP_COMMAND.cyan());
 	println!("{} - Execute command layer", RUN_COMMAND.cyan());
 	println!("{} - Attach image to message", IMAGE_COMMAND.cyan());
+	println!(
+		"{} - Attach PDF or text file to message",
+		ATTACH_COMMAND.cyan()
+	);
+	println!("{} - Keep a message through truncation", PIN_COMMAND.cyan());
 	println!(
 		"{}/{} - Exit the session",
 		EXIT_COMMAND.cyan(),
@@ -15,11 +15,17 @@
 // Model command handler
 
 use super::super::core::ChatSession;
+use super::utils::format_number;
 use crate::config::Config;
+use crate::providers::{model_metadata, ProviderFactory};
 use anyhow::Result;
 use colored::Colorize;
 
-pub fn handle_model(session: &mut ChatSession, config: &Config, params: &[&str]) -> Result<bool> {
+pub async fn handle_model(
+	session: &mut ChatSession,
+	config: &Config,
+	params: &[&str],
+) -> Result<bool> {
 	// Handle model command
 	if params.is_empty() {
 		// Show current model and system default
@@ -41,6 +47,16 @@ pub fn handle_model(session: &mut ChatSession, config: &Config, params: &[&str])
 			"Note: Use '/model <model-name>' to change the model for this session only."
 				.bright_yellow()
 		);
+		println!(
+			"{}",
+			"Use '/model list [filter]' to see the models offered by the current provider."
+				.bright_yellow()
+		);
+		println!(
+			"{}",
+			"Use '/model info' to see the current model's context window and capabilities."
+				.bright_yellow()
+		);
 		println!(
 			"{}",
 			"Model changes are runtime-only and won't be saved to config.".bright_yellow()
@@ -48,6 +64,13 @@ pub fn handle_model(session: &mut ChatSession, config: &Config, params: &[&str])
 		return Ok(false);
 	}
 
+	if params[0] == "list" {
+		return handle_model_list(session, config, params.get(1).copied()).await;
+	}
+	if params[0] == "info" && params.len() == 1 {
+		return Ok(handle_model_info(session));
+	}
+
 	// Change to a new model (runtime only)
 	let new_model = params.join(" ");
 	let old_model = session.model.clone();
@@ -63,6 +86,8 @@ pub fn handle_model(session: &mut ChatSession, config: &Config, params: &[&str])
 	// Update session model (runtime only - don't update config)
 	session.model = new_model.clone();
 	session.session.info.model = new_model.clone();
+	// An explicit /model switch is recorded, replacing any --model override
+	session.session.saved_model = None;
 
 	println!(
 		"{}",
@@ -85,3 +110,139 @@ pub fn handle_model(session: &mut ChatSession, config: &Config, params: &[&str])
 
 	Ok(false)
 }
+
+// Capabilities of the current session's model as the provider and metadata table see them
+fn handle_model_info(session: &ChatSession) -> bool {
+	let (provider, model) = match ProviderFactory::get_provider_for_model(&session.model) {
+		Ok(result) => result,
+		Err(e) => {
+			println!("{} {}", "Cannot determine provider:".bright_red(), e);
+			return false;
+		}
+	};
+	let yes_no = |supported: bool| {
+		if supported {
+			"yes".bright_green()
+		} else {
+			"no".bright_red()
+		}
+	};
+
+	println!("{}", format!("Model info: {}", session.model).bright_cyan());
+	println!("  Provider:        {}", provider.name());
+	println!("  Model:           {}", model);
+	println!(
+		"  Context window:  {} tokens",
+		format_number(provider.get_max_input_tokens(&model) as u64)
+	);
+	if let Some(max_output) = model_metadata::max_output_tokens(provider.name(), &model) {
+		println!(
+			"  Max output:      {} tokens",
+			format_number(max_output as u64)
+		);
+	}
+	println!(
+		"  Vision:          {}",
+		yes_no(provider.supports_vision(&model))
+	);
+	println!(
+		"  Caching:         {}",
+		yes_no(provider.supports_caching(&model))
+	);
+	println!(
+		"  Documents:       {}",
+		yes_no(provider.supports_documents(&model))
+	);
+	match model_metadata::pricing(provider.name(), &model) {
+		Some((input, output)) => println!(
+			"  Pricing:         ${:.2} input / ${:.2} output per 1M tokens",
+			input, output
+		),
+		None => println!("  Pricing:         {}", "unknown".dimmed()),
+	}
+
+	if model_metadata::lookup(provider.name(), &model).is_none() {
+		println!();
+		println!(
+			"{}",
+			"This model is not in the metadata table; the context window is a default. Add a [[model_metadata]] entry to your config to set it."
+				.bright_yellow()
+		);
+	}
+
+	false
+}
+
+// List the models of the current session's provider, fetched once per session
+async fn handle_model_list(
+	session: &mut ChatSession,
+	config: &Config,
+	filter: Option<&str>,
+) -> Result<bool> {
+	let (provider, _) = match ProviderFactory::get_provider_for_model(&session.model) {
+		Ok(result) => result,
+		Err(e) => {
+			println!("{} {}", "Cannot determine provider:".bright_red(), e);
+			return Ok(false);
+		}
+	};
+	let provider_name = provider.name().to_string();
+
+	if !session.model_list_cache.contains_key(&provider_name) {
+		match provider.list_models(config).await {
+			Ok(models) => {
+				session
+					.model_list_cache
+					.insert(provider_name.clone(), models);
+			}
+			Err(e) => {
+				println!(
+					"{} {}",
+					format!("Failed to fetch {} models:", provider_name).bright_red(),
+					e
+				);
+				return Ok(false);
+			}
+		}
+	}
+
+	let models = &session.model_list_cache[&provider_name];
+	let filter = filter.map(|f| f.to_lowercase());
+	let shown: Vec<_> = models
+		.iter()
+		.filter(|m| {
+			filter
+				.as_ref()
+				.is_none_or(|f| m.id.to_lowercase().contains(f.as_str()))
+		})
+		.collect();
+
+	if shown.is_empty() {
+		println!(
+			"{}",
+			format!("No {} models found.", provider_name).bright_yellow()
+		);
+		return Ok(false);
+	}
+
+	println!(
+		"{}",
+		format!("Available {} models ({}):", provider_name, shown.len()).bright_cyan()
+	);
+	let width = shown.iter().map(|m| m.id.len()).max().unwrap_or(0) + provider_name.len() + 1;
+	for model in shown {
+		let context = model
+			.context_window
+			.map(|tokens| format!("{} tokens", format_number(tokens as u64)))
+			.unwrap_or_else(|| "context unknown".to_string());
+		let id = format!("{:width$}", format!("{}:{}", provider_name, model.id));
+		println!("  {}  {}", id.bright_green(), context.dimmed());
+	}
+	println!();
+	println!(
+		"{}",
+		"Use '/model <provider:model>' to switch.".bright_yellow()
+	);
+
+	Ok(false)
+}
@@ -0,0 +1,144 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Pin command handler - keep messages verbatim through truncation and summarization
+
+use super::super::core::ChatSession;
+use crate::session::Message;
+use anyhow::Result;
+use colored::Colorize;
+
+pub fn handle_pin(session: &mut ChatSession, params: &[&str]) -> Result<bool> {
+	let messages = &session.session.messages;
+	match params {
+		[] => match messages.iter().rposition(is_pinnable) {
+			Some(index) => set_pinned(session, index, true)?,
+			None => println!("{}", "No message to pin yet".bright_yellow()),
+		},
+		["list"] => list_pinned(messages),
+		["remove", number] => match parse_message_number(number, messages.len()) {
+			Some(index) => set_pinned(session, index, false)?,
+			None => print_invalid_number(number, messages.len()),
+		},
+		[number] => match parse_message_number(number, messages.len()) {
+			Some(index) => set_pinned(session, index, true)?,
+			None => print_invalid_number(number, messages.len()),
+		},
+		_ => print_usage(),
+	}
+	Ok(false)
+}
+
+// Tool calls and results only make sense as complete pairs, so they are not pinnable
+fn is_pinnable(message: &Message) -> bool {
+	message.role == "user" || (message.role == "assistant" && message.tool_calls.is_none())
+}
+
+// Message numbers are 1-indexed, as shown by /context
+fn parse_message_number(number: &str, message_count: usize) -> Option<usize> {
+	number
+		.parse::<usize>()
+		.ok()
+		.filter(|number| (1..=message_count).contains(number))
+		.map(|number| number - 1)
+}
+
+fn set_pinned(session: &mut ChatSession, index: usize, pinned: bool) -> Result<()> {
+	let message = &session.session.messages[index];
+	if pinned && !is_pinnable(message) {
+		println!(
+			"{}",
+			format!(
+				"Message {} is a {} message; only user messages and assistant replies without tool calls can be pinned",
+				index + 1,
+				message.role
+			)
+			.bright_red()
+		);
+		return Ok(());
+	}
+	if message.pinned == pinned {
+		let state = if pinned { "already" } else { "not" };
+		println!("Message {} is {} pinned", index + 1, state);
+		return Ok(());
+	}
+
+	session.session.set_message_pinned(index, pinned)?;
+	if pinned {
+		println!(
+			"{}",
+			format!(
+				"📌 Pinned message {} ({})",
+				index + 1,
+				message_preview(&session.session.messages[index])
+			)
+			.bright_green()
+		);
+	} else {
+		println!(
+			"{}",
+			format!("Unpinned message {}", index + 1).bright_green()
+		);
+	}
+	Ok(())
+}
+
+fn list_pinned(messages: &[Message]) {
+	let pinned: Vec<(usize, &Message)> = messages
+		.iter()
+		.enumerate()
+		.filter(|(_, message)| message.pinned)
+		.collect();
+
+	if pinned.is_empty() {
+		println!("{}", "No pinned messages".bright_yellow());
+		return;
+	}
+	for (index, message) in pinned {
+		println!(
+			"📌 {} {}",
+			format!("Message {} - {}:", index + 1, message.role.to_uppercase()).bright_cyan(),
+			message_preview(message)
+		);
+	}
+}
+
+// First line of the message, shortened for display
+fn message_preview(message: &Message) -> String {
+	let first_line = message.content.lines().next().unwrap_or_default();
+	if first_line.chars().count() > 80 {
+		format!("{}...", first_line.chars().take(77).collect::<String>())
+	} else {
+		first_line.to_string()
+	}
+}
+
+fn print_invalid_number(number: &str, message_count: usize) {
+	println!(
+		"{}",
+		format!(
+			"Invalid message number '{}': use 1-{} (see /context)",
+			number, message_count
+		)
+		.bright_red()
+	);
+	print_usage();
+}
+
+fn print_usage() {
+	println!(
+		"{}",
+		"Usage: /pin [number] | /pin list | /pin remove <number>".bright_yellow()
+	);
+}
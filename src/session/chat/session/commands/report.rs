@@ -18,15 +18,24 @@ use super::super::core::ChatSession;
 use crate::config::Config;
 use anyhow::Result;
 use colored::Colorize;
+use std::path::PathBuf;
 
-pub fn handle_report(session: &ChatSession, config: &Config) -> Result<bool> {
+pub fn handle_report(session: &ChatSession, config: &Config, params: &[&str]) -> Result<bool> {
 	// Generate and display session usage report
 	if let Some(ref session_file) = session.session.session_file {
 		let session_file_str = session_file.to_string_lossy();
 		match crate::session::report::SessionReport::generate_from_log(&session_file_str) {
-			Ok(report) => {
-				report.display(config);
-			}
+			Ok(report) => match params.first() {
+				Some(&"csv") => write_csv(&report, &params[1..]),
+				Some(format) => {
+					println!(
+						"{}: Unknown report format '{}'. Use: /report [csv [path]]",
+						"Error".bright_red(),
+						format
+					);
+				}
+				None => report.display(config),
+			},
 			Err(e) => {
 				println!("{}: Failed to generate report: {}", "Error".bright_red(), e);
 				println!(
@@ -47,3 +56,26 @@ pub fn handle_report(session: &ChatSession, config: &Config) -> Result<bool> {
 	}
 	Ok(false)
 }
+
+// Write the per-request CSV to the given path, or stdout when no path is given
+fn write_csv(report: &crate::session::report::SessionReport, params: &[&str]) {
+	let csv = report.to_csv();
+	if params.is_empty() {
+		print!("{}", csv);
+		return;
+	}
+
+	let path = PathBuf::from(params.join(" "));
+	match std::fs::write(&path, csv) {
+		Ok(()) => println!(
+			"{}",
+			format!(
+				"Wrote {} request(s) to {}",
+				report.requests.len(),
+				path.display()
+			)
+			.bright_green()
+		),
+		Err(e) => println!("{}: {}", "Failed to write report".bright_red(), e),
+	}
+}
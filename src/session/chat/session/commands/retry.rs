@@ -0,0 +1,146 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Retry command handler - discard the last turn and resend its user message
+
+use super::super::core::ChatSession;
+use anyhow::Result;
+use colored::Colorize;
+
+/// User message to resend, with overrides that apply to this attempt only
+pub struct RetryRequest {
+	pub input: String,
+	pub model: Option<String>,
+	pub temperature: Option<f32>,
+}
+
+// Parse "/retry [--model <model>] [--temperature <value>]"
+fn parse_retry_params(params: &[&str]) -> Result<(Option<String>, Option<f32>), String> {
+	let mut model = None;
+	let mut temperature = None;
+	let mut iter = params.iter();
+
+	while let Some(param) = iter.next() {
+		match *param {
+			"--model" => match iter.next() {
+				Some(value) => model = Some(value.to_string()),
+				None => return Err("--model needs a value".to_string()),
+			},
+			"--temperature" => match iter.next().map(|value| value.parse::<f32>()) {
+				Some(Ok(value)) if (0.0..=2.0).contains(&value) => temperature = Some(value),
+				_ => return Err("--temperature needs a number between 0.0 and 2.0".to_string()),
+			},
+			other => return Err(format!("Unknown option: {}", other)),
+		}
+	}
+
+	Ok((model, temperature))
+}
+
+/// Remove the last assistant turn and return its user message for resending,
+/// or None (after telling the user why) when there is nothing to retry
+pub fn prepare_retry(session: &mut ChatSession, params: &[&str]) -> Result<Option<RetryRequest>> {
+	let (model, temperature) = match parse_retry_params(params) {
+		Ok(overrides) => overrides,
+		Err(e) => {
+			println!("{}", e.bright_red());
+			println!(
+				"{}",
+				"Usage: /retry [--model <provider:model>] [--temperature <0.0-2.0>]"
+					.bright_yellow()
+			);
+			return Ok(None);
+		}
+	};
+
+	if let Some(model) = &model {
+		if let Err(e) = crate::providers::ProviderFactory::parse_model(model) {
+			println!("{}: {}", "Invalid model".bright_red(), e);
+			return Ok(None);
+		}
+	}
+
+	let user_message = match crate::session::discard_last_turn(&mut session.session.messages) {
+		Some(message) => message,
+		None => {
+			println!(
+				"{}",
+				"Nothing to retry: no user message yet".bright_yellow()
+			);
+			return Ok(None);
+		}
+	};
+
+	// Record the discard so a resumed session drops the same messages
+	if let Some(session_file) = &session.session.session_file {
+		let log_entry = serde_json::json!({
+			"type": "RETRY",
+			"timestamp": std::time::SystemTime::now()
+				.duration_since(std::time::UNIX_EPOCH)
+				.unwrap_or_default()
+				.as_secs(),
+		});
+		crate::session::append_to_session_file(session_file, &serde_json::to_string(&log_entry)?)?;
+	}
+
+	// The resent message gets its image and files back when it had them
+	if let Some(image) = user_message
+		.images
+		.and_then(|images| images.into_iter().next())
+	{
+		session.pending_image = Some(image);
+	}
+	if let Some(attachments) = user_message.attachments {
+		session.pending_attachments = attachments;
+	}
+
+	let mut overrides = Vec::new();
+	if let Some(model) = &model {
+		overrides.push(format!("model {}", model));
+	}
+	if let Some(temperature) = temperature {
+		overrides.push(format!("temperature {}", temperature));
+	}
+	if overrides.is_empty() {
+		println!("{}", "Retrying last message".bright_cyan());
+	} else {
+		println!(
+			"{}",
+			format!("Retrying last message with {}", overrides.join(", ")).bright_cyan()
+		);
+	}
+
+	Ok(Some(RetryRequest {
+		input: user_message.content,
+		model,
+		temperature,
+	}))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_retry_params() {
+		assert_eq!(parse_retry_params(&[]).unwrap(), (None, None));
+		assert_eq!(
+			parse_retry_params(&["--model", "openai:gpt-4o", "--temperature", "0.2"]).unwrap(),
+			(Some("openai:gpt-4o".to_string()), Some(0.2))
+		);
+		assert!(parse_retry_params(&["--model"]).is_err());
+		assert!(parse_retry_params(&["--temperature", "3"]).is_err());
+		assert!(parse_retry_params(&["--seed", "1"]).is_err());
+	}
+}
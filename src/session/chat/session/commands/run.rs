@@ -92,8 +92,7 @@ allowed_tools = []"#
 			.session
 			.messages
 			.iter()
-			.filter(|m| m.role == "user")
-			.next_back()
+			.rfind(|m| m.role == "user")
 			.map(|m| m.content.clone())
 			.unwrap_or_else(|| "No recent user input found".to_string())
 	};
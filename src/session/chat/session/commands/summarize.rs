@@ -28,7 +28,8 @@ pub async fn handle_summarize(session: &mut ChatSession, config: &Config) -> Res
 	);
 
 	// Estimate current token usage
-	let current_tokens = crate::session::estimate_message_tokens(&session.session.messages);
+	let current_tokens =
+		crate::session::estimate_message_tokens(&session.session.messages, &session.model);
 	println!(
 		"{}",
 		format!(
@@ -42,7 +43,8 @@ pub async fn handle_summarize(session: &mut ChatSession, config: &Config) -> Res
 	match crate::session::chat::perform_smart_full_summarization(session, config).await {
 		Ok(()) => {
 			// Calculate new token count after summarization
-			let new_tokens = crate::session::estimate_message_tokens(&session.session.messages);
+			let new_tokens =
+				crate::session::estimate_message_tokens(&session.session.messages, &session.model);
 			let tokens_saved = current_tokens.saturating_sub(new_tokens);
 
 			println!(
@@ -0,0 +1,97 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Theme command handler
+
+use super::super::core::ChatSession;
+use crate::config::Config;
+use crate::session::chat::markdown::MarkdownTheme;
+use anyhow::Result;
+use colored::Colorize;
+
+pub fn handle_theme(
+	session: &mut ChatSession,
+	config: &mut Config,
+	params: &[&str],
+) -> Result<bool> {
+	if params.is_empty() {
+		let source = if session.session.info.markdown_theme.is_some() {
+			"session"
+		} else {
+			"global default"
+		};
+		println!(
+			"{}",
+			format!(
+				"Current markdown theme: {} ({})",
+				config.markdown_theme, source
+			)
+			.bright_cyan()
+		);
+		println!(
+			"{}",
+			"Use '/theme <name>' to change it for this session or '/theme list' to see themes."
+				.bright_yellow()
+		);
+		return Ok(false);
+	}
+
+	if params[0] == "list" {
+		println!("{}", "Available markdown themes:".bright_cyan());
+		for theme in MarkdownTheme::all_themes() {
+			let marker = if theme == config.markdown_theme {
+				" (current)"
+			} else {
+				""
+			};
+			println!("  {}{}", theme.bright_yellow(), marker.bright_green());
+		}
+		return Ok(false);
+	}
+
+	let theme = match params[0].parse::<MarkdownTheme>() {
+		Ok(theme) => theme,
+		Err(e) => {
+			println!("{}", e.bright_red());
+			println!(
+				"{}",
+				format!(
+					"Available themes: {}",
+					MarkdownTheme::all_themes().join(", ")
+				)
+				.bright_yellow()
+			);
+			return Ok(false);
+		}
+	};
+
+	// Log the command execution
+	if let Some(session_file) = &session.session.session_file {
+		if let Some(session_name) = session_file.file_stem().and_then(|s| s.to_str()) {
+			let command_line = format!("/theme {}", theme.as_str());
+			let _ = crate::session::logger::log_session_command(session_name, &command_line);
+		}
+	}
+
+	// The logged command restores the theme on reload; the config file is untouched
+	config.markdown_theme = theme.as_str().to_string();
+	session.session.info.markdown_theme = Some(theme.as_str().to_string());
+
+	println!(
+		"{}",
+		format!("Markdown theme set to {} for this session", theme.as_str()).bright_green()
+	);
+
+	Ok(false)
+}
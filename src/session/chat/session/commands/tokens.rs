@@ -0,0 +1,104 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tokens command handler - remaining context budget for the current model
+
+use super::super::core::ChatSession;
+use super::utils::format_number;
+use crate::config::Config;
+use crate::providers::ProviderFactory;
+use anyhow::Result;
+use colored::Colorize;
+
+// Width of the usage bar in characters
+const BAR_WIDTH: usize = 40;
+
+pub fn handle_tokens(session: &ChatSession, config: &Config) -> Result<bool> {
+	let (provider, actual_model) = match ProviderFactory::get_provider_for_model(&session.model) {
+		Ok(resolved) => resolved,
+		Err(e) => {
+			println!(
+				"{}",
+				format!("Cannot resolve model {}: {}", session.model, e).bright_red()
+			);
+			return Ok(false);
+		}
+	};
+
+	// Same estimate the request validation uses before sending
+	let message_tokens =
+		crate::session::estimate_message_tokens(&session.session.messages, &session.model);
+	let tool_tokens = crate::session::estimate_tool_definition_tokens(config);
+	let used = message_tokens + tool_tokens;
+	let max_input_tokens = provider.get_max_input_tokens(&actual_model);
+	let remaining = max_input_tokens.saturating_sub(used);
+	let percentage = if max_input_tokens > 0 {
+		used as f64 / max_input_tokens as f64 * 100.0
+	} else {
+		100.0
+	};
+
+	println!(
+		"{}",
+		"───────────────── Context Budget ─────────────────".bright_cyan()
+	);
+	println!("Model:      {}", session.model.bright_white());
+	println!(
+		"Used:       {} tokens ({} messages + {} tool definitions)",
+		format_number(used as u64).bright_white(),
+		format_number(message_tokens as u64),
+		format_number(tool_tokens as u64)
+	);
+	println!(
+		"Remaining:  {} of {} tokens",
+		format_number(remaining as u64).bright_white(),
+		format_number(max_input_tokens as u64)
+	);
+	println!("{} {:.1}%", usage_bar(percentage), percentage);
+
+	if percentage >= 90.0 {
+		println!(
+			"{}",
+			"Context is nearly full - use /done or /truncate before the next request".bright_red()
+		);
+	} else if percentage >= 75.0 {
+		println!(
+			"{}",
+			"Context is filling up - consider /done or /truncate soon".bright_yellow()
+		);
+	}
+	println!();
+
+	Ok(false)
+}
+
+// Filled bar colored by how much of the context window is used
+fn usage_bar(percentage: f64) -> String {
+	let filled = ((percentage / 100.0) * BAR_WIDTH as f64)
+		.round()
+		.clamp(0.0, BAR_WIDTH as f64) as usize;
+	let bar = "█".repeat(filled);
+	let colored_bar = if percentage >= 90.0 {
+		bar.bright_red()
+	} else if percentage >= 75.0 {
+		bar.bright_yellow()
+	} else {
+		bar.bright_green()
+	};
+	format!(
+		"[{}{}]",
+		colored_bar,
+		"░".repeat(BAR_WIDTH - filled).dimmed()
+	)
+}
@@ -25,7 +25,8 @@ pub async fn handle_truncate(session: &mut ChatSession, config: &Config) -> Resu
 	println!("{}", "Performing smart context truncation...".bright_cyan());
 
 	// Estimate current token usage
-	let current_tokens = crate::session::estimate_message_tokens(&session.session.messages);
+	let current_tokens =
+		crate::session::estimate_message_tokens(&session.session.messages, &session.model);
 	println!(
 		"{}",
 		format!(
@@ -39,7 +40,8 @@ pub async fn handle_truncate(session: &mut ChatSession, config: &Config) -> Resu
 	match crate::session::chat::perform_smart_truncation(session, config, current_tokens).await {
 		Ok(()) => {
 			// Calculate new token count after truncation
-			let new_tokens = crate::session::estimate_message_tokens(&session.session.messages);
+			let new_tokens =
+				crate::session::estimate_message_tokens(&session.session.messages, &session.model);
 			let tokens_saved = current_tokens.saturating_sub(new_tokens);
 
 			if tokens_saved > 0 {
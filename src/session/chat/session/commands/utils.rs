@@ -42,3 +42,54 @@ pub fn format_number(n: u64) -> String {
 		.rev()
 		.collect()
 }
+
+// Levenshtein edit distance between two strings
+pub fn edit_distance(a: &str, b: &str) -> usize {
+	let b_chars: Vec<char> = b.chars().collect();
+	let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+
+	for (i, a_char) in a.chars().enumerate() {
+		let mut current = vec![i + 1; b_chars.len() + 1];
+		for (j, b_char) in b_chars.iter().enumerate() {
+			let substitution = previous[j] + usize::from(a_char != *b_char);
+			current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+		}
+		previous = current;
+	}
+
+	previous[b_chars.len()]
+}
+
+// Closest known command for a mistyped one; short inputs need a proportionally closer match.
+// An exact match is never suggested, since running it would land back here.
+pub fn closest_command<'a>(command: &str, known: &[&'a str]) -> Option<&'a str> {
+	let max_distance = (command.chars().count() / 3).clamp(1, 2);
+	known
+		.iter()
+		.map(|candidate| (*candidate, edit_distance(command, candidate)))
+		.filter(|(_, distance)| (1..=max_distance).contains(distance))
+		.min_by_key(|(_, distance)| *distance)
+		.map(|(candidate, _)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_edit_distance() {
+		assert_eq!(edit_distance("/sumarize", "/summarize"), 1);
+		assert_eq!(edit_distance("/info", "/info"), 0);
+		assert_eq!(edit_distance("", "/run"), 4);
+	}
+
+	#[test]
+	fn test_closest_command() {
+		let known = ["/summarize", "/session", "/save", "/info"];
+		assert_eq!(closest_command("/sumarize", &known), Some("/summarize"));
+		assert_eq!(closest_command("/sav", &known), Some("/save"));
+		assert_eq!(closest_command("/xyz", &known), None);
+		// The command itself is not a suggestion
+		assert_eq!(closest_command("/save", &known), None);
+	}
+}
@@ -16,12 +16,14 @@
 
 use super::utils::format_number;
 use crate::config::Config;
+use crate::providers::ModelInfo;
 use crate::session::{get_sessions_dir, load_session, Session};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use colored::Colorize;
+use std::collections::HashMap;
 use std::fs::File;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 // Generate a session name in format: YYMMDD-HHMMSS-basename-uuid
@@ -32,11 +34,12 @@ fn generate_session_name() -> String {
 
 	// Get current directory basename
 	let current_dir = std::env::current_dir().unwrap_or_default();
-	let basename = current_dir
-		.file_name()
-		.unwrap_or_default()
-		.to_string_lossy()
-		.to_string();
+	let basename = sanitize_session_name(
+		&current_dir
+			.file_name()
+			.unwrap_or_default()
+			.to_string_lossy(),
+	);
 
 	// Generate a short UUID (first 8 characters)
 	let uuid = Uuid::new_v4().to_string();
@@ -45,6 +48,20 @@ fn generate_session_name() -> String {
 	format!("{}-{}-{}-{}", date_str, time_str, basename, short_uuid)
 }
 
+// Session names become file names; keep letters, digits, '-' and '_' and replace the rest
+// (path separators, dots, spaces) with '_'
+pub fn sanitize_session_name(name: &str) -> String {
+	name.chars()
+		.map(|c| {
+			if c.is_alphanumeric() || c == '-' || c == '_' {
+				c
+			} else {
+				'_'
+			}
+		})
+		.collect()
+}
+
 // Chat session manager for interactive coding sessions
 pub struct ChatSession {
 	pub session: Session,
@@ -55,6 +72,10 @@ pub struct ChatSession {
 	pub cache_next_user_message: bool, // Flag to cache the next user message
 	pub spending_threshold_checkpoint: f64, // Track spending at last threshold check
 	pub pending_image: Option<crate::session::image::ImageAttachment>, // Pending image attachment
+	pub pending_attachments: Vec<crate::session::attachment::FileAttachment>, // Files for the next message
+	pub edited_files: Vec<String>, // Paths modified by text_editor during this session (for /diff)
+	pub model_list_cache: HashMap<String, Vec<ModelInfo>>, // Provider name -> models fetched by /model list
+	pub last_sent_input: Option<(String, Instant)>, // Last message sent and when, for dedupe_user_messages
 }
 
 impl ChatSession {
@@ -89,6 +110,10 @@ impl ChatSession {
 			total_api_time_ms: 0,
 			total_tool_time_ms: 0,
 			total_layer_time_ms: 0,
+			markdown_theme: None,
+			tool_iteration_limit_hits: 0,
+			reasoning_tokens: 0,
+			seed: None,
 		};
 
 		Self {
@@ -102,6 +127,7 @@ impl ChatSession {
 					.duration_since(UNIX_EPOCH)
 					.unwrap_or_default()
 					.as_secs(),
+				saved_model: None,
 			},
 			last_response: String::new(),
 			model: model_name,
@@ -110,6 +136,10 @@ impl ChatSession {
 			cache_next_user_message: false,     // Initialize cache flag
 			spending_threshold_checkpoint: 0.0, // Initialize spending checkpoint
 			pending_image: None,                // Initialize pending image
+			pending_attachments: Vec::new(),    // No files attached yet
+			edited_files: Vec::new(),           // No files edited yet
+			model_list_cache: HashMap::new(),   // Model lists are fetched on demand
+			last_sent_input: None,              // Nothing sent yet
 		}
 	}
 
@@ -213,6 +243,10 @@ impl ChatSession {
 						cache_next_user_message: false,     // Initialize cache flag
 						spending_threshold_checkpoint: 0.0, // Initialize spending checkpoint
 						pending_image: None,                // Initialize pending image
+						pending_attachments: Vec::new(),    // No files attached yet
+						edited_files: Vec::new(),           // No files edited yet
+						model_list_cache: HashMap::new(),   // Model lists are fetched on demand
+						last_sent_input: None,              // Nothing sent yet
 					};
 
 					// Update the estimated cost from the loaded session
@@ -330,46 +364,14 @@ impl ChatSession {
 	/// Attach image from file path
 	pub async fn attach_image_from_path(&mut self, path: &str) -> Result<()> {
 		use crate::session::image::ImageProcessor;
-		use std::path::Path;
 
-		// Check if input is a URL
-		if ImageProcessor::is_url(path) {
+		let is_url = ImageProcessor::is_url(path);
+		if is_url {
 			println!("{}", "🌐 Downloading image from URL...".bright_cyan());
-
-			let image_attachment = ImageProcessor::load_from_url(path).await?;
-
-			// Show preview
-			println!("{}", "📸 Image preview:".bright_cyan());
-			ImageProcessor::show_preview(&image_attachment)?;
-
-			// Store for next message
-			self.pending_image = Some(image_attachment);
-
-			println!(
-				"{}",
-				"✅ Image downloaded and ready to attach!".bright_green()
-			);
-			return Ok(());
-		}
-
-		// Handle as file path
-		let image_path = Path::new(path);
-
-		// Check if file exists
-		if !image_path.exists() {
-			return Err(anyhow::anyhow!("Image file not found: {}", path));
-		}
-
-		// Check if it's a supported image format
-		if !ImageProcessor::is_supported_image(image_path) {
-			return Err(anyhow::anyhow!(
-				"Unsupported image format. Supported: {}",
-				ImageProcessor::supported_extensions().join(", ")
-			));
 		}
 
 		// Load and process the image
-		let image_attachment = ImageProcessor::load_from_path(image_path)?;
+		let image_attachment = ImageProcessor::load(path).await?;
 
 		// Show preview
 		println!("{}", "📸 Image preview:".bright_cyan());
@@ -378,6 +380,13 @@ impl ChatSession {
 		// Store for next message
 		self.pending_image = Some(image_attachment);
 
+		if is_url {
+			println!(
+				"{}",
+				"✅ Image downloaded and ready to attach!".bright_green()
+			);
+		}
+
 		Ok(())
 	}
 
@@ -413,6 +422,37 @@ impl ChatSession {
 		self.pending_image.take()
 	}
 
+	/// Attach a file (PDF or text) to the next message
+	pub fn attach_file_from_path(&mut self, path: &str) -> Result<()> {
+		use crate::session::attachment::{AttachmentContent, FileAttachment};
+
+		let attachment = FileAttachment::load_from_path(std::path::Path::new(path))?;
+
+		// Documents need a document API - fail now rather than when the message is sent
+		if let AttachmentContent::Document(_) = attachment.content {
+			let (provider, model_name) =
+				crate::providers::ProviderFactory::get_provider_for_model(&self.model)?;
+			if !provider.supports_documents(&model_name) {
+				return Err(anyhow::anyhow!(
+					"Current model {} does not support PDF attachments. Switch with /model to one that does (for example Claude or GPT-4o)",
+					self.model
+				));
+			}
+		}
+
+		println!(
+			"{}",
+			format!(
+				"📎 {} ({} KB) will be attached to your next message",
+				attachment.file_name,
+				attachment.size_bytes.div_ceil(1024)
+			)
+			.bright_green()
+		);
+		self.pending_attachments.push(attachment);
+		Ok(())
+	}
+
 	/// Process user commands
 	pub async fn process_command(
 		&mut self,
@@ -422,4 +462,12 @@ impl ChatSession {
 	) -> Result<bool> {
 		super::commands::process_command(self, input, config, role).await
 	}
+
+	/// Discard the last turn for /retry and return the user message to resend
+	pub fn prepare_retry(
+		&mut self,
+		params: &[&str],
+	) -> Result<Option<super::commands::RetryRequest>> {
+		super::commands::prepare_retry(self, params)
+	}
 }
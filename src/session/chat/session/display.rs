@@ -18,6 +18,7 @@ use super::core::ChatSession;
 use super::utils::format_number;
 use crate::session::chat::formatting::format_duration;
 use colored::*;
+use std::cmp::Reverse;
 
 impl ChatSession {
 	// Display detailed information about the session, including layer-specific stats
@@ -39,6 +40,9 @@ impl ChatSession {
 			"Main model:".yellow(),
 			self.session.info.model.bright_white()
 		);
+		if let Some(seed) = self.session.info.seed {
+			println!("{} {}", "Seed:".yellow(), seed.to_string().bright_white());
+		}
 
 		// Total token usage
 		let total_tokens = self.session.info.input_tokens
@@ -57,6 +61,38 @@ impl ChatSession {
 			format_number(self.session.info.cached_tokens).bright_magenta()
 		);
 
+		// Reasoning tokens are part of the output, shown only when the provider reports them
+		let reasoning_tokens = self.session.info.reasoning_tokens;
+		if reasoning_tokens > 0 {
+			println!(
+				"{} {} of the output tokens",
+				"Reasoning:".yellow(),
+				format_number(reasoning_tokens).bright_cyan()
+			);
+		}
+
+		// Cache effectiveness: share of input served from cache and what it saved
+		let cached_tokens = self.session.info.cached_tokens;
+		if cached_tokens > 0 {
+			let total_input = self.session.info.input_tokens + cached_tokens;
+			let hit_rate = cached_tokens as f64 * 100.0 / total_input as f64;
+			match crate::providers::estimate_cache_savings(&self.session.info.model, cached_tokens)
+			{
+				Some(saved) => println!(
+					"{} {:.1}% of input tokens, ~${:.5} saved",
+					"Cache hits:".yellow(),
+					hit_rate,
+					saved
+				),
+				None => println!(
+					"{} {:.1}% of input tokens {}",
+					"Cache hits:".yellow(),
+					hit_rate,
+					"(savings unknown: no pricing for this model)".dimmed()
+				),
+			}
+		}
+
 		// Cost information
 		println!(
 			"{} ${:.5}",
@@ -90,6 +126,17 @@ impl ChatSession {
 				self.session.info.tool_calls.to_string().bright_cyan()
 			);
 		}
+		if self.session.info.tool_iteration_limit_hits > 0 {
+			println!(
+				"{} hit in {} turn(s)",
+				"Tool iteration limit:".yellow(),
+				self.session
+					.info
+					.tool_iteration_limit_hits
+					.to_string()
+					.bright_red()
+			);
+		}
 
 		// Display layered stats if available
 		if !self.session.info.layer_stats.is_empty() {
@@ -323,13 +370,23 @@ impl ChatSession {
 					msg.role == "tool" || msg.tool_calls.is_some() || msg.tool_call_id.is_some()
 				})
 				.collect(),
+			"pinned" => self
+				.session
+				.messages
+				.iter()
+				.enumerate()
+				.filter(|(_, msg)| msg.pinned)
+				.collect(),
 			"large" => {
 				// Calculate median and standard deviation for robust outlier detection
 				let mut token_counts: Vec<f64> = self
 					.session
 					.messages
 					.iter()
-					.map(|msg| crate::session::token_counter::estimate_tokens(&msg.content) as f64)
+					.map(|msg| {
+						crate::session::token_counter::estimate_tokens(&msg.content, &self.model)
+							as f64
+					})
 					.collect();
 
 				if token_counts.is_empty() {
@@ -337,7 +394,7 @@ impl ChatSession {
 				} else {
 					// Calculate median
 					token_counts.sort_by(|a, b| a.partial_cmp(b).unwrap());
-					let median = if token_counts.len() % 2 == 0 {
+					let median = if token_counts.len().is_multiple_of(2) {
 						(token_counts[token_counts.len() / 2 - 1]
 							+ token_counts[token_counts.len() / 2])
 							/ 2.0
@@ -351,8 +408,10 @@ impl ChatSession {
 						.messages
 						.iter()
 						.map(|msg| {
-							let tokens =
-								crate::session::token_counter::estimate_tokens(&msg.content) as f64;
+							let tokens = crate::session::token_counter::estimate_tokens(
+								&msg.content,
+								&self.model,
+							) as f64;
 							(tokens - median).powi(2)
 						})
 						.sum::<f64>() / self.session.messages.len() as f64;
@@ -365,8 +424,10 @@ impl ChatSession {
 						.iter()
 						.enumerate()
 						.filter(|(_, msg)| {
-							let msg_tokens =
-								crate::session::token_counter::estimate_tokens(&msg.content) as f64;
+							let msg_tokens = crate::session::token_counter::estimate_tokens(
+								&msg.content,
+								&self.model,
+							) as f64;
 							msg_tokens > threshold
 						})
 						.collect()
@@ -376,14 +437,14 @@ impl ChatSession {
 				println!(
 					"{}",
 					format!(
-						"Unknown filter '{}'. Available filters: all, assistant, user, tool, large",
+						"Unknown filter '{}'. Available filters: all, assistant, user, tool, pinned, large",
 						filter
 					)
 					.bright_red()
 				);
 				println!(
 					"{}",
-					"Usage: /context [all|assistant|user|tool|large]".bright_yellow()
+					"Usage: /context [all|assistant|user|tool|pinned|large]".bright_yellow()
 				);
 				println!();
 				return;
@@ -425,12 +486,14 @@ impl ChatSession {
 				.session
 				.messages
 				.iter()
-				.map(|msg| crate::session::token_counter::estimate_tokens(&msg.content) as f64)
+				.map(|msg| {
+					crate::session::token_counter::estimate_tokens(&msg.content, &self.model) as f64
+				})
 				.collect();
 
 			// Calculate median
 			token_counts.sort_by(|a, b| a.partial_cmp(b).unwrap());
-			let median = if token_counts.len() % 2 == 0 {
+			let median = if token_counts.len().is_multiple_of(2) {
 				(token_counts[token_counts.len() / 2 - 1] + token_counts[token_counts.len() / 2])
 					/ 2.0
 			} else {
@@ -438,16 +501,18 @@ impl ChatSession {
 			};
 
 			// Calculate standard deviation
-			let variance: f64 =
-				self.session
-					.messages
-					.iter()
-					.map(|msg| {
-						let tokens =
-							crate::session::token_counter::estimate_tokens(&msg.content) as f64;
-						(tokens - median).powi(2)
-					})
-					.sum::<f64>() / self.session.messages.len() as f64;
+			let variance: f64 = self
+				.session
+				.messages
+				.iter()
+				.map(|msg| {
+					let tokens =
+						crate::session::token_counter::estimate_tokens(&msg.content, &self.model)
+							as f64;
+					(tokens - median).powi(2)
+				})
+				.sum::<f64>()
+				/ self.session.messages.len() as f64;
 			let std_dev = variance.sqrt();
 
 			let threshold = median + (2.0 * std_dev);
@@ -461,7 +526,8 @@ impl ChatSession {
 		// Process each filtered message
 		for (original_index, message) in &filtered_messages {
 			// Calculate tokens for this message
-			let message_tokens = crate::session::token_counter::estimate_tokens(&message.content);
+			let message_tokens =
+				crate::session::token_counter::estimate_tokens(&message.content, &self.model);
 			let percentage = if total_session_tokens > 0 {
 				(message_tokens as f64 / total_session_tokens as f64) * 100.0
 			} else {
@@ -494,6 +560,11 @@ impl ChatSession {
 				markdown_content.push_str("**Cached:** ✅ Yes\n");
 			}
 
+			// Add pinned status
+			if message.pinned {
+				markdown_content.push_str("**Pinned:** 📌 Yes\n");
+			}
+
 			// Add tool call ID if present
 			if let Some(ref tool_call_id) = message.tool_call_id {
 				markdown_content.push_str(&format!("**Tool Call ID:** {}\n", tool_call_id));
@@ -587,4 +658,82 @@ impl ChatSession {
 
 		println!();
 	}
+
+	// Display where context tokens go: one row per message with a running total
+	pub fn display_token_distribution(&self, sort_largest_first: bool) {
+		println!(
+			"{}",
+			"───────────── Context Token Distribution ─────────────".bright_cyan()
+		);
+
+		if self.session.messages.is_empty() {
+			println!("{}", "No messages in current session.".yellow());
+			println!();
+			return;
+		}
+
+		let mut rows: Vec<(usize, &crate::session::Message, usize)> = self
+			.session
+			.messages
+			.iter()
+			.enumerate()
+			.map(|(i, msg)| {
+				let tokens =
+					crate::session::estimate_message_tokens(std::slice::from_ref(msg), &self.model);
+				(i, msg, tokens)
+			})
+			.collect();
+		let total_tokens: usize = rows.iter().map(|(_, _, tokens)| tokens).sum();
+
+		if sort_largest_first {
+			rows.sort_by_key(|row| Reverse(row.2));
+		}
+
+		println!(
+			"{:>5}  {:<10} {:>9} {:>7} {:>10}  Cache",
+			"#", "Role", "Tokens", "Share", "Running"
+		);
+
+		let mut running_total = 0usize;
+		for (index, message, tokens) in rows {
+			running_total += tokens;
+			let share = if total_tokens > 0 {
+				tokens as f64 / total_tokens as f64 * 100.0
+			} else {
+				0.0
+			};
+
+			let row = format!(
+				"{:>5}  {:<10} {:>9} {:>6.1}% {:>10}  {}",
+				index + 1,
+				message.role,
+				format_number(tokens as u64),
+				share,
+				format_number(running_total as u64),
+				if message.cached { "● breakpoint" } else { "" }
+			);
+
+			// Highlight the messages that dominate the budget
+			if share >= 25.0 {
+				println!("{}", row.bright_red().bold());
+			} else if share >= 10.0 {
+				println!("{}", row.bright_yellow());
+			} else {
+				println!("{}", row);
+			}
+		}
+
+		println!();
+		println!(
+			"{} {} across {} messages",
+			"Total:".yellow(),
+			format_number(total_tokens as u64).bright_white(),
+			self.session.messages.len()
+		);
+		println!(
+			"{}",
+			"Red rows take 25% or more of the context, yellow rows 10% or more.".dimmed()
+		);
+		println!();
+	}
 }
@@ -20,6 +20,10 @@ use crate::session::ProviderExchange;
 use crate::{log_debug, log_info};
 use anyhow::Result;
 use colored::Colorize;
+use std::time::Instant;
+
+// An identical message sent again within this many seconds asks for confirmation
+const DUPLICATE_MESSAGE_WINDOW_SECONDS: u64 = 60;
 
 impl ChatSession {
 	// Save the session
@@ -95,6 +99,98 @@ impl ChatSession {
 		}
 	}
 
+	// Ask for confirmation when a request is projected to exceed the remaining spending budget
+	pub fn confirm_projected_cost(&mut self, config: &Config, estimated_cost: f64) -> Result<bool> {
+		// If threshold is 0 or negative, feature is disabled
+		if config.max_session_spending_threshold <= 0.0 {
+			return Ok(true);
+		}
+
+		let current_cost = self.session.info.total_cost;
+		let limit = self.spending_threshold_checkpoint + config.max_session_spending_threshold;
+		let remaining = limit - current_cost;
+
+		if estimated_cost <= remaining {
+			return Ok(true);
+		}
+
+		// Can't ask without a terminal - let the post-request threshold check handle it
+		if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+			return Ok(true);
+		}
+
+		use std::io::{self, Write};
+
+		println!();
+		println!(
+			"{}",
+			format!(
+				"This request will cost ~${:.2}, session total would be ${:.2} (limit ${:.2})",
+				estimated_cost,
+				current_cost + estimated_cost,
+				limit
+			)
+			.bright_yellow()
+		);
+		print!("{}", "Send anyway? (y/N): ".bright_white().bold());
+		io::stdout().flush()?;
+
+		let mut input = String::new();
+		io::stdin().read_line(&mut input)?;
+		let response = input.trim().to_lowercase();
+
+		if response == "y" || response == "yes" {
+			// User accepted the spend, start a new threshold window from here
+			self.spending_threshold_checkpoint = current_cost;
+			println!();
+			Ok(true)
+		} else {
+			println!(
+				"{}",
+				"✗ Request cancelled due to projected cost.".bright_red()
+			);
+			Ok(false)
+		}
+	}
+
+	// Ask before sending a message identical to the one sent just before it, which is
+	// usually an accidental double submit. Any difference at all counts as a new message.
+	pub fn confirm_repeated_message(&mut self, config: &Config, input: &str) -> Result<bool> {
+		let repeated = self
+			.last_sent_input
+			.as_ref()
+			.is_some_and(|(last, sent_at)| {
+				last == input && sent_at.elapsed().as_secs() < DUPLICATE_MESSAGE_WINDOW_SECONDS
+			});
+
+		// Without a terminal there is nobody to ask, so the message is sent
+		if config.dedupe_user_messages
+			&& repeated
+			&& std::io::IsTerminal::is_terminal(&std::io::stdin())
+		{
+			use std::io::{self, Write};
+
+			println!(
+				"{}",
+				"You just sent this exact message. Sending it again will make another request."
+					.bright_yellow()
+			);
+			print!("{}", "Send again? (y/N): ".bright_white().bold());
+			io::stdout().flush()?;
+
+			let mut response = String::new();
+			io::stdin().read_line(&mut response)?;
+			let response = response.trim().to_lowercase();
+			if response != "y" && response != "yes" {
+				println!("{}", "✗ Duplicate message not sent.".bright_red());
+				return Ok(false);
+			}
+		}
+
+		self.last_sent_input = Some((input.to_string(), Instant::now()));
+		Ok(true)
+	}
+
 	// Add a system message
 	pub fn add_system_message(&mut self, content: &str) -> Result<()> {
 		// Log to raw session log
@@ -105,7 +201,10 @@ impl ChatSession {
 
 		// Save to session file
 		if let Some(session_file) = &self.session.session_file {
-			let message_json = serde_json::to_string(&self.session.messages.last().unwrap())?;
+			let message_json = crate::session::message_to_session_line(
+				session_file,
+				self.session.messages.last().unwrap(),
+			)?;
 			crate::session::append_to_session_file(session_file, &message_json)?;
 		}
 
@@ -130,11 +229,23 @@ impl ChatSession {
 			println!("{}", "📎 Image attached to message".bright_green());
 		}
 
+		// Attach pending files
+		if !self.pending_attachments.is_empty() {
+			let attachments = std::mem::take(&mut self.pending_attachments);
+			println!(
+				"{}",
+				format!("📎 {} file(s) attached to message", attachments.len()).bright_green()
+			);
+			if let Some(last_msg) = self.session.messages.last_mut() {
+				last_msg.attachments = Some(attachments);
+			}
+		}
+
 		// Check if we should cache this user message
 		if self.cache_next_user_message {
 			let supports_caching = crate::session::model_supports_caching(&self.session.info.model);
 			if supports_caching {
-				let cache_manager = crate::session::cache::CacheManager::new();
+				let cache_manager = crate::session::cache::CacheManager::from_thread_config();
 				if let Ok(true) = cache_manager
 					.apply_cache_to_current_user_message(&mut self.session, supports_caching)
 				{
@@ -156,7 +267,10 @@ impl ChatSession {
 
 		// Save to session file
 		if let Some(session_file) = &self.session.session_file {
-			let message_json = serde_json::to_string(&self.session.messages.last().unwrap())?;
+			let message_json = crate::session::message_to_session_line(
+				session_file,
+				self.session.messages.last().unwrap(),
+			)?;
 			crate::session::append_to_session_file(session_file, &message_json)?;
 		}
 
@@ -192,6 +306,8 @@ impl ChatSession {
 			name: Some(tool_name.to_string()),
 			tool_calls: None,
 			images: None,
+			attachments: None,
+			pinned: false,
 		};
 
 		// Add message to session
@@ -199,7 +315,7 @@ impl ChatSession {
 
 		// Update token tracking for auto-cache threshold logic
 		// Tool messages count as "input" for the next API call, so we track them as non-cached input tokens
-		let tool_content_tokens = crate::session::estimate_tokens(content) as u64;
+		let tool_content_tokens = crate::session::estimate_tokens(content, &self.model) as u64;
 		let tool_overhead_tokens = 8; // Rough estimate for role + tool_call_id + name overhead
 
 		// Update the session's current token tracking
@@ -211,7 +327,10 @@ impl ChatSession {
 
 		// Save to session file
 		if let Some(session_file) = &self.session.session_file {
-			let message_json = serde_json::to_string(&self.session.messages.last().unwrap())?;
+			let message_json = crate::session::message_to_session_line(
+				session_file,
+				self.session.messages.last().unwrap(),
+			)?;
 			crate::session::append_to_session_file(session_file, &message_json)?;
 		}
 
@@ -231,12 +350,7 @@ impl ChatSession {
 
 		// Log raw API exchange if available
 		if let Some(ref ex) = exchange {
-			let _ = crate::session::logger::log_api_request(&self.session.info.name, &ex.request);
-			let _ = crate::session::logger::log_api_response(
-				&self.session.info.name,
-				&ex.response,
-				ex.usage.as_ref(),
-			);
+			let _ = crate::session::logger::log_exchange(&self.session.info.name, ex);
 		}
 
 		// Add message to session
@@ -259,6 +373,7 @@ impl ChatSession {
 				if let Some(api_time_ms) = usage.request_time_ms {
 					self.session.info.total_api_time_ms += api_time_ms;
 				}
+				self.session.info.reasoning_tokens += usage.reasoning_tokens;
 
 				// Update session token counts and use proper cache tracking
 				let cache_manager = crate::session::cache::CacheManager::new();
@@ -270,7 +385,7 @@ impl ChatSession {
 				);
 
 				// Check if we should automatically move the cache marker
-				let cache_manager = crate::session::cache::CacheManager::new();
+				let cache_manager = crate::session::cache::CacheManager::from_config(config);
 				let supports_caching =
 					crate::session::model_supports_caching(&self.session.info.model);
 				if let Ok(true) = cache_manager.check_and_apply_auto_cache_threshold(
@@ -362,21 +477,9 @@ impl ChatSession {
 
 		// Save to session file
 		if let Some(session_file) = &self.session.session_file {
-			let message_json = serde_json::to_string(&message)?;
+			let message_json = crate::session::message_to_session_line(session_file, &message)?;
 			crate::session::append_to_session_file(session_file, &message_json)?;
 
-			// If we have a raw exchange, save it inline in session file for complete restoration
-			if let Some(ex) = exchange {
-				// Save API request and response as separate prefixed lines for debugging
-				let _ =
-					crate::session::logger::log_api_request(&self.session.info.name, &ex.request);
-				let _ = crate::session::logger::log_api_response(
-					&self.session.info.name,
-					&ex.response,
-					ex.usage.as_ref(),
-				);
-			}
-
 			// Log session stats snapshot after each assistant response
 			let _ = crate::session::logger::log_session_stats(
 				&self.session.info.name,
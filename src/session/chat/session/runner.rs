@@ -21,13 +21,110 @@ use super::super::input::read_user_input;
 use super::super::response::process_response;
 use super::core::ChatSession;
 use crate::config::Config;
-use crate::session::create_system_prompt;
+use crate::session::{create_system_prompt, SessionInfo};
 use crate::{log_debug, log_info};
 use anyhow::Result;
 use std::io::Write; // Added for stdout flushing
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 
+// A second Ctrl+C within this window exits the session instead of cancelling the operation
+const FORCE_EXIT_WINDOW_MS: u64 = 1000;
+
+// Propagates the global Ctrl+C flag to one operation's cancellation token; stops when dropped
+struct CancelMonitor(tokio::task::JoinHandle<()>);
+
+impl CancelMonitor {
+	fn spawn(ctrl_c_pressed: Arc<AtomicBool>, operation_cancelled: Arc<AtomicBool>) -> Self {
+		Self(tokio::spawn(async move {
+			while !operation_cancelled.load(Ordering::SeqCst) {
+				if ctrl_c_pressed.load(Ordering::SeqCst) {
+					operation_cancelled.store(true, Ordering::SeqCst);
+					break;
+				}
+				// Very fast polling for immediate cancellation
+				tokio::time::sleep(tokio::time::Duration::from_millis(5)).await;
+			}
+		}))
+	}
+}
+
+impl Drop for CancelMonitor {
+	fn drop(&mut self) {
+		self.0.abort();
+	}
+}
+
+fn now_millis() -> u64 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.unwrap_or_default()
+		.as_millis() as u64
+}
+
+// Prompts the user typed in a saved session, in order, plus the seed it ran with; commands
+// and the rest of the history are skipped
+fn load_replay_prompts(source: &str) -> Result<(std::collections::VecDeque<String>, Option<u64>)> {
+	let session_file = crate::session::get_sessions_dir()?.join(format!("{}.jsonl", source));
+	let session = crate::session::load_session(&session_file)
+		.map_err(|e| anyhow::anyhow!("Failed to load session '{}' for replay: {}", source, e))?;
+
+	// Layered processing records the typed input and then the layers' output as a second
+	// user message; replaying that output would run it through the layers again
+	let prompts: std::collections::VecDeque<String> = session
+		.messages
+		.iter()
+		.enumerate()
+		.filter(|(index, msg)| {
+			msg.role == "user"
+				&& !msg.content.trim_start().starts_with('/')
+				&& !(*index > 0 && session.messages[index - 1].role == "user")
+		})
+		.map(|(_, msg)| msg.content.clone())
+		.collect();
+
+	if prompts.is_empty() {
+		return Err(anyhow::anyhow!(
+			"Session '{}' has no user prompts to replay",
+			source
+		));
+	}
+	Ok((prompts, session.info.seed))
+}
+
+// Theme recorded in the session, falling back to the global one
+fn session_theme(chat_session: &ChatSession, global_theme: &str) -> String {
+	chat_session
+		.session
+		.info
+		.markdown_theme
+		.clone()
+		.unwrap_or_else(|| global_theme.to_string())
+}
+
+// Counters that change whenever the session has something new worth checkpointing
+fn checkpoint_state(chat_session: &ChatSession) -> (usize, u64, u64, u64, u64) {
+	let info = &chat_session.session.info;
+	(
+		chat_session.session.messages.len(),
+		info.input_tokens,
+		info.output_tokens,
+		info.tool_calls,
+		info.total_cost.to_bits(),
+	)
+}
+
+// Session file and stats as of the last completed turn, for checkpoints from signal handlers
+type CheckpointSnapshot = std::sync::Mutex<Option<(std::path::PathBuf, SessionInfo)>>;
+
+fn write_snapshot_checkpoint(snapshot: &CheckpointSnapshot) {
+	if let Ok(guard) = snapshot.lock() {
+		if let Some((session_file, info)) = guard.as_ref() {
+			let _ = crate::session::append_summary_line(session_file, info);
+		}
+	}
+}
+
 // Run an interactive session
 pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 	args: &T,
@@ -58,6 +155,34 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 		/// Session role: developer (default with layers and tools) or assistant (simple chat without tools)
 		#[arg(long, default_value = "developer")]
 		role: String,
+
+		/// File to append machine-readable events to
+		#[arg(long)]
+		event_log: Option<String>,
+
+		/// Saved session whose user prompts are re-run in a fresh session
+		#[arg(long)]
+		replay: Option<String>,
+
+		/// Reject file-mutating tools and shell
+		#[arg(long)]
+		read_only: bool,
+
+		/// Send no tool definitions at all
+		#[arg(long)]
+		no_tools: bool,
+
+		/// Leave secrets in tool results unmasked
+		#[arg(long)]
+		no_redact: bool,
+
+		/// Sampling seed passed to providers that support it
+		#[arg(long)]
+		seed: Option<u64>,
+
+		/// Stop sequences replacing the configured ones
+		#[arg(long)]
+		stop: Vec<String>,
 	}
 
 	// Read args as SessionArgs
@@ -112,18 +237,89 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 			0.7 // Default temperature
 		};
 
+		// Get event log path
+		let event_log = if args_str.contains("event_log: Some(\"") {
+			let start = args_str.find("event_log: Some(\"").unwrap() + 17;
+			let end = args_str[start..].find('\"').unwrap() + start;
+			Some(args_str[start..end].to_string())
+		} else {
+			None
+		};
+
+		// Get replay source session
+		let replay = if args_str.contains("replay: Some(\"") {
+			let start = args_str.find("replay: Some(\"").unwrap() + 14;
+			let end = args_str[start..].find('\"').unwrap() + start;
+			Some(args_str[start..end].to_string())
+		} else {
+			None
+		};
+
+		// Get read-only flag
+		let read_only = args_str.contains("read_only: true");
+
+		// Get no-tools flag
+		let no_tools = args_str.contains("no_tools: true");
+
+		// Get no-redact flag
+		let no_redact = args_str.contains("no_redact: true");
+
+		// Get seed
+		let seed = if args_str.contains("seed: Some(") {
+			let start = args_str.find("seed: Some(").unwrap() + 11;
+			let end = args_str[start..].find(')').unwrap() + start;
+			args_str[start..end].parse::<u64>().ok()
+		} else {
+			None
+		};
+
+		// Get stop sequences - the Debug list of strings reads back as JSON
+		let stop = match args_str.find("stop: [") {
+			Some(start) => serde_json::Deserializer::from_str(&args_str[start + 6..])
+				.into_iter::<Vec<String>>()
+				.next()
+				.and_then(|parsed| parsed.ok())
+				.unwrap_or_default(),
+			None => Vec::new(),
+		};
+
 		SessionArgs {
 			name,
 			resume,
 			model,
 			temperature,
 			role,
+			event_log,
+			replay,
+			read_only,
+			no_tools,
+			no_redact,
+			seed,
+			stop,
+		}
+	};
+
+	// Replay prompts are queued up front so a bad source session fails before anything starts
+	let (mut replay_queue, replay_seed) = match &session_args.replay {
+		Some(source) => {
+			if let Some(name) = &session_args.name {
+				let target = crate::session::get_sessions_dir()?.join(format!("{}.jsonl", name));
+				if target.exists() {
+					return Err(anyhow::anyhow!(
+						"Session '{}' already exists - replay needs a fresh session name",
+						name
+					));
+				}
+			}
+			let (prompts, seed) = load_replay_prompts(source)?;
+			(Some(prompts), seed)
 		}
+		None => (None, None),
 	};
 
 	// For developer role, show MCP server status
 	let current_dir = std::env::current_dir()?;
-	if session_args.role == "developer" {
+	if session_args.role == "developer" && !session_args.no_tools {
 		// Check if external MCP server is configured
 		let role_config = config.get_role_config(&session_args.role);
 		let mcp_config = &role_config.1;
@@ -176,7 +372,14 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 	}
 
 	// Get the merged configuration for the specified role
-	let config_for_role = config.get_merged_config_for_role(&session_args.role);
+	let mut config_for_role = config.get_merged_config_for_role(&session_args.role);
+	config_for_role.read_only |= session_args.read_only;
+	if session_args.no_tools {
+		config_for_role.disable_tools();
+	}
+	if session_args.no_redact {
+		config_for_role.redact_secrets = false;
+	}
 
 	// Create or load session
 	let mut chat_session = ChatSession::initialize(
@@ -191,16 +394,76 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 	// If runtime model override is provided, update the session's model (runtime only)
 	if let Some(ref runtime_model) = session_args.model {
 		chat_session.model = runtime_model.clone();
+		// Layers inherit the session model, so resumed sessions must switch too; checkpoints
+		// keep recording the session's own model
+		let saved_model =
+			std::mem::replace(&mut chat_session.session.info.model, runtime_model.clone());
+		chat_session.session.saved_model = Some(saved_model);
 		log_info!("Using runtime model override: {}", runtime_model);
 	}
 
 	// Always set the temperature from the command line (runtime only)
 	chat_session.temperature = session_args.temperature;
 
+	// Seed: --seed, else the replayed session's, else the one recorded in a resumed session
+	let seed = session_args
+		.seed
+		.or(replay_seed)
+		.or(chat_session.session.info.seed);
+	chat_session.session.info.seed = seed;
+	config_for_role.seed = seed;
+	if let Some(seed) = seed {
+		log_info!("Using sampling seed: {}", seed);
+	}
+
+	// --stop replaces the role's stop sequences; layers and commands keep their own
+	if !session_args.stop.is_empty() {
+		config_for_role.stop_sequences = session_args.stop.clone();
+	}
+
+	// Start the operational event log, tagged with the session name for correlation
+	if let Some(ref event_log) = session_args.event_log {
+		crate::session::event_log::init_event_log(
+			std::path::Path::new(event_log),
+			&chat_session.session.info.name,
+		)?;
+	}
+
+	if config_for_role.read_only {
+		use colored::*;
+		println!(
+			"{}",
+			"🔒 Read-only session: file edits and shell commands are blocked".bright_yellow()
+		);
+	}
+
+	if config_for_role.no_tools {
+		use colored::*;
+		println!(
+			"{}",
+			"💬 Tools disabled: no MCP tools are available in this session".bright_yellow()
+		);
+	}
+
 	// Track if the first message has been processed through layers
 	let mut first_message_processed = !chat_session.session.messages.is_empty();
-	println!("Interactive coding session started. Type your questions/requests.");
-	println!("Type /help for available commands.");
+	if let (Some(source), Some(queue)) = (&session_args.replay, &replay_queue) {
+		use colored::*;
+		println!(
+			"{}",
+			format!(
+				"Replaying {} prompts from '{}' into session '{}' with {}",
+				queue.len(),
+				source,
+				chat_session.session.info.name,
+				chat_session.model
+			)
+			.bright_cyan()
+		);
+	} else {
+		println!("Interactive coding session started. Type your questions/requests.");
+		println!("Type /help for available commands.");
+	}
 
 	// Show history usage info for new sessions
 	if chat_session.session.messages.is_empty() {
@@ -214,7 +477,8 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 	// Initialize with system prompt if new session
 	if chat_session.session.messages.is_empty() {
 		// Create system prompt based on role
-		let system_prompt = create_system_prompt(&current_dir, config, &session_args.role).await;
+		let system_prompt =
+			create_system_prompt(&current_dir, &config_for_role, &session_args.role).await;
 		chat_session.add_system_message(&system_prompt)?;
 
 		// Process layer system prompts during session initialization
@@ -299,6 +563,9 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 	// Set up advanced cancellation system for proper CTRL+C handling
 	let ctrl_c_pressed = Arc::new(AtomicBool::new(false));
 	let ctrl_c_pressed_clone = ctrl_c_pressed.clone();
+	let last_ctrl_c_ms = Arc::new(AtomicU64::new(0));
+	let checkpoint_snapshot: Arc<CheckpointSnapshot> = Arc::new(std::sync::Mutex::new(None));
+	let checkpoint_snapshot_clone = checkpoint_snapshot.clone();
 
 	// Enhanced processing state tracking for smart cancellation
 	#[derive(Debug, Clone, PartialEq)]
@@ -329,9 +596,14 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 
 	// Set up sophisticated Ctrl+C handler with immediate feedback
 	ctrlc::set_handler(move || {
-		// Double Ctrl+C forces immediate exit
-		if ctrl_c_pressed_clone.load(Ordering::SeqCst) {
+		// Two quick Ctrl+C presses force immediate exit as a safety escape
+		let now = now_millis();
+		let previous = last_ctrl_c_ms.swap(now, Ordering::SeqCst);
+		if ctrl_c_pressed_clone.load(Ordering::SeqCst)
+			&& now.saturating_sub(previous) < FORCE_EXIT_WINDOW_MS
+		{
 			println!("\n🛑 Forcing exit due to repeated Ctrl+C...");
+			write_snapshot_checkpoint(&checkpoint_snapshot_clone);
 			std::process::exit(130); // 130 is standard exit code for SIGINT
 		}
 
@@ -365,22 +637,67 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 			}
 		}
 
-		println!("💡 Press Ctrl+C again to force exit");
+		println!("💡 Press Ctrl+C again within a second to force exit");
 		std::io::stdout().flush().unwrap();
 	})
 	.expect("Error setting Ctrl+C handler");
 
+	// SIGTERM (service managers, container shutdown) checkpoints the session before exiting
+	#[cfg(unix)]
+	{
+		let checkpoint_snapshot = checkpoint_snapshot.clone();
+		tokio::spawn(async move {
+			use tokio::signal::unix::{signal, SignalKind};
+			if let Ok(mut sigterm) = signal(SignalKind::terminate()) {
+				sigterm.recv().await;
+				write_snapshot_checkpoint(&checkpoint_snapshot);
+				std::process::exit(143); // 128 + SIGTERM
+			}
+		});
+	}
+
 	// We need to handle configuration reloading, so keep our own copy that we can update
 	let mut current_config = config_for_role.clone();
+	current_config.markdown_theme = session_theme(&chat_session, &config_for_role.markdown_theme);
 
 	// Set the thread-local config for logging macros
 	crate::config::set_thread_config(&current_config);
 
+	// Session model and temperature to restore after a /retry attempt with overrides
+	let mut retry_restore: Option<(String, f32)> = None;
+
+	// Periodic autosave only writes when something changed since the last checkpoint
+	let mut last_autosave = std::time::Instant::now();
+	let mut autosaved_state = checkpoint_state(&chat_session);
+
 	// Main interaction loop
 	loop {
 		// Set processing state to idle
 		*processing_state.lock().unwrap() = ProcessingState::Idle;
 
+		// /retry overrides apply to a single attempt
+		if let Some((model, temperature)) = retry_restore.take() {
+			chat_session.model = model;
+			chat_session.temperature = temperature;
+		}
+
+		if current_config.autosave_interval_seconds > 0
+			&& last_autosave.elapsed().as_secs() >= current_config.autosave_interval_seconds
+		{
+			let state = checkpoint_state(&chat_session);
+			if state != autosaved_state {
+				match chat_session.session.save_checkpoint() {
+					Ok(()) => autosaved_state = state,
+					Err(e) => log_debug!("Autosave failed: {}", e),
+				}
+			}
+			last_autosave = std::time::Instant::now();
+		}
+		if let Some(session_file) = &chat_session.session.session_file {
+			*checkpoint_snapshot.lock().unwrap() =
+				Some((session_file.clone(), chat_session.session.checkpoint_info()));
+		}
+
 		// SMART CANCELLATION: Handle cancellation with surgical cleanup
 		if ctrl_c_pressed.load(Ordering::SeqCst) {
 			log_debug!("Ctrl+C detected - performing smart cleanup based on operation state");
@@ -451,7 +768,30 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 		let operation_cancelled = Arc::new(AtomicBool::new(false));
 
 		// Read user input with command completion and cost estimation
-		let mut input = read_user_input(chat_session.estimated_cost)?;
+		// During replay the next recorded prompt stands in for user input
+		let mut input = match replay_queue.as_mut() {
+			Some(queue) => match queue.pop_front() {
+				Some(prompt) => {
+					use colored::*;
+					println!("{} {}", "> (replay)".bright_cyan(), prompt);
+					prompt
+				}
+				None => {
+					use colored::*;
+					println!(
+						"{}",
+						format!(
+							"Replay complete. Compare costs with: octomind session --resume {}",
+							chat_session.session.info.name
+						)
+						.bright_green()
+					);
+					chat_session.save()?;
+					break;
+				}
+			},
+			None => read_user_input(chat_session.estimated_cost)?,
+		};
 
 		// Check if the input is an exit command from Ctrl+D
 		if input == "/exit" || input == "/quit" {
@@ -464,6 +804,27 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 			continue;
 		}
 
+		// /retry swaps in the last user message and continues with the normal flow below
+		let mut is_retry = false;
+		if input.split_whitespace().next() == Some(RETRY_COMMAND) {
+			let params: Vec<&str> = input.split_whitespace().skip(1).collect();
+			let retry = match chat_session.prepare_retry(&params)? {
+				Some(retry) => retry,
+				None => continue,
+			};
+			if retry.model.is_some() || retry.temperature.is_some() {
+				retry_restore = Some((chat_session.model.clone(), chat_session.temperature));
+				if let Some(model) = retry.model {
+					chat_session.model = model;
+				}
+				if let Some(temperature) = retry.temperature {
+					chat_session.temperature = temperature;
+				}
+			}
+			input = retry.input;
+			is_retry = true;
+		}
+
 		// Check if this is a command
 		if input.starts_with('/') {
 			// Handle special /done command separately
@@ -505,13 +866,29 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 				.process_command(&input, &mut current_config, &session_args.role)
 				.await?;
 			if exit {
-				// First check if it's a session switch command
-				if input.starts_with(SESSION_COMMAND) {
+				// First check if it's a session switch command (branching also switches)
+				if input.starts_with(SESSION_COMMAND)
+					|| input.starts_with(BRANCH_COMMAND)
+					|| input.starts_with(FORK_FROM_COMMAND)
+				{
 					// We need to switch to another session
 					let new_session_name = chat_session.session.info.name.clone();
 
+					// The command left the target name in info.name; put the current session's
+					// own name back so its checkpoint isn't recorded under the new session
+					if let Some(current_name) = chat_session
+						.session
+						.session_file
+						.as_ref()
+						.and_then(|path| path.file_stem())
+						.and_then(|stem| stem.to_str())
+					{
+						chat_session.session.info.name = current_name.to_string();
+					}
+
 					// Save current session before switching
 					chat_session.save()?;
+					chat_session.session.save_checkpoint()?;
 
 					// Initialize the new session
 					let new_chat_session = ChatSession::initialize(
@@ -525,6 +902,24 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 
 					// Replace the current chat session
 					chat_session = new_chat_session;
+					// A branch starts with the context it kept, none of which is cached yet
+					if !input.starts_with(SESSION_COMMAND) {
+						let retained_tokens = crate::session::estimate_message_tokens(
+							&chat_session.session.messages,
+							&chat_session.model,
+						) as u64;
+						chat_session.session.current_total_tokens = retained_tokens;
+						chat_session.session.current_non_cached_tokens = retained_tokens;
+					}
+					autosaved_state = checkpoint_state(&chat_session);
+					let seed = session_args.seed.or(chat_session.session.info.seed);
+					chat_session.session.info.seed = seed;
+					current_config.seed = seed;
+					crate::session::event_log::set_event_log_session(
+						&chat_session.session.info.name,
+					);
+					current_config.markdown_theme =
+						session_theme(&chat_session, &config_for_role.markdown_theme);
 
 					// Reset first message flag for new session
 					first_message_processed = !chat_session.session.messages.is_empty();
@@ -559,6 +954,19 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 							// Update our current config with the new role-specific config
 							current_config =
 								updated_config.get_merged_config_for_role(&session_args.role);
+							current_config.read_only |= session_args.read_only;
+							if session_args.no_tools {
+								current_config.disable_tools();
+							}
+							if session_args.no_redact {
+								current_config.redact_secrets = false;
+							}
+							current_config.seed = chat_session.session.info.seed;
+							if !session_args.stop.is_empty() {
+								current_config.stop_sequences = session_args.stop.clone();
+							}
+							current_config.markdown_theme =
+								session_theme(&chat_session, &updated_config.markdown_theme);
 							// Update thread config for logging macros
 							crate::config::set_thread_config(&current_config);
 							log_info!("Configuration reloaded successfully");
@@ -577,17 +985,34 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 			continue;
 		}
 
+		// A retry or replay repeats a message on purpose
+		if !is_retry
+			&& replay_queue.is_none()
+			&& !chat_session.confirm_repeated_message(&current_config, &input)?
+		{
+			continue;
+		}
+
 		// Check for cancellation before starting layered processing
 		if ctrl_c_pressed.load(Ordering::SeqCst) {
 			continue;
 		}
 
+		// Ctrl+C cancels only this operation (layers, API call, tools); the monitor stops
+		// at the end of the iteration so it never outlives the operation
+		let _cancel_monitor =
+			CancelMonitor::spawn(ctrl_c_pressed.clone(), operation_cancelled.clone());
+
 		// SIMPLIFIED FLOW:
 		// 1. Process through layers if needed (first message with layers enabled)
 		// 2. Use the processed input for the main model chat
 
 		// If layers are enabled and this is the first message, process it through layers first
-		if current_config.get_enable_layers(&session_args.role) && !first_message_processed {
+		// A retried message already went through layers when it was first sent
+		if current_config.get_enable_layers(&session_args.role)
+			&& !first_message_processed
+			&& !is_retry
+		{
 			// Set processing state to layers
 			*processing_state.lock().unwrap() = ProcessingState::ProcessingLayers;
 
@@ -734,22 +1159,6 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 			let _ = show_loading_animation(animation_cancel_clone, current_cost).await;
 		});
 
-		// Start a separate task to monitor for Ctrl+C and propagate to operation_cancelled flag
-		let op_cancelled = operation_cancelled.clone();
-		let ctrlc_flag = ctrl_c_pressed.clone();
-		let _cancel_monitor = tokio::spawn(async move {
-			while !op_cancelled.load(Ordering::SeqCst) {
-				// Check if global Ctrl+C flag is set
-				if ctrlc_flag.load(Ordering::SeqCst) {
-					// Set the operation cancellation flag immediately
-					op_cancelled.store(true, Ordering::SeqCst);
-					break; // Exit the loop once cancelled
-				}
-				// Use very fast polling for immediate response
-				tokio::time::sleep(tokio::time::Duration::from_millis(5)).await;
-			}
-		});
-
 		// Check for Ctrl+C before making API call
 		if ctrl_c_pressed.load(Ordering::SeqCst) {
 			// Immediately stop and return to main loop
@@ -836,18 +1245,8 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 				let tool_process_cancelled = Arc::new(AtomicBool::new(false));
 
 				// Connect global cancellation to tool processing cancellation
-				let tool_cancelled_clone = tool_process_cancelled.clone();
-				let ctrl_c_clone = ctrl_c_pressed.clone();
-				let _tool_cancel_monitor = tokio::spawn(async move {
-					while !tool_cancelled_clone.load(Ordering::SeqCst) {
-						if ctrl_c_clone.load(Ordering::SeqCst) {
-							tool_cancelled_clone.store(true, Ordering::SeqCst);
-							break;
-						}
-						// Very fast polling for immediate tool cancellation
-						tokio::time::sleep(tokio::time::Duration::from_millis(5)).await;
-					}
-				});
+				let _tool_cancel_monitor =
+					CancelMonitor::spawn(ctrl_c_pressed.clone(), tool_process_cancelled.clone());
 
 				// Convert to legacy format for compatibility
 				let legacy_exchange = response.exchange;
@@ -908,6 +1307,14 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 					}
 				}
 
+				// Declined cost preview was already reported - not a provider error
+				if e.downcast_ref::<crate::session::ProjectedCostDeclined>()
+					.is_some()
+				{
+					*current_operation.lock().unwrap() = None;
+					continue;
+				}
+
 				// Print colorful error message with provider-aware context
 				use colored::*;
 
@@ -926,32 +1333,40 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 					e
 				);
 
-				// Provider-specific help message
-				match provider_name.to_lowercase().as_str() {
-					"openrouter" => {
-						println!("{}", "Make sure OpenRouter API key is set in the config or as OPENROUTER_API_KEY environment variable.".yellow());
-					}
-					"anthropic" => {
-						println!("{}", "Make sure Anthropic API key is set in the config or as ANTHROPIC_API_KEY environment variable.".yellow());
-					}
-					"openai" => {
-						println!("{}", "Make sure OpenAI API key is set in the config or as OPENAI_API_KEY environment variable.".yellow());
-					}
-					"google" => {
-						println!("{}", "Make sure Google credentials are set in the config or as GOOGLE_APPLICATION_CREDENTIALS environment variable.".yellow());
-					}
-					"amazon" => {
-						println!("{}", "Make sure AWS credentials are configured properly for Amazon Bedrock access.".yellow());
-					}
-					"cloudflare" => {
-						println!("{}", "Make sure Cloudflare API key is set in the config or as CLOUDFLARE_API_KEY environment variable.".yellow());
-					}
-					_ => {
-						println!(
-							"{}",
-							"Make sure the API key for this provider is properly configured."
-								.yellow()
-						);
+				// Timeouts are transient - credentials hints would be misleading
+				if crate::providers::is_timeout_error(&e) {
+					println!("{}", format!("The request timed out. Try again, or increase api_timeout_seconds (currently {}) in the config.", current_config.api_timeout_seconds).yellow());
+				} else {
+					// Provider-specific help message
+					match provider_name.to_lowercase().as_str() {
+						"openrouter" => {
+							println!("{}", "Make sure OpenRouter API key is set in the config or as OPENROUTER_API_KEY environment variable.".yellow());
+						}
+						"anthropic" => {
+							println!("{}", "Make sure Anthropic API key is set in the config or as ANTHROPIC_API_KEY environment variable.".yellow());
+						}
+						"openai" => {
+							println!("{}", "Make sure OpenAI API key is set in the config or as OPENAI_API_KEY environment variable.".yellow());
+						}
+						"google" => {
+							println!("{}", "Make sure Google credentials are set in the config or as GOOGLE_APPLICATION_CREDENTIALS environment variable.".yellow());
+						}
+						"amazon" => {
+							println!("{}", "Make sure AWS credentials are configured properly for Amazon Bedrock access.".yellow());
+						}
+						"cloudflare" => {
+							println!("{}", "Make sure Cloudflare API key is set in the config or as CLOUDFLARE_API_KEY environment variable.".yellow());
+						}
+						"ollama" => {
+							println!("{}", "Make sure the Ollama server is running ('ollama serve') and reachable at OLLAMA_HOST (default http://localhost:11434).".yellow());
+						}
+						_ => {
+							println!(
+								"{}",
+								"Make sure the API key for this provider is properly configured."
+									.yellow()
+							);
+						}
 					}
 				}
 			}
@@ -961,5 +1376,12 @@ pub async fn run_interactive_session<T: clap::Args + std::fmt::Debug>(
 		*current_operation.lock().unwrap() = None;
 	}
 
+	// Graceful shutdown keeps the latest stats
+	if checkpoint_state(&chat_session) != autosaved_state {
+		if let Err(e) = chat_session.session.save_checkpoint() {
+			log_debug!("Final session checkpoint failed: {}", e);
+		}
+	}
+
 	Ok(())
 }
@@ -39,6 +39,11 @@ impl SyntaxHighlighter {
 		language: &str,
 		theme_name: &str,
 	) -> Result<String> {
+		// Diffs get fixed colors per line type rather than theme colors
+		if is_diff_language(language) {
+			return Ok(highlight_diff(code));
+		}
+
 		// Try to find syntax definition for the language
 		let syntax = self
 			.syntax_set
@@ -86,6 +91,51 @@ impl SyntaxHighlighter {
 	}
 }
 
+/// Whether a fenced block's language tag marks a unified diff
+pub fn is_diff_language(language: &str) -> bool {
+	language.eq_ignore_ascii_case("diff") || language.eq_ignore_ascii_case("patch")
+}
+
+/// Color unified diff lines by their prefix; lines that match no diff syntax
+/// (malformed or prose inside the block) are left as they are
+pub fn highlight_diff(code: &str) -> String {
+	const RESET: &str = "\x1b[0m";
+	let mut highlighted = String::new();
+
+	for line in LinesWithEndings::from(code) {
+		let style = if line.starts_with("+++") || line.starts_with("---") {
+			Some("\x1b[1m") // File headers in bold
+		} else if line.starts_with("@@") {
+			Some("\x1b[36m") // Hunk headers in cyan
+		} else if line.starts_with('+') {
+			Some("\x1b[32m") // Added lines in green
+		} else if line.starts_with('-') {
+			Some("\x1b[31m") // Removed lines in red
+		} else if line.starts_with("diff ") || line.starts_with("index ") || line.starts_with('\\')
+		{
+			Some("\x1b[2m") // Git metadata and "\ No newline at end of file" dimmed
+		} else {
+			None
+		};
+
+		match style {
+			Some(style) => {
+				let (text, ending) = match line.strip_suffix('\n') {
+					Some(text) => (text, "\n"),
+					None => (line, ""),
+				};
+				highlighted.push_str(style);
+				highlighted.push_str(text);
+				highlighted.push_str(RESET);
+				highlighted.push_str(ending);
+			}
+			None => highlighted.push_str(line),
+		}
+	}
+
+	highlighted
+}
+
 impl Default for SyntaxHighlighter {
 	fn default() -> Self {
 		Self::new()
@@ -115,6 +165,30 @@ mod tests {
 		assert!(!themes.is_empty());
 	}
 
+	#[test]
+	fn test_diff_highlighting() {
+		let highlighter = SyntaxHighlighter::new();
+		let diff =
+			"--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,2 +1,2 @@\n fn main() {\n-old\n+new\n";
+		let result = highlighter
+			.highlight_code_with_theme(diff, "diff", "base16-ocean.dark")
+			.unwrap();
+		assert!(result.contains("\x1b[1m--- a/src/lib.rs\x1b[0m\n"));
+		assert!(result.contains("\x1b[36m@@ -1,2 +1,2 @@\x1b[0m\n"));
+		assert!(result.contains("\x1b[31m-old\x1b[0m\n"));
+		assert!(result.contains("\x1b[32m+new\x1b[0m\n"));
+		// Context lines are untouched
+		assert!(result.contains("\n fn main() {\n"));
+
+		// Malformed diffs keep every line, coloring only what matches
+		let malformed = "not a diff\n+added";
+		assert_eq!(
+			highlight_diff(malformed),
+			"not a diff\n\x1b[32m+added\x1b[0m"
+		);
+		assert!(is_diff_language("PATCH"));
+	}
+
 	#[test]
 	fn test_rust_highlighting() {
 		let highlighter = SyntaxHighlighter::new();
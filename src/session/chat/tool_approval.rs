@@ -0,0 +1,188 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tool approval module - asks the user which proposed tool calls may run
+
+use crate::config::ToolApproval;
+use crate::mcp::{McpToolCall, McpToolResult};
+use anyhow::Result;
+use colored::Colorize;
+use std::io::{self, Write};
+
+// Longest argument preview shown in the per-call list
+const ARGUMENTS_PREVIEW_CHARS: usize = 100;
+
+/// Ask which tool calls may run according to the approval mode. Returns the approved
+/// calls and error results for the declined ones; without a terminal every call runs.
+pub fn review_tool_calls(
+	tool_calls: Vec<McpToolCall>,
+	mode: ToolApproval,
+) -> Result<(Vec<McpToolCall>, Vec<McpToolResult>)> {
+	if mode == ToolApproval::Auto
+		|| tool_calls.is_empty()
+		|| !std::io::IsTerminal::is_terminal(&io::stdin())
+	{
+		return Ok((tool_calls, Vec::new()));
+	}
+
+	let selected = match mode {
+		ToolApproval::PerCall => select_tool_calls(&tool_calls)?,
+		_ => vec![confirm_all(tool_calls.len())?; tool_calls.len()],
+	};
+
+	let mut approved = Vec::new();
+	let mut declined = Vec::new();
+	for (tool_call, run) in tool_calls.into_iter().zip(selected) {
+		if run {
+			approved.push(tool_call);
+		} else {
+			println!(
+				"{}",
+				format!("✗ Tool '{}' declined", tool_call.tool_name).bright_yellow()
+			);
+			declined.push(McpToolResult::error(
+				tool_call.tool_name,
+				tool_call.tool_id,
+				"The user declined this tool call, so it was not executed. Do not repeat it \
+				 unchanged - continue without it or ask the user how to proceed."
+					.to_string(),
+			));
+		}
+	}
+
+	Ok((approved, declined))
+}
+
+// Approve or decline the whole batch
+fn confirm_all(count: usize) -> Result<bool> {
+	let prompt = if count == 1 {
+		"Run this tool call? (y/N): ".to_string()
+	} else {
+		format!("Run these {} tool calls? (y/N): ", count)
+	};
+	print!("{}", prompt.bright_white().bold());
+	io::stdout().flush()?;
+
+	let mut input = String::new();
+	io::stdin().read_line(&mut input)?;
+	let response = input.trim().to_lowercase();
+	Ok(response == "y" || response == "yes")
+}
+
+// Toggle calls on and off until the user confirms the selection
+fn select_tool_calls(tool_calls: &[McpToolCall]) -> Result<Vec<bool>> {
+	let mut selected = vec![true; tool_calls.len()];
+
+	loop {
+		println!();
+		for (index, (tool_call, run)) in tool_calls.iter().zip(&selected).enumerate() {
+			let mark = if *run {
+				"[x]".bright_green()
+			} else {
+				"[ ]".bright_red()
+			};
+			println!(
+				"{} {}. {} {}",
+				mark,
+				index + 1,
+				tool_call.tool_name.bright_cyan(),
+				arguments_preview(&tool_call.parameters).dimmed()
+			);
+		}
+		print!(
+			"{}",
+			"Toggle calls by number (e.g. 1 3), a = all, n = none, Enter = run selected: "
+				.bright_white()
+				.bold()
+		);
+		io::stdout().flush()?;
+
+		let mut input = String::new();
+		io::stdin().read_line(&mut input)?;
+		match parse_selection(input.trim(), tool_calls.len()) {
+			Some(Selection::Confirm) => return Ok(selected),
+			Some(Selection::All) => selected.fill(true),
+			Some(Selection::Nothing) => selected.fill(false),
+			Some(Selection::Toggle(indices)) => {
+				for index in indices {
+					selected[index] = !selected[index];
+				}
+			}
+			None => println!(
+				"{}",
+				format!(
+					"Enter call numbers between 1 and {}, a, n, or press Enter",
+					tool_calls.len()
+				)
+				.bright_red()
+			),
+		}
+	}
+}
+
+#[derive(Debug, PartialEq)]
+enum Selection {
+	Confirm,
+	All,
+	Nothing,
+	Toggle(Vec<usize>), // 0-indexed
+}
+
+// Parse one line of the per-call prompt; None when it is not valid
+fn parse_selection(input: &str, count: usize) -> Option<Selection> {
+	match input.to_lowercase().as_str() {
+		"" => Some(Selection::Confirm),
+		"a" | "all" => Some(Selection::All),
+		"n" | "none" => Some(Selection::Nothing),
+		other => other
+			.split(|c: char| c == ',' || c.is_whitespace())
+			.filter(|part| !part.is_empty())
+			.map(|part| match part.parse::<usize>() {
+				Ok(number) if (1..=count).contains(&number) => Some(number - 1),
+				_ => None,
+			})
+			.collect::<Option<Vec<_>>>()
+			.map(Selection::Toggle),
+	}
+}
+
+// Compact one-line view of the call arguments
+fn arguments_preview(parameters: &serde_json::Value) -> String {
+	let text = parameters.to_string();
+	if text.chars().count() <= ARGUMENTS_PREVIEW_CHARS {
+		text
+	} else {
+		let truncated: String = text.chars().take(ARGUMENTS_PREVIEW_CHARS - 3).collect();
+		format!("{}...", truncated)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_selection() {
+		assert_eq!(parse_selection("", 3), Some(Selection::Confirm));
+		assert_eq!(parse_selection("A", 3), Some(Selection::All));
+		assert_eq!(parse_selection("none", 3), Some(Selection::Nothing));
+		assert_eq!(
+			parse_selection("1, 3", 3),
+			Some(Selection::Toggle(vec![0, 2]))
+		);
+		assert_eq!(parse_selection("4", 3), None);
+		assert_eq!(parse_selection("0", 3), None);
+		assert_eq!(parse_selection("x", 3), None);
+	}
+}
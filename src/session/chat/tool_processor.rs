@@ -104,6 +104,8 @@ impl ToolProcessor {
 						name: Some(tool_call.tool_name.clone()),
 						tool_calls: None,
 						images: None,
+						attachments: None,
+						pinned: false,
 					};
 
 					chat_session.session.messages.push(tool_message);
@@ -138,6 +140,8 @@ impl ToolProcessor {
 						name: Some(tool_call.tool_name.clone()),
 						tool_calls: None,
 						images: None,
+						attachments: None,
+						pinned: false,
 					};
 
 					chat_session.session.messages.push(tool_message);
@@ -0,0 +1,166 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Append-only operational event log (newline-delimited JSON) for monitoring agent runs
+
+use anyhow::Result;
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{File, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+lazy_static::lazy_static! {
+	// Only one event log per process, enabled with --event-log
+	static ref EVENT_LOG: Mutex<Option<EventLog>> = Mutex::new(None);
+}
+
+struct EventLog {
+	file: File,
+	session_id: String,
+}
+
+/// Start writing events to `path` (appending if it exists), tagged with `session_id`
+pub fn init_event_log(path: &Path, session_id: &str) -> Result<()> {
+	let file = OpenOptions::new()
+		.create(true)
+		.append(true)
+		.open(path)
+		.map_err(|e| anyhow::anyhow!("Failed to open event log {}: {}", path.display(), e))?;
+
+	*EVENT_LOG.lock().unwrap() = Some(EventLog {
+		file,
+		session_id: session_id.to_string(),
+	});
+	Ok(())
+}
+
+/// Update the session id after switching sessions (no-op when the event log is disabled)
+pub fn set_event_log_session(session_id: &str) {
+	if let Some(log) = EVENT_LOG.lock().unwrap().as_mut() {
+		log.session_id = session_id.to_string();
+	}
+}
+
+/// Write one event. `fields` must be a JSON object; failures are ignored so monitoring never breaks a run.
+pub fn emit_event(event: &str, fields: serde_json::Value) {
+	let mut guard = EVENT_LOG.lock().unwrap();
+	if let Some(log) = guard.as_mut() {
+		let line = format_event(event, &log.session_id, fields, get_timestamp());
+		let _ = writeln!(log.file, "{}", line);
+	}
+}
+
+/// Record that a completion request is about to be sent
+pub fn record_request_started(model: &str, message_count: usize) {
+	emit_event(
+		"request_started",
+		serde_json::json!({
+			"model": model,
+			"messages": message_count,
+		}),
+	);
+}
+
+/// Record the outcome of a completion request
+pub fn record_request_finished(model: &str, result: &Result<crate::providers::ProviderResponse>) {
+	match result {
+		Ok(response) => {
+			let usage = response.exchange.usage.as_ref();
+			emit_event(
+				"response_received",
+				serde_json::json!({
+					"model": model,
+					"input_tokens": usage.map(|u| u.prompt_tokens),
+					"output_tokens": usage.map(|u| u.output_tokens),
+					"cost": usage.and_then(|u| u.cost),
+					"tool_calls": response.tool_calls.as_ref().map_or(0, |calls| calls.len()),
+					"finish_reason": response.finish_reason,
+				}),
+			);
+		}
+		Err(e) => emit_event(
+			"error",
+			serde_json::json!({
+				"stage": "request",
+				"model": model,
+				"message": e.to_string(),
+			}),
+		),
+	}
+}
+
+/// Stable short hash of tool arguments so repeated calls can be correlated without logging contents
+pub fn hash_arguments(arguments: &serde_json::Value) -> String {
+	let mut hasher = DefaultHasher::new();
+	arguments.to_string().hash(&mut hasher);
+	format!("{:016x}", hasher.finish())
+}
+
+fn format_event(
+	event: &str,
+	session_id: &str,
+	fields: serde_json::Value,
+	timestamp: u64,
+) -> String {
+	let mut entry = serde_json::json!({
+		"timestamp": timestamp,
+		"session_id": session_id,
+		"event": event,
+	});
+	if let (Some(entry), serde_json::Value::Object(fields)) = (entry.as_object_mut(), fields) {
+		entry.extend(fields);
+	}
+	entry.to_string()
+}
+
+fn get_timestamp() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap_or_default()
+		.as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_format_event_merges_fields() {
+		let line = format_event(
+			"tool_called",
+			"my-session",
+			serde_json::json!({"tool_name": "shell"}),
+			42,
+		);
+		let parsed: serde_json::Value = serde_json::from_str(&line).unwrap();
+
+		assert_eq!(parsed["timestamp"], 42);
+		assert_eq!(parsed["session_id"], "my-session");
+		assert_eq!(parsed["event"], "tool_called");
+		assert_eq!(parsed["tool_name"], "shell");
+	}
+
+	#[test]
+	fn test_hash_arguments_is_stable() {
+		let args = serde_json::json!({"command": "ls"});
+		assert_eq!(hash_arguments(&args), hash_arguments(&args.clone()));
+		assert_ne!(
+			hash_arguments(&args),
+			hash_arguments(&serde_json::json!({"command": "pwd"}))
+		);
+	}
+}
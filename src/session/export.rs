@@ -0,0 +1,209 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Session export to Markdown transcript
+
+use crate::session::chat::formatting::format_duration;
+use crate::session::{Message, Session};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::path::Path;
+
+/// Render a session into a shareable Markdown transcript
+pub fn session_to_markdown(session: &Session) -> String {
+	let info = &session.info;
+	let mut output = String::new();
+
+	output.push_str(&format!("# Session: {}\n\n", info.name));
+	output.push_str(&format!("- **Model**: `{}`\n", info.model));
+	output.push_str(&format!(
+		"- **Created**: {}\n\n",
+		format_timestamp(info.created_at)
+	));
+
+	for message in &session.messages {
+		match message.role.as_str() {
+			"system" => {
+				// Collapse the system prompt - it's long and rarely interesting to readers
+				output.push_str("<details>\n<summary>System prompt</summary>\n\n");
+				output.push_str(message.content.trim());
+				output.push_str("\n\n</details>\n\n");
+			}
+			"user" => {
+				output.push_str("## 👤 User\n\n");
+				output.push_str(message.content.trim());
+				output.push_str("\n\n");
+			}
+			"assistant" => {
+				output.push_str("## 🤖 Assistant\n\n");
+				if !message.content.trim().is_empty() {
+					output.push_str(message.content.trim());
+					output.push_str("\n\n");
+				}
+				for (name, arguments) in extract_tool_calls(message) {
+					output.push_str(&format!("**Tool call:** `{}`\n\n", name));
+					output.push_str(&fenced_block("json", &arguments));
+				}
+			}
+			"tool" => {
+				let name = message.name.as_deref().unwrap_or("tool");
+				output.push_str(&format!("**Tool result:** `{}`\n\n", name));
+				output.push_str(&fenced_block("", message.content.trim_end()));
+			}
+			_ => {
+				output.push_str(&format!("## {}\n\n", message.role));
+				output.push_str(message.content.trim());
+				output.push_str("\n\n");
+			}
+		}
+	}
+
+	// Summary footer with totals from session info
+	output.push_str("---\n\n## Summary\n\n");
+	output.push_str("| Metric | Value |\n|--------|-------|\n");
+	output.push_str(&format!("| Messages | {} |\n", session.messages.len()));
+	output.push_str(&format!("| Input tokens | {} |\n", info.input_tokens));
+	output.push_str(&format!("| Output tokens | {} |\n", info.output_tokens));
+	output.push_str(&format!("| Cached tokens | {} |\n", info.cached_tokens));
+	output.push_str(&format!("| Tool calls | {} |\n", info.tool_calls));
+	output.push_str(&format!("| Total cost | ${:.5} |\n", info.total_cost));
+	output.push_str(&format!(
+		"| API time | {} |\n",
+		format_duration(info.total_api_time_ms)
+	));
+
+	output
+}
+
+/// Render a session to Markdown and write it to the given path
+pub fn export_session_to_file(session: &Session, path: &Path) -> Result<()> {
+	if let Some(parent) = path.parent() {
+		if !parent.as_os_str().is_empty() {
+			std::fs::create_dir_all(parent)
+				.context(format!("Failed to create directory: {}", parent.display()))?;
+		}
+	}
+
+	std::fs::write(path, session_to_markdown(session))
+		.context(format!("Failed to write export to {}", path.display()))
+}
+
+// Extract (tool name, pretty-printed arguments) pairs from stored assistant tool calls.
+// Handles both OpenAI-style `function` objects and Anthropic-style `tool_use` blocks.
+fn extract_tool_calls(message: &Message) -> Vec<(String, String)> {
+	let calls = match &message.tool_calls {
+		Some(Value::Array(calls)) => calls,
+		_ => return Vec::new(),
+	};
+
+	calls
+		.iter()
+		.filter_map(|call| {
+			let (name, arguments) = if let Some(function) = call.get("function") {
+				(function.get("name")?, function.get("arguments"))
+			} else if call.get("type").and_then(|t| t.as_str()) == Some("tool_use") {
+				(call.get("name")?, call.get("input"))
+			} else {
+				return None;
+			};
+
+			let arguments = match arguments {
+				// OpenAI stores arguments as a JSON string - re-parse for pretty output
+				Some(Value::String(raw)) => serde_json::from_str::<Value>(raw)
+					.ok()
+					.and_then(|v| serde_json::to_string_pretty(&v).ok())
+					.unwrap_or_else(|| raw.clone()),
+				Some(value) => serde_json::to_string_pretty(value).unwrap_or_default(),
+				None => "{}".to_string(),
+			};
+
+			Some((name.as_str()?.to_string(), arguments))
+		})
+		.collect()
+}
+
+// Wrap content in a fenced code block, widening the fence if the content contains backticks
+fn fenced_block(language: &str, content: &str) -> String {
+	let mut fence = "```".to_string();
+	while content.contains(&fence) {
+		fence.push('`');
+	}
+	format!("{}{}\n{}\n{}\n\n", fence, language, content, fence)
+}
+
+fn format_timestamp(timestamp: u64) -> String {
+	DateTime::<Utc>::from_timestamp(timestamp as i64, 0)
+		.map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+		.unwrap_or_else(|| "Unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::session::{
+		append_summary_line, append_to_session_file, load_session, message_to_session_line,
+	};
+
+	#[test]
+	fn test_export_round_trip() {
+		let dir = tempfile::tempdir().unwrap();
+		let session_file = dir.path().join("demo.jsonl");
+
+		let mut session = Session::new(
+			"demo".to_string(),
+			"openai:gpt-4o".to_string(),
+			"openai".to_string(),
+		);
+		session.info.input_tokens = 120;
+		session.info.tool_calls = 1;
+		session.add_message("system", "You are a helpful assistant.");
+		session.add_message("user", "List the files");
+		session.add_message("assistant", "");
+		session.messages.last_mut().unwrap().tool_calls = Some(serde_json::json!([{
+			"id": "call_1",
+			"type": "function",
+			"function": {"name": "list_files", "arguments": "{\"path\":\"src\"}"}
+		}]));
+		session.add_message("tool", "main.rs");
+		let result = session.messages.last_mut().unwrap();
+		result.name = Some("list_files".to_string());
+		result.tool_call_id = Some("call_1".to_string());
+		session.add_message("assistant", "There is one file: main.rs");
+
+		// Write it the way sessions are stored, then export what loads back
+		std::fs::File::create(&session_file).unwrap();
+		append_summary_line(&session_file, &session.info).unwrap();
+		for message in &session.messages {
+			let line = message_to_session_line(&session_file, message).unwrap();
+			append_to_session_file(&session_file, &line).unwrap();
+		}
+		let loaded = load_session(&session_file).unwrap();
+		let export_path = dir.path().join("out").join("demo.md");
+		export_session_to_file(&loaded, &export_path).unwrap();
+		let markdown = std::fs::read_to_string(&export_path).unwrap();
+
+		assert!(markdown.starts_with("# Session: demo\n"));
+		assert!(markdown.contains("- **Model**: `openai:gpt-4o`"));
+		assert!(markdown.contains("<summary>System prompt</summary>"));
+		assert!(markdown.contains("## 👤 User\n\nList the files"));
+		assert!(markdown
+			.contains("**Tool call:** `list_files`\n\n```json\n{\n  \"path\": \"src\"\n}\n```"));
+		assert!(markdown.contains("**Tool result:** `list_files`\n\n```\nmain.rs\n```"));
+		assert!(markdown.contains("There is one file: main.rs"));
+		assert!(markdown.contains("| Messages | 5 |"));
+		assert!(markdown.contains("| Input tokens | 120 |"));
+		assert!(markdown.contains("| Tool calls | 1 |"));
+	}
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::config::ProjectContextConfig;
 use crate::session::project_context::ProjectContext;
 use crate::session::Session;
 use chrono::{DateTime, Local};
@@ -25,7 +26,11 @@ use tokio::process::Command;
 // All hardcoded prompts have been moved to the config template
 
 // Function to process placeholders in a system prompt (synchronous version for backward compatibility)
-pub fn process_placeholders(prompt: &str, project_dir: &Path) -> String {
+pub fn process_placeholders(
+	prompt: &str,
+	project_dir: &Path,
+	context_settings: &ProjectContextConfig,
+) -> String {
 	let mut processed_prompt = prompt.to_string();
 
 	// Check which placeholders are actually in the prompt to avoid unnecessary work
@@ -45,7 +50,7 @@ pub fn process_placeholders(prompt: &str, project_dir: &Path) -> String {
 
 	// Collect project context only if needed
 	let project_context = if needs_context || needs_git_status || needs_git_tree || needs_readme {
-		Some(ProjectContext::collect(project_dir))
+		Some(ProjectContext::collect(project_dir, context_settings))
 	} else {
 		None
 	};
@@ -366,7 +371,11 @@ async fn get_os_info() -> String {
 }
 
 // Smart async version of process_placeholders - only gathers data for placeholders that exist in the prompt
-pub async fn process_placeholders_async(prompt: &str, project_dir: &Path) -> String {
+pub async fn process_placeholders_async(
+	prompt: &str,
+	project_dir: &Path,
+	context_settings: &ProjectContextConfig,
+) -> String {
 	let mut processed_prompt = prompt.to_string();
 
 	// Check which placeholders are actually in the prompt to avoid unnecessary work
@@ -408,7 +417,7 @@ pub async fn process_placeholders_async(prompt: &str, project_dir: &Path) -> Str
 
 	// Collect project context only if needed
 	let project_context = if needs_context || needs_git_status || needs_git_tree || needs_readme {
-		Some(ProjectContext::collect(project_dir))
+		Some(ProjectContext::collect(project_dir, context_settings))
 	} else {
 		None
 	};
@@ -516,11 +525,14 @@ pub async fn process_placeholders_async(prompt: &str, project_dir: &Path) -> Str
 }
 
 // Function to get all available placeholders with their current values
-pub async fn get_all_placeholders(project_dir: &Path) -> HashMap<String, String> {
+pub async fn get_all_placeholders(
+	project_dir: &Path,
+	context_settings: &ProjectContextConfig,
+) -> HashMap<String, String> {
 	let mut placeholders = HashMap::new();
 
 	// Collect context information
-	let project_context = ProjectContext::collect(project_dir);
+	let project_context = ProjectContext::collect(project_dir, context_settings);
 
 	// Gather system information asynchronously
 	let system_info = gather_system_info().await;
@@ -347,6 +347,28 @@ impl ImageProcessor {
 	pub fn is_url(input: &str) -> bool {
 		input.starts_with("http://") || input.starts_with("https://")
 	}
+
+	/// Load image from a file path or URL, validating that local files exist and are supported
+	pub async fn load(input: &str) -> Result<ImageAttachment> {
+		if Self::is_url(input) {
+			return Self::load_from_url(input).await;
+		}
+
+		let image_path = Path::new(input);
+
+		if !image_path.exists() {
+			return Err(anyhow::anyhow!("Image file not found: {}", input));
+		}
+
+		if !Self::is_supported_image(image_path) {
+			return Err(anyhow::anyhow!(
+				"Unsupported image format. Supported: {}",
+				Self::supported_extensions().join(", ")
+			));
+		}
+
+		Self::load_from_path(image_path)
+	}
 }
 
 #[cfg(test)]
@@ -34,19 +34,14 @@ pub struct LayerResult {
 }
 
 // Input mode determines what part of the previous layer's output will be used
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum InputMode {
-	Last,    // Only the last assistant message from the session
+	#[default]
+	Last, // Only the last assistant message from the session
 	All,     // All messages/data from the previous layer
 	Summary, // A summarized version of all data from the previous layer
 }
 
-impl Default for InputMode {
-	fn default() -> Self {
-		Self::Last
-	}
-}
-
 impl InputMode {
 	pub fn as_str(&self) -> &'static str {
 		match self {
@@ -84,19 +79,14 @@ where
 }
 
 // Output mode determines how the layer's output affects the session
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum OutputMode {
-	None,    // Don't modify session (intermediate layer like query_processor)
+	#[default]
+	None, // Don't modify session (intermediate layer like query_processor)
 	Append,  // Add output as new message to session
 	Replace, // Replace entire session with output (reducer functionality)
 }
 
-impl Default for OutputMode {
-	fn default() -> Self {
-		Self::None
-	}
-}
-
 impl OutputMode {
 	pub fn as_str(&self) -> &'static str {
 		match self {
@@ -165,6 +155,12 @@ pub struct LayerConfig {
 	// Custom parameters that can be used in system prompts via placeholders
 	#[serde(default)]
 	pub parameters: std::collections::HashMap<String, serde_json::Value>,
+	// Reasoning effort for this layer or command (falls back to the role's setting)
+	#[serde(default)]
+	pub reasoning_effort: Option<crate::config::ReasoningEffort>,
+	// Stop sequences for this layer or command (replace the role's when set)
+	#[serde(default)]
+	pub stop_sequences: Option<Vec<String>>,
 	// Cached processed system prompt (not serialized - computed at session initialization)
 	#[serde(skip)]
 	pub processed_system_prompt: Option<String>,
@@ -190,6 +186,13 @@ impl LayerConfig {
 	) -> crate::config::Config {
 		let mut merged_config = base_config.clone();
 
+		if self.reasoning_effort.is_some() {
+			merged_config.reasoning_effort = self.reasoning_effort;
+		}
+		if let Some(stop_sequences) = &self.stop_sequences {
+			merged_config.stop_sequences = stop_sequences.clone();
+		}
+
 		// Create role-like MCP config from layer's server_refs
 		if !self.mcp.server_refs.is_empty() {
 			// Get servers from the global registry based on server_refs
@@ -218,6 +221,8 @@ impl LayerConfig {
 			merged_config.mcp = crate::config::McpConfig {
 				servers: layer_servers,
 				allowed_tools: self.mcp.allowed_tools.clone(),
+				// Tools denied for the role stay denied inside its layers
+				denied_tools: merged_config.mcp.denied_tools.clone(),
 			};
 		} else {
 			// No server_refs means MCP is disabled for this layer
@@ -247,10 +252,14 @@ impl LayerConfig {
 	}
 
 	/// Process and cache the system prompt for this layer (called once during session initialization)
-	pub async fn process_and_cache_system_prompt(&mut self, project_dir: &std::path::Path) {
+	pub async fn process_and_cache_system_prompt(
+		&mut self,
+		project_dir: &std::path::Path,
+		context_settings: &crate::config::ProjectContextConfig,
+	) {
 		if let Some(ref custom_prompt) = self.system_prompt {
 			let processed = self
-				.process_prompt_placeholders_async(custom_prompt, project_dir)
+				.process_prompt_placeholders_async(custom_prompt, project_dir, context_settings)
 				.await;
 			self.processed_system_prompt = Some(processed);
 		} else {
@@ -265,13 +274,17 @@ impl LayerConfig {
 		&self,
 		prompt: &str,
 		project_dir: &std::path::Path,
+		context_settings: &crate::config::ProjectContextConfig,
 	) -> String {
 		let mut processed = prompt.to_string();
 
 		// Replace standard placeholders using the async version
-		processed =
-			crate::session::helper_functions::process_placeholders_async(&processed, project_dir)
-				.await;
+		processed = crate::session::helper_functions::process_placeholders_async(
+			&processed,
+			project_dir,
+			context_settings,
+		)
+		.await;
 
 		// Replace custom parameter placeholders
 		for (key, value) in &self.parameters {
@@ -303,6 +316,8 @@ impl LayerConfig {
 					allowed_tools: vec![],
 				},
 				parameters: std::collections::HashMap::new(),
+				reasoning_effort: None,
+				stop_sequences: None,
 				processed_system_prompt: None, // Will be processed during session initialization
 			},
 			"context_generator" => Self {
@@ -317,6 +332,8 @@ impl LayerConfig {
 					allowed_tools: vec!["text_editor".to_string(), "list_files".to_string()],
 				},
 				parameters: std::collections::HashMap::new(),
+				reasoning_effort: None,
+				stop_sequences: None,
 				processed_system_prompt: None, // Will be processed during session initialization
 			},
 			"reducer" => Self {
@@ -331,6 +348,8 @@ impl LayerConfig {
 					allowed_tools: vec![],
 				},
 				parameters: std::collections::HashMap::new(),
+				reasoning_effort: None,
+				stop_sequences: None,
 				processed_system_prompt: None, // Will be processed during session initialization
 			},
 			_ => Self {
@@ -342,6 +361,8 @@ impl LayerConfig {
 				output_mode: OutputMode::None, // Default: intermediate layer
 				mcp: LayerMcpConfig::default(),
 				parameters: std::collections::HashMap::new(),
+				reasoning_effort: None,
+				stop_sequences: None,
 				processed_system_prompt: None, // Will be processed during session initialization
 			},
 		}
@@ -379,16 +400,14 @@ pub trait Layer {
 					session
 						.messages
 						.iter()
-						.filter(|m| m.role == "assistant")
-						.next_back()
+						.rfind(|m| m.role == "assistant")
 						.map(|m| m.content.clone())
 						.unwrap_or_else(|| {
 							// Fallback: if no assistant messages, get last user message
 							session
 								.messages
 								.iter()
-								.filter(|m| m.role == "user")
-								.next_back()
+								.rfind(|m| m.role == "user")
 								.map(|m| m.content.clone())
 								.unwrap_or_else(|| "No previous messages found".to_string())
 						})
@@ -397,8 +416,7 @@ pub trait Layer {
 					let last_assistant = session
 						.messages
 						.iter()
-						.filter(|m| m.role == "assistant")
-						.next_back()
+						.rfind(|m| m.role == "assistant")
 						.map(|m| {
 							format!(
 								"Previous response:\n{}\n\nCurrent input:\n{}",
@@ -82,7 +82,7 @@ impl LayeredOrchestrator {
 		for mut layer_config in enabled_layers {
 			// Process and cache the system prompt for this layer
 			layer_config
-				.process_and_cache_system_prompt(project_dir)
+				.process_and_cache_system_prompt(project_dir, &config.project_context)
 				.await;
 			layers.push(Box::new(GenericLayer::new(layer_config)));
 		}
@@ -92,7 +92,7 @@ impl LayeredOrchestrator {
 			let default_layers = Self::create_default_system_layers_configs();
 			for mut layer_config in default_layers {
 				layer_config
-					.process_and_cache_system_prompt(project_dir)
+					.process_and_cache_system_prompt(project_dir, &config.project_context)
 					.await;
 				layers.push(Box::new(GenericLayer::new(layer_config)));
 			}
@@ -224,6 +224,7 @@ impl LayeredOrchestrator {
 						&layer.config().get_effective_model(&session.info.model),
 						usage.prompt_tokens,
 						usage.output_tokens,
+						usage.cached_tokens,
 						cost,
 						result.api_time_ms,
 						result.tool_time_ms,
@@ -255,6 +256,7 @@ impl LayeredOrchestrator {
 							&layer.config().get_effective_model(&session.info.model),
 							usage.prompt_tokens,
 							usage.output_tokens,
+							usage.cached_tokens,
 							cost,
 							result.api_time_ms,
 							result.tool_time_ms,
@@ -284,6 +286,7 @@ impl LayeredOrchestrator {
 							&layer.config().get_effective_model(&session.info.model),
 							usage.prompt_tokens,
 							usage.output_tokens,
+							usage.cached_tokens,
 							0.0, // No cost available
 							result.api_time_ms,
 							result.tool_time_ms,
@@ -54,6 +54,8 @@ impl LayerProcessor {
 			name: None,           // No name for system messages
 			tool_calls: None,     // No tool_calls for system messages
 			images: None,         // No images for system messages
+			attachments: None,    // No attachments for system messages
+			pinned: false,
 		});
 
 		// Prepare input based on input_mode using the trait's prepare_input method
@@ -72,6 +74,8 @@ impl LayerProcessor {
 			name: None,         // No name for user messages
 			tool_calls: None,   // No tool_calls for user messages
 			images: None,       // No images for user messages
+			attachments: None,  // No attachments for user messages
+			pinned: false,
 		});
 
 		messages
@@ -198,6 +202,8 @@ impl Layer for LayerProcessor {
 						name: None,         // No name for assistant messages
 						tool_calls: None,   // No tool_calls for assistant messages
 						images: None,       // No images for assistant messages
+						attachments: None,  // No attachments for assistant messages
+						pinned: false,
 					});
 
 					// Add each tool result as a tool message in standard OpenRouter format
@@ -215,6 +221,8 @@ impl Layer for LayerProcessor {
 							name: Some(tool_result.tool_name.clone()),       // Include the tool name
 							tool_calls: None,                                // No tool_calls for tool messages
 							images: None,                                    // No images for tool messages
+							attachments: None,                               // No attachments for tool messages
+							pinned: false,
 						});
 					}
 
@@ -57,6 +57,8 @@ impl GenericLayer {
 			name: None,
 			tool_calls: None,
 			images: None,
+			attachments: None,
+			pinned: false,
 		});
 
 		// Prepare input based on input_mode using the trait's prepare_input method
@@ -75,6 +77,8 @@ impl GenericLayer {
 			name: None,
 			tool_calls: None,
 			images: None,
+			attachments: None,
+			pinned: false,
 		});
 
 		messages
@@ -196,6 +200,8 @@ impl Layer for GenericLayer {
 						name: None,
 						tool_calls: None,
 						images: None,
+						attachments: None,
+						pinned: false,
 					});
 
 					// Add each tool result as a tool message
@@ -212,6 +218,8 @@ impl Layer for GenericLayer {
 							name: Some(tool_result.tool_name.clone()),
 							tool_calls: None,
 							images: None,
+							attachments: None,
+							pinned: false,
 						});
 					}
 
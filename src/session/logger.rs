@@ -106,6 +106,12 @@ pub fn log_api_response(
 	Ok(())
 }
 
+/// Log a provider exchange (request and response) to the session log
+pub fn log_exchange(session_name: &str, exchange: &crate::session::ProviderExchange) -> Result<()> {
+	log_api_request(session_name, &exchange.request)?;
+	log_api_response(session_name, &exchange.response, exchange.usage.as_ref())
+}
+
 /// Log tool call request
 pub fn log_tool_call(
 	session_name: &str,
@@ -14,9 +14,12 @@
 
 // Session module for handling interactive coding sessions
 
+pub mod attachment; // File attachments (PDF, text) for messages
 pub mod cache;
 pub mod chat; // Chat session logic
 mod chat_helper; // Chat command completion
+pub mod event_log; // Machine-readable operational event log
+pub mod export; // Session export to Markdown transcripts
 pub mod helper_functions; // Helper functions for layers and other components
 pub mod image; // Image processing and attachment utilities
 pub mod layers; // Layered architecture implementation
@@ -25,6 +28,7 @@ mod model_utils; // Model-specific utility functions
 mod project_context; // Project context collection and management
 					 // Provider abstraction layer moved to src/providers
 pub mod report; // Session usage reporting
+pub mod search; // Conversation search across saved sessions
 pub mod smart_summarizer; // Smart text summarization for context management
 mod token_counter; // Token counting utilities // Comprehensive caching system
 
@@ -69,6 +73,10 @@ pub struct Message {
 	pub tool_calls: Option<serde_json::Value>, // For assistant messages: original tool calls from API response
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub images: Option<Vec<crate::session::image::ImageAttachment>>, // For messages with image attachments
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub attachments: Option<Vec<crate::session::attachment::FileAttachment>>, // For messages with file attachments (PDF, text)
+	#[serde(default)]
+	pub pinned: bool, // Pinned messages survive truncation and summarization verbatim
 }
 
 fn default_cache_marker() -> bool {
@@ -91,6 +99,8 @@ pub struct SessionInfo {
 	pub input_tokens: u64,
 	pub output_tokens: u64,
 	pub cached_tokens: u64, // Added to track cached tokens separately
+	#[serde(default)]
+	pub reasoning_tokens: u64, // Subset of output_tokens spent on hidden reasoning
 	pub total_cost: f64,
 	pub duration_seconds: u64,
 	pub layer_stats: Vec<LayerStats>, // Added to track per-layer statistics
@@ -103,6 +113,12 @@ pub struct SessionInfo {
 	pub total_tool_time_ms: u64, // Total time spent executing tools
 	#[serde(default)]
 	pub total_layer_time_ms: u64, // Total time spent in layer processing
+	#[serde(default)]
+	pub markdown_theme: Option<String>, // Overrides the global markdown theme for this session
+	#[serde(default)]
+	pub tool_iteration_limit_hits: u64, // Turns cut short by max_tool_iterations
+	#[serde(default)]
+	pub seed: Option<u64>, // Sampling seed sent with requests (--seed), reused on resume and replay
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -111,6 +127,8 @@ pub struct LayerStats {
 	pub model: String,
 	pub input_tokens: u64,
 	pub output_tokens: u64,
+	#[serde(default)]
+	pub cached_tokens: u64, // Subset of input_tokens served from cache
 	pub cost: f64,
 	pub timestamp: u64,
 	// Time tracking
@@ -133,6 +151,9 @@ pub struct Session {
 	// Track last cache checkpoint time for time-based auto-caching
 	#[serde(default = "current_timestamp")]
 	pub last_cache_checkpoint_time: u64,
+	// Model recorded in checkpoints while a runtime --model override is in info.model
+	#[serde(skip)]
+	pub saved_model: Option<String>,
 }
 
 impl Session {
@@ -160,12 +181,17 @@ impl Session {
 				total_api_time_ms: 0,
 				total_tool_time_ms: 0,
 				total_layer_time_ms: 0,
+				markdown_theme: None,
+				tool_iteration_limit_hits: 0,
+				reasoning_tokens: 0,
+				seed: None,
 			},
 			messages: Vec::new(),
 			session_file: None,
 			current_non_cached_tokens: 0,
 			current_total_tokens: 0,
 			last_cache_checkpoint_time: timestamp,
+			saved_model: None,
 		}
 	}
 
@@ -183,6 +209,8 @@ impl Session {
 			name: None,         // Default to no name
 			tool_calls: None,   // Default to no tool_calls
 			images: None,       // Default to no images
+			attachments: None,
+			pinned: false,
 		};
 
 		self.messages.push(message.clone());
@@ -224,6 +252,7 @@ impl Session {
 		model: &str,
 		input_tokens: u64,
 		output_tokens: u64,
+		cached_tokens: u64,
 		cost: f64,
 	) {
 		self.add_layer_stats_with_time(
@@ -231,6 +260,7 @@ impl Session {
 			model,
 			input_tokens,
 			output_tokens,
+			cached_tokens,
 			cost,
 			0,
 			0,
@@ -246,6 +276,7 @@ impl Session {
 		model: &str,
 		input_tokens: u64,
 		output_tokens: u64,
+		cached_tokens: u64,
 		cost: f64,
 		api_time_ms: u64,
 		tool_time_ms: u64,
@@ -257,6 +288,7 @@ impl Session {
 			model: model.to_string(),
 			input_tokens,
 			output_tokens,
+			cached_tokens,
 			cost,
 			timestamp: SystemTime::now()
 				.duration_since(UNIX_EPOCH)
@@ -273,6 +305,7 @@ impl Session {
 		// Also update the overall session totals
 		self.info.input_tokens += input_tokens;
 		self.info.output_tokens += output_tokens;
+		self.info.cached_tokens += cached_tokens;
 		self.info.total_cost += cost;
 
 		// Update time tracking totals
@@ -281,6 +314,45 @@ impl Session {
 		self.info.total_layer_time_ms += total_time_ms;
 	}
 
+	// Checkpoint the session without rewriting the file: messages are appended as they
+	// are added, so only the session info is missing - append it as a SUMMARY line
+	// (load_session keeps the last one)
+	pub fn save_checkpoint(&self) -> Result<(), anyhow::Error> {
+		match &self.session_file {
+			Some(session_file) => append_summary_line(session_file, &self.checkpoint_info()),
+			None => Err(anyhow::anyhow!("No session file specified")),
+		}
+	}
+
+	// Session info as it should be recorded, leaving out a runtime model override
+	pub fn checkpoint_info(&self) -> SessionInfo {
+		let mut info = self.info.clone();
+		if let Some(model) = &self.saved_model {
+			info.model = model.clone();
+		}
+		info
+	}
+
+	// Set a message's pinned flag; the change is logged because the message line is already written
+	pub fn set_message_pinned(&mut self, index: usize, pinned: bool) -> Result<(), anyhow::Error> {
+		let message = self
+			.messages
+			.get_mut(index)
+			.ok_or_else(|| anyhow::anyhow!("No message {}", index + 1))?;
+		message.pinned = pinned;
+
+		if let Some(session_file) = &self.session_file {
+			let log_entry = serde_json::json!({
+				"type": "PIN",
+				"timestamp": current_timestamp(),
+				"index": index,
+				"pinned": pinned,
+			});
+			append_to_session_file(session_file, &serde_json::to_string(&log_entry)?)?;
+		}
+		Ok(())
+	}
+
 	// Save the session to a file - clean JSONL approach without summary
 	pub fn save(&self) -> Result<(), anyhow::Error> {
 		if let Some(session_file) = &self.session_file {
@@ -290,7 +362,7 @@ impl Session {
 
 			// Save all messages in standard JSONL format
 			for message in &self.messages {
-				let message_json = serde_json::to_string(message)?;
+				let message_json = message_to_session_line(session_file, message)?;
 				append_to_session_file(session_file, &message_json)?;
 			}
 
@@ -361,11 +433,150 @@ pub fn list_available_sessions() -> Result<Vec<(String, SessionInfo)>, anyhow::E
 	}
 
 	// Sort sessions by creation time (newest first)
-	sessions.sort_by(|a, b| b.1.created_at.cmp(&a.1.created_at));
+	sessions.sort_by_key(|s| std::cmp::Reverse(s.1.created_at));
 
 	Ok(sessions)
 }
 
+// Expand session names and glob patterns (e.g. "251016-*") into saved session names
+pub fn resolve_session_names(patterns: &[String]) -> Result<Vec<String>, anyhow::Error> {
+	let sessions_dir = get_sessions_dir()?;
+	let mut available = Vec::new();
+	if sessions_dir.exists() {
+		for entry in std_fs::read_dir(sessions_dir)? {
+			let path = entry?.path();
+			if path.is_file() && path.extension().is_some_and(|ext| ext == "jsonl") {
+				if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+					available.push(name.to_string());
+				}
+			}
+		}
+	}
+	available.sort();
+
+	match_session_names(patterns, &available)
+}
+
+// Plain names are kept as given; glob patterns must match at least one available session
+fn match_session_names(
+	patterns: &[String],
+	available: &[String],
+) -> Result<Vec<String>, anyhow::Error> {
+	let mut names: Vec<String> = Vec::new();
+	for pattern in patterns {
+		if !pattern.contains(['*', '?', '[']) {
+			if !names.contains(pattern) {
+				names.push(pattern.clone());
+			}
+			continue;
+		}
+
+		let glob = glob::Pattern::new(pattern)
+			.map_err(|e| anyhow::anyhow!("Invalid session pattern '{}': {}", pattern, e))?;
+		let matched: Vec<&String> = available.iter().filter(|n| glob.matches(n)).collect();
+		if matched.is_empty() {
+			return Err(anyhow::anyhow!("No sessions match '{}'", pattern));
+		}
+		for name in matched {
+			if !names.contains(name) {
+				names.push(name.clone());
+			}
+		}
+	}
+	Ok(names)
+}
+
+// Parse a relative duration like "30m", "24h", "7d" or "2w" into seconds
+pub fn parse_relative_duration(input: &str) -> Result<u64, anyhow::Error> {
+	let input = input.trim();
+	let invalid = || {
+		anyhow::anyhow!(
+			"Invalid duration '{}'. Use a number followed by s, m, h, d or w (e.g. 30m, 24h, 7d)",
+			input
+		)
+	};
+
+	let unit_pos = input
+		.find(|c: char| !c.is_ascii_digit())
+		.ok_or_else(invalid)?;
+	let (amount, unit) = input.split_at(unit_pos);
+	let amount: u64 = amount.parse().map_err(|_| invalid())?;
+
+	let multiplier = match unit {
+		"s" => 1,
+		"m" => 60,
+		"h" => 60 * 60,
+		"d" => 24 * 60 * 60,
+		"w" => 7 * 24 * 60 * 60,
+		_ => return Err(invalid()),
+	};
+
+	amount.checked_mul(multiplier).ok_or_else(invalid)
+}
+
+// Name of the session that was active most recently: latest file modification,
+// falling back to creation time when the file time is unavailable
+pub fn most_recent_session_name() -> Result<Option<String>, anyhow::Error> {
+	let sessions_dir = get_sessions_dir()?;
+	let latest = list_available_sessions()?
+		.into_iter()
+		.max_by_key(|(name, info)| {
+			std_fs::metadata(sessions_dir.join(format!("{}.jsonl", name)))
+				.and_then(|metadata| metadata.modified())
+				.ok()
+				.and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+				.map(|modified| modified.as_secs())
+				.unwrap_or(info.created_at)
+		})
+		.map(|(name, _)| name);
+	Ok(latest)
+}
+
+// Latest session info in a session file. Checkpoints append SUMMARY lines, so the first
+// line only reflects the session as it was when it started
+pub fn latest_session_info(session_file: &Path) -> Result<Option<SessionInfo>, anyhow::Error> {
+	let reader = BufReader::new(File::open(session_file)?);
+	let mut latest = None;
+
+	for line in reader.lines() {
+		let line = line?;
+		// Cheap check before parsing - most lines are messages
+		if !line.contains("\"SUMMARY\"") {
+			continue;
+		}
+		if let Ok(json_value) = serde_json::from_str::<serde_json::Value>(&line) {
+			if json_value.get("type").and_then(|t| t.as_str()) == Some("SUMMARY") {
+				if let Some(session_info_value) = json_value.get("session_info") {
+					if let Ok(info) =
+						serde_json::from_value::<SessionInfo>(session_info_value.clone())
+					{
+						latest = Some(info);
+					}
+				}
+			}
+		}
+	}
+
+	Ok(latest)
+}
+
+// Keep only sessions created within the last `window_seconds`
+pub fn filter_sessions_since(
+	sessions: Vec<(String, SessionInfo)>,
+	window_seconds: u64,
+) -> Vec<(String, SessionInfo)> {
+	let now = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap_or_default()
+		.as_secs();
+	let cutoff = now.saturating_sub(window_seconds);
+
+	sessions
+		.into_iter()
+		.filter(|(_, info)| info.created_at >= cutoff)
+		.collect()
+}
+
 // Helper function to load a session from file - optimized to use streams
 pub fn load_session(session_file: &PathBuf) -> Result<Session, anyhow::Error> {
 	// Ensure the file exists
@@ -423,6 +634,29 @@ pub fn load_session(session_file: &PathBuf) -> Result<Session, anyhow::Error> {
 							);
 						}
 					}
+					"RETRY" => {
+						// /retry discarded the last turn before resending its user message
+						if restoration_point_found {
+							discard_last_turn(&mut restoration_messages);
+						} else {
+							discard_last_turn(&mut messages);
+						}
+					}
+					"PIN" => {
+						// /pin changed a message's pinned flag after the message was written
+						let target = if restoration_point_found {
+							&mut restoration_messages
+						} else {
+							&mut messages
+						};
+						let index = json_value.get("index").and_then(|i| i.as_u64());
+						let pinned = json_value.get("pinned").and_then(|p| p.as_bool());
+						if let (Some(index), Some(pinned)) = (index, pinned) {
+							if let Some(message) = target.get_mut(index as usize) {
+								message.pinned = pinned;
+							}
+						}
+					}
 					"OUTPUT_MODE_APPEND" => {
 						// Handle Append mode operations during session restoration
 						// These are tracked but don't need special handling since the messages
@@ -441,7 +675,12 @@ pub fn load_session(session_file: &PathBuf) -> Result<Session, anyhow::Error> {
 				}
 			} else if line.contains("\"role\":") && line.contains("\"content\":") {
 				// This is a regular message JSON line
-				if let Ok(message) = serde_json::from_str::<Message>(&line) {
+				if let Ok(mut message) = serde_json::from_str::<Message>(&line) {
+					// Large responses are stored in a sidecar file - read them back in
+					if let Some(file_name) = json_value.get("content_file").and_then(|f| f.as_str())
+					{
+						message.content = read_sidecar_content(session_file, file_name)?;
+					}
 					if restoration_point_found {
 						restoration_messages.push(message);
 					} else {
@@ -537,6 +776,9 @@ pub fn load_session(session_file: &PathBuf) -> Result<Session, anyhow::Error> {
 		if let Some(model) = runtime_state.model {
 			info.model = model;
 		}
+		if let Some(theme) = runtime_state.markdown_theme {
+			info.markdown_theme = Some(theme);
+		}
 
 		let session = Session {
 			info,
@@ -545,6 +787,7 @@ pub fn load_session(session_file: &PathBuf) -> Result<Session, anyhow::Error> {
 			current_non_cached_tokens: 0,
 			current_total_tokens: 0,
 			last_cache_checkpoint_time: current_timestamp(), // Initialize to current time for existing sessions
+			saved_model: None,
 		};
 
 		Ok(session)
@@ -561,6 +804,7 @@ pub struct SessionRuntimeState {
 	pub model: Option<String>,
 	pub layers_enabled: Option<bool>,
 	pub cache_next_message: bool,
+	pub markdown_theme: Option<String>,
 }
 
 /// Extract runtime state from session log file
@@ -601,11 +845,11 @@ fn apply_command_to_runtime_state(state: &mut SessionRuntimeState, command_line:
 	}
 
 	match parts[0] {
-		"/model" => {
-			if parts.len() > 1 {
-				let new_model = parts[1..].join(" ");
-				state.model = Some(new_model);
-			}
+		"/model" if parts.len() > 1 => {
+			state.model = Some(parts[1..].join(" "));
+		}
+		"/theme" if parts.len() > 1 && parts[1] != "list" => {
+			state.markdown_theme = Some(parts[1].to_string());
 		}
 		"/layers" => {
 			// Toggle layers state - we don't know the previous state, so we assume it toggles
@@ -634,6 +878,91 @@ pub fn append_to_session_file(session_file: &PathBuf, content: &str) -> Result<(
 	Ok(())
 }
 
+// Append session info as a SUMMARY line
+pub fn append_summary_line(
+	session_file: &PathBuf,
+	info: &SessionInfo,
+) -> Result<(), anyhow::Error> {
+	let summary_entry = serde_json::json!({
+		"type": "SUMMARY",
+		"timestamp": current_timestamp(),
+		"session_info": info
+	});
+	append_to_session_file(session_file, &serde_json::to_string(&summary_entry)?)
+}
+
+// Remove the last user message and everything after it (assistant reply, tool results),
+// returning the removed user message
+pub fn discard_last_turn(messages: &mut Vec<Message>) -> Option<Message> {
+	let user_index = messages.iter().rposition(|msg| msg.role == "user")?;
+	let user_message = messages[user_index].clone();
+	messages.truncate(user_index);
+	Some(user_message)
+}
+
+// Serialize a message as a session file line, moving assistant responses above
+// session_sidecar_threshold into a sidecar file (load_session reads them back)
+pub fn message_to_session_line(
+	session_file: &Path,
+	message: &Message,
+) -> Result<String, anyhow::Error> {
+	let threshold =
+		crate::config::with_thread_config(|config| config.session_sidecar_threshold).unwrap_or(0);
+	serialize_message_with_sidecar(session_file, message, threshold)
+}
+
+fn serialize_message_with_sidecar(
+	session_file: &Path,
+	message: &Message,
+	threshold: usize,
+) -> Result<String, anyhow::Error> {
+	if threshold == 0 || message.role != "assistant" || message.content.len() <= threshold {
+		return Ok(serde_json::to_string(message)?);
+	}
+
+	// Name by content so rewriting the session (save) reuses the same file
+	use std::collections::hash_map::DefaultHasher;
+	use std::hash::{Hash, Hasher};
+	let mut hasher = DefaultHasher::new();
+	message.content.hash(&mut hasher);
+	let file_name = format!("{}-{:016x}.md", message.timestamp, hasher.finish());
+
+	let sidecar_dir = get_sidecar_dir(session_file);
+	std_fs::create_dir_all(&sidecar_dir)?;
+	let sidecar_path = sidecar_dir.join(&file_name);
+	if !sidecar_path.exists() {
+		std_fs::write(&sidecar_path, &message.content)?;
+	}
+
+	let mut value = serde_json::to_value(message)?;
+	value["content"] = serde_json::Value::String(String::new());
+	value["content_file"] = serde_json::Value::String(file_name);
+	Ok(serde_json::to_string(&value)?)
+}
+
+// Sidecar files of a session live in <session name>.files next to its log
+fn get_sidecar_dir(session_file: &Path) -> PathBuf {
+	session_file.with_extension("files")
+}
+
+pub(crate) fn read_sidecar_content(
+	session_file: &Path,
+	file_name: &str,
+) -> Result<String, anyhow::Error> {
+	// Only a bare file name is ever written, never follow anything else
+	let file_name = Path::new(file_name)
+		.file_name()
+		.ok_or_else(|| anyhow::anyhow!("Invalid sidecar file reference: {}", file_name))?;
+	let sidecar_path = get_sidecar_dir(session_file).join(file_name);
+	std_fs::read_to_string(&sidecar_path).map_err(|e| {
+		anyhow::anyhow!(
+			"Failed to read session sidecar file {}: {}",
+			sidecar_path.display(),
+			e
+		)
+	})
+}
+
 pub async fn create_system_prompt(
 	project_dir: &Path,
 	config: &crate::config::Config,
@@ -642,9 +971,13 @@ pub async fn create_system_prompt(
 	// Get mode-specific configuration
 	let (_, mcp_config, _, _, system_prompt_opt) = config.get_role_config(mode);
 
-	// For developer role, process placeholders to add project context
-	let mut prompt =
-		helper_functions::process_placeholders_async(system_prompt_opt.unwrap(), project_dir).await;
+	// Ordering: prefix, role system, tool list, suffix
+	let mut prompt = String::new();
+	if let Some(prefix) = &config.system_prefix {
+		prompt.push_str(prefix);
+		prompt.push_str("\n\n");
+	}
+	prompt.push_str(system_prompt_opt.unwrap());
 
 	// Add MCP tools information if enabled
 	if !mcp_config.server_refs.is_empty() {
@@ -662,9 +995,37 @@ pub async fn create_system_prompt(
 		}
 	}
 
-	prompt
+	if let Some(suffix) = &config.system_suffix {
+		prompt.push_str("\n\n");
+		prompt.push_str(suffix);
+	}
+
+	// Process placeholders on the combined prompt to add project context
+	helper_functions::process_placeholders_async(&prompt, project_dir, &config.project_context)
+		.await
+}
+
+/// Error returned when the user declines a request whose projected cost exceeds the spending threshold
+#[derive(Debug)]
+pub struct ProjectedCostDeclined {
+	pub estimated_cost: f64,
+}
+
+impl std::fmt::Display for ProjectedCostDeclined {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"Request cancelled: projected cost ~${:.2} exceeds spending threshold",
+			self.estimated_cost
+		)
+	}
 }
 
+impl std::error::Error for ProjectedCostDeclined {}
+
+// Output tokens assumed when previewing request cost (actual output is unknown before sending)
+const COST_PREVIEW_OUTPUT_TOKENS: u64 = 1_000;
+
 /// High-level function to send a chat completion with input validation and context management
 /// This function checks input size and prompts user for handling when limits are exceeded
 pub async fn chat_completion_with_validation(
@@ -674,6 +1035,34 @@ pub async fn chat_completion_with_validation(
 	config: &Config,
 	chat_session: Option<&mut crate::session::chat::session::ChatSession>,
 	cancellation_token: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+) -> Result<ProviderResponse> {
+	event_log::record_request_started(model, messages.len());
+	let result = send_validated_completion(
+		messages,
+		model,
+		temperature,
+		config,
+		chat_session,
+		cancellation_token,
+	)
+	.await;
+	event_log::record_request_finished(model, &result);
+	result
+}
+
+// Estimated tokens taken by tool definitions sent alongside the messages
+pub fn estimate_tool_definition_tokens(config: &Config) -> usize {
+	// More accurate estimate: ~150 tokens per tool definition on average
+	config.mcp.servers.len() * 150
+}
+
+async fn send_validated_completion(
+	messages: &[Message],
+	model: &str,
+	temperature: f32,
+	config: &Config,
+	chat_session: Option<&mut crate::session::chat::session::ChatSession>,
+	cancellation_token: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
 ) -> Result<ProviderResponse> {
 	// Check for cancellation before starting
 	if let Some(ref token) = cancellation_token {
@@ -685,18 +1074,19 @@ pub async fn chat_completion_with_validation(
 	// Parse the model string and get the appropriate provider
 	let (provider, actual_model) = ProviderFactory::get_provider_for_model(model)?;
 
+	// Inline text attachments and check the model accepts any documents
+	let prepared =
+		attachment::prepare_attachments_for_provider(messages, provider.as_ref(), &actual_model)?;
+	let messages: &[Message] = &prepared;
+
 	// Get maximum input tokens for this provider/model (actual context window)
 	let max_input_tokens = provider.get_max_input_tokens(&actual_model);
 
-	// Calculate EXACTLY what we're about to send to the API
-	let mut total_input_tokens = estimate_message_tokens(messages);
+	// Calculate EXACTLY what we're about to send to the API, using the model's tokenizer
+	let mut total_input_tokens = estimate_message_tokens(messages, model);
 
 	// Add estimated tokens for tool definitions if MCP is configured
-	if !config.mcp.servers.is_empty() {
-		// More accurate estimate: ~150 tokens per tool definition on average
-		let tool_count = config.mcp.servers.len();
-		total_input_tokens += tool_count * 150;
-	}
+	total_input_tokens += estimate_tool_definition_tokens(config);
 
 	// Check if our total input exceeds what the provider can handle
 	if total_input_tokens > max_input_tokens {
@@ -731,6 +1121,19 @@ pub async fn chat_completion_with_validation(
 		}
 	}
 
+	// Preview the cost and confirm when it would exceed the remaining spending budget
+	if let Some(session) = chat_session {
+		if let Some(estimated_cost) = provider.estimate_cost(
+			&actual_model,
+			total_input_tokens as u64,
+			COST_PREVIEW_OUTPUT_TOKENS,
+		) {
+			if !session.confirm_projected_cost(config, estimated_cost)? {
+				return Err(ProjectedCostDeclined { estimated_cost }.into());
+			}
+		}
+	}
+
 	// Check for cancellation before API call
 	if let Some(ref token) = cancellation_token {
 		if token.load(std::sync::atomic::Ordering::SeqCst) {
@@ -738,6 +1141,15 @@ pub async fn chat_completion_with_validation(
 		}
 	}
 
+	// Wait for provider rate limit capacity (no-op unless configured)
+	crate::providers::acquire_rate_limit(
+		provider.name(),
+		config,
+		total_input_tokens,
+		cancellation_token.clone(),
+	)
+	.await?;
+
 	// Input size is acceptable, proceed with API call
 	provider
 		.chat_completion(
@@ -750,7 +1162,8 @@ pub async fn chat_completion_with_validation(
 		.await
 }
 
-/// Handle context limit exceeded by prompting user for action
+/// Handle context limit exceeded using the configured reduction strategy, prompting only when
+/// the strategy is `prompt` and a TTY is available
 async fn handle_context_limit_exceeded(
 	chat_session: &mut crate::session::chat::session::ChatSession,
 	config: &Config,
@@ -759,9 +1172,35 @@ async fn handle_context_limit_exceeded(
 	temperature: f32,
 	cancellation_token: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
 ) -> Result<ProviderResponse> {
+	use crate::config::ContextReductionStrategy;
 	use colored::Colorize;
 	use rustyline::DefaultEditor;
 
+	// Automated runs have nobody to answer the prompt, so fall back to truncation
+	let strategy = match config.context_reduction_strategy {
+		ContextReductionStrategy::Prompt if !std::io::IsTerminal::is_terminal(&std::io::stdin()) => {
+			ContextReductionStrategy::Truncate
+		}
+		strategy => strategy,
+	};
+
+	if strategy != ContextReductionStrategy::Prompt {
+		crate::log_info!(
+			"Context limit exceeded, applying '{}' context reduction strategy automatically",
+			strategy
+		);
+		return reduce_context_and_retry(
+			chat_session,
+			config,
+			provider,
+			model,
+			temperature,
+			cancellation_token,
+			strategy,
+		)
+		.await;
+	}
+
 	println!("{}", "Choose action:".bright_cyan());
 	println!(
 		"  {} - Smart truncate (keep recent + summarize removed)",
@@ -788,50 +1227,9 @@ async fn handle_context_limit_exceeded(
 		match rl.readline("Your choice (t/s/c): ") {
 			Ok(line) => {
 				let choice = line.trim().to_lowercase();
-				match choice.as_str() {
-					"t" | "truncate" => {
-						println!("{}", "Applying smart truncation...".bright_blue());
-
-						// Apply enhanced smart truncation
-						crate::session::chat::perform_smart_truncation(
-							chat_session,
-							config,
-							crate::session::estimate_message_tokens(&chat_session.session.messages),
-						)
-						.await?;
-
-						// Retry the API call with truncated context and cancellation support
-						return provider
-							.chat_completion(
-								&chat_session.session.messages,
-								model,
-								temperature,
-								config,
-								cancellation_token,
-							)
-							.await;
-					}
-					"s" | "summarize" => {
-						println!("{}", "Applying smart summarization...".bright_blue());
-
-						// Apply full context summarization
-						crate::session::chat::perform_smart_full_summarization(
-							chat_session,
-							config,
-						)
-						.await?;
-
-						// Retry the API call with summarized context and cancellation support
-						return provider
-							.chat_completion(
-								&chat_session.session.messages,
-								model,
-								temperature,
-								config,
-								cancellation_token,
-							)
-							.await;
-					}
+				let strategy = match choice.as_str() {
+					"t" | "truncate" => ContextReductionStrategy::Truncate,
+					"s" | "summarize" => ContextReductionStrategy::Summarize,
 					"c" | "cancel" => {
 						println!("{}", "Operation cancelled.".bright_yellow());
 						return Err(anyhow::anyhow!("User cancelled due to context size limit"));
@@ -843,7 +1241,17 @@ async fn handle_context_limit_exceeded(
 						);
 						continue;
 					}
-				}
+				};
+				return reduce_context_and_retry(
+					chat_session,
+					config,
+					provider,
+					model,
+					temperature,
+					cancellation_token,
+					strategy,
+				)
+				.await;
 			}
 			Err(rustyline::error::ReadlineError::Interrupted) => {
 				println!("{}", "Operation cancelled.".bright_yellow());
@@ -860,6 +1268,53 @@ async fn handle_context_limit_exceeded(
 	}
 }
 
+/// Apply smart truncation or full summarization, then retry the API call with the reduced context
+async fn reduce_context_and_retry(
+	chat_session: &mut crate::session::chat::session::ChatSession,
+	config: &Config,
+	provider: &dyn AiProvider,
+	model: &str,
+	temperature: f32,
+	cancellation_token: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+	strategy: crate::config::ContextReductionStrategy,
+) -> Result<ProviderResponse> {
+	use colored::Colorize;
+
+	if strategy == crate::config::ContextReductionStrategy::Summarize {
+		println!("{}", "Applying smart summarization...".bright_blue());
+
+		// Apply full context summarization
+		crate::session::chat::perform_smart_full_summarization(chat_session, config).await?;
+	} else {
+		println!("{}", "Applying smart truncation...".bright_blue());
+
+		// Apply enhanced smart truncation
+		crate::session::chat::perform_smart_truncation(
+			chat_session,
+			config,
+			crate::session::estimate_message_tokens(&chat_session.session.messages, model),
+		)
+		.await?;
+	}
+
+	// Retry the API call with reduced context and cancellation support
+	let messages = attachment::prepare_attachments_for_provider(
+		&chat_session.session.messages,
+		provider,
+		model,
+	)?;
+	crate::providers::acquire_rate_limit(
+		provider.name(),
+		config,
+		estimate_message_tokens(&messages, model),
+		cancellation_token.clone(),
+	)
+	.await?;
+	provider
+		.chat_completion(&messages, model, temperature, config, cancellation_token)
+		.await
+}
+
 /// High-level function to send a chat completion using the provider abstraction
 /// This function handles model parsing and provider selection automatically
 pub async fn chat_completion_with_provider(
@@ -867,12 +1322,159 @@ pub async fn chat_completion_with_provider(
 	model: &str,
 	temperature: f32,
 	config: &Config,
+) -> Result<ProviderResponse> {
+	event_log::record_request_started(model, messages.len());
+	let result = send_completion(messages, model, temperature, config).await;
+	event_log::record_request_finished(model, &result);
+	result
+}
+
+async fn send_completion(
+	messages: &[Message],
+	model: &str,
+	temperature: f32,
+	config: &Config,
 ) -> Result<ProviderResponse> {
 	// Parse the model string and get the appropriate provider
 	let (provider, actual_model) = ProviderFactory::get_provider_for_model(model)?;
 
+	// Inline text attachments and check the model accepts any documents
+	let messages =
+		attachment::prepare_attachments_for_provider(messages, provider.as_ref(), &actual_model)?;
+
+	// Wait for provider rate limit capacity (no-op unless configured)
+	crate::providers::acquire_rate_limit(
+		provider.name(),
+		config,
+		estimate_message_tokens(&messages, model),
+		None,
+	)
+	.await?;
+
 	// Call the provider's chat completion method
 	provider
-		.chat_completion(messages, &actual_model, temperature, config, None)
+		.chat_completion(&messages, &actual_model, temperature, config, None)
 		.await
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn message(role: &str, content: &str) -> Message {
+		Message {
+			role: role.to_string(),
+			content: content.to_string(),
+			timestamp: 1,
+			cached: false,
+			tool_call_id: None,
+			name: None,
+			tool_calls: None,
+			images: None,
+			attachments: None,
+			pinned: false,
+		}
+	}
+
+	#[test]
+	fn test_parse_relative_duration() {
+		assert_eq!(parse_relative_duration("30m").unwrap(), 30 * 60);
+		assert_eq!(parse_relative_duration("24h").unwrap(), 24 * 60 * 60);
+		assert_eq!(parse_relative_duration("7d").unwrap(), 7 * 24 * 60 * 60);
+		assert_eq!(parse_relative_duration("2w").unwrap(), 14 * 24 * 60 * 60);
+
+		assert!(parse_relative_duration("7").is_err());
+		assert!(parse_relative_duration("d").is_err());
+		assert!(parse_relative_duration("7days").is_err());
+		assert!(parse_relative_duration("-1h").is_err());
+	}
+
+	#[test]
+	fn test_match_session_names() {
+		let available: Vec<String> = ["251015-090000-app-1", "251016-100000-app-2", "review"]
+			.iter()
+			.map(|s| s.to_string())
+			.collect();
+		let patterns = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+		assert_eq!(
+			match_session_names(&patterns(&["251016-*", "review"]), &available).unwrap(),
+			vec!["251016-100000-app-2", "review"]
+		);
+		// Overlapping patterns do not duplicate sessions
+		assert_eq!(
+			match_session_names(&patterns(&["*app*", "251015-*"]), &available)
+				.unwrap()
+				.len(),
+			2
+		);
+		assert!(match_session_names(&patterns(&["2024*"]), &available).is_err());
+	}
+
+	#[test]
+	fn test_checkpoint_info_skips_model_override() {
+		let mut session = Session::new(
+			"test".to_string(),
+			"openai:gpt-4o".to_string(),
+			"openai".to_string(),
+		);
+		assert_eq!(session.checkpoint_info().model, "openai:gpt-4o");
+
+		session.saved_model = Some(std::mem::replace(
+			&mut session.info.model,
+			"openai:gpt-4o-mini".to_string(),
+		));
+		assert_eq!(session.info.model, "openai:gpt-4o-mini");
+		assert_eq!(session.checkpoint_info().model, "openai:gpt-4o");
+	}
+
+	#[test]
+	fn test_discard_last_turn() {
+		let mut messages = vec![
+			message("system", "prompt"),
+			message("user", "first"),
+			message("assistant", "answer"),
+			message("user", "second"),
+			message("assistant", "calling tool"),
+			message("tool", "result"),
+			message("assistant", "done"),
+		];
+
+		let removed = discard_last_turn(&mut messages).unwrap();
+		assert_eq!(removed.content, "second");
+		assert_eq!(messages.len(), 3);
+		assert_eq!(messages.last().unwrap().content, "answer");
+
+		let mut no_user = vec![message("system", "prompt")];
+		assert!(discard_last_turn(&mut no_user).is_none());
+		assert_eq!(no_user.len(), 1);
+	}
+
+	#[test]
+	fn test_sidecar_round_trip() {
+		let dir = tempfile::tempdir().unwrap();
+		let session_file = dir.path().join("test.jsonl");
+
+		// Small responses and non-assistant messages stay inline
+		let line =
+			serialize_message_with_sidecar(&session_file, &message("assistant", "short"), 10)
+				.unwrap();
+		assert!(line.contains("\"content\":\"short\""));
+		let line =
+			serialize_message_with_sidecar(&session_file, &message("user", &"u".repeat(50)), 10)
+				.unwrap();
+		assert!(!line.contains("content_file"));
+
+		// Large responses are replaced by a reference that reads back in full
+		let long = "line\n".repeat(20);
+		let line = serialize_message_with_sidecar(&session_file, &message("assistant", &long), 10)
+			.unwrap();
+		let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+		assert_eq!(value["content"], "");
+		let file_name = value["content_file"].as_str().unwrap();
+		assert_eq!(
+			read_sidecar_content(&session_file, file_name).unwrap(),
+			long
+		);
+	}
+}
@@ -14,11 +14,24 @@
 
 // Project context module for gathering and managing contextual information
 
+use crate::config::ProjectContextConfig;
 use anyhow::Result;
 use colored::*;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+// Modification times of the files a cached context was built from
+type Fingerprint = Vec<Option<SystemTime>>;
+
+lazy_static::lazy_static! {
+	// Collected context per project directory and settings, reused until a tracked file changes
+	static ref CONTEXT_CACHE: Mutex<HashMap<(PathBuf, ProjectContextConfig), (Fingerprint, ProjectContext)>> =
+		Mutex::new(HashMap::new());
+}
 
 /// Represents the contextual information about the project
 #[derive(Debug, Clone)]
@@ -48,26 +61,81 @@ impl ProjectContext {
 		}
 	}
 
-	/// Collect all contextual information for the project
-	pub fn collect(project_dir: &Path) -> Self {
+	/// Collect the enabled contextual information for the project.
+	/// README, CHANGES, file tree and branch are cached until their source files change;
+	/// git status is always refreshed since any edit in the working tree changes it.
+	pub fn collect(project_dir: &Path, settings: &ProjectContextConfig) -> Self {
+		let key = (project_dir.to_path_buf(), settings.clone());
+		let fingerprint = Self::fingerprint(project_dir);
+
+		let cached = CONTEXT_CACHE
+			.lock()
+			.unwrap()
+			.get(&key)
+			.filter(|(cached_fingerprint, _)| *cached_fingerprint == fingerprint)
+			.map(|(_, context)| context.clone());
+
+		let mut context = match cached {
+			Some(context) => context,
+			None => {
+				let context = Self::collect_cacheable(project_dir, settings);
+				CONTEXT_CACHE
+					.lock()
+					.unwrap()
+					.insert(key, (fingerprint, context.clone()));
+				context
+			}
+		};
+
+		if settings.include_git_status {
+			context.git_status = Self::get_git_status(project_dir);
+		}
+
+		context
+	}
+
+	/// Collect everything except git status, which is not cacheable
+	fn collect_cacheable(project_dir: &Path, settings: &ProjectContextConfig) -> Self {
 		let mut context = Self::new();
 
 		// Collect README.md content
-		context.readme_content = Self::read_file_if_exists(project_dir.join("README.md"));
+		if settings.include_readme {
+			context.readme_content = Self::read_file_if_exists(project_dir.join("README.md"));
+		}
 
 		// Collect CHANGES.md content
-		context.changes_content = Self::read_file_if_exists(project_dir.join("CHANGES.md"));
+		if settings.include_changes {
+			context.changes_content = Self::read_file_if_exists(project_dir.join("CHANGES.md"));
+		}
 
 		// Get file tree (excluding .gitignore patterns)
-		context.file_tree = Self::get_file_tree(project_dir);
+		if settings.include_file_tree {
+			context.file_tree = Self::get_file_tree(project_dir);
+		}
 
-		// Get git status and branch if available
-		context.git_status = Self::get_git_status(project_dir);
-		context.git_branch = Self::get_git_branch(project_dir);
+		// Get git branch if available
+		if settings.include_git_status {
+			context.git_branch = Self::get_git_branch(project_dir);
+		}
 
 		context
 	}
 
+	/// Modification times that invalidate the cache: the context files, the git index
+	/// (tracked files added or removed), HEAD (branch switches) and the project root itself
+	fn fingerprint(project_dir: &Path) -> Fingerprint {
+		[
+			project_dir.join("README.md"),
+			project_dir.join("CHANGES.md"),
+			project_dir.join(".git").join("index"),
+			project_dir.join(".git").join("HEAD"),
+			project_dir.to_path_buf(),
+		]
+		.iter()
+		.map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+		.collect()
+	}
+
 	/// Read file content if file exists
 	fn read_file_if_exists(path: PathBuf) -> Option<String> {
 		if path.exists() && path.is_file() {
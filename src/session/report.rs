@@ -19,7 +19,7 @@ use crate::session::chat::formatting::format_duration;
 use crate::session::chat::markdown::MarkdownRenderer;
 use anyhow::Result;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 
@@ -27,6 +27,21 @@ use std::io::{BufRead, BufReader};
 pub struct SessionReport {
 	pub entries: Vec<ReportEntry>,
 	pub totals: ReportTotals,
+	pub requests: Vec<ApiRequestRow>, // One row per API request, in time order
+	pub seed: Option<u64>,            // Sampling seed sent with the requests, if any
+}
+
+/// A single API request with its usage, as exported by `to_csv`
+#[derive(Debug, Clone)]
+pub struct ApiRequestRow {
+	pub timestamp: u64,
+	pub layer_type: String,
+	pub model: String,
+	pub input_tokens: u64,
+	pub output_tokens: u64,
+	pub cached_tokens: u64,
+	pub cost: f64,
+	pub api_time_ms: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -74,6 +89,11 @@ impl SessionReport {
 		let mut last_total_cost = 0.0;
 		let mut last_total_api_time_ms = 0u64;
 		let mut last_total_tool_time_ms = 0u64;
+		let mut requests: Vec<ApiRequestRow> = Vec::new();
+		let mut last_request_model = String::new();
+		let mut seed = None;
+		let mut layer_stats: Vec<crate::session::LayerStats> = Vec::new();
+		let mut seen_response_ids: HashSet<String> = HashSet::new();
 
 		// Read all log entries
 		let mut all_entries: Vec<Value> = Vec::new();
@@ -149,9 +169,58 @@ impl SessionReport {
 						tool_time_after: last_total_tool_time_ms,
 					});
 				}
+				"API_REQUEST" => {
+					// Remember the model so the following response can be attributed to it
+					if let Some(model) = log_entry
+						.get("data")
+						.and_then(|d| d.get("model"))
+						.and_then(|m| m.as_str())
+					{
+						last_request_model = model.to_string();
+					}
+					if let Some(request_seed) = log_entry
+						.get("data")
+						.and_then(|d| d.get("seed"))
+						.and_then(|s| s.as_u64())
+					{
+						seed = Some(request_seed);
+					}
+				}
 				"API_RESPONSE" => {
-					// API responses are now tracked via STATS entries for timing
-					// We don't need to extract timing here anymore
+					// Older logs wrote each exchange twice; a response id only counts once
+					if let Some(id) = log_entry
+						.get("data")
+						.and_then(|d| d.get("id"))
+						.and_then(|i| i.as_str())
+					{
+						if !seen_response_ids.insert(id.to_string()) {
+							continue;
+						}
+					}
+					// Session totals come from STATS entries; usage here feeds the per-request rows
+					if let Some(usage) = log_entry.get("usage").and_then(|u| {
+						serde_json::from_value::<crate::providers::TokenUsage>(u.clone()).ok()
+					}) {
+						let model = log_entry
+							.get("data")
+							.and_then(|d| d.get("model"))
+							.and_then(|m| m.as_str())
+							.unwrap_or(&last_request_model)
+							.to_string();
+						requests.push(ApiRequestRow {
+							timestamp: log_entry
+								.get("timestamp")
+								.and_then(|t| t.as_u64())
+								.unwrap_or(0),
+							layer_type: "main".to_string(),
+							model,
+							input_tokens: usage.prompt_tokens,
+							output_tokens: usage.output_tokens,
+							cached_tokens: usage.cached_tokens,
+							cost: usage.cost.unwrap_or(0.0),
+							api_time_ms: usage.request_time_ms.unwrap_or(0),
+						});
+					}
 				}
 				"TOOL_CALL" => {
 					// Track tool usage
@@ -174,11 +243,30 @@ impl SessionReport {
 						{
 							last_total_cost = total_cost;
 						}
+						// Layer calls are only recorded in the session summary, latest wins
+						if let Some(stats) = session_info
+							.get("layer_stats")
+							.and_then(|l| serde_json::from_value(l.clone()).ok())
+						{
+							layer_stats = stats;
+						}
 					}
 				}
 			}
 		}
 
+		requests.extend(layer_stats.into_iter().map(|stat| ApiRequestRow {
+			timestamp: stat.timestamp,
+			layer_type: stat.layer_type,
+			model: stat.model,
+			input_tokens: stat.input_tokens,
+			output_tokens: stat.output_tokens,
+			cached_tokens: stat.cached_tokens,
+			cost: stat.cost,
+			api_time_ms: stat.api_time_ms,
+		}));
+		requests.sort_by_key(|row| row.timestamp);
+
 		// Save the last context if exists
 		if let Some(mut ctx) = current_context {
 			ctx.cost_after = last_total_cost;
@@ -271,7 +359,12 @@ impl SessionReport {
 			});
 		}
 
-		Ok(SessionReport { entries, totals })
+		Ok(SessionReport {
+			entries,
+			totals,
+			requests,
+			seed,
+		})
 	}
 
 	/// Format tools used as "tool_name(count), tool_name(count)"
@@ -334,6 +427,36 @@ impl SessionReport {
 		markdown
 	}
 
+	/// Generate CSV with one row per API request, for spreadsheets and billing reconciliation
+	pub fn to_csv(&self) -> String {
+		let mut csv = String::from(
+			"timestamp,layer_type,model,input_tokens,output_tokens,cached_tokens,cost,api_time_ms\n",
+		);
+		for row in &self.requests {
+			csv.push_str(&format!(
+				"{},{},{},{},{},{},{:.5},{}\n",
+				row.timestamp,
+				Self::escape_csv(&row.layer_type),
+				Self::escape_csv(&row.model),
+				row.input_tokens,
+				row.output_tokens,
+				row.cached_tokens,
+				row.cost,
+				row.api_time_ms
+			));
+		}
+		csv
+	}
+
+	/// Quote a CSV field when it contains separators, quotes or newlines
+	fn escape_csv(field: &str) -> String {
+		if field.contains([',', '"', '\n', '\r']) {
+			format!("\"{}\"", field.replace('"', "\"\""))
+		} else {
+			field.to_string()
+		}
+	}
+
 	/// Escape markdown special characters in table cells
 	fn escape_markdown(&self, text: &str) -> String {
 		text.replace("|", "\\|")
@@ -341,6 +464,65 @@ impl SessionReport {
 			.replace("\r", "")
 	}
 
+	/// Combined markdown report for several sessions: per-session subtotals plus grand totals
+	pub fn generate_combined_markdown(reports: &[(String, SessionReport)]) -> String {
+		let mut markdown = String::new();
+		markdown.push_str(&format!(
+			"# 📊 Combined Usage Report ({} sessions)\n\n",
+			reports.len()
+		));
+		markdown.push_str(
+			"| Session | Requests | Cost ($) | Tool Calls | AI Time | Processing Time |\n",
+		);
+		markdown.push_str(
+			"|---------|----------|----------|------------|---------|-----------------|\n",
+		);
+
+		let mut grand_total = ReportTotals {
+			total_cost: 0.0,
+			total_tool_calls: 0,
+			total_human_time_ms: 0,
+			total_ai_time_ms: 0,
+			total_processing_time_ms: 0,
+			total_requests: 0,
+		};
+
+		for (name, report) in reports {
+			let totals = &report.totals;
+			markdown.push_str(&format!(
+				"| {} | {} | {:.5} | {} | {} | {} |\n",
+				name.replace('|', "\\|"),
+				totals.total_requests,
+				totals.total_cost,
+				totals.total_tool_calls,
+				format_duration(totals.total_ai_time_ms),
+				format_duration(totals.total_processing_time_ms)
+			));
+
+			grand_total.total_cost += totals.total_cost;
+			grand_total.total_tool_calls += totals.total_tool_calls;
+			grand_total.total_ai_time_ms += totals.total_ai_time_ms;
+			grand_total.total_processing_time_ms += totals.total_processing_time_ms;
+			grand_total.total_requests += totals.total_requests;
+		}
+
+		markdown.push_str(&format!(
+			"| **TOTAL** | **{}** | **{:.5}** | **{}** | **{}** | **{}** |\n",
+			grand_total.total_requests,
+			grand_total.total_cost,
+			grand_total.total_tool_calls,
+			format_duration(grand_total.total_ai_time_ms),
+			format_duration(grand_total.total_processing_time_ms)
+		));
+
+		markdown
+	}
+
+	/// Display a combined report for several sessions
+	pub fn display_combined(reports: &[(String, SessionReport)], config: &crate::config::Config) {
+		Self::render_markdown(&Self::generate_combined_markdown(reports), config);
+	}
+
 	/// Display the report with summary information using markdown rendering
 	pub fn display(&self, config: &crate::config::Config) {
 		// Generate the full markdown report
@@ -363,28 +545,36 @@ impl SessionReport {
 			format_duration(self.totals.total_ai_time_ms),
 			format_duration(self.totals.total_processing_time_ms)
 		));
+		if let Some(seed) = self.seed {
+			markdown_report.push_str(&format!("\n**Seed:** {}\n", seed));
+		}
 
+		Self::render_markdown(&markdown_report, config);
+	}
+
+	/// Render a markdown report, falling back to plain text
+	fn render_markdown(markdown_report: &str, config: &crate::config::Config) {
 		// Render using markdown renderer if enabled
 		if config.enable_markdown_rendering {
 			let theme = config.markdown_theme.parse().unwrap_or_default();
 			let renderer = MarkdownRenderer::with_theme(theme);
-			match renderer.render_and_print(&markdown_report) {
+			match renderer.render_and_print(markdown_report) {
 				Ok(_) => {
 					// Successfully rendered as markdown
 				}
 				Err(_) => {
 					// Fallback to plain text if markdown rendering fails
-					self.display_plain(&markdown_report);
+					Self::display_plain(markdown_report);
 				}
 			}
 		} else {
 			// Use plain text rendering
-			self.display_plain(&markdown_report);
+			Self::display_plain(markdown_report);
 		}
 	}
 
 	/// Display report as plain text (fallback)
-	fn display_plain(&self, markdown_report: &str) {
+	fn display_plain(markdown_report: &str) {
 		// Convert markdown to plain text for fallback
 		let plain_text = markdown_report
 			.replace("# ", "")
@@ -394,3 +584,53 @@ impl SessionReport {
 		println!("{}", plain_text);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_csv_from_session_log() {
+		let dir = tempfile::tempdir().unwrap();
+		let log_path = dir.path().join("session.jsonl");
+		let usage = |prompt: u64, output: u64, cost: f64| {
+			serde_json::json!({
+				"prompt_tokens": prompt,
+				"output_tokens": output,
+				"total_tokens": prompt + output,
+				"cached_tokens": 0,
+				"cost": cost,
+				"request_time_ms": 500
+			})
+		};
+		let entries = [
+			serde_json::json!({"type": "USER", "timestamp": 1700000000, "content": "list files"}),
+			serde_json::json!({"type": "API_REQUEST", "timestamp": 1700000001, "data": {"model": "claude-sonnet-4"}}),
+			// Tool call response followed by the tool follow-up response
+			serde_json::json!({"type": "API_RESPONSE", "timestamp": 1700000002, "data": {"id": "msg_1", "model": "claude-sonnet-4"}, "usage": usage(1000, 50, 0.004)}),
+			serde_json::json!({"type": "API_REQUEST", "timestamp": 1700000003, "data": {"model": "claude-sonnet-4"}}),
+			serde_json::json!({"type": "API_RESPONSE", "timestamp": 1700000004, "data": {"id": "msg_2", "model": "claude-sonnet-4"}, "usage": usage(1200, 80, 0.005)}),
+			// Same exchange logged a second time, as older versions did
+			serde_json::json!({"type": "API_RESPONSE", "timestamp": 1700000004, "data": {"id": "msg_2", "model": "claude-sonnet-4"}, "usage": usage(1200, 80, 0.005)}),
+		];
+		let log = entries
+			.iter()
+			.map(|entry| entry.to_string())
+			.collect::<Vec<_>>()
+			.join("\n");
+		std::fs::write(&log_path, log).unwrap();
+
+		let report = SessionReport::generate_from_log(&log_path.to_string_lossy()).unwrap();
+
+		let csv = report.to_csv();
+		let lines: Vec<&str> = csv.lines().collect();
+		assert_eq!(
+			lines,
+			vec![
+				"timestamp,layer_type,model,input_tokens,output_tokens,cached_tokens,cost,api_time_ms",
+				"1700000002,main,claude-sonnet-4,1000,50,0,0.00400,500",
+				"1700000004,main,claude-sonnet-4,1200,80,0,0.00500,500",
+			]
+		);
+	}
+}
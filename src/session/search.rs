@@ -0,0 +1,176 @@
+// Copyright 2025 Muvon Un Limited
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Conversation search across all saved sessions
+
+use crate::session::{get_sessions_dir, read_sidecar_content, Message};
+use anyhow::Result;
+use regex::{Regex, RegexBuilder};
+use std::cmp::Reverse;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+// Characters of context shown on each side of a match
+const SNIPPET_CONTEXT_CHARS: usize = 60;
+
+/// A single message that matched a search query
+#[derive(Debug, Clone)]
+pub struct SessionSearchMatch {
+	pub session_name: String,
+	pub role: String,
+	pub timestamp: u64,
+	pub snippet: String,
+}
+
+/// Build the matcher: literal queries are escaped and matched case-insensitively
+pub fn build_search_pattern(query: &str, use_regex: bool) -> Result<Regex> {
+	if use_regex {
+		Regex::new(query).map_err(|e| anyhow::anyhow!("Invalid regex '{}': {}", query, e))
+	} else {
+		Ok(RegexBuilder::new(&regex::escape(query))
+			.case_insensitive(true)
+			.build()?)
+	}
+}
+
+/// Search message contents of every saved session, newest matches first
+pub fn search_sessions(pattern: &Regex) -> Result<Vec<SessionSearchMatch>> {
+	let sessions_dir = get_sessions_dir()?;
+	let mut matches = Vec::new();
+
+	if !sessions_dir.exists() {
+		return Ok(matches);
+	}
+
+	for entry in fs::read_dir(sessions_dir)? {
+		let path = entry?.path();
+		if path.is_file() && path.extension().is_some_and(|ext| ext == "jsonl") {
+			// Unreadable files are skipped so one bad session doesn't break the search
+			if let Ok(session_matches) = search_session_file(&path, pattern) {
+				matches.extend(session_matches);
+			}
+		}
+	}
+
+	matches.sort_by_key(|m| Reverse(m.timestamp));
+	Ok(matches)
+}
+
+// Stream a session file line by line, matching only conversation messages
+fn search_session_file(path: &Path, pattern: &Regex) -> Result<Vec<SessionSearchMatch>> {
+	let session_name = path
+		.file_stem()
+		.and_then(|s| s.to_str())
+		.unwrap_or_default()
+		.to_string();
+	let reader = BufReader::new(File::open(path)?);
+	let mut matches = Vec::new();
+
+	for line in reader.lines() {
+		let line = line?;
+
+		// Same heuristic as load_session: message lines carry role and content, log entries a type
+		if !line.contains("\"role\":") || !line.contains("\"content\":") {
+			continue;
+		}
+
+		if let Ok(mut message) = serde_json::from_str::<Message>(&line) {
+			if message.role == "system" {
+				continue;
+			}
+			// Large responses live in a sidecar file, search their full text
+			if line.contains("\"content_file\":") {
+				if let Some(content) = serde_json::from_str::<serde_json::Value>(&line)
+					.ok()
+					.and_then(|value| value["content_file"].as_str().map(str::to_string))
+					.and_then(|file_name| read_sidecar_content(path, &file_name).ok())
+				{
+					message.content = content;
+				}
+			}
+			if let Some(found) = pattern.find(&message.content) {
+				matches.push(SessionSearchMatch {
+					session_name: session_name.clone(),
+					role: message.role,
+					timestamp: message.timestamp,
+					snippet: make_snippet(&message.content, found.start(), found.end()),
+				});
+			}
+		}
+	}
+
+	Ok(matches)
+}
+
+// Extract the match with surrounding context on a single line
+fn make_snippet(content: &str, start: usize, end: usize) -> String {
+	let mut snippet_start = start;
+	for _ in 0..SNIPPET_CONTEXT_CHARS {
+		match content[..snippet_start].chars().next_back() {
+			Some(c) => snippet_start -= c.len_utf8(),
+			None => break,
+		}
+	}
+
+	let mut snippet_end = end;
+	for _ in 0..SNIPPET_CONTEXT_CHARS {
+		match content[snippet_end..].chars().next() {
+			Some(c) => snippet_end += c.len_utf8(),
+			None => break,
+		}
+	}
+
+	let mut snippet = content[snippet_start..snippet_end]
+		.split_whitespace()
+		.collect::<Vec<_>>()
+		.join(" ");
+	if snippet_start > 0 {
+		snippet.insert_str(0, "...");
+	}
+	if snippet_end < content.len() {
+		snippet.push_str("...");
+	}
+	snippet
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_literal_pattern_is_escaped_and_case_insensitive() {
+		let pattern = build_search_pattern("Vec<String>", false).unwrap();
+		assert!(pattern.is_match("returns a vec<string> here"));
+		assert!(!pattern.is_match("returns a VecString here"));
+
+		assert!(build_search_pattern("fn\\s+main", true)
+			.unwrap()
+			.is_match("fn  main()"));
+		assert!(build_search_pattern("(unclosed", true).is_err());
+	}
+
+	#[test]
+	fn test_make_snippet() {
+		let content = format!("{}needle\nin the {}", "a".repeat(100), "b".repeat(100));
+		let start = content.find("needle").unwrap();
+		let snippet = make_snippet(&content, start, start + "needle".len());
+
+		assert!(snippet.starts_with("..."));
+		assert!(snippet.ends_with("..."));
+		assert!(snippet.contains("needle in the"));
+
+		assert_eq!(make_snippet("short needle", 6, 12), "short needle");
+	}
+}
@@ -15,10 +15,26 @@
 // Token counting utilities
 
 use std::sync::OnceLock;
-use tiktoken_rs::{cl100k_base, CoreBPE};
+use tiktoken_rs::{cl100k_base, o200k_base, CoreBPE};
 
-// Global tokenizer instance - created once and reused
+// Global tokenizer instances - created once and reused
 static TOKENIZER: OnceLock<CoreBPE> = OnceLock::new();
+static O200K_TOKENIZER: OnceLock<CoreBPE> = OnceLock::new();
+
+// Claude's tokenizer is not public; it produces more tokens than cl100k for the same text,
+// so scale the cl100k count up to stay on the safe side of context limits
+const ANTHROPIC_TOKEN_RATIO: f64 = 1.2;
+
+// Tokenizer family used for a given model
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TokenizerKind {
+	// GPT-4 / GPT-3.5 encoding, also the generic fallback for unknown models
+	Cl100k,
+	// GPT-4o, GPT-4.1, GPT-4.5 and o-series encoding
+	O200k,
+	// cl100k scaled by ANTHROPIC_TOKEN_RATIO
+	Anthropic,
+}
 
 // Get or initialize the global tokenizer instance
 fn get_tokenizer() -> &'static CoreBPE {
@@ -30,16 +46,50 @@ fn get_tokenizer() -> &'static CoreBPE {
 	})
 }
 
-// Simple token counter that uses tiktoken to estimate token counts
-pub fn estimate_tokens(text: &str) -> usize {
-	// Use the cached global tokenizer
-	let tokenizer = get_tokenizer();
-	let tokens = tokenizer.encode_ordinary(text);
-	tokens.len()
+// Get or initialize the o200k tokenizer used by newer OpenAI models
+fn get_o200k_tokenizer() -> &'static CoreBPE {
+	O200K_TOKENIZER.get_or_init(|| {
+		o200k_base().unwrap_or_else(|_| {
+			// Fallback - this shouldn't happen in practice
+			panic!("Failed to initialize o200k tokenizer")
+		})
+	})
+}
+
+// Pick the tokenizer from a model string like "openai:gpt-4o" or "openrouter:anthropic/claude-sonnet-4"
+fn tokenizer_for_model(model: &str) -> TokenizerKind {
+	let model = model.to_lowercase();
+	// Strip provider prefixes so "openrouter:openai/o3-mini" becomes "o3-mini"
+	let name = model.rsplit([':', '/']).next().unwrap_or(&model);
+
+	if model.contains("claude") {
+		TokenizerKind::Anthropic
+	} else if name.starts_with("gpt-4o")
+		|| name.starts_with("chatgpt-4o")
+		|| name.starts_with("gpt-4.1")
+		|| name.starts_with("gpt-4.5")
+		|| name.starts_with("o1")
+		|| name.starts_with("o3")
+		|| name.starts_with("o4")
+	{
+		TokenizerKind::O200k
+	} else {
+		TokenizerKind::Cl100k
+	}
 }
 
-// Estimate tokens for a full message list
-pub fn estimate_message_tokens(messages: &[crate::session::Message]) -> usize {
+fn count_tokens(text: &str, kind: TokenizerKind) -> usize {
+	match kind {
+		TokenizerKind::Cl100k => get_tokenizer().encode_ordinary(text).len(),
+		TokenizerKind::O200k => get_o200k_tokenizer().encode_ordinary(text).len(),
+		TokenizerKind::Anthropic => {
+			let base = get_tokenizer().encode_ordinary(text).len();
+			(base as f64 * ANTHROPIC_TOKEN_RATIO).ceil() as usize
+		}
+	}
+}
+
+fn count_message_tokens(messages: &[crate::session::Message], kind: TokenizerKind) -> usize {
 	let mut total = 0;
 
 	for msg in messages {
@@ -47,7 +97,7 @@ pub fn estimate_message_tokens(messages: &[crate::session::Message]) -> usize {
 		total += 4;
 
 		// Add content tokens
-		total += estimate_tokens(&msg.content);
+		total += count_tokens(&msg.content, kind);
 	}
 
 	// Add some overhead for message formatting
@@ -55,3 +105,82 @@ pub fn estimate_message_tokens(messages: &[crate::session::Message]) -> usize {
 
 	total
 }
+
+// Estimate tokens using the tokenizer matching the model (cl100k for unknown models)
+pub fn estimate_tokens(text: &str, model: &str) -> usize {
+	count_tokens(text, tokenizer_for_model(model))
+}
+
+// Estimate tokens for a full message list using the tokenizer matching the model
+pub fn estimate_message_tokens(messages: &[crate::session::Message], model: &str) -> usize {
+	count_message_tokens(messages, tokenizer_for_model(model))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn message(role: &str, content: &str) -> crate::session::Message {
+		crate::session::Message {
+			role: role.to_string(),
+			content: content.to_string(),
+			timestamp: 0,
+			cached: false,
+			tool_call_id: None,
+			name: None,
+			tool_calls: None,
+			images: None,
+			attachments: None,
+			pinned: false,
+		}
+	}
+
+	#[test]
+	fn test_tokenizer_selection() {
+		assert_eq!(tokenizer_for_model("openai:gpt-4o"), TokenizerKind::O200k);
+		assert_eq!(tokenizer_for_model("openai:o3-mini"), TokenizerKind::O200k);
+		assert_eq!(
+			tokenizer_for_model("openrouter:openai/gpt-4.1"),
+			TokenizerKind::O200k
+		);
+		assert_eq!(tokenizer_for_model("openai:gpt-4"), TokenizerKind::Cl100k);
+		assert_eq!(
+			tokenizer_for_model("openrouter:anthropic/claude-sonnet-4"),
+			TokenizerKind::Anthropic
+		);
+		assert_eq!(
+			tokenizer_for_model("amazon:claude-3-5-sonnet"),
+			TokenizerKind::Anthropic
+		);
+		assert_eq!(
+			tokenizer_for_model("ollama:llama3.1"),
+			TokenizerKind::Cl100k
+		);
+	}
+
+	#[test]
+	fn test_known_token_counts() {
+		// "hello world" is two tokens in both OpenAI encodings
+		assert_eq!(estimate_tokens("hello world", "openai:gpt-4"), 2);
+		assert_eq!(estimate_tokens("hello world", "openai:gpt-4o"), 2);
+		assert_eq!(estimate_tokens("Hello, world!", "openai:gpt-4o"), 4);
+		// Anthropic estimate rounds the scaled cl100k count up
+		assert_eq!(
+			estimate_tokens("hello world", "anthropic:claude-3-5-sonnet"),
+			3
+		);
+	}
+
+	#[test]
+	fn test_message_token_overhead() {
+		let messages = vec![message("user", "hello world")];
+
+		// 4 role tokens + 2 content tokens + 2 formatting tokens
+		assert_eq!(estimate_message_tokens(&messages, "openai:gpt-4"), 8);
+		assert_eq!(estimate_message_tokens(&messages, "openai:gpt-4o"), 8);
+		assert_eq!(
+			estimate_message_tokens(&messages, "anthropic:claude-sonnet-4"),
+			9
+		);
+	}
+}